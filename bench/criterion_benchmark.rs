@@ -1,10 +1,33 @@
-use std::path::Path;
-
 use cairo_rs::{
     cairo_run,
-    hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
+    hint_processor::{
+        builtin_hint_processor::{
+            builtin_hint_processor_definition::{
+                BuiltinHintProcessor, HintFunc, HintProcessorData,
+            },
+            hint_utils::get_ptr_from_var_name,
+        },
+        hint_processor_definition::{HintProcessor, HintReference},
+    },
+    serde::deserialize_program::ApTracking,
+    types::{
+        exec_scope::ExecutionScopes,
+        program::{Program, ProgramBuilder},
+        relocatable::{MaybeRelocatable, Relocatable},
+    },
+    vm::{
+        errors::hint_errors::HintError,
+        runners::{
+            builtin_runner::{HashBuiltinRunner, DEFAULT_HASH_CACHE_CAPACITY},
+            cairo_runner::{CairoRunner, RunResources},
+        },
+        vm_core::VirtualMachine,
+        vm_memory::{memory::Memory, memory_segments::MemorySegmentManager},
+    },
 };
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use felt::{Felt, NewFelt};
+use std::{any::Any, collections::HashMap, path::Path, sync::Arc};
 
 const BENCH_NAMES: &[&str] = &[
     "compare_arrays_200000",
@@ -37,6 +60,7 @@ pub fn criterion_benchmarks(c: &mut Criterion) {
                     false,
                     "all",
                     false,
+                    false,
                     &mut hint_executor,
                 )
             })
@@ -44,6 +68,222 @@ pub fn criterion_benchmarks(c: &mut Criterion) {
     }
 }
 
+pub fn load_data_benchmark(c: &mut Criterion) {
+    let data: Vec<MaybeRelocatable> = (0..1_000_000_u64)
+        .map(|i| MaybeRelocatable::from(Felt::new(i)))
+        .collect();
+    c.bench_function("load_data_1000000", |b| {
+        b.iter_batched(
+            || {
+                let mut segments = MemorySegmentManager::new();
+                let mut memory = Memory::new();
+                let base = segments.add(&mut memory);
+                (segments, memory, base)
+            },
+            |(mut segments, mut memory, base)| {
+                segments
+                    .load_data(&mut memory, &base.into(), black_box(&data))
+                    .unwrap()
+            },
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+// Deduces the outputs of 2000 pedersen instances where every input pair appears twice
+pub fn pedersen_cache_benchmark(c: &mut Criterion) {
+    let instances: usize = 2000;
+    let mut segments = MemorySegmentManager::new();
+    let mut memory = Memory::new();
+    let base = segments.add(&mut memory);
+    for i in 0..instances {
+        let pair = (i % (instances / 2)) as u64;
+        for (offset, value) in [(0, pair), (1, pair + 1)] {
+            memory
+                .insert_value(&(base + (3 * i + offset)), Felt::new(value))
+                .unwrap();
+        }
+    }
+    let outputs: Vec<Relocatable> = (0..instances).map(|i| base + (3 * i + 2)).collect();
+
+    for (name, capacity) in [
+        ("pedersen_50%_duplicates_no_cache", 0),
+        ("pedersen_50%_duplicates_cache", DEFAULT_HASH_CACHE_CAPACITY),
+    ] {
+        c.bench_function(name, |b| {
+            b.iter_batched(
+                || HashBuiltinRunner::new_with_cache_capacity(8, true, capacity),
+                |builtin| {
+                    for output in &outputs {
+                        builtin
+                            .deduce_memory_cell(black_box(output), &memory)
+                            .unwrap();
+                    }
+                },
+                BatchSize::SmallInput,
+            )
+        });
+    }
+}
+
+// Runs a small program many times, building a new VM for every run or resetting a single one
+pub fn vm_reuse_benchmark(c: &mut Criterion) {
+    let program = Program::from_file(Path::new("cairo_programs/fibonacci.json"), Some("main"))
+        .expect("Failed to deserialize program");
+    let mut hint_executor = BuiltinHintProcessor::new_empty();
+    let mut run = |vm: &mut VirtualMachine| {
+        let mut cairo_runner = CairoRunner::new(&program, "all", false).unwrap();
+        let end = cairo_runner.initialize(vm).unwrap();
+        cairo_runner
+            .run_until_pc(end, &mut RunResources::default(), vm, &mut hint_executor)
+            .unwrap();
+        cairo_runner.relocate(vm).unwrap();
+        cairo_runner
+    };
+
+    c.bench_function("fibonacci_100_runs_fresh_vm", |b| {
+        b.iter(|| {
+            for _ in 0..100 {
+                let mut vm = VirtualMachine::new(false, Vec::new());
+                black_box(run(&mut vm));
+            }
+        })
+    });
+    let mut vm = VirtualMachine::new(false, Vec::new());
+    c.bench_function("fibonacci_100_runs_reset_vm", |b| {
+        b.iter(|| {
+            for _ in 0..100 {
+                vm.reset();
+                black_box(run(&mut vm));
+            }
+        })
+    });
+}
+
+// Runs the first 1000 steps of a hint heavy program one step at a time, each step being a
+// separate run_for_steps call
+pub fn single_step_benchmark(c: &mut Criterion) {
+    let program = Program::from_file(
+        Path::new("cairo_programs/benchmarks/keccak_integration_benchmark.json"),
+        Some("main"),
+    )
+    .expect("Failed to deserialize program");
+    let mut hint_executor = BuiltinHintProcessor::new_empty();
+    c.bench_function("keccak_integration_1000_single_steps", |b| {
+        b.iter_batched(
+            || {
+                let mut cairo_runner = CairoRunner::new(&program, "all", false).unwrap();
+                let mut vm = VirtualMachine::new(false, Vec::new());
+                cairo_runner.initialize(&mut vm).unwrap();
+                (cairo_runner, vm)
+            },
+            |(mut cairo_runner, mut vm)| {
+                for _ in 0..1000 {
+                    cairo_runner
+                        .run_for_steps(1, &mut vm, &mut hint_executor)
+                        .unwrap();
+                }
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+// Runs 100000 steps of a `jmp rel 0` loop, where every step executes the same instruction, so
+// that decoding it dominates unless it is cached
+pub fn tight_loop_benchmark(c: &mut Criterion) {
+    let program = ProgramBuilder::new()
+        .data(vec![
+            MaybeRelocatable::from(Felt::new(0x10780017fff7fff_u64)),
+            MaybeRelocatable::from(Felt::new(0)),
+        ])
+        .main(0)
+        .build()
+        .unwrap();
+    let mut hint_executor = BuiltinHintProcessor::new_empty();
+    c.bench_function("jmp_rel_0_100000_steps", |b| {
+        b.iter_batched(
+            || {
+                let mut cairo_runner = CairoRunner::new(&program, "all", false).unwrap();
+                let mut vm = VirtualMachine::new(false, Vec::new());
+                cairo_runner.initialize(&mut vm).unwrap();
+                (cairo_runner, vm)
+            },
+            |(mut cairo_runner, mut vm)| {
+                cairo_runner
+                    .run_for_steps(black_box(100_000), &mut vm, &mut hint_executor)
+                    .unwrap()
+            },
+            BatchSize::SmallInput,
+        )
+    });
+}
+
+// Sums a 100000 element array of full size felts from a hint, reading each element as a
+// borrowed value or cloning it as the memory getters used to
+pub fn hint_array_read_benchmark(c: &mut Criterion) {
+    const ARRAY_LEN: usize = 100_000;
+    let mut vm = VirtualMachine::new(false, Vec::new());
+    for _ in 0..3 {
+        vm.add_memory_segment();
+    }
+    let array: Vec<MaybeRelocatable> = (0..ARRAY_LEN as i64)
+        .map(|i| MaybeRelocatable::from(Felt::new(-i - 1)))
+        .collect();
+    vm.load_data(&Relocatable::from((2, 0)).into(), &array)
+        .unwrap();
+    vm.insert_value(&Relocatable::from((1, 0)), Relocatable::from((2, 0)))
+        .unwrap();
+
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    let read_array = |clone_values: bool| {
+        HintFunc(Box::new(
+            move |vm: &mut VirtualMachine,
+                  _exec_scopes: &mut ExecutionScopes,
+                  ids_data: &HashMap<String, HintReference>,
+                  ap_tracking: &ApTracking,
+                  _constants: &HashMap<String, Felt>|
+                  -> Result<(), HintError> {
+                let array = get_ptr_from_var_name("array", vm, ids_data, ap_tracking)?;
+                let mut sum = Felt::new(0);
+                for i in 0..ARRAY_LEN {
+                    let value = vm.get_integer(&(array + i))?;
+                    if clone_values {
+                        sum += &value.into_owned();
+                    } else {
+                        sum += value.as_ref();
+                    }
+                }
+                black_box(sum);
+                Ok(())
+            },
+        ))
+    };
+    hint_processor.add_hint("read_array".to_string(), Arc::new(read_array(false)));
+    hint_processor.add_hint("read_array_cloned".to_string(), Arc::new(read_array(true)));
+
+    let ids_data = HashMap::from([("array".to_string(), HintReference::new_simple(0))]);
+    for code in ["read_array", "read_array_cloned"] {
+        let hint_data: Box<dyn Any> = Box::new(HintProcessorData::new_default(
+            code.to_string(),
+            ids_data.clone(),
+        ));
+        c.bench_function(&format!("{code}_100000_through_hint"), |b| {
+            b.iter(|| {
+                hint_processor
+                    .execute_hint(
+                        &mut vm,
+                        &mut ExecutionScopes::new(),
+                        &hint_data,
+                        &HashMap::new(),
+                        &mut RunResources::default(),
+                    )
+                    .unwrap()
+            })
+        });
+    }
+}
+
 fn build_bench_strings() -> Vec<(String, String)> {
     let mut full_string = Vec::<(String, String)>::new();
 
@@ -59,5 +299,14 @@ fn build_bench_strings() -> Vec<(String, String)> {
     full_string
 }
 
-criterion_group!(benches, criterion_benchmarks);
+criterion_group!(
+    benches,
+    criterion_benchmarks,
+    load_data_benchmark,
+    pedersen_cache_benchmark,
+    vm_reuse_benchmark,
+    single_step_benchmark,
+    tight_loop_benchmark,
+    hint_array_read_benchmark
+);
 criterion_main!(benches);
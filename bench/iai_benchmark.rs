@@ -23,6 +23,7 @@ macro_rules! iai_bench_expand_prog {
                 false,
                 "all",
                 false,
+                false,
                 &mut hint_executor,
             )
         }
@@ -0,0 +1,18 @@
+struct Pair {
+    a: felt,
+    b: felt*,
+}
+
+func sum_with_array(x: felt, arr: felt*) -> (res: felt) {
+    return (res=x + arr[0] + arr[1]);
+}
+
+func sum_pair(pair: Pair*) -> (res: felt) {
+    return (res=pair.a + pair.b[0]);
+}
+
+func main() {
+    let (res) = sum_with_array(1, new (2, 3));
+    assert res = 6;
+    return ();
+}
@@ -0,0 +1,8 @@
+// The local `skipped` is never written nor read, leaving a hole at [fp] in the execution segment
+func main() {
+    alloc_locals;
+    local skipped;
+    local used = 5;
+    assert used = 5;
+    return ();
+}
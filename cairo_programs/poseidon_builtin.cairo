@@ -0,0 +1,17 @@
+%builtins poseidon
+from starkware.cairo.common.cairo_builtins import PoseidonBuiltin
+from starkware.cairo.common.poseidon_state import PoseidonBuiltinState
+
+func main{poseidon_ptr: PoseidonBuiltin*}() {
+    // poseidon_hash(1, 2) is the first element of the permutation of [1, 2, 2].
+    assert poseidon_ptr[0].input = PoseidonBuiltinState(s0=1, s1=2, s2=2);
+
+    // Chain the output of the first permutation into a second one.
+    assert poseidon_ptr[1].input = PoseidonBuiltinState(
+        s0=poseidon_ptr[0].output.s0, s1=3, s2=2
+    );
+    tempvar result = poseidon_ptr[1].output.s0;
+
+    let poseidon_ptr = poseidon_ptr + 2 * PoseidonBuiltin.SIZE;
+    return ();
+}
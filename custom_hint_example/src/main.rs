@@ -43,6 +43,7 @@ fn main() {
         false,
         "all",
         false,
+        false,
         &mut hint_processor,
     )
     .expect("Couldn't run program");
@@ -0,0 +1,14 @@
+#![no_std]
+
+extern crate alloc;
+
+use alloc::string::String;
+use felt::{Felt, FeltOps};
+
+/// Parses both hex strings and returns `(a + b) * a` as a fixed-width hex string.
+pub fn add_mul(a: &str, b: &str) -> Option<String> {
+    let a = Felt::from_hex(a).ok()?;
+    let b = Felt::from_hex(b).ok()?;
+    let result = (&a + &b) * a;
+    Some(result.to_fixed_hex_string())
+}
@@ -0,0 +1,66 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use felt::{Felt, FeltOps, NewFelt, PRIME_STR};
+use num_bigint::BigUint;
+use num_integer::Integer;
+use num_traits::Num;
+
+// Small values, like most of the felts handled by the VM, and full size values
+fn felt_operands(n: usize) -> (Vec<Felt>, Vec<Felt>) {
+    let small = (1..=n as u64)
+        .map(|i| Felt::new(i.wrapping_mul(0x9e3779b97f4a7c15)))
+        .collect();
+    let mut acc = Felt::new(-0x1234567_i64);
+    let full = (0..n)
+        .map(|_| {
+            acc = &acc * &acc + Felt::new(7);
+            acc.clone()
+        })
+        .collect();
+    (small, full)
+}
+
+pub fn criterion_benchmarks(c: &mut Criterion) {
+    let prime = BigUint::from_str_radix(&PRIME_STR[2..], 16).unwrap();
+    for (name, values) in [
+        ("small", felt_operands(1000).0),
+        ("full", felt_operands(1000).1),
+    ] {
+        let biguints: Vec<BigUint> = values.iter().map(|value| value.to_biguint()).collect();
+        c.bench_function(&format!("add {name} x1000"), |b| {
+            b.iter(|| {
+                black_box(&values)
+                    .windows(2)
+                    .map(|pair| &pair[0] + &pair[1])
+                    .collect::<Vec<_>>()
+            })
+        });
+        // Reference: the same additions reduced with mod_floor
+        c.bench_function(&format!("add {name} mod_floor x1000"), |b| {
+            b.iter(|| {
+                black_box(&biguints)
+                    .windows(2)
+                    .map(|pair| (&pair[0] + &pair[1]).mod_floor(&prime))
+                    .collect::<Vec<_>>()
+            })
+        });
+        c.bench_function(&format!("mul {name} x1000"), |b| {
+            b.iter(|| {
+                black_box(&values)
+                    .windows(2)
+                    .map(|pair| &pair[0] * &pair[1])
+                    .collect::<Vec<_>>()
+            })
+        });
+        c.bench_function(&format!("mul {name} mod_floor x1000"), |b| {
+            b.iter(|| {
+                black_box(&biguints)
+                    .windows(2)
+                    .map(|pair| (&pair[0] * &pair[1]).mod_floor(&prime))
+                    .collect::<Vec<_>>()
+            })
+        });
+    }
+}
+
+criterion_group!(benches, criterion_benchmarks);
+criterion_main!(benches);
@@ -0,0 +1,26 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use felt::{Felt, FeltOps, NewFelt};
+
+fn felt_values(n: usize) -> Vec<Felt> {
+    (1..=n as u64)
+        .map(|i| Felt::new(i.wrapping_mul(0x9e3779b97f4a7c15)))
+        .collect()
+}
+
+pub fn criterion_benchmarks(c: &mut Criterion) {
+    let values = felt_values(1000);
+    c.bench_function("mul_inverse x1000", |b| {
+        b.iter(|| {
+            black_box(&values)
+                .iter()
+                .map(|value| value.mul_inverse())
+                .collect::<Vec<_>>()
+        })
+    });
+    c.bench_function("batch_mul_inverse x1000", |b| {
+        b.iter(|| Felt::batch_mul_inverse(black_box(&values)))
+    });
+}
+
+criterion_group!(benches, criterion_benchmarks);
+criterion_main!(benches);
@@ -1,19 +1,27 @@
-use lazy_static::lazy_static;
-use num_bigint::{BigInt, BigUint, ToBigInt, U64Digits};
-use num_integer::Integer;
-use num_traits::{Bounded, FromPrimitive, Num, One, Pow, Signed, ToPrimitive, Zero};
-use serde::Deserialize;
-use std::{
-    convert::Into,
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    convert::{Infallible, Into},
     fmt,
+    hash::{Hash, Hasher},
     iter::Sum,
     ops::{
-        Add, AddAssign, BitAnd, BitOr, BitXor, Div, Mul, MulAssign, Neg, Rem, Shl, Shr, ShrAssign,
-        Sub, SubAssign,
+        Add, AddAssign, BitAnd, BitOr, BitXor, Div, Mul, MulAssign, Neg, Rem, RemAssign, Shl, Shr,
+        ShrAssign, Sub, SubAssign,
     },
 };
+use lazy_static::lazy_static;
+use num_bigint::{BigInt, BigUint, ToBigInt, U64Digits};
+use num_integer::Integer;
+use num_traits::{Bounded, CheckedRem, FromPrimitive, Num, One, Pow, Signed, ToPrimitive, Zero};
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::{FeltOps, NewFelt, ParseFeltError, FIELD};
+use crate::{FeltOps, FeltTryFromError, NewFelt, ParseFeltError, FIELD};
 
 lazy_static! {
     pub static ref CAIRO_PRIME: BigUint =
@@ -24,9 +32,22 @@ lazy_static! {
         .expect("Conversion BigUint -> BigInt can't fail");
 }
 
-#[derive(Eq, Hash, PartialEq, PartialOrd, Ord, Clone, Deserialize, Default)]
+/// A field element, always stored as its canonical representative in `[0, PRIME)`.
+///
+/// Every constructor and operation reduces its result, so two equal elements always have
+/// the same limbs. `PartialEq` and `Hash` rely on this to compare and hash the limbs
+/// directly, which keeps felts usable as `HashMap` keys whichever way they were built.
+#[derive(Eq, PartialEq, PartialOrd, Ord, Clone, Default)]
 pub struct FeltBigInt(BigUint);
 
+impl Hash for FeltBigInt {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for digit in self.0.iter_u64_digits() {
+            digit.hash(state);
+        }
+    }
+}
+
 macro_rules! from_integer {
     ($type:ty) => {
         impl From<$type> for FeltBigInt {
@@ -34,7 +55,7 @@ macro_rules! from_integer {
                 Self(
                     value
                         .try_into()
-                        .unwrap_or_else(|_| &*CAIRO_PRIME - (-value as u128)),
+                        .unwrap_or_else(|_| &*CAIRO_PRIME - BigUint::from(value.unsigned_abs())),
                 )
             }
         }
@@ -67,7 +88,7 @@ from_unsigned!(usize);
 
 impl From<BigUint> for FeltBigInt {
     fn from(value: BigUint) -> Self {
-        if value > *CAIRO_PRIME {
+        if value >= *CAIRO_PRIME {
             Self(value.mod_floor(&CAIRO_PRIME))
         } else {
             Self(value)
@@ -77,7 +98,7 @@ impl From<BigUint> for FeltBigInt {
 
 impl From<&BigUint> for FeltBigInt {
     fn from(value: &BigUint) -> Self {
-        if value > &*CAIRO_PRIME {
+        if value >= &*CAIRO_PRIME {
             Self(value.mod_floor(&CAIRO_PRIME))
         } else {
             Self(value.clone())
@@ -101,6 +122,14 @@ impl From<&BigUint> for FeltBigInt {
    }
 */
 
+impl TryFrom<&FeltBigInt> for BigUint {
+    type Error = Infallible;
+
+    fn try_from(value: &FeltBigInt) -> Result<Self, Self::Error> {
+        Ok(value.to_biguint())
+    }
+}
+
 impl From<BigInt> for FeltBigInt {
     fn from(value: BigInt) -> Self {
         (&value).into()
@@ -118,6 +147,22 @@ impl From<&BigInt> for FeltBigInt {
     }
 }
 
+/// Builds a felt from its little-endian bytes, reducing values greater than or equal to the prime.
+impl From<[u8; 32]> for FeltBigInt {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self::new(BigUint::from_bytes_le(&bytes))
+    }
+}
+
+/// Builds a felt from its little-endian bytes, like `FeltOps::try_from_bytes_le`.
+impl TryFrom<&[u8]> for FeltBigInt {
+    type Error = ParseFeltError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_from_bytes_le(bytes)
+    }
+}
+
 impl NewFelt for FeltBigInt {
     fn new<T: Into<Self>>(value: T) -> Self {
         value.into()
@@ -153,10 +198,81 @@ impl FeltOps for FeltBigInt {
         Self::new(BigUint::from_bytes_be(bytes))
     }
 
+    fn try_from_bytes_be(bytes: &[u8]) -> Result<Self, ParseFeltError> {
+        if bytes.len() > 32 {
+            return Err(ParseFeltError);
+        }
+        let value = BigUint::from_bytes_be(bytes);
+        if value >= *CAIRO_PRIME {
+            return Err(ParseFeltError);
+        }
+        Ok(FeltBigInt(value))
+    }
+
+    fn try_from_bytes_le(bytes: &[u8]) -> Result<Self, ParseFeltError> {
+        if bytes.len() > 32 {
+            return Err(ParseFeltError);
+        }
+        let value = BigUint::from_bytes_le(bytes);
+        if value >= *CAIRO_PRIME {
+            return Err(ParseFeltError);
+        }
+        Ok(FeltBigInt(value))
+    }
+
+    fn to_bytes_le(&self) -> [u8; 32] {
+        let bytes = self.0.to_bytes_le();
+        let mut fixed = [0_u8; 32];
+        fixed[..bytes.len()].copy_from_slice(&bytes);
+        fixed
+    }
+
+    fn from_le_digits(limbs: [u64; 4]) -> Self {
+        let mut digits = [0_u32; 8];
+        for (i, limb) in limbs.iter().enumerate() {
+            digits[2 * i] = *limb as u32;
+            digits[2 * i + 1] = (*limb >> 32) as u32;
+        }
+        Self::new(BigUint::from_slice(&digits))
+    }
+
+    fn to_le_digits(&self) -> [u64; 4] {
+        let mut limbs = [0_u64; 4];
+        for (limb, digit) in limbs.iter_mut().zip(self.0.iter_u64_digits()) {
+            *limb = digit;
+        }
+        limbs
+    }
+
+    fn from_hex(s: &str) -> Result<Self, ParseFeltError> {
+        let digits = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ParseFeltError);
+        }
+        BigUint::parse_bytes(digits.as_bytes(), 16)
+            .map(FeltBigInt::new)
+            .ok_or(ParseFeltError)
+    }
+
     fn to_str_radix(&self, radix: u32) -> String {
         self.0.to_str_radix(radix)
     }
 
+    fn to_fixed_hex_string(&self) -> String {
+        format!("0x{:0>64}", self.0.to_str_radix(16))
+    }
+
+    fn to_signed_felt_string(&self) -> String {
+        if self.0 > *SIGNED_FELT_MAX {
+            format!("-{}", &*CAIRO_PRIME - &self.0)
+        } else {
+            self.0.to_string()
+        }
+    }
+
     fn to_bigint(&self) -> BigInt {
         if self.is_negative() {
             BigInt::from_biguint(num_bigint::Sign::Minus, &*CAIRO_PRIME - &self.0)
@@ -173,18 +289,128 @@ impl FeltOps for FeltBigInt {
         FeltBigInt(self.0.sqrt())
     }
 
+    fn is_quad_residue(&self) -> bool {
+        // Euler's criterion: a^((p - 1) / 2) is 1 for non-zero squares and p - 1 otherwise
+        self.0.is_zero() || self.0.modpow(&SIGNED_FELT_MAX, &CAIRO_PRIME).is_one()
+    }
+
+    fn checked_sqrt(&self) -> Option<Self> {
+        if self.0.is_zero() {
+            return Some(FeltBigInt::zero());
+        }
+        if !self.is_quad_residue() {
+            return None;
+        }
+        // Tonelli-Shanks, with p - 1 = q * 2^s and q odd
+        let prime = &*CAIRO_PRIME;
+        let p_minus_one = prime - 1_u32;
+        let s = p_minus_one.trailing_zeros().unwrap_or(0);
+        let q = p_minus_one >> s;
+        // 3 generates the multiplicative group, so it isn't a square
+        let mut c = BigUint::from(3_u32).modpow(&q, prime);
+        let mut t = self.0.modpow(&q, prime);
+        let mut root = self.0.modpow(&((&q + 1_u32) >> 1_u32), prime);
+        let mut m = s;
+        while !t.is_one() {
+            // Find the least i such that t^(2^i) = 1, which is smaller than m
+            let mut i = 0;
+            let mut t_pow = t.clone();
+            while !t_pow.is_one() {
+                t_pow = (&t_pow * &t_pow).mod_floor(prime);
+                i += 1;
+            }
+            let b = c.modpow(&(BigUint::one() << (m - i - 1)), prime);
+            root = (root * &b).mod_floor(prime);
+            c = (&b * &b).mod_floor(prime);
+            t = (t * &c).mod_floor(prime);
+            m = i;
+        }
+        let other_root = prime - &root;
+        Some(FeltBigInt(root.min(other_root)))
+    }
+
     fn bits(&self) -> u64 {
         self.0.bits()
     }
+
+    fn to_bits_le(&self) -> [bool; 252] {
+        let mut bits = [false; 252];
+        for (i, bit) in bits.iter_mut().enumerate() {
+            *bit = self.0.bit(i as u64);
+        }
+        bits
+    }
+
+    fn mul_inverse(&self) -> Self {
+        // Fermat's little theorem: a^(p - 2) = a^(-1) (mod p)
+        FeltBigInt(self.0.modpow(&(&*CAIRO_PRIME - 2_u32), &CAIRO_PRIME))
+    }
+
+    fn ct_eq(&self, other: &Self) -> bool {
+        let (a, b) = (to_fixed_bytes_be(&self.0), to_fixed_bytes_be(&other.0));
+        a.iter()
+            .zip(b.iter())
+            .fold(0_u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+    }
+
+    fn batch_mul_inverse(values: &[Self]) -> Vec<Self> {
+        // prefix_products[i] holds the product of every non-zero value before index i
+        let mut prefix_products = Vec::with_capacity(values.len());
+        let mut acc = BigUint::one();
+        for value in values {
+            prefix_products.push(acc.clone());
+            if !value.is_zero() {
+                acc = reduce_product(acc * &value.0);
+            }
+        }
+
+        let mut acc_inverse = FeltBigInt(acc).mul_inverse().0;
+        let mut inverses = vec![FeltBigInt::zero(); values.len()];
+        for (i, value) in values.iter().enumerate().rev() {
+            if value.is_zero() {
+                continue;
+            }
+            inverses[i] = FeltBigInt(reduce_product(&acc_inverse * &prefix_products[i]));
+            acc_inverse = reduce_product(acc_inverse * &value.0);
+        }
+        inverses
+    }
+}
+
+// Left-pads the big-endian bytes of a canonical felt to 32 bytes
+fn to_fixed_bytes_be(value: &BigUint) -> [u8; 32] {
+    let bytes = value.to_bytes_be();
+    let mut fixed = [0_u8; 32];
+    fixed[32 - bytes.len()..].copy_from_slice(&bytes);
+    fixed
+}
+
+// Every value with fewer bits than the prime is below it
+const PRIME_BITS: u64 = 252;
+
+// Reduces a value below 2 * PRIME, such as the sum of two felts, with at most one
+// subtraction. The comparison against the prime is skipped when the bit length is enough.
+fn reduce_once(value: &mut BigUint) {
+    if value.bits() >= PRIME_BITS && *value >= *CAIRO_PRIME {
+        *value -= &*CAIRO_PRIME;
+    }
+}
+
+// Reduces a product of two felts, skipping the division when it is already below the prime
+fn reduce_product(product: BigUint) -> BigUint {
+    if product.bits() < PRIME_BITS {
+        product
+    } else {
+        product.mod_floor(&CAIRO_PRIME)
+    }
 }
 
 impl Add for FeltBigInt {
     type Output = Self;
     fn add(mut self, rhs: Self) -> Self {
         self.0 += rhs.0;
-        if self.0 >= *CAIRO_PRIME {
-            self.0 -= &*CAIRO_PRIME;
-        }
+        reduce_once(&mut self.0);
         self
     }
 }
@@ -194,9 +420,7 @@ impl<'a> Add for &'a FeltBigInt {
 
     fn add(self, rhs: Self) -> Self::Output {
         let mut sum = &self.0 + &rhs.0;
-        if sum >= *CAIRO_PRIME {
-            sum -= &*CAIRO_PRIME;
-        }
+        reduce_once(&mut sum);
         FeltBigInt(sum)
     }
 }
@@ -206,9 +430,7 @@ impl<'a> Add<&'a FeltBigInt> for FeltBigInt {
 
     fn add(mut self, rhs: &'a FeltBigInt) -> Self::Output {
         self.0 += &rhs.0;
-        if self.0 >= *CAIRO_PRIME {
-            self.0 -= &*CAIRO_PRIME;
-        }
+        reduce_once(&mut self.0);
         self
     }
 }
@@ -217,9 +439,7 @@ impl Add<u32> for FeltBigInt {
     type Output = Self;
     fn add(mut self, rhs: u32) -> Self {
         self.0 += rhs;
-        if self.0 >= *CAIRO_PRIME {
-            self.0 -= &*CAIRO_PRIME;
-        }
+        reduce_once(&mut self.0);
         self
     }
 }
@@ -228,9 +448,7 @@ impl Add<usize> for FeltBigInt {
     type Output = Self;
     fn add(mut self, rhs: usize) -> Self {
         self.0 += rhs;
-        if self.0 >= *CAIRO_PRIME {
-            self.0 -= &*CAIRO_PRIME;
-        }
+        reduce_once(&mut self.0);
         self
     }
 }
@@ -239,9 +457,7 @@ impl<'a> Add<usize> for &'a FeltBigInt {
     type Output = FeltBigInt;
     fn add(self, rhs: usize) -> Self::Output {
         let mut sum = &self.0 + rhs;
-        if sum >= *CAIRO_PRIME {
-            sum -= &*CAIRO_PRIME;
-        }
+        reduce_once(&mut sum);
         FeltBigInt(sum)
     }
 }
@@ -270,14 +486,22 @@ impl Sum for FeltBigInt {
 impl Neg for FeltBigInt {
     type Output = FeltBigInt;
     fn neg(self) -> Self::Output {
-        FeltBigInt(&*CAIRO_PRIME - self.0)
+        if self.is_zero() {
+            self
+        } else {
+            FeltBigInt(&*CAIRO_PRIME - self.0)
+        }
     }
 }
 
 impl<'a> Neg for &'a FeltBigInt {
     type Output = FeltBigInt;
     fn neg(self) -> Self::Output {
-        FeltBigInt(&*CAIRO_PRIME - &self.0)
+        if self.is_zero() {
+            self.clone()
+        } else {
+            FeltBigInt(&*CAIRO_PRIME - &self.0)
+        }
     }
 }
 
@@ -375,21 +599,21 @@ impl Sub<&FeltBigInt> for usize {
 impl Mul for FeltBigInt {
     type Output = Self;
     fn mul(self, rhs: Self) -> Self::Output {
-        FeltBigInt((self.0 * rhs.0).mod_floor(&CAIRO_PRIME))
+        FeltBigInt(reduce_product(self.0 * rhs.0))
     }
 }
 
 impl<'a> Mul for &'a FeltBigInt {
     type Output = FeltBigInt;
     fn mul(self, rhs: Self) -> Self::Output {
-        FeltBigInt((&self.0 * &rhs.0).mod_floor(&CAIRO_PRIME))
+        FeltBigInt(reduce_product(&self.0 * &rhs.0))
     }
 }
 
 impl<'a> Mul<&'a FeltBigInt> for FeltBigInt {
     type Output = FeltBigInt;
     fn mul(self, rhs: &'a FeltBigInt) -> Self::Output {
-        FeltBigInt((&self.0 * &rhs.0).mod_floor(&CAIRO_PRIME))
+        FeltBigInt(reduce_product(&self.0 * &rhs.0))
     }
 }
 
@@ -413,6 +637,37 @@ impl<'a> Pow<u32> for &'a FeltBigInt {
     }
 }
 
+impl<'a> Pow<&'a FeltBigInt> for FeltBigInt {
+    type Output = Self;
+    fn pow(self, rhs: &'a FeltBigInt) -> Self {
+        self.pow_felt(rhs)
+    }
+}
+
+impl<'a> Pow<&'a FeltBigInt> for &'a FeltBigInt {
+    type Output = FeltBigInt;
+    fn pow(self, rhs: &'a FeltBigInt) -> Self::Output {
+        self.pow_felt(rhs)
+    }
+}
+
+impl FeltBigInt {
+    /// Raises `self` to the power of `exp` modulo the field prime.
+    /// The exponent is taken as its canonical representative in `[0, PRIME)`, so a
+    /// "negative" exponent such as `-1` is treated as `PRIME - 1`. `0^0` is 1.
+    pub fn pow_felt(&self, exp: &FeltBigInt) -> FeltBigInt {
+        let mut result = BigUint::one();
+        let mut base = self.0.clone();
+        for i in 0..exp.0.bits() {
+            if exp.0.bit(i) {
+                result = reduce_product(result * &base);
+            }
+            base = reduce_product(&base * &base);
+        }
+        FeltBigInt(result)
+    }
+}
+
 impl Div for FeltBigInt {
     type Output = Self;
     fn div(self, rhs: Self) -> Self::Output {
@@ -448,6 +703,45 @@ impl<'a> Rem<&'a FeltBigInt> for FeltBigInt {
     }
 }
 
+impl<'a> Rem<&'a FeltBigInt> for &'a FeltBigInt {
+    type Output = FeltBigInt;
+    fn rem(self, rhs: &'a FeltBigInt) -> Self::Output {
+        FeltBigInt(&self.0 % &rhs.0)
+    }
+}
+
+impl Rem<u32> for FeltBigInt {
+    type Output = Self;
+    fn rem(self, rhs: u32) -> Self::Output {
+        FeltBigInt(self.0 % rhs)
+    }
+}
+
+impl<'a> Rem<u32> for &'a FeltBigInt {
+    type Output = FeltBigInt;
+    fn rem(self, rhs: u32) -> Self::Output {
+        FeltBigInt(&self.0 % rhs)
+    }
+}
+
+impl RemAssign for FeltBigInt {
+    fn rem_assign(&mut self, rhs: Self) {
+        self.0 %= rhs.0;
+    }
+}
+
+impl<'a> RemAssign<&'a FeltBigInt> for FeltBigInt {
+    fn rem_assign(&mut self, rhs: &'a FeltBigInt) {
+        self.0 %= &rhs.0;
+    }
+}
+
+impl CheckedRem for FeltBigInt {
+    fn checked_rem(&self, rhs: &Self) -> Option<Self> {
+        self.0.checked_rem(&rhs.0).map(FeltBigInt)
+    }
+}
+
 impl Zero for FeltBigInt {
     fn zero() -> Self {
         Self(BigUint::zero())
@@ -490,6 +784,9 @@ impl Num for FeltBigInt {
     }
 }
 
+/// Integer operations over the canonical representatives in `[0, PRIME)`, not over the field.
+/// `gcd` and `divides` are those of the representatives (in the field, every nonzero element
+/// divides every other), and `lcm` is reduced modulo the prime like any other result.
 impl Integer for FeltBigInt {
     fn div_floor(&self, other: &Self) -> Self {
         FeltBigInt(self.0.div_floor(&other.0))
@@ -606,6 +903,26 @@ impl<'a> Shr<u32> for &'a FeltBigInt {
     }
 }
 
+impl Shr<usize> for FeltBigInt {
+    type Output = Self;
+    fn shr(self, other: usize) -> Self::Output {
+        FeltBigInt(self.0.shr(other).mod_floor(&CAIRO_PRIME))
+    }
+}
+
+impl<'a> Shr<usize> for &'a FeltBigInt {
+    type Output = FeltBigInt;
+    fn shr(self, other: usize) -> Self::Output {
+        FeltBigInt((&self.0).shr(other).mod_floor(&CAIRO_PRIME))
+    }
+}
+
+impl ShrAssign<u32> for FeltBigInt {
+    fn shr_assign(&mut self, other: u32) {
+        self.0 = (&self.0).shr(other).mod_floor(&CAIRO_PRIME);
+    }
+}
+
 impl ShrAssign<usize> for FeltBigInt {
     fn shr_assign(&mut self, other: usize) {
         self.0 = (&self.0).shr(other).mod_floor(&CAIRO_PRIME);
@@ -636,14 +953,14 @@ impl<'a> BitAnd<FeltBigInt> for &'a FeltBigInt {
 impl<'a> BitOr for &'a FeltBigInt {
     type Output = FeltBigInt;
     fn bitor(self, rhs: Self) -> Self::Output {
-        FeltBigInt(&self.0 | &rhs.0)
+        FeltBigInt::new(&self.0 | &rhs.0)
     }
 }
 
 impl<'a> BitXor for &'a FeltBigInt {
     type Output = FeltBigInt;
     fn bitxor(self, rhs: Self) -> Self::Output {
-        FeltBigInt(&self.0 ^ &rhs.0)
+        FeltBigInt::new(&self.0 ^ &rhs.0)
     }
 }
 
@@ -666,6 +983,25 @@ impl ToPrimitive for FeltBigInt {
     }
 }
 
+macro_rules! try_from_felt_for_primitive {
+    ($type: ty, $method: ident) => {
+        impl TryFrom<&FeltBigInt> for $type {
+            type Error = FeltTryFromError;
+
+            fn try_from(value: &FeltBigInt) -> Result<Self, Self::Error> {
+                value
+                    .0
+                    .$method()
+                    .ok_or_else(|| FeltTryFromError(value.clone()))
+            }
+        }
+    };
+}
+
+try_from_felt_for_primitive!(usize, to_usize);
+try_from_felt_for_primitive!(u64, to_u64);
+try_from_felt_for_primitive!(u32, to_u32);
+
 impl FromPrimitive for FeltBigInt {
     fn from_u64(n: u64) -> Option<Self> {
         BigUint::from_u64(n).map(Self)
@@ -688,7 +1024,91 @@ impl fmt::Display for FeltBigInt {
 
 impl fmt::Debug for FeltBigInt {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.0)
+        write!(f, "{:#x}", self.0)
+    }
+}
+
+// The radix formats honor the width, fill, zero-padding and alternate (0x, 0b, 0o) flags
+impl fmt::LowerHex for FeltBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl fmt::UpperHex for FeltBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Binary for FeltBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Binary::fmt(&self.0, f)
+    }
+}
+
+impl fmt::Octal for FeltBigInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Octal::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for FeltBigInt {
+    // Human-readable formats get the decimal representation, compact ones the
+    // big-endian bytes of the canonical representative.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&self.0.to_str_radix(10))
+        } else {
+            serializer.serialize_bytes(&self.0.to_bytes_be())
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+struct FeltBigIntVisitor;
+
+#[cfg(feature = "serde")]
+impl FeltBigIntVisitor {
+    fn checked<E: de::Error>(value: BigUint) -> Result<FeltBigInt, E> {
+        if value >= *CAIRO_PRIME {
+            return Err(E::custom(format!(
+                "{} is not a valid field element, it must be smaller than the prime",
+                value
+            )));
+        }
+        Ok(FeltBigInt(value))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> de::Visitor<'de> for FeltBigIntVisitor {
+    type Value = FeltBigInt;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal string or big-endian bytes representing a field element")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        let value = BigUint::from_str_radix(value, 10)
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(value), &"a decimal string"))?;
+        Self::checked(value)
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        Self::checked(BigUint::from_bytes_be(value))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for FeltBigInt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(FeltBigIntVisitor)
+        } else {
+            deserializer.deserialize_bytes(FeltBigIntVisitor)
+        }
     }
 }
 
@@ -698,6 +1118,12 @@ impl fmt::Display for ParseFeltError {
     }
 }
 
+impl fmt::Display for FeltTryFromError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} doesn't fit in the target integer type", self.0)
+    }
+}
+
 #[macro_export]
 macro_rules! felt_str {
     ($val: expr) => {
@@ -716,6 +1142,362 @@ macro_rules! felt_str {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::SignedFeltDisplay;
+
+    // Random canonical representatives, half of them small enough to hit common factors
+    fn random_felts(rng: &mut impl rand::Rng, n: usize) -> Vec<FeltBigInt> {
+        (0..n)
+            .map(|i| {
+                if i % 2 == 0 {
+                    FeltBigInt::new(rng.gen_range(1..u64::MAX) as u128 * 6)
+                } else {
+                    let bytes: [u8; 32] = rng.gen();
+                    FeltBigInt::new(BigUint::from_bytes_be(&bytes))
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn format_radix() {
+        let felt = FeltBigInt::new(0xbeef_u32);
+        assert_eq!(format!("{:x}", felt), "beef");
+        assert_eq!(format!("{:X}", felt), "BEEF");
+        assert_eq!(format!("{:b}", FeltBigInt::new(5_u32)), "101");
+        assert_eq!(format!("{:o}", FeltBigInt::new(8_u32)), "10");
+    }
+
+    #[test]
+    fn format_radix_alternate() {
+        let felt = FeltBigInt::new(10_u32);
+        assert_eq!(format!("{:#x}", felt), "0xa");
+        assert_eq!(format!("{:#X}", felt), "0xA");
+        assert_eq!(format!("{:#b}", felt), "0b1010");
+        assert_eq!(format!("{:#o}", felt), "0o12");
+    }
+
+    #[test]
+    fn format_radix_padding() {
+        let felt = FeltBigInt::new(0xff_u32);
+        assert_eq!(format!("{:064x}", felt), format!("{}ff", "0".repeat(62)));
+        assert_eq!(format!("{:#010x}", felt), "0x000000ff");
+        assert_eq!(format!("{:>6b}", FeltBigInt::new(3_u32)), "    11");
+        let max = FeltBigInt::zero() - FeltBigInt::one();
+        assert_eq!(
+            format!("{:064x}", max),
+            "0800000000000011000000000000000000000000000000000000000000000000"
+        );
+    }
+
+    #[test]
+    fn format_radix_zero() {
+        let zero = FeltBigInt::zero();
+        assert_eq!(format!("{:x}", zero), "0");
+        assert_eq!(format!("{:#x}", zero), "0x0");
+        assert_eq!(format!("{:04b}", zero), "0000");
+        assert_eq!(format!("{:#o}", zero), "0o0");
+        assert_eq!(format!("{:?}", zero), "0x0");
+    }
+
+    #[test]
+    fn format_debug_is_hex() {
+        assert_eq!(format!("{:?}", FeltBigInt::new(255_u32)), "0xff");
+        assert_eq!(format!("{}", FeltBigInt::new(255_u32)), "255");
+    }
+
+    #[test]
+    fn integer_matches_biguint_over_representatives() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x1234);
+        let felts = random_felts(&mut rng, 64);
+        for a in &felts {
+            for b in felts.iter().step_by(7) {
+                let (x, y) = (&a.0, &b.0);
+                assert_eq!(a.gcd(b).0, x.gcd(y));
+                assert_eq!(a.lcm(b).0, x.lcm(y) % &*CAIRO_PRIME);
+                assert_eq!(a.div_floor(b).0, x.div_floor(y));
+                assert_eq!(a.mod_floor(b).0, x.mod_floor(y));
+                let (div, rem) = a.div_mod_floor(b);
+                assert_eq!((div.0, rem.0), x.div_mod_floor(y));
+                assert_eq!(a.is_multiple_of(b), x.is_multiple_of(y));
+                assert_eq!(a.is_even(), x.is_even());
+            }
+        }
+    }
+
+    // Random felts along with the values around the prime and around the bit lengths at
+    // which the reductions can't be skipped anymore
+    fn arithmetic_operands() -> Vec<FeltBigInt> {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x7ea5);
+        let prime = &*CAIRO_PRIME;
+        let mut operands = random_felts(&mut rng, 32);
+        for value in [
+            BigUint::zero(),
+            BigUint::one(),
+            BigUint::from(2_u32),
+            prime - 1_u32,
+            prime - 2_u32,
+            prime >> 1_u32,
+            (prime >> 1_u32) + 1_u32,
+            BigUint::one() << 125_u32,
+            (BigUint::one() << 126_u32) - 1_u32,
+            (BigUint::one() << 250_u32) - 1_u32,
+            BigUint::one() << 250_u32,
+            (BigUint::one() << 251_u32) - 1_u32,
+            BigUint::one() << 251_u32,
+            (BigUint::one() << 251_u32) + 1_u32,
+        ] {
+            operands.push(FeltBigInt(value));
+        }
+        operands
+    }
+
+    fn naive_mod(value: BigInt) -> BigUint {
+        value
+            .mod_floor(&CAIRO_SIGNED_PRIME)
+            .to_biguint()
+            .expect("mod_floor is always positive")
+    }
+
+    #[test]
+    fn prime_bits() {
+        assert_eq!(CAIRO_PRIME.bits(), PRIME_BITS);
+        assert_eq!(FeltBigInt::max_value().0.bits(), PRIME_BITS);
+    }
+
+    #[test]
+    fn arithmetic_matches_naive_reduction() {
+        let operands = arithmetic_operands();
+        for a in &operands {
+            for b in &operands {
+                let (x, y) = (a.0.to_bigint().unwrap(), b.0.to_bigint().unwrap());
+                let sum = naive_mod(&x + &y);
+                let difference = naive_mod(&x - &y);
+                let product = naive_mod(&x * &y);
+
+                assert_eq!((a + b).0, sum);
+                assert_eq!((a.clone() + b).0, sum);
+                assert_eq!((a.clone() + b.clone()).0, sum);
+                let mut acc = a.clone();
+                acc += b;
+                assert_eq!(acc.0, sum);
+
+                assert_eq!((a - b).0, difference);
+                assert_eq!((a.clone() - b).0, difference);
+                assert_eq!((a.clone() - b.clone()).0, difference);
+                let mut acc = a.clone();
+                acc -= b;
+                assert_eq!(acc.0, difference);
+
+                assert_eq!((a * b).0, product);
+                assert_eq!((a.clone() * b).0, product);
+                assert_eq!((a.clone() * b.clone()).0, product);
+                let mut acc = a.clone();
+                acc *= b;
+                assert_eq!(acc.0, product);
+            }
+            let x = a.0.to_bigint().unwrap();
+            let (u32_max, usize_max) = (BigInt::from(u32::MAX), BigInt::from(usize::MAX));
+            assert_eq!((-a).0, naive_mod(-&x));
+            assert_eq!((-a.clone()).0, naive_mod(-&x));
+            assert_eq!((a.clone() + u32::MAX).0, naive_mod(&x + &u32_max));
+            assert_eq!((a.clone() + usize::MAX).0, naive_mod(&x + &usize_max));
+            assert_eq!((a + usize::MAX).0, naive_mod(&x + &usize_max));
+            assert_eq!((a.clone() - u32::MAX).0, naive_mod(&x - &u32_max));
+            assert_eq!((a - u32::MAX).0, naive_mod(&x - &u32_max));
+            assert_eq!((a.clone() - usize::MAX).0, naive_mod(&x - &usize_max));
+            assert_eq!((usize::MAX - a).0, naive_mod(&usize_max - &x));
+            assert_eq!(a.pow(3_u32).0, naive_mod(x.pow(3_u32)));
+        }
+    }
+
+    #[test]
+    fn gcd_is_over_integers() {
+        let a = FeltBigInt::new(12);
+        let b = FeltBigInt::new(18);
+        assert_eq!(a.gcd(&b), FeltBigInt::new(6));
+        assert_eq!(a.lcm(&b), FeltBigInt::new(36));
+        assert!(FeltBigInt::new(6).is_multiple_of(&FeltBigInt::new(3)));
+        // 3 is invertible in the field, but 7 isn't a multiple of it as an integer
+        assert!(!FeltBigInt::new(7).is_multiple_of(&FeltBigInt::new(3)));
+    }
+
+    #[test]
+    fn try_from_felt_for_primitives() {
+        let max = FeltBigInt::new(u64::MAX);
+        assert_eq!(u64::try_from(&max), Ok(u64::MAX));
+        assert_eq!(u32::try_from(&FeltBigInt::new(u32::MAX)), Ok(u32::MAX));
+        assert_eq!(usize::try_from(&FeltBigInt::new(7_u8)), Ok(7));
+
+        let too_big = FeltBigInt::new(u32::MAX as u64 + 1);
+        assert_eq!(u32::try_from(&too_big), Err(FeltTryFromError(too_big)));
+    }
+
+    #[test]
+    fn try_from_felt_keeps_oversized_value() {
+        let too_big = FeltBigInt::new(u64::MAX as u128 + 1);
+        assert_eq!(
+            u64::try_from(&too_big),
+            Err(FeltTryFromError(too_big.clone()))
+        );
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(
+            usize::try_from(&too_big),
+            Err(FeltTryFromError(too_big.clone()))
+        );
+        assert_eq!(
+            FeltTryFromError(too_big).to_string(),
+            "18446744073709551616 doesn't fit in the target integer type"
+        );
+    }
+
+    #[test]
+    fn small_felts() {
+        assert_eq!(FeltBigInt::two(), FeltBigInt::new(2));
+        assert_eq!(FeltBigInt::three(), FeltBigInt::new(3));
+    }
+
+    #[test]
+    fn felt_const_matches_parse_bytes() {
+        assert_eq!(crate::felt_const!("0"), FeltBigInt::zero());
+        assert_eq!(
+            crate::felt_const!("0x11"),
+            FeltBigInt::parse_bytes(b"11", 16).unwrap()
+        );
+        assert_eq!(
+            crate::felt_const!("0X3FFFFFFFFFFFFFFFF"),
+            FeltBigInt::parse_bytes(b"3ffffffffffffffff", 16).unwrap()
+        );
+        assert_eq!(
+            crate::felt_const!("340282366920938463463374607431768211456"),
+            FeltBigInt::parse_bytes(b"340282366920938463463374607431768211456", 10).unwrap()
+        );
+        assert_eq!(
+            crate::felt_const!("0x800000000000011000000000000000000000000000000000000000000000000"),
+            FeltBigInt::new(-1)
+        );
+    }
+
+    #[test]
+    fn felt_limbs_from_str_limb_order() {
+        assert_eq!(
+            crate::felt_limbs_from_str("0x3000000000000000200000000000000010000000000000000"),
+            [0, 1, 2, 3]
+        );
+        assert_eq!(
+            FeltBigInt::from_le_digits(crate::PRIME_LIMBS),
+            FeltBigInt::zero()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Felt literal is greater than or equal to the prime")]
+    fn felt_limbs_from_str_rejects_prime() {
+        crate::felt_limbs_from_str(crate::PRIME_STR);
+    }
+
+    #[test]
+    #[should_panic(expected = "Felt literal is greater than or equal to the prime")]
+    fn felt_limbs_from_str_rejects_overflowing_value() {
+        crate::felt_limbs_from_str(
+            "0x10000000000000000000000000000000000000000000000000000000000000000",
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid digit in felt literal")]
+    fn felt_limbs_from_str_rejects_hex_digit_in_decimal() {
+        crate::felt_limbs_from_str("12a");
+    }
+
+    #[test]
+    fn try_from_bytes_be_rejects_prime() {
+        let prime_bytes = CAIRO_PRIME.to_bytes_be();
+        assert_eq!(
+            FeltBigInt::try_from_bytes_be(&prime_bytes),
+            Err(ParseFeltError)
+        );
+        // The reducing variant maps the prime to zero
+        assert_eq!(FeltBigInt::from_bytes_be(&prime_bytes), FeltBigInt::zero());
+        let prime_minus_one = (&*CAIRO_PRIME - 1_u32).to_bytes_be();
+        assert_eq!(
+            FeltBigInt::try_from_bytes_be(&prime_minus_one),
+            Ok(FeltBigInt::new(-1))
+        );
+    }
+
+    #[test]
+    fn try_from_bytes_le_rejects_prime() {
+        let prime_bytes = CAIRO_PRIME.to_bytes_le();
+        assert_eq!(
+            FeltBigInt::try_from_bytes_le(&prime_bytes),
+            Err(ParseFeltError)
+        );
+        let prime_minus_one = (&*CAIRO_PRIME - 1_u32).to_bytes_le();
+        assert_eq!(
+            FeltBigInt::try_from_bytes_le(&prime_minus_one),
+            Ok(FeltBigInt::new(-1))
+        );
+    }
+
+    #[test]
+    fn try_from_bytes_rejects_oversized_slices() {
+        let mut bytes = [0_u8; 33];
+        bytes[32] = 1;
+        assert_eq!(FeltBigInt::try_from_bytes_be(&bytes), Err(ParseFeltError));
+        assert_eq!(FeltBigInt::try_from_bytes_le(&bytes), Err(ParseFeltError));
+        assert_eq!(FeltBigInt::from_bytes_be(&bytes), FeltBigInt::one());
+    }
+
+    #[test]
+    fn try_from_bytes_accepts_short_slices() {
+        assert_eq!(FeltBigInt::try_from_bytes_be(&[]), Ok(FeltBigInt::zero()));
+        assert_eq!(
+            FeltBigInt::try_from_bytes_be(&[1, 0]),
+            Ok(FeltBigInt::new(256))
+        );
+        assert_eq!(
+            FeltBigInt::try_from_bytes_le(&[1, 0]),
+            Ok(FeltBigInt::one())
+        );
+    }
+
+    #[test]
+    fn to_bytes_le_round_trip() {
+        assert_eq!(FeltBigInt::zero().to_bytes_le(), [0_u8; 32]);
+        let mut expected = [0_u8; 32];
+        expected[0] = 0x34;
+        expected[1] = 0x12;
+        assert_eq!(FeltBigInt::new(0x1234).to_bytes_le(), expected);
+        let felt = FeltBigInt::new(-1);
+        assert_eq!(FeltBigInt::try_from_bytes_le(&felt.to_bytes_le()), Ok(felt));
+    }
+
+    #[test]
+    fn signed_felt_string_small_values() {
+        assert_eq!(FeltBigInt::zero().to_signed_felt_string(), "0");
+        assert_eq!(FeltBigInt::one().to_signed_felt_string(), "1");
+    }
+
+    #[test]
+    fn signed_felt_string_prime_minus_one() {
+        assert_eq!(FeltBigInt::new(-1).to_signed_felt_string(), "-1");
+        assert_eq!(format!("{}", SignedFeltDisplay(&FeltBigInt::new(-1))), "-1");
+    }
+
+    #[test]
+    fn signed_felt_string_half_prime() {
+        let half_prime = FeltBigInt::new(SIGNED_FELT_MAX.clone());
+        assert_eq!(
+            half_prime.to_signed_felt_string(),
+            SIGNED_FELT_MAX.to_string()
+        );
+        assert_eq!(
+            (half_prime + 1_u32).to_signed_felt_string(),
+            format!("-{}", &*SIGNED_FELT_MAX)
+        );
+    }
 
     #[test]
     fn add_felts_within_field() {
@@ -804,4 +1586,593 @@ mod tests {
         let d = c.neg();
         assert_eq!(d, FeltBigInt::new(10_i32));
     }
+
+    #[test]
+    fn from_biguint_matches_parse_bytes() {
+        let below_prime = BigUint::from(1_u64 << 63);
+        let at_prime = CAIRO_PRIME.clone();
+        let above_prime = &*CAIRO_PRIME + 5_u32;
+        for value in [below_prime, at_prime, above_prime] {
+            let felt = FeltBigInt::from(value.clone());
+            assert_eq!(
+                Some(felt.clone()),
+                FeltBigInt::parse_bytes(value.to_str_radix(10).as_bytes(), 10)
+            );
+            assert!(felt.to_biguint() < *CAIRO_PRIME);
+        }
+        assert_eq!(FeltBigInt::from(CAIRO_PRIME.clone()), FeltBigInt::zero());
+        assert_eq!(FeltBigInt::from(&*CAIRO_PRIME + 5_u32), FeltBigInt::new(5));
+    }
+
+    #[test]
+    fn biguint_round_trip() {
+        let felt = FeltBigInt::max_value();
+        let big_uint = BigUint::try_from(&felt).unwrap();
+        assert_eq!(big_uint, felt.to_biguint());
+        assert_eq!(FeltBigInt::from(big_uint), felt);
+    }
+
+    #[test]
+    fn ct_eq_matches_eq() {
+        // xorshift64, to get reproducible pseudo-random felts without extra dependencies
+        let mut state = 0x2545f4914f6cdd1d_u64;
+        let mut next_felt = || {
+            let mut limbs = [0_u64; 4];
+            for limb in limbs.iter_mut() {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                *limb = state;
+            }
+            FeltBigInt::from(BigUint::from_slice(&[
+                limbs[0] as u32,
+                (limbs[0] >> 32) as u32,
+                limbs[1] as u32,
+                (limbs[1] >> 32) as u32,
+                limbs[2] as u32,
+                (limbs[2] >> 32) as u32,
+                limbs[3] as u32,
+                (limbs[3] >> 32) as u32,
+            ]))
+        };
+        for i in 0..4000 {
+            let a = next_felt();
+            let b = if i % 2 == 0 { a.clone() } else { next_felt() };
+            assert_eq!(a.ct_eq(&b), a == b);
+            assert_eq!(b.ct_eq(&a), a == b);
+        }
+        assert!(FeltBigInt::zero().ct_eq(&FeltBigInt::zero()));
+        assert!(!FeltBigInt::zero().ct_eq(&FeltBigInt::one()));
+        assert!(FeltBigInt::max_value().ct_eq(&FeltBigInt::new(-1)));
+        assert!(!FeltBigInt::max_value().ct_eq(&FeltBigInt::one()));
+    }
+
+    #[test]
+    fn shr_boundaries() {
+        let a = FeltBigInt::max_value();
+        assert_eq!(&a >> 0_usize, a);
+        assert_eq!(&a >> 250_usize, FeltBigInt::new(2));
+        assert_eq!(&a >> 251_usize, FeltBigInt::one());
+        assert_eq!(&a >> 252_usize, FeltBigInt::zero());
+        assert_eq!(a.clone() >> 1000_usize, FeltBigInt::zero());
+        assert_eq!(&a >> 251_u32, &a >> 251_usize);
+    }
+
+    #[test]
+    fn shr_assign_boundaries() {
+        let mut a = FeltBigInt::max_value();
+        a >>= 250_u32;
+        assert_eq!(a, FeltBigInt::new(2));
+        a >>= 1_usize;
+        assert_eq!(a, FeltBigInt::one());
+        a >>= 1_u32;
+        assert_eq!(a, FeltBigInt::zero());
+
+        let mut b = FeltBigInt::max_value();
+        b >>= 252_usize;
+        assert_eq!(b, FeltBigInt::zero());
+    }
+
+    #[test]
+    fn bits_and_to_bits_le() {
+        assert_eq!(FeltBigInt::zero().bits(), 0);
+        assert_eq!(FeltBigInt::zero().to_bits_le(), [false; 252]);
+        assert_eq!(FeltBigInt::max_value().bits(), 252);
+
+        let mut sample = BigUint::from(0x9e3779b97f4a7c15_u64);
+        for _ in 0..100 {
+            sample = (&sample * &sample + 7_u32).mod_floor(&CAIRO_PRIME);
+            let felt = FeltBigInt::from(sample.clone());
+            assert_eq!(felt.bits(), sample.bits());
+            let bits = felt.to_bits_le();
+            let rebuilt = bits
+                .iter()
+                .rev()
+                .fold(BigUint::zero(), |acc, bit| (acc << 1_u32) + *bit as u32);
+            assert_eq!(rebuilt, sample);
+            assert!(bits[felt.bits() as usize..].iter().all(|bit| !bit));
+        }
+    }
+
+    #[test]
+    fn from_hex_valid() {
+        assert_eq!(FeltBigInt::from_hex("0x1aF"), Ok(FeltBigInt::new(0x1af)));
+        assert_eq!(FeltBigInt::from_hex("0X1Af"), Ok(FeltBigInt::new(0x1af)));
+        assert_eq!(FeltBigInt::from_hex("1af"), Ok(FeltBigInt::new(0x1af)));
+        assert_eq!(FeltBigInt::from_hex("0x0"), Ok(FeltBigInt::zero()));
+        assert_eq!(
+            FeltBigInt::from_hex(&format!("0x{}", CAIRO_PRIME.to_str_radix(16))),
+            Ok(FeltBigInt::zero())
+        );
+        assert_eq!(
+            FeltBigInt::from_hex(&(&*CAIRO_PRIME + 2_u32).to_str_radix(16)),
+            Ok(FeltBigInt::new(2))
+        );
+    }
+
+    #[test]
+    fn from_hex_invalid() {
+        for invalid in ["", "0x", "0xg1", "12 3", "+12", "1_0", "-1", "0x0x1"] {
+            assert_eq!(FeltBigInt::from_hex(invalid), Err(ParseFeltError));
+        }
+    }
+
+    #[test]
+    fn to_fixed_hex_string() {
+        assert_eq!(
+            FeltBigInt::zero().to_fixed_hex_string(),
+            format!("0x{}", "0".repeat(64))
+        );
+        assert_eq!(
+            FeltBigInt::new(0xabc).to_fixed_hex_string(),
+            format!("0x{}abc", "0".repeat(61))
+        );
+        let max = FeltBigInt::max_value().to_fixed_hex_string();
+        assert_eq!(
+            max,
+            "0x0800000000000011000000000000000000000000000000000000000000000000"
+        );
+        assert_eq!(FeltBigInt::from_hex(&max), Ok(FeltBigInt::max_value()));
+    }
+
+    #[test]
+    fn rem_matches_bigint_rem() {
+        let mut state = 0x853c49e6748fea9b_u64;
+        let mut next_biguint = |limbs: usize| {
+            let digits: Vec<u32> = (0..limbs * 2)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state as u32
+                })
+                .collect();
+            BigUint::from_slice(&digits).mod_floor(&CAIRO_PRIME)
+        };
+        for i in 0..1000 {
+            let a = next_biguint(4);
+            let b = next_biguint(1 + i % 4) + 1_u32;
+            let (felt_a, felt_b) = (FeltBigInt::from(a.clone()), FeltBigInt::from(b.clone()));
+            let expected = FeltBigInt::from(BigInt::from(a.clone()) % BigInt::from(b));
+            assert_eq!(&felt_a % &felt_b, expected);
+            assert_eq!(felt_a.clone() % &felt_b, expected);
+            assert_eq!(felt_a.clone() % felt_b.clone(), expected);
+            assert_eq!(felt_a.checked_rem(&felt_b), Some(expected.clone()));
+            assert_eq!(felt_a.mod_floor(&felt_b), expected);
+
+            let mut assigned = felt_a.clone();
+            assigned %= &felt_b;
+            assert_eq!(assigned, expected);
+            let mut assigned = felt_a.clone();
+            assigned %= felt_b;
+            assert_eq!(assigned, expected);
+
+            let small = (i as u32).wrapping_mul(2654435761) | 1;
+            assert_eq!(&felt_a % small, FeltBigInt::from(&a % small));
+        }
+    }
+
+    #[test]
+    fn checked_rem_by_zero() {
+        assert_eq!(FeltBigInt::new(5).checked_rem(&FeltBigInt::zero()), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rem_by_zero_panics() {
+        let _ = FeltBigInt::new(5) % FeltBigInt::zero();
+    }
+
+    #[test]
+    fn is_quad_residue() {
+        assert!(FeltBigInt::zero().is_quad_residue());
+        assert!(FeltBigInt::one().is_quad_residue());
+        assert!(FeltBigInt::new(4).is_quad_residue());
+        // 3 generates the multiplicative group
+        assert!(!FeltBigInt::new(3).is_quad_residue());
+        assert!(FeltBigInt::new(2).is_quad_residue());
+        assert!(FeltBigInt::new(-1).is_quad_residue());
+    }
+
+    #[test]
+    fn checked_sqrt_returns_smaller_root() {
+        assert_eq!(FeltBigInt::zero().checked_sqrt(), Some(FeltBigInt::zero()));
+        assert_eq!(FeltBigInt::new(4).checked_sqrt(), Some(FeltBigInt::new(2)));
+        assert_eq!(FeltBigInt::new(3).checked_sqrt(), None);
+        assert_eq!(
+            FeltBigInt::new(-1).checked_sqrt(),
+            Some(FeltBigInt::new(
+                BigUint::parse_bytes(
+                    b"839237011175385726789510135931031354814687537620706622849166492834709948446",
+                    10
+                )
+                .unwrap()
+            ))
+        );
+    }
+
+    #[test]
+    fn checked_sqrt_of_squares() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x5147);
+        for x in random_felts(&mut rng, 64) {
+            let square = &x * &x;
+            let root = square.checked_sqrt().unwrap();
+            assert!(root == x || root == -&x);
+            assert!(root.0 <= *SIGNED_FELT_MAX);
+            assert!((&square * &FeltBigInt::new(3)).checked_sqrt().is_none());
+        }
+    }
+
+    #[test]
+    fn mul_inverse_of_felt() {
+        let a = FeltBigInt::new(7);
+        assert_eq!(&a * &a.mul_inverse(), FeltBigInt::one());
+        assert_eq!(
+            FeltBigInt::max_value().mul_inverse(),
+            FeltBigInt::max_value()
+        );
+        assert_eq!(FeltBigInt::zero().mul_inverse(), FeltBigInt::zero());
+    }
+
+    #[test]
+    fn batch_mul_inverse_matches_mul_inverse() {
+        let values = vec![
+            FeltBigInt::new(1),
+            FeltBigInt::new(2),
+            FeltBigInt::zero(),
+            FeltBigInt::new(u64::MAX),
+            FeltBigInt::new(-3),
+            FeltBigInt::zero(),
+            FeltBigInt::max_value(),
+        ];
+        let inverses = FeltBigInt::batch_mul_inverse(&values);
+        assert_eq!(inverses.len(), values.len());
+        for (value, inverse) in values.iter().zip(inverses.iter()) {
+            assert_eq!(inverse, &value.mul_inverse());
+            if !value.is_zero() {
+                assert_eq!(value * inverse, FeltBigInt::one());
+            }
+        }
+    }
+
+    #[test]
+    fn batch_mul_inverse_empty_and_zeros() {
+        assert_eq!(FeltBigInt::batch_mul_inverse(&[]), Vec::new());
+        assert_eq!(
+            FeltBigInt::batch_mul_inverse(&[FeltBigInt::zero(), FeltBigInt::zero()]),
+            vec![FeltBigInt::zero(), FeltBigInt::zero()]
+        );
+    }
+
+    #[test]
+    fn pow_felt_matches_modpow() {
+        let prime = FeltBigInt(CAIRO_PRIME.clone());
+        let bases = [
+            FeltBigInt::new(3),
+            FeltBigInt::new(u64::MAX),
+            FeltBigInt::max_value(),
+        ];
+        let exponents = [
+            FeltBigInt::zero(),
+            FeltBigInt::one(),
+            FeltBigInt::new(u32::MAX),
+            // Exponents above 2^64
+            FeltBigInt::new(u128::MAX),
+            FeltBigInt::one() << 200_u32,
+            FeltBigInt::max_value(),
+        ];
+        for base in bases.iter() {
+            for exp in exponents.iter() {
+                let expected = FeltBigInt(base.0.modpow(&exp.0, &prime.0));
+                assert_eq!(base.pow_felt(exp), expected);
+                assert_eq!(base.pow(exp), expected);
+                assert_eq!(base.clone().pow(exp), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn pow_felt_zero_base() {
+        assert_eq!(
+            FeltBigInt::zero().pow_felt(&FeltBigInt::zero()),
+            FeltBigInt::one()
+        );
+        assert_eq!(
+            FeltBigInt::zero().pow_felt(&FeltBigInt::new(5)),
+            FeltBigInt::zero()
+        );
+    }
+
+    #[test]
+    fn pow_felt_negative_exponent() {
+        // -1 is treated as PRIME - 1, so x^(-1) = 1 for any non-zero x (Fermat)
+        let minus_one = FeltBigInt::new(-1);
+        assert_eq!(FeltBigInt::new(7).pow_felt(&minus_one), FeltBigInt::one());
+        // -2 is treated as PRIME - 2, which yields the multiplicative inverse
+        let minus_two = FeltBigInt::new(-2);
+        let a = FeltBigInt::new(7);
+        assert_eq!(a.pow_felt(&minus_two), a.mul_inverse());
+    }
+
+    #[cfg(feature = "serde")]
+    fn serde_test_values() -> Vec<FeltBigInt> {
+        vec![
+            FeltBigInt::zero(),
+            FeltBigInt::one(),
+            FeltBigInt::max_value(),
+            FeltBigInt::new(17_u64),
+            FeltBigInt::new(u64::MAX),
+            FeltBigInt::new(-5_i32),
+            FeltBigInt::from_str_radix(
+                "1809251394333065553493296640760748560207343510400633813116524750123642650624",
+                10,
+            )
+            .expect("Couldn't parse int"),
+            FeltBigInt::from_str_radix(
+                "123456789012345678901234567890123456789012345678901234567890",
+                10,
+            )
+            .expect("Couldn't parse int"),
+        ]
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json_round_trip() {
+        for value in serde_test_values() {
+            let serialized = serde_json::to_string(&value).unwrap();
+            assert_eq!(serialized, format!("\"{}\"", value));
+            let deserialized: FeltBigInt = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(deserialized, value);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bincode_round_trip() {
+        for value in serde_test_values() {
+            let serialized = bincode::serialize(&value).unwrap();
+            let deserialized: FeltBigInt = bincode::deserialize(&serialized).unwrap();
+            assert_eq!(deserialized, value);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json_rejects_prime() {
+        let serialized = format!("\"{}\"", *CAIRO_PRIME);
+        assert!(serde_json::from_str::<FeltBigInt>(&serialized).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json_rejects_invalid_string() {
+        assert!(serde_json::from_str::<FeltBigInt>("\"0x1\"").is_err());
+        assert!(serde_json::from_str::<FeltBigInt>("\"\"").is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn bincode_rejects_prime() {
+        // bincode encodes a Vec<u8> exactly like a byte slice: length prefix + raw bytes
+        let serialized = bincode::serialize(&CAIRO_PRIME.to_bytes_be()).unwrap();
+        assert!(bincode::deserialize::<FeltBigInt>(&serialized).is_err());
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_felts_are_below_prime() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..1000 {
+            assert!(FeltBigInt::random(&mut rng).to_biguint() < *CAIRO_PRIME);
+        }
+    }
+
+    // Buckets samples by their top 4 bits below 2^251 and checks the chi-square statistic
+    // against the 0.001 critical value for 15 degrees of freedom. Values in [2^251, PRIME)
+    // have probability ~2^-55, so they are ignored.
+    #[cfg(feature = "rand")]
+    #[test]
+    fn random_felts_chi_square() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let samples = 16000;
+        let mut buckets = [0_u32; 16];
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..samples {
+            let top = (FeltBigInt::random(&mut rng).to_biguint() >> 247_u32)
+                .to_usize()
+                .unwrap();
+            if top < 16 {
+                buckets[top] += 1;
+            }
+        }
+        let expected = samples as f64 / 16.0;
+        let chi_square: f64 = buckets
+            .iter()
+            .map(|&observed| (observed as f64 - expected).powi(2) / expected)
+            .sum();
+        assert!(chi_square < 37.7, "chi-square statistic {chi_square}");
+    }
+
+    fn hash_of(felt: &FeltBigInt) -> u64 {
+        use std::collections::hash_map::DefaultHasher;
+
+        let mut hasher = DefaultHasher::new();
+        felt.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    // Builds the same element along different paths, some of which go through values
+    // past the prime, and checks that they are equal and hash the same.
+    #[test]
+    fn equal_felts_hash_the_same() {
+        let values = [
+            BigUint::zero(),
+            BigUint::one(),
+            BigUint::from(17_u32),
+            BigUint::from(u64::MAX),
+            BigUint::one() << 128_u32,
+            BigUint::one() << 251_u32,
+            &*CAIRO_PRIME - 1_u32,
+        ];
+        for value in values {
+            let unreduced = &*CAIRO_PRIME + &value;
+            let felt = FeltBigInt::new(value.clone());
+            let constructed = [
+                FeltBigInt::from_bytes_be(&value.to_bytes_be()),
+                FeltBigInt::from_bytes_be(&unreduced.to_bytes_be()),
+                FeltBigInt::parse_bytes(unreduced.to_str_radix(10).as_bytes(), 10).unwrap(),
+                FeltBigInt::from_str_radix(&unreduced.to_str_radix(16), 16).unwrap(),
+                FeltBigInt::max_value() + (FeltBigInt::new(value.clone()) + 1_u32),
+                FeltBigInt::new(value.clone()) * FeltBigInt::one(),
+                -(-FeltBigInt::new(value.clone())),
+                FeltBigInt::zero() - (FeltBigInt::zero() - FeltBigInt::new(value.clone())),
+                &FeltBigInt::new(value.clone()) | &FeltBigInt::zero(),
+            ];
+            for other in constructed {
+                assert_eq!(felt, other);
+                assert_eq!(hash_of(&felt), hash_of(&other));
+            }
+        }
+    }
+
+    #[test]
+    fn neg_zero_is_zero() {
+        assert_eq!(-FeltBigInt::zero(), FeltBigInt::zero());
+        assert_eq!(-&FeltBigInt::zero(), FeltBigInt::zero());
+        assert_eq!((-FeltBigInt::zero()).0, BigUint::zero());
+    }
+
+    #[test]
+    fn bit_operations_are_reduced() {
+        // PRIME - 1 = 2^251 + 17 * 2^192, so or-ing in any other bit goes past the prime
+        let a = FeltBigInt::max_value();
+        let b = FeltBigInt::new(BigUint::one() << 191_u32);
+        let or = &a | &b;
+        assert!(or.0 < *CAIRO_PRIME);
+        assert_eq!(or, FeltBigInt::new(&a.0 | &b.0));
+        let xor = &a ^ &b;
+        assert!(xor.0 < *CAIRO_PRIME);
+        assert_eq!(xor, FeltBigInt::new(&a.0 ^ &b.0));
+    }
+
+    #[test]
+    fn from_min_signed_integers() {
+        assert_eq!(
+            FeltBigInt::new(i128::MIN),
+            FeltBigInt::new(BigUint::one() << 127_u32).neg()
+        );
+        assert_eq!(
+            FeltBigInt::new(i64::MIN),
+            FeltBigInt::new(BigUint::one() << 63_u32).neg()
+        );
+    }
+
+    #[test]
+    fn le_digits_limb_order() {
+        assert_eq!(FeltBigInt::zero().to_le_digits(), [0; 4]);
+        let felt = FeltBigInt::new((2_u128 << 64) + 1);
+        assert_eq!(felt.to_le_digits(), [1, 2, 0, 0]);
+        assert_eq!(FeltBigInt::from_le_digits([1, 2, 0, 0]), felt);
+        assert_eq!(
+            FeltBigInt::new(-1).to_le_digits(),
+            [0, 0, 0, 0x0800000000000011]
+        );
+    }
+
+    #[test]
+    fn from_le_digits_reduces() {
+        assert_eq!(
+            FeltBigInt::from_le_digits(crate::PRIME_LIMBS),
+            FeltBigInt::zero()
+        );
+        assert_eq!(
+            FeltBigInt::from_le_digits([2, 0, 0, 0x0800000000000011]),
+            FeltBigInt::one()
+        );
+        assert_eq!(
+            FeltBigInt::from_le_digits([u64::MAX; 4]),
+            FeltBigInt::new(BigUint::from_slice(&[u32::MAX; 8]))
+        );
+    }
+
+    // The limbs, read from the most significant one, are the big-endian bytes of the felt
+    fn le_digits_to_bytes_be(limbs: [u64; 4]) -> Vec<u8> {
+        limbs
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes())
+            .skip_while(|byte| *byte == 0)
+            .collect()
+    }
+
+    #[test]
+    fn le_digits_round_trip() {
+        use rand::SeedableRng;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(0x4c1b5);
+        let mut felts = random_felts(&mut rng, 100);
+        felts.push(FeltBigInt::new(-1));
+        felts.push(FeltBigInt::zero());
+        for felt in felts {
+            let limbs = felt.to_le_digits();
+            assert_eq!(le_digits_to_bytes_be(limbs), felt.to_bytes_be());
+            assert_eq!(FeltBigInt::from_le_digits(limbs), felt);
+        }
+    }
+
+    #[test]
+    fn from_le_byte_array() {
+        let felt = FeltBigInt::new(-1);
+        assert_eq!(FeltBigInt::from(felt.to_bytes_le()), felt);
+        let mut bytes = [0_u8; 32];
+        bytes[0] = 0x34;
+        bytes[1] = 0x12;
+        assert_eq!(FeltBigInt::from(bytes), FeltBigInt::new(0x1234));
+        // PRIME + 1
+        let mut bytes = [0_u8; 32];
+        bytes[0] = 2;
+        bytes[24] = 0x11;
+        bytes[31] = 0x08;
+        assert_eq!(FeltBigInt::from(bytes), FeltBigInt::one());
+    }
+
+    #[test]
+    fn try_from_le_byte_slice() {
+        let felt = FeltBigInt::new(-1);
+        assert_eq!(FeltBigInt::try_from(&felt.to_bytes_le()[..]), Ok(felt));
+        assert_eq!(
+            FeltBigInt::try_from(&[0x34_u8, 0x12][..]),
+            Ok(FeltBigInt::new(0x1234))
+        );
+        assert_eq!(FeltBigInt::try_from(&[0_u8; 33][..]), Err(ParseFeltError));
+        let mut prime = [0_u8; 32];
+        prime[0] = 1;
+        prime[24] = 0x11;
+        prime[31] = 0x08;
+        assert_eq!(FeltBigInt::try_from(&prime[..]), Err(ParseFeltError));
+    }
 }
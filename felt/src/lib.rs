@@ -1,27 +1,57 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+compile_error!("felt requires either the `std` or the `alloc` feature");
+
+extern crate alloc;
+
 mod bigint_felt;
 
+use alloc::{string::String, vec::Vec};
 use bigint_felt::FeltBigInt;
-use num_bigint::{BigInt, BigUint, U64Digits};
-use num_integer::Integer;
-use num_traits::{Bounded, FromPrimitive, Num, One, Pow, Signed, ToPrimitive, Zero};
-use std::{
+use core::{
     convert::Into,
-    fmt::{Debug, Display},
+    fmt::{Binary, Debug, Display, LowerHex, Octal, UpperHex},
     iter::Sum,
     ops::{
-        Add, AddAssign, BitAnd, BitOr, BitXor, Div, Mul, MulAssign, Neg, Rem, Shl, Shr, ShrAssign,
-        Sub, SubAssign,
+        Add, AddAssign, BitAnd, BitOr, BitXor, Div, Mul, MulAssign, Neg, Rem, RemAssign, Shl, Shr,
+        ShrAssign, Sub, SubAssign,
     },
 };
+use num_bigint::{BigInt, BigUint, U64Digits};
+use num_integer::Integer;
+use num_traits::{Bounded, CheckedRem, FromPrimitive, Num, One, Pow, Signed, ToPrimitive, Zero};
 
 pub type Felt = FeltBigInt;
 
+#[cfg(feature = "rand")]
+use rand::{
+    distributions::{Distribution, Standard},
+    Rng,
+};
+
 pub const PRIME_STR: &str = "0x800000000000011000000000000000000000000000000000000000000000001";
 pub const FIELD: (u128, u128) = ((1 << 123) + (17 << 64), 1);
 
+// Big-endian bytes of the prime. Fixed-width big-endian arrays compare like the numbers
+// they encode, which lets the sampling below stay independent of the felt backend.
+#[cfg(feature = "rand")]
+const PRIME_BYTES_BE: [u8; 32] = [
+    0x08, 0, 0, 0, 0, 0, 0, 0x11, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0x01,
+];
+
+// Little-endian 64-bit limbs of the prime
+const PRIME_LIMBS: [u64; 4] = [1, 0, 0, 0x0800000000000011];
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ParseFeltError;
 
+/// The error of the `TryFrom<&Felt>` conversions into primitive integers, holding the felt
+/// that doesn't fit in the target type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeltTryFromError(pub Felt);
+
 pub trait NewFelt {
     fn new<T: Into<Felt>>(value: T) -> Self;
 }
@@ -32,12 +62,183 @@ pub trait FeltOps {
     fn to_signed_bytes_le(&self) -> Vec<u8>;
     fn to_bytes_be(&self) -> Vec<u8>;
     fn parse_bytes(buf: &[u8], radix: u32) -> Option<Felt>;
+    /// Builds a felt from big-endian bytes, reducing values greater than or equal to the prime.
+    /// Slices of any length are accepted.
     fn from_bytes_be(bytes: &[u8]) -> Self;
+    /// Checked variant of `from_bytes_be`: instead of reducing, fails with `ParseFeltError` when
+    /// the slice is longer than 32 bytes or encodes a value greater than or equal to the prime.
+    fn try_from_bytes_be(bytes: &[u8]) -> Result<Self, ParseFeltError>;
+    /// Same as `try_from_bytes_be`, for little-endian bytes.
+    fn try_from_bytes_le(bytes: &[u8]) -> Result<Self, ParseFeltError>;
+    /// Returns the little-endian bytes of the felt, zero-padded to 32 bytes.
+    fn to_bytes_le(&self) -> [u8; 32];
+    /// Builds a felt from its little-endian 64-bit limbs, reducing values greater than or equal
+    /// to the prime. Same as `from_le_digits`.
+    #[deprecated(note = "use `FeltOps::from_le_digits` instead")]
+    fn from_limbs(limbs: [u64; 4]) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_le_digits(limbs)
+    }
+    /// Builds a felt from its 64-bit limbs in little-endian order, i.e. `limbs[0]` holds the
+    /// least significant bits. Values greater than or equal to the prime are reduced.
+    fn from_le_digits(limbs: [u64; 4]) -> Self;
+    /// Returns the 64-bit limbs of the canonical representative of the felt, in little-endian
+    /// order. `from_le_digits` turns them back into the same felt.
+    fn to_le_digits(&self) -> [u64; 4];
+    /// Parses a hex string, with or without `0x` prefix and in any case.
+    /// Values greater than or equal to the prime are reduced, like in `parse_bytes`.
+    fn from_hex(s: &str) -> Result<Self, ParseFeltError>;
     fn to_str_radix(&self, radix: u32) -> String;
+    /// Returns `0x` followed by the 64 zero-padded lowercase hex digits of the felt.
+    fn to_fixed_hex_string(&self) -> String;
+    /// Returns the decimal representation of the felt, printing values greater than `PRIME / 2`
+    /// as negative numbers, i.e. `PRIME - 1` is printed as `-1`.
+    fn to_signed_felt_string(&self) -> String;
     fn to_bigint(&self) -> BigInt;
     fn to_biguint(&self) -> BigUint;
+    /// Returns the integer square root of the felt's canonical representative, rounded down.
+    /// This isn't a square root in the field; see `checked_sqrt` for that.
     fn sqrt(&self) -> Self;
+    /// Returns whether the felt is a square in the field, using Euler's criterion. Zero is a
+    /// square.
+    fn is_quad_residue(&self) -> bool;
+    /// Returns the square root of the felt in the field, or `None` if it isn't a square.
+    /// Of the two roots `r` and `PRIME - r`, the smaller one (i.e. the one in
+    /// `[0, PRIME / 2]`) is always returned.
+    fn checked_sqrt(&self) -> Option<Self>;
+    /// Returns the minimal number of bits needed to represent the felt. Zero has 0 bits.
     fn bits(&self) -> u64;
+    /// Returns the little-endian bit decomposition of the felt.
+    fn to_bits_le(&self) -> [bool; 252];
+    /// Returns the multiplicative inverse of the felt. Zero has no inverse, so it maps to zero.
+    fn mul_inverse(&self) -> Self;
+    /// Inverts every value using Montgomery's trick: a single field inversion plus 3(n-1)
+    /// multiplications. Zeros are skipped and map to zero in the returned vector, matching
+    /// `mul_inverse`.
+    fn batch_mul_inverse(values: &[Felt]) -> Vec<Felt>;
+    /// Compares the canonical 32-byte big-endian representations of both felts in constant
+    /// time, regardless of the position of the first differing byte.
+    fn ct_eq(&self, other: &Self) -> bool;
+}
+
+/// Displays a felt with `FeltOps::to_signed_felt_string`, for use in format strings.
+pub struct SignedFeltDisplay<'a>(pub &'a Felt);
+
+impl Display for SignedFeltDisplay<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0.to_signed_felt_string())
+    }
+}
+
+/// Parses a decimal or `0x`-prefixed hexadecimal string into the little-endian 64-bit limbs
+/// of a felt. Being a `const fn`, it can run at compile time, which is how `felt_const!` uses it.
+///
+/// Panics if the string has invalid digits or encodes a value greater than or equal to the prime.
+pub const fn felt_limbs_from_str(value: &str) -> [u64; 4] {
+    let bytes = value.as_bytes();
+    let (radix, mut i) = if bytes.len() > 2 && bytes[0] == b'0' && (bytes[1] | 0x20) == b'x' {
+        (16, 2)
+    } else {
+        (10, 0)
+    };
+    if i == bytes.len() {
+        panic!("Empty felt literal");
+    }
+    let mut limbs = [0_u64; 4];
+    while i < bytes.len() {
+        let digit = match bytes[i] {
+            b'0'..=b'9' => bytes[i] - b'0',
+            b'a'..=b'f' if radix == 16 => bytes[i] - b'a' + 10,
+            b'A'..=b'F' if radix == 16 => bytes[i] - b'A' + 10,
+            _ => panic!("Invalid digit in felt literal"),
+        };
+        // limbs = limbs * radix + digit
+        let mut carry = digit as u128;
+        let mut j = 0;
+        while j < limbs.len() {
+            let value = limbs[j] as u128 * radix + carry;
+            limbs[j] = value as u64;
+            carry = value >> 64;
+            j += 1;
+        }
+        if carry != 0 {
+            panic!("Felt literal is greater than or equal to the prime");
+        }
+        i += 1;
+    }
+    // Compare with the prime starting from the most significant limb
+    let mut j = limbs.len();
+    while j > 0 {
+        j -= 1;
+        if limbs[j] < PRIME_LIMBS[j] {
+            return limbs;
+        }
+        if limbs[j] > PRIME_LIMBS[j] {
+            break;
+        }
+    }
+    panic!("Felt literal is greater than or equal to the prime")
+}
+
+/// Builds a felt from a decimal or `0x`-prefixed hexadecimal string literal, which is parsed
+/// into limbs at compile time, so no string parsing happens when the expression is evaluated.
+///
+/// ```
+/// use felt::{felt_const, Felt, NewFelt};
+/// assert_eq!(felt_const!("0x11"), Felt::new(17));
+/// ```
+///
+/// Literals greater than or equal to the prime are rejected at compile time:
+///
+/// ```compile_fail
+/// let prime = felt::felt_const!("0x800000000000011000000000000000000000000000000000000000000000001");
+/// ```
+#[macro_export]
+macro_rules! felt_const {
+    ($val: expr) => {{
+        const LIMBS: [u64; 4] = $crate::felt_limbs_from_str($val);
+        <$crate::Felt as $crate::FeltOps>::from_le_digits(LIMBS)
+    }};
+}
+
+impl Felt {
+    /// Returns the felt 2. Like `Zero::zero` and `One::one`, the small felts are functions
+    /// instead of associated consts, as the big integer backing a felt can't be built in a
+    /// const context.
+    pub fn two() -> Felt {
+        felt_const!("2")
+    }
+
+    /// Returns the felt 3.
+    pub fn three() -> Felt {
+        felt_const!("3")
+    }
+}
+
+/// Samples felts uniformly over `[0, PRIME)` by rejection sampling 252-bit values.
+/// Since the prime is slightly above 2^251, each draw is accepted with probability ~1/2.
+#[cfg(feature = "rand")]
+impl Distribution<Felt> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Felt {
+        loop {
+            let mut bytes = [0_u8; 32];
+            rng.fill_bytes(&mut bytes);
+            bytes[0] &= 0x0f;
+            if bytes < PRIME_BYTES_BE {
+                return Felt::from_bytes_be(&bytes);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "rand")]
+impl Felt {
+    /// Returns a felt sampled uniformly over `[0, PRIME)`.
+    pub fn random<R: Rng>(rng: &mut R) -> Felt {
+        rng.gen()
+    }
 }
 
 macro_rules! assert_felt_impl {
@@ -64,10 +265,15 @@ macro_rules! assert_felt_impl {
             fn assert_mul_ref<'a, T: Mul<&'a $type>>() {}
             fn assert_mul_assign_ref<'a, T: MulAssign<&'a $type>>() {}
             fn assert_pow<T: Pow<u32>>() {}
+            fn assert_pow_felt<'a, T: Pow<&'a $type>>() {}
             fn assert_div<T: Div>() {}
             fn assert_ref_div<T: Div<$type>>() {}
             fn assert_rem<T: Rem>() {}
             fn assert_rem_ref<'a, T: Rem<&'a $type>>() {}
+            fn assert_rem_u32<T: Rem<u32>>() {}
+            fn assert_rem_assign<T: RemAssign>() {}
+            fn assert_rem_assign_ref<'a, T: RemAssign<&'a $type>>() {}
+            fn assert_checked_rem<T: CheckedRem>() {}
             fn assert_zero<T: Zero>() {}
             fn assert_one<T: One>() {}
             fn assert_bounded<T: Bounded>() {}
@@ -77,6 +283,8 @@ macro_rules! assert_felt_impl {
             fn assert_shl_u32<T: Shl<u32>>() {}
             fn assert_shl_usize<T: Shl<usize>>() {}
             fn assert_shr_u32<T: Shr<u32>>() {}
+            fn assert_shr_usize<T: Shr<usize>>() {}
+            fn assert_shr_assign_u32<T: ShrAssign<u32>>() {}
             fn assert_shr_assign_usize<T: ShrAssign<usize>>() {}
             fn assert_bitand_ref<T: BitAnd>() {}
             fn assert_bitand<'a, T: BitAnd<&'a $type>>() {}
@@ -87,6 +295,10 @@ macro_rules! assert_felt_impl {
             fn assert_to_primitive<T: ToPrimitive>() {}
             fn assert_display<T: Display>() {}
             fn assert_debug<T: Debug>() {}
+            fn assert_lower_hex<T: LowerHex>() {}
+            fn assert_upper_hex<T: UpperHex>() {}
+            fn assert_binary<T: Binary>() {}
+            fn assert_octal<T: Octal>() {}
 
             // RFC 2056
             #[allow(dead_code)]
@@ -118,11 +330,19 @@ macro_rules! assert_felt_impl {
                 assert_mul_assign_ref::<$type>();
                 assert_pow::<$type>();
                 assert_pow::<&$type>();
+                assert_pow_felt::<$type>();
+                assert_pow_felt::<&$type>();
                 assert_div::<$type>();
                 assert_div::<&$type>();
                 assert_ref_div::<&$type>();
                 assert_rem::<$type>();
                 assert_rem_ref::<$type>();
+                assert_rem_ref::<&$type>();
+                assert_rem_u32::<$type>();
+                assert_rem_u32::<&$type>();
+                assert_rem_assign::<$type>();
+                assert_rem_assign_ref::<$type>();
+                assert_checked_rem::<$type>();
                 assert_zero::<$type>();
                 assert_one::<$type>();
                 assert_bounded::<$type>();
@@ -135,6 +355,9 @@ macro_rules! assert_felt_impl {
                 assert_shl_usize::<&$type>();
                 assert_shr_u32::<$type>();
                 assert_shr_u32::<&$type>();
+                assert_shr_usize::<$type>();
+                assert_shr_usize::<&$type>();
+                assert_shr_assign_u32::<$type>();
                 assert_shr_assign_usize::<$type>();
                 assert_bitand_ref::<&$type>();
                 assert_bitand::<$type>();
@@ -145,6 +368,10 @@ macro_rules! assert_felt_impl {
                 assert_to_primitive::<$type>();
                 assert_display::<$type>();
                 assert_debug::<$type>();
+                assert_lower_hex::<$type>();
+                assert_upper_hex::<$type>();
+                assert_binary::<$type>();
+                assert_octal::<$type>();
             }
         };
     };
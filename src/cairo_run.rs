@@ -3,9 +3,11 @@ use crate::{
     types::program::Program,
     vm::{
         errors::{
-            cairo_run_errors::CairoRunError, runner_errors::RunnerError, vm_exception::VmException,
+            cairo_run_errors::CairoRunError, encode_errors::EncodeError,
+            runner_errors::RunnerError, vm_exception::VmException,
         },
-        runners::cairo_runner::CairoRunner,
+        runners::cairo_runner::{CairoRunner, RunResources},
+        security::verify_secure_runner,
         trace::trace_entry::RelocatedTraceEntry,
         vm_core::VirtualMachine,
     },
@@ -13,7 +15,7 @@ use crate::{
 use felt::{Felt, FeltOps};
 use std::{
     fs::File,
-    io::{self, BufWriter, Error, ErrorKind, Write},
+    io::{self, BufWriter, Error, ErrorKind, Read, Write},
     path::Path,
 };
 
@@ -24,6 +26,7 @@ pub fn cairo_run(
     print_output: bool,
     layout: &str,
     proof_mode: bool,
+    secure_run: bool,
     hint_executor: &mut dyn HintProcessor,
 ) -> Result<CairoRunner, CairoRunError> {
     let program = match Program::from_file(path, Some(entrypoint)) {
@@ -36,7 +39,7 @@ pub fn cairo_run(
     let end = cairo_runner.initialize(&mut vm)?;
 
     cairo_runner
-        .run_until_pc(end, &mut vm, hint_executor)
+        .run_until_pc(end, &mut RunResources::default(), &mut vm, hint_executor)
         .map_err(|err| VmException::from_vm_error(&cairo_runner, &vm, err))?;
     cairo_runner.end_run(false, false, &mut vm, hint_executor)?;
 
@@ -45,6 +48,9 @@ pub fn cairo_run(
         cairo_runner.read_return_values(&vm)?;
         cairo_runner.finalize_segments(&mut vm)?;
     }
+    if secure_run {
+        verify_secure_runner(&cairo_runner, true, &mut vm)?;
+    }
     cairo_runner.relocate(&mut vm)?;
 
     if print_output {
@@ -88,47 +94,68 @@ pub fn write_binary_trace(
     buffer.flush()
 }
 
-/*
-   Writes a binary memory file with the relocated memory as input.
-   The memory pairs (address, value) are encoded and concatenated in the file
-   given by the path `memory_file`.
+// Each encoded memory cell is an 8-byte address followed by a 32-byte value, both little endian
+const ENCODED_ADDRESS_SIZE: usize = 8;
+const ENCODED_VALUE_SIZE: usize = 32;
+const ENCODED_CELL_SIZE: usize = ENCODED_ADDRESS_SIZE + ENCODED_VALUE_SIZE;
 
-   * address -> 8-byte encoded
-   * value -> 32-byte encoded
-*/
+/// Writes a binary memory file with the relocated memory as input, in the format produced by
+/// cairo-lang. See `write_encoded_memory`.
 pub fn write_binary_memory(
     relocated_memory: &[Option<Felt>],
     memory_file: &Path,
-) -> io::Result<()> {
+) -> Result<(), EncodeError> {
     let file = File::create(memory_file)?;
     let mut buffer = BufWriter::new(file);
 
-    // initialize bytes vector that will be dumped to file
-    let mut memory_bytes: Vec<u8> = Vec::new();
+    write_encoded_memory(relocated_memory, &mut buffer)?;
+    buffer.flush()?;
+    Ok(())
+}
 
-    for (i, memory_cell) in relocated_memory.iter().enumerate() {
-        match memory_cell {
-            None => continue,
-            Some(unwrapped_memory_cell) => {
-                encode_relocated_memory(&mut memory_bytes, i, unwrapped_memory_cell);
-            }
+/// Encodes the relocated memory into writer as consecutive (address, value) pairs, skipping
+/// the holes. The address is encoded as 8 little endian bytes and the value as 32.
+pub fn write_encoded_memory<W: Write>(
+    relocated_memory: &[Option<Felt>],
+    writer: &mut W,
+) -> Result<(), EncodeError> {
+    for (addr, memory_cell) in relocated_memory.iter().enumerate() {
+        if let Some(value) = memory_cell {
+            writer.write_all(&(addr as u64).to_le_bytes())?;
+            writer.write_all(&value.to_bytes_le())?;
         }
     }
-
-    buffer.write_all(&memory_bytes)?;
-    buffer.flush()
+    Ok(())
 }
 
-// encodes a given memory cell.
-fn encode_relocated_memory(memory_bytes: &mut Vec<u8>, addr: usize, memory_cell: &Felt) {
-    // append memory address to bytes vector using a 8 bytes representation
-    let mut addr_bytes = (addr as u64).to_le_bytes().to_vec();
-    memory_bytes.append(&mut addr_bytes);
+/// Decodes memory encoded by `write_encoded_memory` back into relocated memory. The addresses
+/// must be strictly increasing, and the addresses that don't appear are left as holes.
+pub fn read_encoded_memory<R: Read>(reader: &mut R) -> Result<Vec<Option<Felt>>, EncodeError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let cells = bytes.chunks_exact(ENCODED_CELL_SIZE);
+    if !cells.remainder().is_empty() {
+        return Err(EncodeError::TruncatedCell(cells.remainder().len()));
+    }
 
-    // append memory value at address using a 32 bytes representation
-    let mut value_bytes = memory_cell.to_signed_bytes_le();
-    value_bytes.resize(32, 0);
-    memory_bytes.append(&mut value_bytes);
+    let mut relocated_memory = Vec::new();
+    for cell in cells {
+        let (addr_bytes, value_bytes) = cell.split_at(ENCODED_ADDRESS_SIZE);
+        let mut addr = [0; ENCODED_ADDRESS_SIZE];
+        addr.copy_from_slice(addr_bytes);
+        let addr = u64::from_le_bytes(addr);
+
+        let index = addr as usize;
+        if index < relocated_memory.len() {
+            return Err(EncodeError::UnsortedAddress(addr));
+        }
+        let value =
+            Felt::try_from_bytes_le(value_bytes).map_err(|_| EncodeError::InvalidValue(addr))?;
+        relocated_memory.resize(index, None);
+        relocated_memory.push(Some(value));
+    }
+    Ok(relocated_memory)
 }
 
 #[cfg(test)]
@@ -142,7 +169,6 @@ mod tests {
         utils::test_utils::*,
     };
     use felt::NewFelt;
-    use std::io::Read;
 
     fn run_test_program(
         program_path: &Path,
@@ -158,7 +184,7 @@ mod tests {
             .map_err(CairoRunError::Runner)?;
 
         assert!(cairo_runner
-            .run_until_pc(end, &mut vm, hint_processor)
+            .run_until_pc(end, &mut RunResources::default(), &mut vm, hint_processor)
             .is_ok());
 
         Ok((cairo_runner, vm))
@@ -174,7 +200,12 @@ mod tests {
 
         let end = cairo_runner.initialize(&mut vm).unwrap();
         assert!(cairo_runner
-            .run_until_pc(end, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            )
             .is_ok());
         assert!(cairo_runner.relocate(&mut vm).is_ok());
         // `main` returns without doing nothing, but `not_main` sets `[ap]` to `1`
@@ -213,6 +244,7 @@ mod tests {
             false,
             "plain",
             false,
+            false,
             &mut hint_processor
         )
         .is_err());
@@ -231,6 +263,7 @@ mod tests {
             false,
             "plain",
             false,
+            false,
             &mut hint_processor
         )
         .is_err());
@@ -249,6 +282,7 @@ mod tests {
             false,
             "plain",
             false,
+            false,
             &mut hint_processor
         )
         .is_err());
@@ -309,6 +343,78 @@ mod tests {
         assert!(compare_files(cairo_rs_memory_path, expected_memory_path).is_ok());
     }
 
+    #[test]
+    fn read_binary_memory_file() {
+        let program_path = Path::new("cairo_programs/struct.json");
+        let expected_memory_path = Path::new("cairo_programs/trace_memory/cairo_memory_struct");
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let (mut cairo_runner, mut vm) =
+            run_test_program(program_path, &mut hint_processor).unwrap();
+        assert!(cairo_runner.relocate(&mut vm).is_ok());
+
+        // the memory dumped by cairo-lang decodes to the relocated memory, up to its last cell
+        let mut relocated_memory = cairo_runner.relocated_memory;
+        while relocated_memory.last() == Some(&None) {
+            relocated_memory.pop();
+        }
+        let mut file = File::open(expected_memory_path).unwrap();
+        assert_eq!(read_encoded_memory(&mut file).unwrap(), relocated_memory);
+    }
+
+    #[test]
+    fn encoded_memory_round_trip() {
+        let relocated_memory = vec![
+            None,
+            Some(Felt::new(0x48_u64 << 56)),
+            Some(Felt::new(-1)),
+            None,
+            None,
+            Some(Felt::new(5)),
+        ];
+        let mut bytes = Vec::new();
+        write_encoded_memory(&relocated_memory, &mut bytes).unwrap();
+        assert_eq!(bytes.len(), 3 * ENCODED_CELL_SIZE);
+        assert_eq!(bytes[..8], [1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(bytes[8..40], Felt::new(0x48_u64 << 56).to_bytes_le());
+        assert_eq!(
+            read_encoded_memory(&mut bytes.as_slice()).unwrap(),
+            relocated_memory
+        );
+    }
+
+    #[test]
+    fn read_encoded_memory_truncated_cell() {
+        let mut bytes = Vec::new();
+        write_encoded_memory(&[None, Some(Felt::new(1))], &mut bytes).unwrap();
+        bytes.pop();
+        assert!(matches!(
+            read_encoded_memory(&mut bytes.as_slice()),
+            Err(EncodeError::TruncatedCell(39))
+        ));
+    }
+
+    #[test]
+    fn read_encoded_memory_unsorted_addresses() {
+        let mut bytes = Vec::new();
+        write_encoded_memory(&[None, None, Some(Felt::new(1))], &mut bytes).unwrap();
+        write_encoded_memory(&[None, Some(Felt::new(2))], &mut bytes).unwrap();
+        assert!(matches!(
+            read_encoded_memory(&mut bytes.as_slice()),
+            Err(EncodeError::UnsortedAddress(1))
+        ));
+    }
+
+    #[test]
+    fn read_encoded_memory_invalid_value() {
+        let mut bytes = 1_u64.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&[0xff; ENCODED_VALUE_SIZE]);
+        assert!(matches!(
+            read_encoded_memory(&mut bytes.as_slice()),
+            Err(EncodeError::InvalidValue(1))
+        ));
+    }
+
     #[test]
     fn run_with_no_trace() {
         let program_path = Path::new("cairo_programs/struct.json");
@@ -318,7 +424,12 @@ mod tests {
         let mut vm = vm!();
         let end = cairo_runner.initialize(&mut vm).unwrap();
         assert!(cairo_runner
-            .run_until_pc(end, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            )
             .is_ok());
         assert!(vm.trace.is_none());
     }
@@ -2,7 +2,7 @@ use crate::{
     hint_processor::{
         builtin_hint_processor::{
             blake2s_hash::{blake2s_compress, IV},
-            hint_utils::{get_ptr_from_var_name, get_relocatable_from_var_name},
+            hint_utils::{get_integer_from_var_name, get_ptr_from_var_name},
         },
         hint_processor_definition::HintReference,
         hint_processor_utils::felt_to_u32,
@@ -14,7 +14,7 @@ use crate::{
         vm_core::VirtualMachine,
     },
 };
-use felt::{Felt, NewFelt};
+use felt::{Felt, FeltOps, NewFelt};
 use num_traits::ToPrimitive;
 use std::{borrow::Cow, collections::HashMap};
 
@@ -38,9 +38,6 @@ fn get_maybe_relocatable_array_from_u32(array: &Vec<u32>) -> Vec<MaybeRelocatabl
     new_array
 }
 
-fn get_maybe_relocatable_array_from_felt(array: &[Felt]) -> Vec<MaybeRelocatable> {
-    array.iter().map(MaybeRelocatable::from).collect()
-}
 /*Helper function for the Cairo blake2s() implementation.
 Computes the blake2s compress function and fills the value in the right position.
 output_ptr should point to the middle of an instance, right after initial_state, message, t, f,
@@ -121,6 +118,16 @@ pub fn finalize_blake2s(
     Ok(())
 }
 
+// Splits a uint256 limb into its four 32-bit words, least significant first
+fn split_uint256_limb(limb: &Felt) -> Result<Vec<u32>, HintError> {
+    if limb.bits() > 128 {
+        return Err(HintError::Uint256LimbOutOfRange(limb.clone()));
+    }
+    let mut words = limb.to_biguint().to_u32_digits();
+    words.resize(4, 0);
+    Ok(words)
+}
+
 /* Implements Hint:
     B = 32
     MASK = 2 ** 32 - 1
@@ -134,37 +141,15 @@ pub fn blake2s_add_uint256(
 ) -> Result<(), HintError> {
     //Get variables from ids
     let data_ptr = get_ptr_from_var_name("data", vm, ids_data, ap_tracking)?;
-    let low_addr = get_relocatable_from_var_name("low", vm, ids_data, ap_tracking)?;
-    let high_addr = get_relocatable_from_var_name("high", vm, ids_data, ap_tracking)?;
-    let low = vm.get_integer(&low_addr)?.into_owned();
-    let high = vm.get_integer(&high_addr)?.into_owned();
+    let low = get_integer_from_var_name("low", vm, ids_data, ap_tracking)?;
+    let high = get_integer_from_var_name("high", vm, ids_data, ap_tracking)?;
     //Main logic
-    //Declare constant
-    const MASK: u32 = u32::MAX;
-    const B: u32 = 32;
-    //Convert MASK to felt
-    let mask = Felt::new(MASK);
-    //Build first batch of data
-    let mut inner_data = Vec::<Felt>::new();
-    for i in 0..4 {
-        inner_data.push((&low >> (B * i)) & &mask);
-    }
-    //Insert first batch of data
-    let data = get_maybe_relocatable_array_from_felt(&inner_data);
+    let mut words = split_uint256_limb(&low)?;
+    words.extend(split_uint256_limb(&high)?);
+    //Insert the words, the low ones at ids.data and the high ones at ids.data + 4
+    let data = get_maybe_relocatable_array_from_u32(&words);
     vm.load_data(&MaybeRelocatable::RelocatableValue(data_ptr), &data)
         .map_err(VirtualMachineError::MemoryError)?;
-    //Build second batch of data
-    let mut inner_data = Vec::<Felt>::new();
-    for i in 0..4 {
-        inner_data.push((&high >> (B * i)) & &mask);
-    }
-    //Insert second batch of data
-    let data = get_maybe_relocatable_array_from_felt(&inner_data);
-    vm.load_data(
-        &MaybeRelocatable::RelocatableValue(data_ptr).add_usize(4),
-        &data,
-    )
-    .map_err(VirtualMachineError::MemoryError)?;
     Ok(())
 }
 
@@ -181,37 +166,19 @@ pub fn blake2s_add_uint256_bigend(
 ) -> Result<(), HintError> {
     //Get variables from ids
     let data_ptr = get_ptr_from_var_name("data", vm, ids_data, ap_tracking)?;
-    let low_addr = get_relocatable_from_var_name("low", vm, ids_data, ap_tracking)?;
-    let high_addr = get_relocatable_from_var_name("high", vm, ids_data, ap_tracking)?;
-    let low = vm.get_integer(&low_addr)?.into_owned();
-    let high = vm.get_integer(&high_addr)?.into_owned();
+    let low = get_integer_from_var_name("low", vm, ids_data, ap_tracking)?;
+    let high = get_integer_from_var_name("high", vm, ids_data, ap_tracking)?;
     //Main logic
-    //Declare constant
-    const MASK: u32 = u32::MAX as u32;
-    const B: u32 = 32;
-    //Convert MASK to felt
-    let mask = Felt::new(MASK);
-    //Build first batch of data
-    let mut inner_data = Vec::<Felt>::new();
-    for i in 0..4 {
-        inner_data.push((&high >> (B * (3 - i))) & &mask);
-    }
-    //Insert first batch of data
-    let data = get_maybe_relocatable_array_from_felt(&inner_data);
+    //Words are written most significant first, starting with the high ones
+    let mut words = split_uint256_limb(&high)?;
+    words.reverse();
+    let mut low_words = split_uint256_limb(&low)?;
+    low_words.reverse();
+    words.extend(low_words);
+    //Insert the words, the high ones at ids.data and the low ones at ids.data + 4
+    let data = get_maybe_relocatable_array_from_u32(&words);
     vm.load_data(&MaybeRelocatable::RelocatableValue(data_ptr), &data)
         .map_err(VirtualMachineError::MemoryError)?;
-    //Build second batch of data
-    let mut inner_data = Vec::<Felt>::new();
-    for i in 0..4 {
-        inner_data.push((&low >> (B * (3 - i))) & &mask);
-    }
-    //Insert second batch of data
-    let data = get_maybe_relocatable_array_from_felt(&inner_data);
-    vm.load_data(
-        &MaybeRelocatable::RelocatableValue(data_ptr).add_usize(4),
-        &data,
-    )
-    .map_err(VirtualMachineError::MemoryError)?;
     Ok(())
 }
 
@@ -221,16 +188,21 @@ mod tests {
     use crate::{
         any_box,
         hint_processor::{
-            builtin_hint_processor::builtin_hint_processor_definition::{
-                BuiltinHintProcessor, HintProcessorData,
+            builtin_hint_processor::{
+                builtin_hint_processor_definition::{BuiltinHintProcessor, HintProcessorData},
+                hint_code,
             },
             hint_processor_definition::HintProcessor,
         },
         relocatable,
         types::exec_scope::ExecutionScopes,
         utils::test_utils::*,
-        vm::{errors::memory_errors::MemoryError, vm_memory::memory::Memory},
+        vm::{
+            errors::{math_errors::MathError, memory_errors::MemoryError},
+            vm_memory::memory::Memory,
+        },
     };
+    use felt::felt_str;
     use std::any::Any;
 
     #[test]
@@ -247,8 +219,9 @@ mod tests {
         //Execute the hint
         assert_eq!(
             run_hint!(vm, ids_data, hint_code),
-            Err(HintError::Internal(VirtualMachineError::CantSubOffset(
-                5, 26
+            Err(HintError::Math(MathError::RelocatableSubNegOffset(
+                relocatable!(2, 5),
+                26
             )))
         );
     }
@@ -535,4 +508,116 @@ mod tests {
         ];
         assert_eq!(vm.memory.get(&MaybeRelocatable::from((2, 8))), Ok(None));
     }
+
+    #[test]
+    fn blake2s_add_uint256_valid_all_words() {
+        let hint_code = hint_code::BLAKE2S_ADD_UINT256;
+        //Create vm
+        let mut vm = vm!();
+        //Initialize fp
+        vm.run_context.fp = 3;
+        //Insert ids into memory
+        vm.memory = memory![
+            ((1, 0), (2, 0)),
+            ((1, 1), ("295990755076957304698151809395947782352", 10)),
+            ((1, 2), ("1512366075204170947332355369683137040", 10))
+        ];
+        add_segments!(vm, 1);
+        let ids_data = ids_data!["data", "high", "low"];
+        //Execute the hint
+        assert_eq!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        //Check data ptr
+        check_memory![
+            vm.memory,
+            ((2, 0), 1985229328_i64),
+            ((2, 1), 4275878552_i64),
+            ((2, 2), 2309737967_i64),
+            ((2, 3), 19088743_i64),
+            ((2, 4), 2695938256_i64),
+            ((2, 5), 16909060_i64),
+            ((2, 6), 3405691582_i64),
+            ((2, 7), 3735928559_i64)
+        ];
+        assert_eq!(vm.memory.get(&MaybeRelocatable::from((2, 8))), Ok(None));
+    }
+
+    #[test]
+    fn blake2s_add_uint256_bigend_valid_all_words() {
+        let hint_code = hint_code::BLAKE2S_ADD_UINT256_BIGEND;
+        //Create vm
+        let mut vm = vm!();
+        //Initialize fp
+        vm.run_context.fp = 3;
+        //Insert ids into memory
+        vm.memory = memory![
+            ((1, 0), (2, 0)),
+            ((1, 1), ("295990755076957304698151809395947782352", 10)),
+            ((1, 2), ("1512366075204170947332355369683137040", 10))
+        ];
+        add_segments!(vm, 1);
+        let ids_data = ids_data!["data", "high", "low"];
+        //Execute the hint
+        assert_eq!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        //Check data ptr
+        check_memory![
+            vm.memory,
+            ((2, 0), 3735928559_i64),
+            ((2, 1), 3405691582_i64),
+            ((2, 2), 16909060_i64),
+            ((2, 3), 2695938256_i64),
+            ((2, 4), 19088743_i64),
+            ((2, 5), 2309737967_i64),
+            ((2, 6), 4275878552_i64),
+            ((2, 7), 1985229328_i64)
+        ];
+        assert_eq!(vm.memory.get(&MaybeRelocatable::from((2, 8))), Ok(None));
+    }
+
+    #[test]
+    fn blake2s_add_uint256_high_out_of_range() {
+        let hint_code = hint_code::BLAKE2S_ADD_UINT256;
+        //Create vm
+        let mut vm = vm!();
+        //Initialize fp
+        vm.run_context.fp = 3;
+        //Insert ids into memory, the limb is 2**128
+        vm.memory = memory![
+            ((1, 0), (2, 0)),
+            ((1, 1), ("340282366920938463463374607431768211456", 10)),
+            ((1, 2), 0)
+        ];
+        add_segments!(vm, 1);
+        let ids_data = ids_data!["data", "high", "low"];
+        //Execute the hint
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code),
+            Err(HintError::Uint256LimbOutOfRange(felt_str!(
+                "340282366920938463463374607431768211456"
+            )))
+        );
+    }
+
+    #[test]
+    fn blake2s_add_uint256_bigend_low_out_of_range() {
+        let hint_code = hint_code::BLAKE2S_ADD_UINT256_BIGEND;
+        //Create vm
+        let mut vm = vm!();
+        //Initialize fp
+        vm.run_context.fp = 3;
+        //Insert ids into memory, the limb is 2**128
+        vm.memory = memory![
+            ((1, 0), (2, 0)),
+            ((1, 1), 0),
+            ((1, 2), ("340282366920938463463374607431768211456", 10))
+        ];
+        add_segments!(vm, 1);
+        let ids_data = ids_data!["data", "high", "low"];
+        //Execute the hint
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code),
+            Err(HintError::Uint256LimbOutOfRange(felt_str!(
+                "340282366920938463463374607431768211456"
+            )))
+        );
+    }
 }
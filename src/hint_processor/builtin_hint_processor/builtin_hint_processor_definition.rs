@@ -26,13 +26,18 @@ use crate::{
                 ec_utils::{
                     compute_doubling_slope, compute_slope, ec_double_assign_new_x,
                     ec_double_assign_new_y, ec_mul_inner, ec_negate, fast_ec_add_assign_new_x,
-                    fast_ec_add_assign_new_y,
+                    fast_ec_add_assign_new_y, import_secp256r1_alpha, import_secp256r1_p,
                 },
                 field_utils::{
                     is_zero_assign_scope_variables, is_zero_nondet, is_zero_pack, reduce,
                     verify_zero,
                 },
-                signature::{div_mod_n_packed_divmod, div_mod_n_safe_div, get_point_from_x},
+                secp_utils::{get_secp_p, get_secp_p_from_scope_or_constants},
+                signature::{
+                    div_mod_n_packed_divmod, div_mod_n_safe_div, ec_recover_divmod_n_packed,
+                    ec_recover_product_div_m, ec_recover_product_mod, ec_recover_sub_a_b,
+                    get_point_from_x,
+                },
             },
             segments::{relocate_segment, temporary_array},
             set::set_add,
@@ -45,7 +50,8 @@ use crate::{
                 squash_dict_inner_used_accesses_assert,
             },
             uint256_utils::{
-                split_64, uint256_add, uint256_signed_nn, uint256_sqrt, uint256_unsigned_div_rem,
+                split_64, uint256_add, uint256_expanded_unsigned_div_rem, uint256_mul_div_mod,
+                uint256_signed_nn, uint256_sqrt, uint256_unsigned_div_rem,
             },
             usort::{
                 usort_body, usort_enter_scope, verify_multiplicity_assert,
@@ -53,28 +59,44 @@ use crate::{
             },
         },
         hint_processor_definition::{HintProcessor, HintReference},
+        hint_processor_utils::get_ids_data_snapshot,
     },
     serde::deserialize_program::ApTracking,
     types::exec_scope::ExecutionScopes,
-    vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
+    vm::{
+        errors::hint_errors::HintError, runners::cairo_runner::RunResources,
+        vm_core::VirtualMachine,
+    },
 };
 use felt::Felt;
-use std::{any::Any, collections::HashMap, rc::Rc};
+use num_bigint::BigInt;
+use num_traits::Zero;
+use std::{any::Any, collections::HashMap, sync::Arc};
 
+/// The compiled data of a hint. The code and the ids are shared, so cloning it is cheap.
+#[derive(Clone, Debug)]
 pub struct HintProcessorData {
-    pub code: String,
+    pub code: Arc<str>,
     pub ap_tracking: ApTracking,
-    pub ids_data: HashMap<String, HintReference>,
+    pub ids_data: Arc<HashMap<String, HintReference>>,
 }
 
 impl HintProcessorData {
-    pub fn new_default(code: String, ids_data: HashMap<String, HintReference>) -> Self {
+    pub fn new(
+        code: &str,
+        ap_tracking: ApTracking,
+        ids_data: HashMap<String, HintReference>,
+    ) -> Self {
         HintProcessorData {
-            code,
-            ap_tracking: ApTracking::default(),
-            ids_data,
+            code: code.into(),
+            ap_tracking,
+            ids_data: Arc::new(ids_data),
         }
     }
+
+    pub fn new_default(code: String, ids_data: HashMap<String, HintReference>) -> Self {
+        HintProcessorData::new(&code, ApTracking::default(), ids_data)
+    }
 }
 
 #[allow(clippy::type_complexity)]
@@ -87,41 +109,61 @@ pub struct HintFunc(
                 &ApTracking,
                 &HashMap<String, Felt>,
             ) -> Result<(), HintError>
+            + Send
             + Sync,
     >,
 );
+
+/// Handles the hints that are neither builtin nor extra hints, e.g. by delegating syscalls to
+/// an execution layer. Returning `HintError::UnknownHint` declines the hint.
+pub type FallbackHandler = Box<
+    dyn Fn(&HintProcessorData, &mut VirtualMachine, &mut ExecutionScopes) -> Result<(), HintError>
+        + Send
+        + Sync,
+>;
+
 pub struct BuiltinHintProcessor {
-    pub extra_hints: HashMap<String, Rc<HintFunc>>,
+    pub extra_hints: HashMap<String, Arc<HintFunc>>,
+    fallback_handler: Option<FallbackHandler>,
+    debug_ids: bool,
 }
 impl BuiltinHintProcessor {
     pub fn new_empty() -> Self {
+        BuiltinHintProcessor::new(HashMap::new())
+    }
+
+    pub fn new(extra_hints: HashMap<String, Arc<HintFunc>>) -> Self {
         BuiltinHintProcessor {
-            extra_hints: HashMap::new(),
+            extra_hints,
+            fallback_handler: None,
+            debug_ids: false,
         }
     }
 
-    pub fn new(extra_hints: HashMap<String, Rc<HintFunc>>) -> Self {
-        BuiltinHintProcessor { extra_hints }
+    pub fn add_hint(&mut self, hint_code: String, hint_func: Arc<HintFunc>) {
+        self.extra_hints.insert(hint_code, hint_func);
     }
 
-    pub fn add_hint(&mut self, hint_code: String, hint_func: Rc<HintFunc>) {
-        self.extra_hints.insert(hint_code, hint_func);
+    /// Sets the handler of unknown hint codes, replacing the previous one. If it declines the
+    /// hint, the processor returns `HintError::UnknownHint` with the hint code.
+    pub fn set_fallback_handler(&mut self, fallback_handler: FallbackHandler) {
+        self.fallback_handler = Some(fallback_handler);
     }
-}
 
-impl HintProcessor for BuiltinHintProcessor {
-    fn execute_hint(
-        &mut self,
+    /// When set, hint errors are wrapped in `HintError::WithIdsSnapshot` along with the values
+    /// of the hint's ids at the time of the failure.
+    pub fn set_debug_ids(&mut self, debug_ids: bool) {
+        self.debug_ids = debug_ids;
+    }
+
+    fn execute_builtin_hint(
+        &self,
         vm: &mut VirtualMachine,
         exec_scopes: &mut ExecutionScopes,
-        hint_data: &Box<dyn Any>,
+        hint_data: &HintProcessorData,
         constants: &HashMap<String, Felt>,
     ) -> Result<(), HintError> {
-        let hint_data = hint_data
-            .downcast_ref::<HintProcessorData>()
-            .ok_or(HintError::WrongHintData)?;
-
-        if let Some(hint_func) = self.extra_hints.get(&hint_data.code) {
+        if let Some(hint_func) = self.extra_hints.get(&*hint_data.code) {
             return hint_func.0(
                 vm,
                 exec_scopes,
@@ -137,6 +179,9 @@ impl HintProcessor for BuiltinHintProcessor {
             hint_code::IS_NN_OUT_OF_RANGE => {
                 is_nn_out_of_range(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            hint_code::ASSERT_LE_FELT_V_0_6 => {
+                assert_le_felt_v_0_6(vm, &hint_data.ids_data, &hint_data.ap_tracking)
+            }
             hint_code::ASSERT_LE_FELT => assert_le_felt(
                 vm,
                 exec_scopes,
@@ -180,7 +225,13 @@ impl HintProcessor for BuiltinHintProcessor {
             hint_code::MEMSET_CONTINUE_LOOP => {
                 memset_continue_loop(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
             }
-            hint_code::SPLIT_FELT => split_felt(vm, &hint_data.ids_data, &hint_data.ap_tracking),
+            hint_code::SPLIT_FELT | hint_code::SPLIT_128 => {
+                split_felt(vm, &hint_data.ids_data, &hint_data.ap_tracking)
+            }
+            hint_code::IS_ADDR_BOUNDED => {
+                is_addr_bounded(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
+            }
+            hint_code::IS_250_BITS => is_250_bits(vm, &hint_data.ids_data, &hint_data.ap_tracking),
             hint_code::UNSIGNED_DIV_REM => {
                 unsigned_div_rem(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
@@ -234,13 +285,9 @@ impl HintProcessor for BuiltinHintProcessor {
             hint_code::VERIFY_ZERO => {
                 verify_zero(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
             }
-            hint_code::NONDET_BIGINT3 => nondet_bigint3(
-                vm,
-                exec_scopes,
-                &hint_data.ids_data,
-                &hint_data.ap_tracking,
-                constants,
-            ),
+            hint_code::NONDET_BIGINT3 => {
+                nondet_bigint3(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
+            }
             hint_code::REDUCE => reduce(
                 vm,
                 exec_scopes,
@@ -325,6 +372,12 @@ impl HintProcessor for BuiltinHintProcessor {
             hint_code::UINT256_UNSIGNED_DIV_REM => {
                 uint256_unsigned_div_rem(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
+            hint_code::UINT256_EXPANDED_UNSIGNED_DIV_REM => {
+                uint256_expanded_unsigned_div_rem(vm, &hint_data.ids_data, &hint_data.ap_tracking)
+            }
+            hint_code::UINT256_MUL_DIV_MOD => {
+                uint256_mul_div_mod(vm, &hint_data.ids_data, &hint_data.ap_tracking)
+            }
             hint_code::BIGINT_TO_UINT256 => {
                 bigint_to_uint256(vm, &hint_data.ids_data, &hint_data.ap_tracking, constants)
             }
@@ -354,35 +407,94 @@ impl HintProcessor for BuiltinHintProcessor {
                 &hint_data.ap_tracking,
                 constants,
             ),
+            hint_code::EC_RECOVER_DIV_MOD_N_PACKED => ec_recover_divmod_n_packed(
+                vm,
+                exec_scopes,
+                &hint_data.ids_data,
+                &hint_data.ap_tracking,
+            ),
+            hint_code::EC_RECOVER_SUB_A_B => {
+                ec_recover_sub_a_b(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
+            }
+            hint_code::EC_RECOVER_PRODUCT_MOD => {
+                ec_recover_product_mod(vm, exec_scopes, &hint_data.ids_data, &hint_data.ap_tracking)
+            }
+            hint_code::EC_RECOVER_PRODUCT_DIV_M => ec_recover_product_div_m(exec_scopes),
             hint_code::EC_NEGATE => ec_negate(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
-                constants,
+                &get_secp_p(constants)?,
             ),
+            hint_code::EC_NEGATE_SECP256R1 => {
+                let secp_p = exec_scopes.get::<BigInt>("SECP_P")?;
+                ec_negate(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                    &secp_p,
+                )
+            }
             hint_code::EC_DOUBLE_SCOPE => compute_doubling_slope(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
-                constants,
+                &get_secp_p(constants)?,
+                &BigInt::zero(),
             ),
+            hint_code::EC_DOUBLE_SCOPE_SECP256R1 => {
+                let secp_p = exec_scopes.get::<BigInt>("SECP_P")?;
+                let alpha = exec_scopes.get::<BigInt>("ALPHA")?;
+                compute_doubling_slope(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                    &secp_p,
+                    &alpha,
+                )
+            }
             hint_code::COMPUTE_SLOPE => compute_slope(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
-                constants,
+                &get_secp_p(constants)?,
             ),
+            hint_code::COMPUTE_SLOPE_SECP256R1 => {
+                let secp_p = exec_scopes.get::<BigInt>("SECP_P")?;
+                compute_slope(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                    &secp_p,
+                )
+            }
             hint_code::EC_DOUBLE_ASSIGN_NEW_X => ec_double_assign_new_x(
                 vm,
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
-                constants,
+                &get_secp_p(constants)?,
             ),
-            hint_code::EC_DOUBLE_ASSIGN_NEW_Y => ec_double_assign_new_y(exec_scopes, constants),
+            hint_code::EC_DOUBLE_ASSIGN_NEW_X_SECP256R1 => {
+                let secp_p = exec_scopes.get::<BigInt>("SECP_P")?;
+                ec_double_assign_new_x(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                    &secp_p,
+                )
+            }
+            hint_code::EC_DOUBLE_ASSIGN_NEW_Y => {
+                let secp_p = get_secp_p_from_scope_or_constants(exec_scopes, constants)?;
+                ec_double_assign_new_y(exec_scopes, &secp_p)
+            }
             hint_code::KECCAK_WRITE_ARGS => {
                 keccak_write_args(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
@@ -418,9 +530,24 @@ impl HintProcessor for BuiltinHintProcessor {
                 exec_scopes,
                 &hint_data.ids_data,
                 &hint_data.ap_tracking,
-                constants,
+                &get_secp_p(constants)?,
             ),
-            hint_code::FAST_EC_ADD_ASSIGN_NEW_Y => fast_ec_add_assign_new_y(exec_scopes, constants),
+            hint_code::FAST_EC_ADD_ASSIGN_NEW_X_SECP256R1 => {
+                let secp_p = exec_scopes.get::<BigInt>("SECP_P")?;
+                fast_ec_add_assign_new_x(
+                    vm,
+                    exec_scopes,
+                    &hint_data.ids_data,
+                    &hint_data.ap_tracking,
+                    &secp_p,
+                )
+            }
+            hint_code::FAST_EC_ADD_ASSIGN_NEW_Y => {
+                let secp_p = get_secp_p_from_scope_or_constants(exec_scopes, constants)?;
+                fast_ec_add_assign_new_y(exec_scopes, &secp_p)
+            }
+            hint_code::IMPORT_SECP256R1_P => import_secp256r1_p(exec_scopes),
+            hint_code::IMPORT_SECP256R1_ALPHA => import_secp256r1_alpha(exec_scopes),
             hint_code::EC_MUL_INNER => {
                 ec_mul_inner(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
@@ -430,31 +557,82 @@ impl HintProcessor for BuiltinHintProcessor {
             hint_code::TEMPORARY_ARRAY => {
                 temporary_array(vm, &hint_data.ids_data, &hint_data.ap_tracking)
             }
-            code => Err(HintError::UnknownHint(code.to_string())),
+            _ => self.execute_fallback(vm, exec_scopes, hint_data),
+        }
+    }
+
+    fn execute_fallback(
+        &self,
+        vm: &mut VirtualMachine,
+        exec_scopes: &mut ExecutionScopes,
+        hint_data: &HintProcessorData,
+    ) -> Result<(), HintError> {
+        let unknown_hint = HintError::UnknownHint(hint_data.code.to_string());
+        match &self.fallback_handler {
+            Some(fallback_handler) => match fallback_handler(hint_data, vm, exec_scopes) {
+                Err(HintError::UnknownHint(_)) => Err(unknown_hint),
+                result => result,
+            },
+            None => Err(unknown_hint),
         }
     }
 }
 
+impl HintProcessor for BuiltinHintProcessor {
+    fn execute_hint(
+        &mut self,
+        vm: &mut VirtualMachine,
+        exec_scopes: &mut ExecutionScopes,
+        hint_data: &Box<dyn Any>,
+        constants: &HashMap<String, Felt>,
+        _run_resources: &mut RunResources,
+    ) -> Result<(), HintError> {
+        let hint_data = hint_data
+            .downcast_ref::<HintProcessorData>()
+            .ok_or(HintError::WrongHintData)?;
+
+        self.execute_builtin_hint(vm, exec_scopes, hint_data, constants)
+            .map_err(|error| {
+                if !self.debug_ids {
+                    return error;
+                }
+                let ids_snapshot =
+                    get_ids_data_snapshot(&hint_data.ids_data, &hint_data.ap_tracking, vm);
+                HintError::WithIdsSnapshot(Box::new(error), ids_snapshot)
+            })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         any_box,
-        hint_processor::hint_processor_definition::HintProcessor,
-        types::{exec_scope::ExecutionScopes, relocatable::MaybeRelocatable},
+        hint_processor::{
+            builtin_hint_processor::hint_utils::insert_value_from_var_name,
+            hint_processor_definition::HintProcessor,
+        },
+        serde::deserialize_program::{
+            FlowTrackingData, HintParams, OffsetValue, Reference, ReferenceManager, ValueAddress,
+        },
+        types::{
+            exec_scope::ExecutionScopes, instruction::Register, program::Program,
+            relocatable::MaybeRelocatable,
+        },
         utils::test_utils::*,
         vm::{
             errors::{
                 exec_scope_errors::ExecScopeError, memory_errors::MemoryError,
                 vm_errors::VirtualMachineError,
             },
+            runners::cairo_runner::CairoRunner,
             vm_core::VirtualMachine,
             vm_memory::memory::Memory,
         },
     };
     use felt::NewFelt;
     use num_traits::{One, Zero};
-    use std::any::Any;
+    use std::{any::Any, sync::Arc};
 
     #[test]
     fn run_alloc_hint_empty_memory() {
@@ -613,6 +791,92 @@ mod tests {
         );
     }
 
+    // Runs the continue_copying hint with ids.continue_copying at (1, fp - 1) and returns
+    // the value it wrote there
+    fn run_continue_copying(
+        vm: &mut VirtualMachine,
+        exec_scopes: &mut ExecutionScopes,
+        fp: usize,
+    ) -> Result<Felt, HintError> {
+        let hint_code = "n -= 1\nids.continue_copying = 1 if n > 0 else 0";
+        vm.run_context.fp = fp;
+        let ids_data = ids_data!["continue_copying"];
+        run_hint!(*vm, ids_data, hint_code, exec_scopes)?;
+        Ok(vm
+            .memory
+            .get_integer(&(1, fp - 1).into())
+            .unwrap()
+            .into_owned())
+    }
+
+    #[test]
+    fn memcpy_continue_copying_nested_scopes() {
+        let enter_scope_code = "vm_enter_scope({'n': ids.len})";
+        let mut vm = vm!();
+        add_segments!(vm, 2);
+        // ids.len of the outer copy is at (1, 0), the one of the inner copy at (1, 1)
+        vm.memory = memory![((1, 0), 3), ((1, 1), 2)];
+        let mut exec_scopes = ExecutionScopes::new();
+
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["len"];
+        run_hint!(vm, ids_data, enter_scope_code, &mut exec_scopes).unwrap();
+        assert_eq!(
+            run_continue_copying(&mut vm, &mut exec_scopes, 11),
+            Ok(Felt::one())
+        );
+
+        // The inner copy runs its own two iterations
+        vm.run_context.fp = 2;
+        let ids_data = ids_data!["len"];
+        run_hint!(vm, ids_data, enter_scope_code, &mut exec_scopes).unwrap();
+        assert_eq!(
+            run_continue_copying(&mut vm, &mut exec_scopes, 12),
+            Ok(Felt::one())
+        );
+        assert_eq!(
+            run_continue_copying(&mut vm, &mut exec_scopes, 13),
+            Ok(Felt::zero())
+        );
+        assert!(run_hint!(vm, HashMap::new(), "vm_exit_scope()", &mut exec_scopes).is_ok());
+
+        // The outer copy resumes from where it left off
+        assert_eq!(exec_scopes.get::<Felt>("n"), Ok(Felt::new(2)));
+        assert_eq!(
+            run_continue_copying(&mut vm, &mut exec_scopes, 14),
+            Ok(Felt::one())
+        );
+        assert_eq!(
+            run_continue_copying(&mut vm, &mut exec_scopes, 15),
+            Ok(Felt::zero())
+        );
+    }
+
+    #[test]
+    fn memcpy_continue_copying_ignores_outer_scope_n() {
+        let mut vm = vm!();
+        add_segments!(vm, 2);
+        let mut exec_scopes = scope![("n", Felt::new(5))];
+        exec_scopes.enter_scope(HashMap::new());
+        assert_eq!(
+            run_continue_copying(&mut vm, &mut exec_scopes, 1),
+            Err(HintError::VariableNotInScopeError("n".to_string()))
+        );
+        exec_scopes.exit_scope().unwrap();
+        assert_eq!(exec_scopes.get::<Felt>("n"), Ok(Felt::new(5)));
+    }
+
+    #[test]
+    fn memcpy_continue_copying_zero_len_stops() {
+        let mut vm = vm!();
+        add_segments!(vm, 2);
+        let mut exec_scopes = scope![("n", Felt::zero())];
+        assert_eq!(
+            run_continue_copying(&mut vm, &mut exec_scopes, 1),
+            Ok(Felt::zero())
+        );
+    }
+
     #[test]
     fn exit_scope_valid() {
         let hint_code = "vm_exit_scope()";
@@ -812,6 +1076,102 @@ mod tests {
         assert!(run_hint!(vm, ids_data, hint_code).is_err());
     }
 
+    // Asserts that the cells at high_addr and low_addr hold the halves of the hex encoded hash
+    fn check_keccak_output(
+        vm: &VirtualMachine,
+        high_addr: (isize, usize),
+        low_addr: (isize, usize),
+        hash: &str,
+    ) {
+        assert_eq!(
+            vm.get_integer(&high_addr.into()).unwrap().as_ref(),
+            &felt::felt_str!(&hash[..32], 16)
+        );
+        assert_eq!(
+            vm.get_integer(&low_addr.into()).unwrap().as_ref(),
+            &felt::felt_str!(&hash[32..], 16)
+        );
+    }
+
+    #[test]
+    fn unsafe_keccak_empty_input() {
+        let hint_code = "from eth_hash.auto import keccak\n\ndata, length = ids.data, ids.length\n\nif '__keccak_max_size' in globals():\n    assert length <= __keccak_max_size, \\\n        f'unsafe_keccak() can only be used with length<={__keccak_max_size}. ' \\\n        f'Got: length={length}.'\n\nkeccak_input = bytearray()\nfor word_i, byte_i in enumerate(range(0, length, 16)):\n    word = memory[data + word_i]\n    n_bytes = min(16, length - byte_i)\n    assert 0 <= word < 2 ** (8 * n_bytes)\n    keccak_input += word.to_bytes(n_bytes, 'big')\n\nhashed = keccak(keccak_input)\nids.high = int.from_bytes(hashed[:16], 'big')\nids.low = int.from_bytes(hashed[16:32], 'big')";
+        let mut vm = vm!();
+        vm.run_context.fp = 4;
+        vm.memory = memory![((1, 0), 0), ((1, 1), (2, 0))];
+        let ids_data = ids_data!["length", "data", "high", "low"];
+        assert_eq!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        check_keccak_output(
+            &vm,
+            (1, 2),
+            (1, 3),
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        );
+    }
+
+    #[test]
+    fn unsafe_keccak_partial_word() {
+        let hint_code = "from eth_hash.auto import keccak\n\ndata, length = ids.data, ids.length\n\nif '__keccak_max_size' in globals():\n    assert length <= __keccak_max_size, \\\n        f'unsafe_keccak() can only be used with length<={__keccak_max_size}. ' \\\n        f'Got: length={length}.'\n\nkeccak_input = bytearray()\nfor word_i, byte_i in enumerate(range(0, length, 16)):\n    word = memory[data + word_i]\n    n_bytes = min(16, length - byte_i)\n    assert 0 <= word < 2 ** (8 * n_bytes)\n    keccak_input += word.to_bytes(n_bytes, 'big')\n\nhashed = keccak(keccak_input)\nids.high = int.from_bytes(hashed[:16], 'big')\nids.low = int.from_bytes(hashed[16:32], 'big')";
+        let mut vm = vm!();
+        vm.run_context.fp = 4;
+        // "abc" fits in a single 3-byte word
+        vm.memory = memory![((1, 0), 3), ((1, 1), (2, 0)), ((2, 0), 0x616263)];
+        let ids_data = ids_data!["length", "data", "high", "low"];
+        assert_eq!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        check_keccak_output(
+            &vm,
+            (1, 2),
+            (1, 3),
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+        );
+    }
+
+    #[test]
+    fn unsafe_keccak_word_exceeds_remaining_length() {
+        let hint_code = "from eth_hash.auto import keccak\n\ndata, length = ids.data, ids.length\n\nif '__keccak_max_size' in globals():\n    assert length <= __keccak_max_size, \\\n        f'unsafe_keccak() can only be used with length<={__keccak_max_size}. ' \\\n        f'Got: length={length}.'\n\nkeccak_input = bytearray()\nfor word_i, byte_i in enumerate(range(0, length, 16)):\n    word = memory[data + word_i]\n    n_bytes = min(16, length - byte_i)\n    assert 0 <= word < 2 ** (8 * n_bytes)\n    keccak_input += word.to_bytes(n_bytes, 'big')\n\nhashed = keccak(keccak_input)\nids.high = int.from_bytes(hashed[:16], 'big')\nids.low = int.from_bytes(hashed[16:32], 'big')";
+        let mut vm = vm!();
+        vm.run_context.fp = 4;
+        // The last word can only hold length % 16 = 1 byte
+        vm.memory = memory![((1, 0), 17), ((1, 1), (2, 0)), ((2, 0), 1), ((2, 1), 256)];
+        let ids_data = ids_data!["length", "data", "high", "low"];
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code),
+            Err(HintError::InvalidWordSize(Felt::new(256)))
+        );
+    }
+
+    #[test]
+    fn unsafe_keccak_finalize_two_words() {
+        let hint_code = "from eth_hash.auto import keccak\nkeccak_input = bytearray()\nn_elms = ids.keccak_state.end_ptr - ids.keccak_state.start_ptr\nfor word in memory.get_range(ids.keccak_state.start_ptr, n_elms):\n    keccak_input += word.to_bytes(16, 'big')\nhashed = keccak(keccak_input)\nids.high = int.from_bytes(hashed[:16], 'big')\nids.low = int.from_bytes(hashed[16:32], 'big')";
+        let mut vm = vm!();
+        vm.run_context.fp = 4;
+        vm.memory = memory![((1, 0), (2, 0)), ((1, 1), (2, 2)), ((2, 0), 1), ((2, 1), 2)];
+        let ids_data = non_continuous_ids_data![("keccak_state", -4), ("high", -2), ("low", -1)];
+        assert_eq!(run_hint!(vm, ids_data, hint_code), Ok(()));
+        // keccak256 of the words 1 and 2, as 16 big-endian bytes each
+        check_keccak_output(
+            &vm,
+            (1, 2),
+            (1, 3),
+            "8e351c482075133a6a3360c3a9d6a16fa95658fb68db49e8efd2f773012f5b1e",
+        );
+    }
+
+    #[test]
+    fn unsafe_keccak_finalize_word_exceeds_16_bytes() {
+        let hint_code = "from eth_hash.auto import keccak\nkeccak_input = bytearray()\nn_elms = ids.keccak_state.end_ptr - ids.keccak_state.start_ptr\nfor word in memory.get_range(ids.keccak_state.start_ptr, n_elms):\n    keccak_input += word.to_bytes(16, 'big')\nhashed = keccak(keccak_input)\nids.high = int.from_bytes(hashed[:16], 'big')\nids.low = int.from_bytes(hashed[16:32], 'big')";
+        let mut vm = vm!();
+        vm.run_context.fp = 4;
+        vm.memory = memory![((1, 0), (2, 0)), ((1, 1), (2, 1)), ((2, 1), 0)];
+        let word = Felt::one() << 128_u32;
+        vm.insert_value(&(2, 0).into(), &word).unwrap();
+        let ids_data = non_continuous_ids_data![("keccak_state", -4), ("high", -2), ("low", -1)];
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code),
+            Err(HintError::InvalidWordSize(word))
+        );
+    }
+
     fn enter_scope(
         _vm: &mut VirtualMachine,
         exec_scopes: &mut ExecutionScopes,
@@ -826,8 +1186,8 @@ mod tests {
     #[test]
     fn add_hint_add_same_hint_twice() {
         let mut hint_processor = BuiltinHintProcessor::new_empty();
-        let hint_func = Rc::new(HintFunc(Box::new(enter_scope)));
-        hint_processor.add_hint(String::from("enter_scope_custom_a"), Rc::clone(&hint_func));
+        let hint_func = Arc::new(HintFunc(Box::new(enter_scope)));
+        hint_processor.add_hint(String::from("enter_scope_custom_a"), Arc::clone(&hint_func));
         hint_processor.add_hint(String::from("enter_scope_custom_b"), hint_func);
         let mut vm = vm!();
         let exec_scopes = exec_scopes_ref!();
@@ -839,7 +1199,8 @@ mod tests {
                 &mut vm,
                 exec_scopes,
                 &any_box!(hint_data),
-                &HashMap::new()
+                &HashMap::new(),
+                &mut RunResources::default()
             ),
             Ok(())
         );
@@ -851,10 +1212,249 @@ mod tests {
                 &mut vm,
                 exec_scopes,
                 &any_box!(hint_data),
-                &HashMap::new()
+                &HashMap::new(),
+                &mut RunResources::default()
             ),
             Ok(())
         );
         assert_eq!(exec_scopes.data.len(), 3);
     }
+
+    #[test]
+    fn add_hint_run_program_with_custom_hint() {
+        fn write_constant_hint(
+            vm: &mut VirtualMachine,
+            _exec_scopes: &mut ExecutionScopes,
+            ids_data: &HashMap<String, HintReference>,
+            ap_tracking: &ApTracking,
+            _constants: &HashMap<String, Felt>,
+        ) -> Result<(), HintError> {
+            insert_value_from_var_name("a", Felt::new(42), vm, ids_data, ap_tracking)
+        }
+
+        // func main() { %{ ids.a = 42 %} ret }, with a = [fp]
+        let program = program!(
+            data = vec_data!((2345108766317314046_i64)),
+            main = Some(0),
+            hints = Arc::new(HashMap::from([(
+                0,
+                vec![HintParams {
+                    code: String::from("ids.a = 42"),
+                    accessible_scopes: Vec::new(),
+                    flow_tracking_data: FlowTrackingData {
+                        ap_tracking: ApTracking::new(),
+                        reference_ids: HashMap::from([(String::from("main.a"), 0)]),
+                    },
+                }],
+            )])),
+            reference_manager = ReferenceManager {
+                references: vec![Reference {
+                    ap_tracking_data: ApTracking::new(),
+                    pc: Some(0),
+                    value_address: ValueAddress {
+                        offset1: OffsetValue::Reference(Register::FP, 0, false),
+                        offset2: OffsetValue::Value(0),
+                        dereference: true,
+                        inner_dereference: false,
+                        value_type: String::from("felt"),
+                    },
+                }],
+            },
+        );
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        hint_processor.add_hint(
+            String::from("ids.a = 42"),
+            Arc::new(HintFunc(Box::new(write_constant_hint))),
+        );
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        assert_eq!(
+            cairo_runner.run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            ),
+            Ok(())
+        );
+        check_memory![vm.memory, ((1, 2), 42)];
+    }
+
+    #[test]
+    fn builtin_hint_processor_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<BuiltinHintProcessor>();
+    }
+
+    #[test]
+    fn builtin_hint_processor_runs_in_another_thread() {
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        hint_processor.add_hint(
+            String::from("enter_scope_custom"),
+            Arc::new(HintFunc(Box::new(enter_scope))),
+        );
+        hint_processor.set_fallback_handler(Box::new(|hint_data, _vm, _exec_scopes| {
+            Err(HintError::UnknownHint(hint_data.code.to_string()))
+        }));
+        let result = std::thread::spawn(move || {
+            let mut vm = vm!();
+            let hint_data =
+                HintProcessorData::new_default(String::from("enter_scope_custom"), HashMap::new());
+            hint_processor.execute_hint(
+                &mut vm,
+                exec_scopes_ref!(),
+                &any_box!(hint_data),
+                &HashMap::new(),
+                &mut RunResources::default(),
+            )
+        })
+        .join()
+        .unwrap();
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn fallback_handler_runs_unknown_hint() {
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        hint_processor.set_fallback_handler(Box::new(
+            |hint_data, vm, _exec_scopes| match &*hint_data.code {
+                "memory[ap] = syscall_handler.get_block_number()" => {
+                    let ap = vm.get_ap();
+                    vm.insert_value(&ap, Felt::new(1000))?;
+                    Ok(())
+                }
+                code => Err(HintError::UnknownHint(code.to_string())),
+            },
+        ));
+        let mut vm = vm!();
+        add_segments!(vm, 2);
+        let hint_data = HintProcessorData::new_default(
+            String::from("memory[ap] = syscall_handler.get_block_number()"),
+            HashMap::new(),
+        );
+        assert_eq!(
+            hint_processor.execute_hint(
+                &mut vm,
+                exec_scopes_ref!(),
+                &any_box!(hint_data),
+                &HashMap::new(),
+                &mut RunResources::default()
+            ),
+            Ok(())
+        );
+        check_memory![vm.memory, ((1, 0), 1000)];
+    }
+
+    #[test]
+    fn fallback_handler_declines_unknown_hint() {
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        hint_processor.set_fallback_handler(Box::new(|_hint_data, _vm, _exec_scopes| {
+            Err(HintError::UnknownHint(String::from("declined")))
+        }));
+        let mut vm = vm!();
+        let hint_data =
+            HintProcessorData::new_default(String::from("random_invalid_code"), HashMap::new());
+        assert_eq!(
+            hint_processor.execute_hint(
+                &mut vm,
+                exec_scopes_ref!(),
+                &any_box!(hint_data),
+                &HashMap::new(),
+                &mut RunResources::default()
+            ),
+            Err(HintError::UnknownHint(String::from("random_invalid_code")))
+        );
+    }
+
+    #[test]
+    fn fallback_handler_errors_are_returned() {
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        hint_processor.set_fallback_handler(Box::new(|_hint_data, _vm, _exec_scopes| {
+            Err(HintError::WrongHintData)
+        }));
+        let mut vm = vm!();
+        let hint_data =
+            HintProcessorData::new_default(String::from("random_invalid_code"), HashMap::new());
+        assert_eq!(
+            hint_processor.execute_hint(
+                &mut vm,
+                exec_scopes_ref!(),
+                &any_box!(hint_data),
+                &HashMap::new(),
+                &mut RunResources::default()
+            ),
+            Err(HintError::WrongHintData)
+        );
+    }
+
+    #[test]
+    fn debug_ids_attaches_snapshot_to_failed_assert() {
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        hint_processor.set_debug_ids(true);
+        let mut vm = vm!();
+        vm.run_context.fp = 10;
+        vm.memory = memory![((1, 7), 3), ((1, 8), 3)];
+        let ids_data = ids_data!["a", "b", "unset_value"];
+        let hint_data =
+            HintProcessorData::new_default(String::from(hint_code::ASSERT_NOT_EQUAL), ids_data);
+        let error = hint_processor
+            .execute_hint(
+                &mut vm,
+                exec_scopes_ref!(),
+                &any_box!(hint_data),
+                &HashMap::new(),
+                &mut RunResources::default(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            error,
+            HintError::WithIdsSnapshot(
+                Box::new(HintError::AssertNotEqualFail(
+                    MaybeRelocatable::from(Felt::new(3)),
+                    MaybeRelocatable::from(Felt::new(3))
+                )),
+                HashMap::from([
+                    ("a".to_string(), Some(MaybeRelocatable::from(Felt::new(3)))),
+                    ("b".to_string(), Some(MaybeRelocatable::from(Felt::new(3)))),
+                    ("unset_value".to_string(), None),
+                ])
+            )
+        );
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "{}\nids:\n    a           | 3\n    b           | 3\n    unset_value | <unknown>",
+                HintError::AssertNotEqualFail(
+                    MaybeRelocatable::from(Felt::new(3)),
+                    MaybeRelocatable::from(Felt::new(3))
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn failed_hint_without_debug_ids_has_no_snapshot() {
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut vm = vm!();
+        vm.run_context.fp = 10;
+        vm.memory = memory![((1, 8), 3), ((1, 9), 3)];
+        let hint_data = HintProcessorData::new_default(
+            String::from(hint_code::ASSERT_NOT_EQUAL),
+            ids_data!["a", "b"],
+        );
+        assert_eq!(
+            hint_processor.execute_hint(
+                &mut vm,
+                exec_scopes_ref!(),
+                &any_box!(hint_data),
+                &HashMap::new(),
+                &mut RunResources::default()
+            ),
+            Err(HintError::AssertNotEqualFail(
+                MaybeRelocatable::from(Felt::new(3)),
+                MaybeRelocatable::from(Felt::new(3))
+            ))
+        );
+    }
 }
@@ -45,8 +45,8 @@ pub fn keccak_write_args(
     let low = low.as_ref();
     let high = high.as_ref();
 
-    let low_args = [low & Felt::new(u64::MAX), low >> 64];
-    let high_args = [high & Felt::new(u64::MAX), high >> 64];
+    let low_args = [low & Felt::new(u64::MAX), low >> 64_u32];
+    let high_args = [high & Felt::new(u64::MAX), high >> 64_u32];
 
     let low_args: Vec<_> = low_args.into_iter().map(MaybeRelocatable::from).collect();
     vm.write_arg(&inputs_ptr, &low_args)
@@ -143,14 +143,27 @@ pub fn block_permutation(
     let keccak_ptr = get_ptr_from_var_name("keccak_ptr", vm, ids_data, ap_tracking)?;
 
     let keccak_state_size_felts = keccak_state_size_felts.to_usize().unwrap();
+    let state_ptr = keccak_ptr.sub_usize(keccak_state_size_felts)?;
     let values = vm
         .get_range(
-            &MaybeRelocatable::RelocatableValue(keccak_ptr.sub_usize(keccak_state_size_felts)?),
+            &MaybeRelocatable::RelocatableValue(state_ptr),
             keccak_state_size_felts,
         )
         .map_err(VirtualMachineError::MemoryError)?;
 
-    let mut u64_values = maybe_reloc_vec_to_u64_array(&values)?
+    let mut u64_values = values
+        .iter()
+        .enumerate()
+        .map(|(offset, value)| match value.as_deref() {
+            Some(MaybeRelocatable::Int(num)) => num
+                .to_u64()
+                .ok_or_else(|| HintError::InvalidKeccakStateWord(state_ptr + offset, num.clone())),
+            _ => Err(VirtualMachineError::ExpectedIntAtRange(
+                value.as_ref().map(|x| x.to_owned().into_owned()),
+            )
+            .into()),
+        })
+        .collect::<Result<Vec<u64>, HintError>>()?
         .try_into()
         .map_err(|_| VirtualMachineError::SliceToArrayError)?;
 
@@ -209,14 +222,12 @@ pub fn cairo_keccak_finalize(
         .map_err(|_| VirtualMachineError::SliceToArrayError)?;
     keccak::f1600(&mut inp);
 
-    let mut padding = vec![Felt::zero().into(); keccak_state_size_felts];
-    padding.extend(u64_array_to_mayberelocatable_vec(&inp));
+    let mut base_padding = vec![Felt::zero().into(); keccak_state_size_felts];
+    base_padding.extend(u64_array_to_mayberelocatable_vec(&inp));
 
-    let base_padding = padding.clone();
-
-    for _ in 0..(block_size - 1) {
-        padding.extend_from_slice(base_padding.as_slice());
-    }
+    let padding: Vec<MaybeRelocatable> = (0..block_size)
+        .flat_map(|_| base_padding.iter().cloned())
+        .collect();
 
     let keccak_ptr_end = get_ptr_from_var_name("keccak_ptr_end", vm, ids_data, ap_tracking)?;
 
@@ -257,8 +268,9 @@ mod tests {
     use crate::{
         any_box,
         hint_processor::{
-            builtin_hint_processor::builtin_hint_processor_definition::{
-                BuiltinHintProcessor, HintProcessorData,
+            builtin_hint_processor::{
+                builtin_hint_processor_definition::{BuiltinHintProcessor, HintProcessorData},
+                hint_code::{BLOCK_PERMUTATION, CAIRO_KECCAK_FINALIZE},
             },
             hint_processor_definition::{HintProcessor, HintReference},
         },
@@ -384,4 +396,229 @@ mod tests {
             Ok(())
         );
     }
+
+    // keccak-f[1600] of the all-zero state, as computed by keccak_func in cairo-lang
+    const ZERO_STATE_PERMUTATION: [u64; 25] = [
+        0xf1258f7940e1dde7,
+        0x84d5ccf933c0478a,
+        0xd598261ea65aa9ee,
+        0xbd1547306f80494d,
+        0x8b284e056253d057,
+        0xff97a42d7f8e6fd4,
+        0x90fee5a0a44647c4,
+        0x8c5bda0cd6192e76,
+        0xad30a6f71b19059c,
+        0x30935ab7d08ffc64,
+        0xeb5aa93f2317d635,
+        0xa9a6e6260d712103,
+        0x81a57c16dbcf555f,
+        0x43b831cd0347c826,
+        0x01f22f1a11a5569f,
+        0x05e5635a21d9ae61,
+        0x64befef28cc970f2,
+        0x613670957bc46611,
+        0xb87c5a554fd00ecb,
+        0x8c3ee88a1ccf32c8,
+        0x940c7922ae3a2614,
+        0x1841f924a2c509e4,
+        0x16f53526e70465c2,
+        0x75f644e97f30a13b,
+        0xeaf1ff7b5ceca249,
+    ];
+
+    fn keccak_constants(block_size: i32) -> HashMap<String, Felt> {
+        HashMap::from([
+            (KECCAK_STATE_SIZE_FELTS.to_string(), Felt::new(25)),
+            (BLOCK_SIZE.to_string(), Felt::new(block_size)),
+        ])
+    }
+
+    // Writes the state at (2, 0) and points ids.keccak_ptr (fp - 1) right after it
+    fn vm_with_keccak_state(state: &[Felt]) -> VirtualMachine {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), (2, 25))];
+        vm.memory.data.push(Vec::new());
+        for (offset, word) in state.iter().enumerate() {
+            vm.memory
+                .insert(
+                    &MaybeRelocatable::from((2, offset)),
+                    &MaybeRelocatable::from(word.clone()),
+                )
+                .unwrap();
+        }
+        vm.run_context.fp = 1;
+        vm
+    }
+
+    fn get_u64_range(vm: &VirtualMachine, addr: (isize, usize), size: usize) -> Vec<u64> {
+        let values = vm.get_range(&MaybeRelocatable::from(addr), size).unwrap();
+        maybe_reloc_vec_to_u64_array(&values).unwrap()
+    }
+
+    #[test]
+    fn block_permutation_zero_state() {
+        let mut vm = vm_with_keccak_state(&vec![Felt::zero(); 25]);
+        let ids_data = ids_data!["keccak_ptr"];
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                BLOCK_PERMUTATION,
+                exec_scopes_ref!(),
+                &keccak_constants(3)
+            ),
+            Ok(())
+        );
+        assert_eq!(get_u64_range(&vm, (2, 25), 25), ZERO_STATE_PERMUTATION);
+    }
+
+    #[test]
+    fn block_permutation_hashes_hello() {
+        // The padded block of "hello": keccak256 pads with 0x01 and sets the last bit of the
+        // 136-byte rate
+        let mut state = vec![Felt::zero(); 25];
+        state[0] = Felt::new(u64::from_le_bytes(*b"hello\x01\0\0"));
+        state[16] = Felt::new(0x8000000000000000_u64);
+        let mut vm = vm_with_keccak_state(&state);
+        let ids_data = ids_data!["keccak_ptr"];
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                BLOCK_PERMUTATION,
+                exec_scopes_ref!(),
+                &keccak_constants(3)
+            ),
+            Ok(())
+        );
+        let digest: Vec<u8> = get_u64_range(&vm, (2, 25), 4)
+            .iter()
+            .flat_map(|word| word.to_le_bytes())
+            .collect();
+        assert_eq!(
+            digest,
+            [
+                0x1c, 0x8a, 0xff, 0x95, 0x06, 0x85, 0xc2, 0xed, 0x4b, 0xc3, 0x17, 0x4f, 0x34, 0x72,
+                0x28, 0x7b, 0x56, 0xd9, 0x51, 0x7b, 0x9c, 0x94, 0x81, 0x27, 0x31, 0x9a, 0x09, 0xa7,
+                0xa3, 0x6d, 0xea, 0xc8
+            ]
+        );
+    }
+
+    #[test]
+    fn block_permutation_word_out_of_range() {
+        let mut state = vec![Felt::zero(); 25];
+        state[7] = Felt::new(u64::MAX) + Felt::new(1);
+        let mut vm = vm_with_keccak_state(&state);
+        let ids_data = ids_data!["keccak_ptr"];
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                BLOCK_PERMUTATION,
+                exec_scopes_ref!(),
+                &keccak_constants(3)
+            ),
+            Err(HintError::InvalidKeccakStateWord(
+                Relocatable::from((2, 7)),
+                Felt::new(u64::MAX) + Felt::new(1)
+            ))
+        );
+    }
+
+    #[test]
+    fn block_permutation_missing_word() {
+        let mut vm = vm_with_keccak_state(&vec![Felt::zero(); 24]);
+        let ids_data = ids_data!["keccak_ptr"];
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                BLOCK_PERMUTATION,
+                exec_scopes_ref!(),
+                &keccak_constants(3)
+            ),
+            Err(HintError::Internal(
+                VirtualMachineError::ExpectedIntAtRange(None)
+            ))
+        );
+    }
+
+    #[test]
+    fn block_permutation_invalid_state_size() {
+        let mut vm = vm_with_keccak_state(&vec![Felt::zero(); 25]);
+        let ids_data = ids_data!["keccak_ptr"];
+        let constants = HashMap::from([(KECCAK_STATE_SIZE_FELTS.to_string(), Felt::new(100))]);
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                BLOCK_PERMUTATION,
+                exec_scopes_ref!(),
+                &constants
+            ),
+            Err(HintError::InvalidKeccakStateSizeFelts(Felt::new(100)))
+        );
+    }
+
+    #[test]
+    fn cairo_keccak_finalize_writes_padding() {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), (2, 0))];
+        vm.memory.data.push(Vec::new());
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["keccak_ptr_end"];
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                CAIRO_KECCAK_FINALIZE,
+                exec_scopes_ref!(),
+                &keccak_constants(3)
+            ),
+            Ok(())
+        );
+        let mut block = vec![0; 25];
+        block.extend(ZERO_STATE_PERMUTATION);
+        assert_eq!(get_u64_range(&vm, (2, 0), 150), block.repeat(3));
+        assert_eq!(vm.memory.data[2].len(), 150);
+    }
+
+    #[test]
+    fn cairo_keccak_finalize_empty_block() {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), (2, 0))];
+        vm.memory.data.push(Vec::new());
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["keccak_ptr_end"];
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                CAIRO_KECCAK_FINALIZE,
+                exec_scopes_ref!(),
+                &keccak_constants(0)
+            ),
+            Ok(())
+        );
+        assert!(vm.memory.data[2].is_empty());
+    }
+
+    #[test]
+    fn cairo_keccak_finalize_invalid_block_size() {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), (2, 0))];
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["keccak_ptr_end"];
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                CAIRO_KECCAK_FINALIZE,
+                exec_scopes_ref!(),
+                &keccak_constants(10)
+            ),
+            Err(HintError::InvalidBlockSize(Felt::new(10)))
+        );
+    }
 }
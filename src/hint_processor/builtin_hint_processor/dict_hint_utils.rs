@@ -4,14 +4,14 @@ use crate::{
         builtin_hint_processor::{
             dict_manager::DictManager,
             hint_utils::{
-                get_integer_from_var_name, get_ptr_from_var_name, insert_value_from_var_name,
-                insert_value_into_ap,
+                get_integer_from_var_name, get_maybe_relocatable_from_var_name,
+                get_ptr_from_var_name, insert_value_from_var_name, insert_value_into_ap,
             },
         },
         hint_processor_definition::HintReference,
     },
     serde::deserialize_program::ApTracking,
-    types::exec_scope::ExecutionScopes,
+    types::{exec_scope::ExecutionScopes, relocatable::MaybeRelocatable},
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
 use felt::Felt;
@@ -20,14 +20,33 @@ use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
 //DictAccess struct has three memebers, so the size of DictAccess* is 3
 pub const DICT_ACCESS_SIZE: usize = 3;
 
-fn copy_initial_dict(exec_scopes: &mut ExecutionScopes) -> Option<HashMap<Felt, Felt>> {
-    let mut initial_dict: Option<HashMap<Felt, Felt>> = None;
-    if let Some(variable) = exec_scopes.get_local_variables().ok()?.get("initial_dict") {
-        if let Some(dict) = variable.downcast_ref::<HashMap<Felt, Felt>>() {
-            initial_dict = Some(dict.clone());
-        }
+//The types initial_dict can be stored as in the execution scope
+const INITIAL_DICT_TYPES: &str =
+    "HashMap<Felt, Felt> or HashMap<MaybeRelocatable, MaybeRelocatable>";
+
+//Copies the initial_dict from the current scope, if there is one. Its keys and values can be either
+//integers or relocatables. Dicts which only hold integers can also be stored as a HashMap<Felt, Felt>
+fn copy_initial_dict(
+    exec_scopes: &ExecutionScopes,
+) -> Result<Option<HashMap<MaybeRelocatable, MaybeRelocatable>>, HintError> {
+    let variable = match exec_scopes.get_local_variables()?.get("initial_dict") {
+        Some(variable) => variable,
+        None => return Ok(None),
+    };
+    if let Some(dict) = variable.downcast_ref::<HashMap<MaybeRelocatable, MaybeRelocatable>>() {
+        Ok(Some(dict.clone()))
+    } else if let Some(dict) = variable.downcast_ref::<HashMap<Felt, Felt>>() {
+        Ok(Some(
+            dict.iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        ))
+    } else {
+        Err(HintError::WrongScopeTypeError(
+            "initial_dict".to_string(),
+            INITIAL_DICT_TYPES.to_string(),
+        ))
     }
-    initial_dict
 }
 
 /*Implements hint:
@@ -37,16 +56,13 @@ fn copy_initial_dict(exec_scopes: &mut ExecutionScopes) -> Option<HashMap<Felt,
 
        memory[ap] = __dict_manager.new_dict(segments, initial_dict)
        del initial_dict
-
-For now, the functionality to create a dictionary from a previously defined initial_dict (using a hint)
-is not available
 */
 pub fn dict_new(
     vm: &mut VirtualMachine,
     exec_scopes: &mut ExecutionScopes,
 ) -> Result<(), HintError> {
     //Get initial dictionary from scope (defined by an earlier hint)
-    let initial_dict = copy_initial_dict(exec_scopes).ok_or(HintError::NoInitialDict)?;
+    let initial_dict = copy_initial_dict(exec_scopes)?.ok_or(HintError::NoInitialDict)?;
     //Check if there is a dict manager in scope, create it if there isnt one
     let base = if let Ok(dict_manager) = exec_scopes.get_dict_manager() {
         dict_manager.borrow_mut().new_dict(vm, initial_dict)?
@@ -76,10 +92,11 @@ pub fn default_dict_new(
     ap_tracking: &ApTracking,
 ) -> Result<(), HintError> {
     //Check that ids contains the reference id for each variable used by the hint
-    let default_value =
-        get_integer_from_var_name("default_value", vm, ids_data, ap_tracking)?.into_owned();
+    let default_value = MaybeRelocatable::from(
+        get_integer_from_var_name("default_value", vm, ids_data, ap_tracking)?.into_owned(),
+    );
     //Get initial dictionary from scope (defined by an earlier hint) if available
-    let initial_dict = copy_initial_dict(exec_scopes);
+    let initial_dict = copy_initial_dict(exec_scopes)?;
     //Check if there is a dict manager in scope, create it if there isnt one
     let base = if let Ok(dict_manager) = exec_scopes.get_dict_manager() {
         dict_manager
@@ -105,14 +122,13 @@ pub fn dict_read(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
 ) -> Result<(), HintError> {
-    let key = get_integer_from_var_name("key", vm, ids_data, ap_tracking)?;
-    let key = key.as_ref();
+    let key = get_maybe_relocatable_from_var_name("key", vm, ids_data, ap_tracking)?.into_owned();
     let dict_ptr = get_ptr_from_var_name("dict_ptr", vm, ids_data, ap_tracking)?;
     let dict_manager_ref = exec_scopes.get_dict_manager()?;
     let mut dict = dict_manager_ref.borrow_mut();
     let tracker = dict.get_tracker_mut(&dict_ptr)?;
     tracker.current_ptr.offset += DICT_ACCESS_SIZE;
-    let value = tracker.get_value(key)?;
+    let value = tracker.get_value(&key, vm)?;
     insert_value_from_var_name("value", value.clone(), vm, ids_data, ap_tracking)
 }
 
@@ -128,10 +144,9 @@ pub fn dict_write(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
 ) -> Result<(), HintError> {
-    let key = get_integer_from_var_name("key", vm, ids_data, ap_tracking)?;
-    let new_value = get_integer_from_var_name("new_value", vm, ids_data, ap_tracking)?;
-    let key = key.as_ref();
-    let new_value = new_value.as_ref();
+    let key = get_maybe_relocatable_from_var_name("key", vm, ids_data, ap_tracking)?.into_owned();
+    let new_value =
+        get_maybe_relocatable_from_var_name("new_value", vm, ids_data, ap_tracking)?.into_owned();
     let dict_ptr = get_ptr_from_var_name("dict_ptr", vm, ids_data, ap_tracking)?;
     //Get tracker for dictionary
     let dict_manager_ref = exec_scopes.get_dict_manager()?;
@@ -143,9 +158,9 @@ pub fn dict_write(
     //Tracker set to track next dictionary entry
     tracker.current_ptr.offset += DICT_ACCESS_SIZE;
     //Get previous value
-    let prev_value = tracker.get_value(key)?.clone();
+    let prev_value = tracker.get_value(&key, vm)?.clone();
     //Insert new value into tracker
-    tracker.insert_value(key, new_value);
+    tracker.insert_value(&key, &new_value);
     //Insert previous value into dict_ptr.prev_value
     //Addres for dict_ptr.prev_value should be dict_ptr* + 1 (defined above)
     vm.insert_value(&dict_ptr_prev_value, prev_value)?;
@@ -169,9 +184,11 @@ pub fn dict_update(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
 ) -> Result<(), HintError> {
-    let key = get_integer_from_var_name("key", vm, ids_data, ap_tracking)?;
-    let prev_value = get_integer_from_var_name("prev_value", vm, ids_data, ap_tracking)?;
-    let new_value = get_integer_from_var_name("new_value", vm, ids_data, ap_tracking)?;
+    let key = get_maybe_relocatable_from_var_name("key", vm, ids_data, ap_tracking)?.into_owned();
+    let prev_value =
+        get_maybe_relocatable_from_var_name("prev_value", vm, ids_data, ap_tracking)?.into_owned();
+    let new_value =
+        get_maybe_relocatable_from_var_name("new_value", vm, ids_data, ap_tracking)?.into_owned();
     let dict_ptr = get_ptr_from_var_name("dict_ptr", vm, ids_data, ap_tracking)?;
 
     //Get tracker for dictionary
@@ -179,16 +196,16 @@ pub fn dict_update(
     let mut dict = dict_manager_ref.borrow_mut();
     let tracker = dict.get_tracker_mut(&dict_ptr)?;
     //Check that prev_value is equal to the current value at the given key
-    let current_value = tracker.get_value(key.as_ref())?;
-    if current_value != prev_value.as_ref() {
+    let current_value = tracker.get_value(&key, vm)?;
+    if current_value != &prev_value {
         return Err(HintError::WrongPrevValue(
-            prev_value.into_owned(),
+            prev_value,
             current_value.clone(),
-            key.into_owned(),
+            key,
         ));
     }
     //Update Value
-    tracker.insert_value(key.as_ref(), new_value.as_ref());
+    tracker.insert_value(&key, &new_value);
     tracker.current_ptr.offset += DICT_ACCESS_SIZE;
     Ok(())
 }
@@ -305,6 +322,84 @@ mod tests {
         );
     }
 
+    #[test]
+    fn run_dict_new_with_relocatable_initial_dict() {
+        let hint_code = "if '__dict_manager' not in globals():\n    from starkware.cairo.common.dict import DictManager\n    __dict_manager = DictManager()\n\nmemory[ap] = __dict_manager.new_dict(segments, initial_dict)\ndel initial_dict";
+        let mut vm = vm!();
+        add_segments!(vm, 1);
+        let initial_dict = HashMap::from([
+            (mayberelocatable!(1), mayberelocatable!(4, 2)),
+            (mayberelocatable!(2), mayberelocatable!(7)),
+        ]);
+        //Store initial dict in scope
+        let mut exec_scopes = scope![("initial_dict", initial_dict.clone())];
+        //ids and references are not needed for this test
+        run_hint!(vm, HashMap::new(), hint_code, &mut exec_scopes)
+            .expect("Error while executing hint");
+        check_memory![vm.memory, ((1, 0), (1, 0))];
+        assert_eq!(
+            exec_scopes
+                .get_dict_manager()
+                .unwrap()
+                .borrow()
+                .trackers
+                .get(&1),
+            Some(&DictTracker::new_with_initial(
+                &relocatable!(1, 0),
+                initial_dict
+            ))
+        );
+    }
+
+    #[test]
+    fn run_dict_new_with_felt_initial_dict() {
+        let hint_code = "if '__dict_manager' not in globals():\n    from starkware.cairo.common.dict import DictManager\n    __dict_manager = DictManager()\n\nmemory[ap] = __dict_manager.new_dict(segments, initial_dict)\ndel initial_dict";
+        let mut vm = vm!();
+        add_segments!(vm, 1);
+        //Store initial dict in scope
+        let mut exec_scopes = scope![(
+            "initial_dict",
+            HashMap::from([(Felt::new(1), Felt::new(5)), (Felt::new(3), Felt::new(8))])
+        )];
+        //ids and references are not needed for this test
+        run_hint!(vm, HashMap::new(), hint_code, &mut exec_scopes)
+            .expect("Error while executing hint");
+        check_memory![vm.memory, ((1, 0), (1, 0))];
+        assert_eq!(
+            exec_scopes
+                .get_dict_manager()
+                .unwrap()
+                .borrow()
+                .trackers
+                .get(&1),
+            Some(&DictTracker::new_with_initial(
+                &relocatable!(1, 0),
+                HashMap::from([
+                    (mayberelocatable!(1), mayberelocatable!(5)),
+                    (mayberelocatable!(3), mayberelocatable!(8)),
+                ])
+            ))
+        );
+    }
+
+    #[test]
+    fn run_dict_new_with_wrong_initial_dict_type() {
+        let hint_code = "if '__dict_manager' not in globals():\n    from starkware.cairo.common.dict import DictManager\n    __dict_manager = DictManager()\n\nmemory[ap] = __dict_manager.new_dict(segments, initial_dict)\ndel initial_dict";
+        let mut vm = vm!();
+        add_segments!(vm, 1);
+        let mut exec_scopes = scope![("initial_dict", vec![Felt::new(1)])];
+        //ids and references are not needed for this test
+        assert_eq!(
+            run_hint!(vm, HashMap::new(), hint_code, &mut exec_scopes),
+            Err(HintError::WrongScopeTypeError(
+                "initial_dict".to_string(),
+                "HashMap<Felt, Felt> or HashMap<MaybeRelocatable, MaybeRelocatable>".to_string()
+            ))
+        );
+        //No dictionary was created
+        assert_eq!(vm.segments.num_segments, 1);
+    }
+
     #[test]
     fn run_dict_new_with_no_initial_dict() {
         let hint_code = "if '__dict_manager' not in globals():\n    from starkware.cairo.common.dict import DictManager\n    __dict_manager = DictManager()\n\nmemory[ap] = __dict_manager.new_dict(segments, initial_dict)\ndel initial_dict";
@@ -362,6 +457,54 @@ mod tests {
         check_dict_ptr!(&exec_scopes, 2, (2, 3));
     }
 
+    #[test]
+    fn run_dict_read_relocatable_value() {
+        let hint_code = "dict_tracker = __dict_manager.get_tracker(ids.dict_ptr)\ndict_tracker.current_ptr += ids.DictAccess.SIZE\nids.value = dict_tracker.data[ids.key]";
+        let mut vm = vm!();
+        //Initialize fp
+        vm.run_context.fp = 3;
+        //Insert ids into memory
+        vm.memory = memory![((1, 0), 5), ((1, 2), (2, 0))];
+        let ids_data = ids_data!["key", "value", "dict_ptr"];
+        add_segments!(vm, 1);
+        let mut tracker = DictTracker::new_empty(&relocatable!(2, 0));
+        tracker.insert_value(&mayberelocatable!(5), &mayberelocatable!(3, 4));
+        let mut dict_manager = DictManager::new();
+        dict_manager.trackers.insert(2, tracker);
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("dict_manager", Rc::new(RefCell::new(dict_manager)));
+        //Execute the hint
+        assert_eq!(run_hint!(vm, ids_data, hint_code, &mut exec_scopes), Ok(()));
+        //Check that value variable (at address (1,1)) contains the pointer stored at key 5
+        check_memory![vm.memory, ((1, 1), (3, 4))];
+        check_dict_ptr!(&exec_scopes, 2, (2, 3));
+    }
+
+    #[test]
+    fn run_dict_read_segment_default_dict() {
+        let hint_code = "dict_tracker = __dict_manager.get_tracker(ids.dict_ptr)\ndict_tracker.current_ptr += ids.DictAccess.SIZE\nids.value = dict_tracker.data[ids.key]";
+        let mut vm = vm!();
+        //Initialize fp
+        vm.run_context.fp = 3;
+        //Insert ids into memory
+        vm.memory = memory![((1, 0), 5), ((1, 2), (2, 0))];
+        let ids_data = ids_data!["key", "value", "dict_ptr"];
+        add_segments!(vm, 3);
+        let mut dict_manager = DictManager::new();
+        dict_manager.trackers.insert(
+            2,
+            DictTracker::new_segment_default_dict(&relocatable!(2, 0), None),
+        );
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("dict_manager", Rc::new(RefCell::new(dict_manager)));
+        //Execute the hint
+        assert_eq!(run_hint!(vm, ids_data, hint_code, &mut exec_scopes), Ok(()));
+        //A new segment was allocated for the missing key, and its base written into ids.value
+        assert_eq!(vm.segments.num_segments, 4);
+        check_memory![vm.memory, ((1, 1), (3, 0))];
+        check_dict_ptr!(&exec_scopes, 2, (2, 3));
+    }
+
     #[test]
     fn run_dict_read_invalid_key() {
         let hint_code = "dict_tracker = __dict_manager.get_tracker(ids.dict_ptr)\ndict_tracker.current_ptr += ids.DictAccess.SIZE\nids.value = dict_tracker.data[ids.key]";
@@ -376,7 +519,7 @@ mod tests {
         dict_manager!(&mut exec_scopes, 2, (5, 12));
         assert_eq!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
-            Err(HintError::NoValueForKey(Felt::new(6)))
+            Err(HintError::NoValueForKey(mayberelocatable!(6)))
         );
     }
     #[test]
@@ -424,12 +567,30 @@ mod tests {
                 .get(&0),
             Some(&DictTracker::new_default_dict(
                 &relocatable!(0, 0),
-                &Felt::new(17),
+                &mayberelocatable!(17),
                 None
             ))
         );
     }
 
+    #[test]
+    fn run_default_dict_new_with_wrong_initial_dict_type() {
+        let hint_code = "if '__dict_manager' not in globals():\n    from starkware.cairo.common.dict import DictManager\n    __dict_manager = DictManager()\n\nmemory[ap] = __dict_manager.new_default_dict(segments, ids.default_value)";
+        let mut vm = vm!();
+        run_context!(vm, 0, 1, 1);
+        //insert ids.default_value into memory
+        vm.memory = memory![((1, 0), 17)];
+        let ids_data = ids_data!["default_value"];
+        let mut exec_scopes = scope![("initial_dict", HashMap::<Felt, u64>::new())];
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
+            Err(HintError::WrongScopeTypeError(
+                "initial_dict".to_string(),
+                "HashMap<Felt, Felt> or HashMap<MaybeRelocatable, MaybeRelocatable>".to_string()
+            ))
+        );
+    }
+
     #[test]
     fn run_default_dict_new_no_default_value() {
         let hint_code = "if '__dict_manager' not in globals():\n    from starkware.cairo.common.dict import DictManager\n    __dict_manager = DictManager()\n\nmemory[ap] = __dict_manager.new_default_dict(segments, ids.default_value)";
@@ -527,6 +688,73 @@ mod tests {
         check_memory![vm.memory, ((2, 1), 10)];
     }
 
+    #[test]
+    fn run_dict_write_relocatable_value() {
+        let hint_code = "dict_tracker = __dict_manager.get_tracker(ids.dict_ptr)\ndict_tracker.current_ptr += ids.DictAccess.SIZE\nids.dict_ptr.prev_value = dict_tracker.data[ids.key]\ndict_tracker.data[ids.key] = ids.new_value";
+        let mut vm = vm!();
+        //Initialize fp
+        vm.run_context.fp = 3;
+        let mut exec_scopes = ExecutionScopes::new();
+        dict_manager_default!(exec_scopes, 2, 2, (5, 10));
+        //Insert ids into memory, ids.new_value is a pointer
+        vm.memory = memory![((1, 0), 5), ((1, 1), (4, 2)), ((1, 2), (2, 0))];
+        add_segments!(vm, 1);
+        let ids_data = ids_data!["key", "new_value", "dict_ptr"];
+        //Execute the hint
+        assert_eq!(run_hint!(vm, ids_data, hint_code, &mut exec_scopes), Ok(()));
+        //Check that the dictionary now maps key 5 to the pointer (4, 2)
+        assert_eq!(
+            exec_scopes
+                .get_dict_manager()
+                .unwrap()
+                .borrow_mut()
+                .trackers
+                .get_mut(&2)
+                .unwrap()
+                .peek_value(&mayberelocatable!(5)),
+            Some(mayberelocatable!(4, 2))
+        );
+        check_dict_ptr!(exec_scopes, 2, (2, 3));
+        check_memory![vm.memory, ((2, 1), 10)];
+    }
+
+    #[test]
+    fn run_dict_update_relocatable_value() {
+        let hint_code = "# Verify dict pointer and prev value.\ndict_tracker = __dict_manager.get_tracker(ids.dict_ptr)\ncurrent_value = dict_tracker.data[ids.key]\nassert current_value == ids.prev_value, \\\n    f'Wrong previous value in dict. Got {ids.prev_value}, expected {current_value}.'\n\n# Update value.\ndict_tracker.data[ids.key] = ids.new_value\ndict_tracker.current_ptr += ids.DictAccess.SIZE";
+        let mut vm = vm!();
+        //Initialize fp
+        vm.run_context.fp = 4;
+        //Insert ids into memory, ids.prev_value and ids.new_value are pointers
+        vm.memory = memory![
+            ((1, 0), 5),
+            ((1, 1), (3, 4)),
+            ((1, 2), (3, 7)),
+            ((1, 3), (2, 0))
+        ];
+        add_segments!(vm, 1);
+        let mut tracker = DictTracker::new_empty(&relocatable!(2, 0));
+        tracker.insert_value(&mayberelocatable!(5), &mayberelocatable!(3, 4));
+        let mut dict_manager = DictManager::new();
+        dict_manager.trackers.insert(2, tracker);
+        let mut exec_scopes = ExecutionScopes::new();
+        exec_scopes.insert_value("dict_manager", Rc::new(RefCell::new(dict_manager)));
+        let ids_data = ids_data!["key", "prev_value", "new_value", "dict_ptr"];
+        //Execute the hint
+        assert_eq!(run_hint!(vm, ids_data, hint_code, &mut exec_scopes), Ok(()));
+        assert_eq!(
+            exec_scopes
+                .get_dict_manager()
+                .unwrap()
+                .borrow()
+                .trackers
+                .get(&2)
+                .unwrap()
+                .peek_value(&mayberelocatable!(5)),
+            Some(mayberelocatable!(3, 7))
+        );
+        check_dict_ptr!(exec_scopes, 2, (2, 3));
+    }
+
     #[test]
     fn run_dict_write_simple_valid_cant_write_new_key() {
         let hint_code = "dict_tracker = __dict_manager.get_tracker(ids.dict_ptr)\ndict_tracker.current_ptr += ids.DictAccess.SIZE\nids.dict_ptr.prev_value = dict_tracker.data[ids.key]\ndict_tracker.data[ids.key] = ids.new_value";
@@ -547,7 +775,7 @@ mod tests {
         //Execute the hint
         assert_eq!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
-            Err(HintError::NoValueForKey(Felt::new(5)))
+            Err(HintError::NoValueForKey(mayberelocatable!(5)))
         );
     }
 
@@ -619,11 +847,38 @@ mod tests {
         assert_eq!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
             Err(HintError::WrongPrevValue(
-                Felt::new(11),
-                Felt::new(10),
-                Felt::new(5)
+                mayberelocatable!(11),
+                mayberelocatable!(10),
+                mayberelocatable!(5)
+            ))
+        );
+    }
+
+    #[test]
+    fn run_dict_update_wrong_prev_value_leaves_tracker_unchanged() {
+        let hint_code = crate::hint_processor::builtin_hint_processor::hint_code::DICT_UPDATE;
+        let mut vm = vm!();
+        //Initialize fp
+        vm.run_context.fp = 4;
+        let mut exec_scopes = ExecutionScopes::new();
+        //The trackers of DictManager::new validate prev_value
+        dict_manager!(exec_scopes, 2, (5, 10));
+        //Insert ids into memory: key = 5, prev_value = 11, new_value = 20
+        vm.memory = memory![((1, 0), 5), ((1, 1), 11), ((1, 2), 20), ((1, 3), (2, 0))];
+        add_segments!(vm, 1);
+        let ids_data = ids_data!["key", "prev_value", "new_value", "dict_ptr"];
+        //Execute the hint
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
+            Err(HintError::WrongPrevValue(
+                mayberelocatable!(11),
+                mayberelocatable!(10),
+                mayberelocatable!(5)
             ))
         );
+        //Neither the value nor the tracker's current_ptr were updated
+        check_dictionary![exec_scopes, 2, (5, 10)];
+        check_dict_ptr!(exec_scopes, 2, (2, 0));
     }
 
     #[test]
@@ -645,7 +900,7 @@ mod tests {
         //Execute the hint
         assert_eq!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
-            Err(HintError::NoValueForKey(Felt::new(6)))
+            Err(HintError::NoValueForKey(mayberelocatable!(6)))
         );
     }
 
@@ -717,9 +972,9 @@ mod tests {
         assert_eq!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
             Err(HintError::WrongPrevValue(
-                Felt::new(11),
-                Felt::new(10),
-                Felt::new(5)
+                mayberelocatable!(11),
+                mayberelocatable!(10),
+                mayberelocatable!(5)
             ))
         );
     }
@@ -744,9 +999,9 @@ mod tests {
         assert_eq!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
             Err(HintError::WrongPrevValue(
-                Felt::new(10),
-                Felt::new(17),
-                Felt::new(6)
+                mayberelocatable!(10),
+                mayberelocatable!(17),
+                mayberelocatable!(6)
             ))
         );
     }
@@ -798,8 +1053,8 @@ mod tests {
             variables
                 .get("initial_dict")
                 .unwrap()
-                .downcast_ref::<HashMap<Felt, Felt>>(),
-            Some(&HashMap::<Felt, Felt>::new())
+                .downcast_ref::<HashMap<MaybeRelocatable, MaybeRelocatable>>(),
+            Some(&HashMap::new())
         );
     }
 
@@ -825,15 +1080,50 @@ mod tests {
             variables
                 .get("initial_dict")
                 .unwrap()
-                .downcast_ref::<HashMap<Felt, Felt>>(),
+                .downcast_ref::<HashMap<MaybeRelocatable, MaybeRelocatable>>(),
             Some(&HashMap::from([
-                (Felt::one(), Felt::new(2)),
-                (Felt::new(3), Felt::new(4)),
-                (Felt::new(5), Felt::new(6))
+                (mayberelocatable!(1), mayberelocatable!(2)),
+                (mayberelocatable!(3), mayberelocatable!(4)),
+                (mayberelocatable!(5), mayberelocatable!(6))
             ]))
         );
     }
 
+    #[test]
+    fn run_dict_squash_copy_dict_shares_manager_and_copies_dict() {
+        let hint_code = "# Prepare arguments for dict_new. In particular, the same dictionary values should be copied\n# to the new (squashed) dictionary.\nvm_enter_scope({\n    # Make __dict_manager accessible.\n    '__dict_manager': __dict_manager,\n    # Create a copy of the dict, in case it changes in the future.\n    'initial_dict': dict(__dict_manager.get_dict(ids.dict_accesses_end)),\n})";
+        let mut vm = vm!();
+        //Initialize fp
+        vm.run_context.fp = 1;
+        vm.memory = memory![((1, 0), (2, 0))];
+        add_segments!(vm, 1);
+        let ids_data = ids_data!["dict_accesses_end"];
+        let mut exec_scopes = ExecutionScopes::new();
+        dict_manager!(exec_scopes, 2, (1, 2));
+        let outer_dict_manager = exec_scopes.get_dict_manager().unwrap();
+        assert_eq!(run_hint!(vm, ids_data, hint_code, &mut exec_scopes), Ok(()));
+        //The new scope sees the same manager as the outer one
+        let inner_dict_manager = exec_scopes.get_dict_manager().unwrap();
+        assert!(Rc::ptr_eq(&outer_dict_manager, &inner_dict_manager));
+        //Writes after the hint don't change the copied dict
+        inner_dict_manager
+            .borrow_mut()
+            .get_tracker_mut(&relocatable!(2, 0))
+            .unwrap()
+            .insert_value(&mayberelocatable!(1), &mayberelocatable!(7));
+        let variables = exec_scopes.get_local_variables().unwrap();
+        assert_eq!(
+            variables
+                .get("initial_dict")
+                .unwrap()
+                .downcast_ref::<HashMap<MaybeRelocatable, MaybeRelocatable>>(),
+            Some(&HashMap::from([(
+                mayberelocatable!(1),
+                mayberelocatable!(2)
+            )]))
+        );
+    }
+
     #[test]
     fn run_dict_squash_copy_dict_invalid_no_dict() {
         let hint_code = "# Prepare arguments for dict_new. In particular, the same dictionary values should be copied\n# to the new (squashed) dictionary.\nvm_enter_scope({\n    # Make __dict_manager accessible.\n    '__dict_manager': __dict_manager,\n    # Create a copy of the dict, in case it changes in the future.\n    'initial_dict': dict(__dict_manager.get_dict(ids.dict_accesses_end)),\n})";
@@ -1,4 +1,5 @@
 use crate::{
+    serde::deserialize_program::{deserialize_felt_hex, serialize_felt_hex},
     types::relocatable::{MaybeRelocatable, Relocatable},
     vm::{
         errors::{
@@ -8,12 +9,14 @@ use crate::{
     },
 };
 use felt::Felt;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 #[derive(PartialEq, Debug, Clone)]
 ///Manages dictionaries in a Cairo program.
 ///Uses the segment index to associate the corresponding python dict with the Cairo dict.
 pub struct DictManager {
+    //Iteration order is unspecified, use iter_sorted to walk the trackers by segment index.
     pub trackers: HashMap<isize, DictTracker>,
 }
 
@@ -26,17 +29,92 @@ pub struct DictTracker {
     pub current_ptr: Relocatable,
 }
 
+//Keys and values can be either integers or relocatables, so that dictionaries can hold pointers
 #[derive(PartialEq, Debug, Clone)]
 pub enum Dictionary {
-    SimpleDictionary(HashMap<Felt, Felt>),
+    SimpleDictionary(HashMap<MaybeRelocatable, MaybeRelocatable>),
     DefaultDictionary {
-        dict: HashMap<Felt, Felt>,
-        default_value: Felt,
+        dict: HashMap<MaybeRelocatable, MaybeRelocatable>,
+        default_value: MaybeRelocatable,
     },
+    //Maps each missing key to a new memory segment, allocated the first time it is read,
+    //as done by defaultdict(lambda: segments.add())
+    SegmentDefaultDictionary(HashMap<MaybeRelocatable, MaybeRelocatable>),
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+///Serializable snapshot of a DictManager, used to checkpoint dictionaries between executions.
+///Integers are serialized as hex strings and relocatables as {segment_index, offset} objects.
+pub struct SerializableDictManager {
+    pub trackers: Vec<SerializableDictTracker>,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct SerializableDictTracker {
+    pub data: SerializableDictionary,
+    pub current_ptr: Relocatable,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SerializableDictionary {
+    SimpleDictionary {
+        entries: Vec<SerializableDictEntry>,
+    },
+    DefaultDictionary {
+        entries: Vec<SerializableDictEntry>,
+        default_value: SerializableDictValue,
+    },
+    SegmentDefaultDictionary {
+        entries: Vec<SerializableDictEntry>,
+    },
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct SerializableDictEntry {
+    pub key: SerializableDictValue,
+    pub value: SerializableDictValue,
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SerializableDictValue {
+    Int(
+        #[serde(
+            serialize_with = "serialize_felt_hex",
+            deserialize_with = "deserialize_felt_hex"
+        )]
+        Felt,
+    ),
+    Relocatable(Relocatable),
+}
+
+impl From<MaybeRelocatable> for SerializableDictValue {
+    fn from(value: MaybeRelocatable) -> Self {
+        match value {
+            MaybeRelocatable::Int(num) => SerializableDictValue::Int(num),
+            MaybeRelocatable::RelocatableValue(rel) => SerializableDictValue::Relocatable(rel),
+        }
+    }
+}
+
+impl From<SerializableDictValue> for MaybeRelocatable {
+    fn from(value: SerializableDictValue) -> Self {
+        match value {
+            SerializableDictValue::Int(num) => MaybeRelocatable::Int(num),
+            SerializableDictValue::Relocatable(rel) => MaybeRelocatable::RelocatableValue(rel),
+        }
+    }
 }
 
 impl Dictionary {
-    fn get(&mut self, key: &Felt) -> Option<&Felt> {
+    //Returns the value stored for key. Missing keys of a default dictionary are inserted with the
+    //default value, or with a new segment allocated in vm if it is a segment default dictionary
+    pub fn get(
+        &mut self,
+        key: &MaybeRelocatable,
+        vm: &mut VirtualMachine,
+    ) -> Option<&MaybeRelocatable> {
         match self {
             Self::SimpleDictionary(dict) => dict.get(key),
             Self::DefaultDictionary {
@@ -46,16 +124,34 @@ impl Dictionary {
                 dict.entry(key.clone())
                     .or_insert_with(|| default_value.clone()),
             ),
+            Self::SegmentDefaultDictionary(dict) => Some(
+                dict.entry(key.clone())
+                    .or_insert_with(|| MaybeRelocatable::from(vm.add_memory_segment())),
+            ),
         }
     }
 
-    fn insert(&mut self, key: &Felt, value: &Felt) {
+    //Same as get, but doesn't insert the default value for missing keys of a default dictionary.
+    //Missing keys of a segment default dictionary return None, as no segment is allocated for them
+    pub fn peek(&self, key: &MaybeRelocatable) -> Option<&MaybeRelocatable> {
+        match self {
+            Self::SimpleDictionary(dict) => dict.get(key),
+            Self::DefaultDictionary {
+                dict,
+                default_value,
+            } => Some(dict.get(key).unwrap_or(default_value)),
+            Self::SegmentDefaultDictionary(dict) => dict.get(key),
+        }
+    }
+
+    fn insert(&mut self, key: &MaybeRelocatable, value: &MaybeRelocatable) {
         let dict = match self {
             Self::SimpleDictionary(dict) => dict,
             Self::DefaultDictionary {
                 dict,
                 default_value: _,
             } => dict,
+            Self::SegmentDefaultDictionary(dict) => dict,
         };
         dict.insert(key.clone(), value.clone());
     }
@@ -67,13 +163,12 @@ impl DictManager {
             trackers: HashMap::<isize, DictTracker>::new(),
         }
     }
-    //Creates a new Cairo dictionary. The values of initial_dict can be integers, tuples or
-    //lists. See MemorySegments.gen_arg().
-    //For now, no initial dict will be processed (Assumes initial_dict = None)
+    //Creates a new Cairo dictionary. The keys and values of initial_dict can be integers or
+    //relocatables, such as pointers to structs previously written into memory.
     pub fn new_dict(
         &mut self,
         vm: &mut VirtualMachine,
-        initial_dict: HashMap<Felt, Felt>,
+        initial_dict: HashMap<MaybeRelocatable, MaybeRelocatable>,
     ) -> Result<MaybeRelocatable, HintError> {
         let base = vm.add_memory_segment();
         if self.trackers.contains_key(&base.segment_index) {
@@ -99,8 +194,8 @@ impl DictManager {
     pub fn new_default_dict(
         &mut self,
         vm: &mut VirtualMachine,
-        default_value: &Felt,
-        initial_dict: Option<HashMap<Felt, Felt>>,
+        default_value: &MaybeRelocatable,
+        initial_dict: Option<HashMap<MaybeRelocatable, MaybeRelocatable>>,
     ) -> Result<MaybeRelocatable, HintError> {
         let base = vm.add_memory_segment();
         if self.trackers.contains_key(&base.segment_index) {
@@ -115,6 +210,25 @@ impl DictManager {
         Ok(MaybeRelocatable::RelocatableValue(base))
     }
 
+    //Creates a new Cairo dictionary which maps each missing key to a new memory segment
+    pub fn new_segment_default_dict(
+        &mut self,
+        vm: &mut VirtualMachine,
+        initial_dict: Option<HashMap<MaybeRelocatable, MaybeRelocatable>>,
+    ) -> Result<MaybeRelocatable, HintError> {
+        let base = vm.add_memory_segment();
+        if self.trackers.contains_key(&base.segment_index) {
+            return Err(HintError::CantCreateDictionaryOnTakenSegment(
+                base.segment_index,
+            ));
+        }
+        self.trackers.insert(
+            base.segment_index,
+            DictTracker::new_segment_default_dict(&base, initial_dict),
+        );
+        Ok(MaybeRelocatable::RelocatableValue(base))
+    }
+
     //Returns the tracker which's current_ptr matches with the given dict_ptr
     pub fn get_tracker_mut(
         &mut self,
@@ -141,6 +255,82 @@ impl DictManager {
         }
         Ok(tracker)
     }
+
+    //Writes the contents of the dictionary tracked at dict_ptr into a new segment as a squashed
+    //dict: one (key, prev_value, new_value) triple per key, in ascending key order.
+    //As no access history is kept, prev_value and new_value both hold the current value.
+    //The tracker is moved to the new segment, with its current_ptr pointing to the end of the
+    //squashed dict. Returns the start and end pointers of the squashed dict.
+    pub fn squash_dict(
+        &mut self,
+        vm: &mut VirtualMachine,
+        dict_ptr: &Relocatable,
+    ) -> Result<(Relocatable, Relocatable), HintError> {
+        let mut entries: Vec<(MaybeRelocatable, MaybeRelocatable)> = self
+            .get_tracker(dict_ptr)?
+            .get_dictionary_copy()
+            .into_iter()
+            .collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+        let squashed_dict_start = vm.add_memory_segment();
+        let data: Vec<MaybeRelocatable> = entries
+            .into_iter()
+            .flat_map(|(key, value)| [key, value.clone(), value])
+            .collect();
+        vm.load_data(&MaybeRelocatable::from(squashed_dict_start), &data)
+            .map_err(VirtualMachineError::MemoryError)?;
+        let squashed_dict_end = &squashed_dict_start + data.len();
+
+        let mut tracker = self
+            .trackers
+            .remove(&dict_ptr.segment_index)
+            .ok_or(HintError::NoDictTracker(dict_ptr.segment_index))?;
+        tracker.current_ptr = squashed_dict_end;
+        self.trackers
+            .insert(squashed_dict_start.segment_index, tracker);
+        Ok((squashed_dict_start, squashed_dict_end))
+    }
+
+    //Returns a serializable copy of all the trackers, sorted by segment index
+    pub fn to_serializable(&self) -> SerializableDictManager {
+        SerializableDictManager {
+            trackers: self
+                .iter_sorted()
+                .map(|(_, tracker)| tracker.to_serializable())
+                .collect(),
+        }
+    }
+
+    //Iterates over the trackers in ascending segment index order
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (&isize, &DictTracker)> {
+        let mut trackers: Vec<(&isize, &DictTracker)> = self.trackers.iter().collect();
+        trackers.sort_by_key(|(segment_index, _)| **segment_index);
+        trackers.into_iter()
+    }
+
+    //Returns the segment indices of the tracked dictionaries in ascending order
+    pub fn segment_indices(&self) -> Vec<isize> {
+        self.iter_sorted()
+            .map(|(segment_index, _)| *segment_index)
+            .collect()
+    }
+
+    //Rebuilds a DictManager from its serializable copy
+    pub fn from_serializable(serializable: SerializableDictManager) -> Self {
+        DictManager {
+            trackers: serializable
+                .trackers
+                .into_iter()
+                .map(|tracker| {
+                    (
+                        tracker.current_ptr.segment_index,
+                        DictTracker::from_serializable(tracker),
+                    )
+                })
+                .collect(),
+        }
+    }
 }
 
 impl Default for DictManager {
@@ -159,8 +349,8 @@ impl DictTracker {
 
     pub fn new_default_dict(
         base: &Relocatable,
-        default_value: &Felt,
-        initial_dict: Option<HashMap<Felt, Felt>>,
+        default_value: &MaybeRelocatable,
+        initial_dict: Option<HashMap<MaybeRelocatable, MaybeRelocatable>>,
     ) -> Self {
         DictTracker {
             data: Dictionary::DefaultDictionary {
@@ -175,7 +365,20 @@ impl DictTracker {
         }
     }
 
-    pub fn new_with_initial(base: &Relocatable, initial_dict: HashMap<Felt, Felt>) -> Self {
+    pub fn new_segment_default_dict(
+        base: &Relocatable,
+        initial_dict: Option<HashMap<MaybeRelocatable, MaybeRelocatable>>,
+    ) -> Self {
+        DictTracker {
+            data: Dictionary::SegmentDefaultDictionary(initial_dict.unwrap_or_default()),
+            current_ptr: *base,
+        }
+    }
+
+    pub fn new_with_initial(
+        base: &Relocatable,
+        initial_dict: HashMap<MaybeRelocatable, MaybeRelocatable>,
+    ) -> Self {
         DictTracker {
             data: Dictionary::SimpleDictionary(initial_dict),
             current_ptr: *base,
@@ -183,25 +386,95 @@ impl DictTracker {
     }
 
     //Returns a copy of the contained dictionary, losing the dictionary type in the process
-    pub fn get_dictionary_copy(&self) -> HashMap<Felt, Felt> {
+    pub fn get_dictionary_copy(&self) -> HashMap<MaybeRelocatable, MaybeRelocatable> {
         match &self.data {
             Dictionary::SimpleDictionary(dict) => dict.clone(),
             Dictionary::DefaultDictionary {
                 dict,
                 default_value: _,
             } => dict.clone(),
+            Dictionary::SegmentDefaultDictionary(dict) => dict.clone(),
         }
     }
 
-    pub fn get_value(&mut self, key: &Felt) -> Result<&Felt, HintError> {
+    //Returns the value stored for key, vm is used to allocate the segments of a segment default dictionary
+    pub fn get_value(
+        &mut self,
+        key: &MaybeRelocatable,
+        vm: &mut VirtualMachine,
+    ) -> Result<&MaybeRelocatable, HintError> {
         self.data
-            .get(key)
+            .get(key, vm)
             .ok_or_else(|| HintError::NoValueForKey(key.clone()))
     }
 
-    pub fn insert_value(&mut self, key: &Felt, val: &Felt) {
+    //Returns the value stored for key (or the default value) without modifying the dictionary
+    pub fn peek_value(&self, key: &MaybeRelocatable) -> Option<MaybeRelocatable> {
+        self.data.peek(key).cloned()
+    }
+
+    pub fn insert_value(&mut self, key: &MaybeRelocatable, val: &MaybeRelocatable) {
         self.data.insert(key, val)
     }
+
+    pub fn to_serializable(&self) -> SerializableDictTracker {
+        let mut dict: Vec<(MaybeRelocatable, MaybeRelocatable)> =
+            self.get_dictionary_copy().into_iter().collect();
+        dict.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let entries = dict
+            .into_iter()
+            .map(|(key, value)| SerializableDictEntry {
+                key: key.into(),
+                value: value.into(),
+            })
+            .collect();
+        SerializableDictTracker {
+            data: match &self.data {
+                Dictionary::SimpleDictionary(_) => {
+                    SerializableDictionary::SimpleDictionary { entries }
+                }
+                Dictionary::DefaultDictionary {
+                    dict: _,
+                    default_value,
+                } => SerializableDictionary::DefaultDictionary {
+                    entries,
+                    default_value: default_value.clone().into(),
+                },
+                Dictionary::SegmentDefaultDictionary(_) => {
+                    SerializableDictionary::SegmentDefaultDictionary { entries }
+                }
+            },
+            current_ptr: self.current_ptr,
+        }
+    }
+
+    pub fn from_serializable(serializable: SerializableDictTracker) -> Self {
+        let into_dict =
+            |entries: Vec<SerializableDictEntry>| -> HashMap<MaybeRelocatable, MaybeRelocatable> {
+                entries
+                    .into_iter()
+                    .map(|entry| (entry.key.into(), entry.value.into()))
+                    .collect()
+            };
+        DictTracker {
+            data: match serializable.data {
+                SerializableDictionary::SimpleDictionary { entries } => {
+                    Dictionary::SimpleDictionary(into_dict(entries))
+                }
+                SerializableDictionary::DefaultDictionary {
+                    entries,
+                    default_value,
+                } => Dictionary::DefaultDictionary {
+                    dict: into_dict(entries),
+                    default_value: default_value.into(),
+                },
+                SerializableDictionary::SegmentDefaultDictionary { entries } => {
+                    Dictionary::SegmentDefaultDictionary(into_dict(entries))
+                }
+            },
+            current_ptr: serializable.current_ptr,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -209,7 +482,6 @@ mod tests {
     use super::*;
     use crate::{relocatable, utils::test_utils::*, vm::vm_core::VirtualMachine};
     use felt::NewFelt;
-    use num_traits::One;
 
     #[test]
     fn create_dict_manager() {
@@ -229,12 +501,13 @@ mod tests {
 
     #[test]
     fn create_dict_tracker_default() {
-        let dict_tracker = DictTracker::new_default_dict(&relocatable!(1, 0), &Felt::new(5), None);
+        let dict_tracker =
+            DictTracker::new_default_dict(&relocatable!(1, 0), &mayberelocatable!(5), None);
         assert_eq!(
             dict_tracker.data,
             Dictionary::DefaultDictionary {
                 dict: HashMap::new(),
-                default_value: Felt::new(5)
+                default_value: mayberelocatable!(5)
             }
         );
         assert_eq!(dict_tracker.current_ptr, relocatable!(1, 0));
@@ -258,14 +531,14 @@ mod tests {
     fn dict_manager_new_dict_default() {
         let mut dict_manager = DictManager::new();
         let mut vm = vm!();
-        let base = dict_manager.new_default_dict(&mut vm, &Felt::new(5), None);
+        let base = dict_manager.new_default_dict(&mut vm, &mayberelocatable!(5), None);
         assert_eq!(base, Ok(MaybeRelocatable::from((0, 0))));
         assert!(dict_manager.trackers.contains_key(&0));
         assert_eq!(
             dict_manager.trackers.get(&0),
             Some(&DictTracker::new_default_dict(
                 &relocatable!(0, 0),
-                &Felt::new(5),
+                &mayberelocatable!(5),
                 None
             ))
         );
@@ -276,8 +549,8 @@ mod tests {
     fn dict_manager_new_dict_with_initial_dict() {
         let mut dict_manager = DictManager::new();
         let mut vm = vm!();
-        let mut initial_dict = HashMap::<Felt, Felt>::new();
-        initial_dict.insert(Felt::new(5), Felt::new(5));
+        let mut initial_dict = HashMap::<MaybeRelocatable, MaybeRelocatable>::new();
+        initial_dict.insert(mayberelocatable!(5), mayberelocatable!(5));
         let base = dict_manager.new_dict(&mut vm, initial_dict.clone());
         assert_eq!(base, Ok(MaybeRelocatable::from((0, 0))));
         assert!(dict_manager.trackers.contains_key(&0));
@@ -294,24 +567,94 @@ mod tests {
     #[test]
     fn dict_manager_new_default_dict_with_initial_dict() {
         let mut dict_manager = DictManager::new();
-        let mut initial_dict = HashMap::<Felt, Felt>::new();
+        let mut initial_dict = HashMap::<MaybeRelocatable, MaybeRelocatable>::new();
         let mut vm = vm!();
-        initial_dict.insert(Felt::new(5), Felt::new(5));
-        let base =
-            dict_manager.new_default_dict(&mut vm, &Felt::new(7), Some(initial_dict.clone()));
+        initial_dict.insert(mayberelocatable!(5), mayberelocatable!(5));
+        let base = dict_manager.new_default_dict(
+            &mut vm,
+            &mayberelocatable!(7),
+            Some(initial_dict.clone()),
+        );
         assert_eq!(base, Ok(MaybeRelocatable::from((0, 0))));
         assert!(dict_manager.trackers.contains_key(&0));
         assert_eq!(
             dict_manager.trackers.get(&0),
             Some(&DictTracker::new_default_dict(
                 &relocatable!(0, 0),
-                &Felt::new(7),
+                &mayberelocatable!(7),
                 Some(initial_dict)
             ))
         );
         assert_eq!(vm.segments.num_segments, 1);
     }
 
+    #[test]
+    fn dict_manager_new_segment_default_dict() {
+        let mut dict_manager = DictManager::new();
+        let mut vm = vm!();
+        let base = dict_manager.new_segment_default_dict(&mut vm, None);
+        assert_eq!(base, Ok(MaybeRelocatable::from((0, 0))));
+        assert_eq!(
+            dict_manager.trackers.get(&0),
+            Some(&DictTracker::new_segment_default_dict(
+                &relocatable!(0, 0),
+                None
+            ))
+        );
+        assert_eq!(vm.segments.num_segments, 1);
+    }
+
+    #[test]
+    fn segment_default_dict_missing_keys_get_distinct_segments() {
+        let mut vm = vm!();
+        let mut tracker = DictTracker::new_segment_default_dict(&relocatable!(0, 0), None);
+        let first = tracker
+            .get_value(&mayberelocatable!(1), &mut vm)
+            .unwrap()
+            .clone();
+        let second = tracker
+            .get_value(&mayberelocatable!(2), &mut vm)
+            .unwrap()
+            .clone();
+        assert_eq!(first, mayberelocatable!(0, 0));
+        assert_eq!(second, mayberelocatable!(1, 0));
+        assert_eq!(vm.segments.num_segments, 2);
+    }
+
+    #[test]
+    fn segment_default_dict_repeated_reads_return_same_segment() {
+        let mut vm = vm!();
+        let mut tracker = DictTracker::new_segment_default_dict(&relocatable!(0, 0), None);
+        assert_eq!(tracker.peek_value(&mayberelocatable!(3)), None);
+        let first = tracker
+            .get_value(&mayberelocatable!(3), &mut vm)
+            .unwrap()
+            .clone();
+        assert_eq!(
+            tracker.get_value(&mayberelocatable!(3), &mut vm),
+            Ok(&first)
+        );
+        assert_eq!(tracker.peek_value(&mayberelocatable!(3)), Some(first));
+        assert_eq!(vm.segments.num_segments, 1);
+    }
+
+    #[test]
+    fn segment_default_dict_keeps_initial_values() {
+        let mut vm = vm!();
+        let mut tracker = DictTracker::new_segment_default_dict(
+            &relocatable!(0, 0),
+            Some(HashMap::from([(
+                mayberelocatable!(1),
+                mayberelocatable!(5),
+            )])),
+        );
+        assert_eq!(
+            tracker.get_value(&mayberelocatable!(1), &mut vm),
+            Ok(&mayberelocatable!(5))
+        );
+        assert_eq!(vm.segments.num_segments, 0);
+    }
+
     #[test]
     fn dict_manager_new_dict_empty_same_segment() {
         let mut dict_manager = DictManager::new();
@@ -330,7 +673,7 @@ mod tests {
         let mut dict_manager = DictManager::new();
         dict_manager.trackers.insert(
             0,
-            DictTracker::new_default_dict(&relocatable!(0, 0), &Felt::new(6), None),
+            DictTracker::new_default_dict(&relocatable!(0, 0), &mayberelocatable!(6), None),
         );
         let mut vm = vm!();
         assert_eq!(
@@ -339,22 +682,301 @@ mod tests {
         );
     }
 
+    #[test]
+    fn squash_dict_simple() {
+        let mut vm = vm!();
+        let mut dict_manager = DictManager::new();
+        let mut initial_dict = HashMap::<MaybeRelocatable, MaybeRelocatable>::new();
+        initial_dict.insert(mayberelocatable!(7), mayberelocatable!(70));
+        initial_dict.insert(mayberelocatable!(3), mayberelocatable!(30));
+        dict_manager.new_dict(&mut vm, initial_dict).unwrap();
+        let tracker = dict_manager.trackers.get_mut(&0).unwrap();
+        tracker.insert_value(&mayberelocatable!(5), &mayberelocatable!(50));
+        tracker.current_ptr = relocatable!(0, 3);
+        assert_eq!(
+            dict_manager.squash_dict(&mut vm, &relocatable!(0, 3)),
+            Ok((relocatable!(1, 0), relocatable!(1, 9)))
+        );
+        check_memory![
+            vm.memory,
+            ((1, 0), 3),
+            ((1, 1), 30),
+            ((1, 2), 30),
+            ((1, 3), 5),
+            ((1, 4), 50),
+            ((1, 5), 50),
+            ((1, 6), 7),
+            ((1, 7), 70),
+            ((1, 8), 70)
+        ];
+        assert!(!dict_manager.trackers.contains_key(&0));
+        assert_eq!(
+            dict_manager.trackers.get(&1).unwrap().current_ptr,
+            relocatable!(1, 9)
+        );
+    }
+
+    #[test]
+    fn squash_dict_relocatable_values() {
+        let mut vm = vm!();
+        let mut dict_manager = DictManager::new();
+        let initial_dict = HashMap::from([
+            (mayberelocatable!(4), mayberelocatable!(5, 2)),
+            (mayberelocatable!(1), mayberelocatable!(10)),
+        ]);
+        dict_manager.new_dict(&mut vm, initial_dict).unwrap();
+        assert_eq!(
+            dict_manager.squash_dict(&mut vm, &relocatable!(0, 0)),
+            Ok((relocatable!(1, 0), relocatable!(1, 6)))
+        );
+        check_memory![
+            vm.memory,
+            ((1, 0), 1),
+            ((1, 1), 10),
+            ((1, 2), 10),
+            ((1, 3), 4),
+            ((1, 4), (5, 2)),
+            ((1, 5), (5, 2))
+        ];
+    }
+
+    #[test]
+    fn squash_dict_default_includes_read_keys() {
+        let mut vm = vm!();
+        let mut dict_manager = DictManager::new();
+        dict_manager
+            .new_default_dict(&mut vm, &mayberelocatable!(17), None)
+            .unwrap();
+        let tracker = dict_manager.trackers.get_mut(&0).unwrap();
+        tracker.insert_value(&mayberelocatable!(2), &mayberelocatable!(4));
+        assert_eq!(
+            tracker.get_value(&mayberelocatable!(1), &mut vm),
+            Ok(&mayberelocatable!(17))
+        );
+        assert_eq!(
+            dict_manager.squash_dict(&mut vm, &relocatable!(0, 0)),
+            Ok((relocatable!(1, 0), relocatable!(1, 6)))
+        );
+        check_memory![
+            vm.memory,
+            ((1, 0), 1),
+            ((1, 1), 17),
+            ((1, 2), 17),
+            ((1, 3), 2),
+            ((1, 4), 4),
+            ((1, 5), 4)
+        ];
+    }
+
+    #[test]
+    fn squash_dict_empty() {
+        let mut vm = vm!();
+        let mut dict_manager = DictManager::new();
+        dict_manager.new_dict(&mut vm, HashMap::new()).unwrap();
+        assert_eq!(
+            dict_manager.squash_dict(&mut vm, &relocatable!(0, 0)),
+            Ok((relocatable!(1, 0), relocatable!(1, 0)))
+        );
+        assert_eq!(
+            dict_manager.trackers.get(&1).unwrap().current_ptr,
+            relocatable!(1, 0)
+        );
+    }
+
+    #[test]
+    fn squash_dict_mismatched_ptr() {
+        let mut vm = vm!();
+        let mut dict_manager = DictManager::new();
+        dict_manager.new_dict(&mut vm, HashMap::new()).unwrap();
+        assert_eq!(
+            dict_manager.squash_dict(&mut vm, &relocatable!(0, 3)),
+            Err(HintError::MismatchedDictPtr(
+                relocatable!(0, 0),
+                relocatable!(0, 3)
+            ))
+        );
+    }
+
     #[test]
     fn dictionary_get_insert_simple() {
+        let mut vm = vm!();
         let mut dictionary = Dictionary::SimpleDictionary(HashMap::new());
-        dictionary.insert(&Felt::one(), &Felt::new(2));
-        assert_eq!(dictionary.get(&Felt::one()), Some(&Felt::new(2)));
-        assert_eq!(dictionary.get(&Felt::new(2)), None);
+        dictionary.insert(&mayberelocatable!(1), &mayberelocatable!(2));
+        assert_eq!(
+            dictionary.get(&mayberelocatable!(1), &mut vm),
+            Some(&mayberelocatable!(2))
+        );
+        assert_eq!(dictionary.get(&mayberelocatable!(2), &mut vm), None);
     }
 
     #[test]
     fn dictionary_get_insert_default() {
+        let mut vm = vm!();
         let mut dictionary = Dictionary::DefaultDictionary {
             dict: HashMap::new(),
-            default_value: Felt::new(7),
+            default_value: mayberelocatable!(7),
         };
-        dictionary.insert(&Felt::one(), &Felt::new(2));
-        assert_eq!(dictionary.get(&Felt::one()), Some(&Felt::new(2)));
-        assert_eq!(dictionary.get(&Felt::new(2)), Some(&Felt::new(7)));
+        dictionary.insert(&mayberelocatable!(1), &mayberelocatable!(2));
+        assert_eq!(
+            dictionary.get(&mayberelocatable!(1), &mut vm),
+            Some(&mayberelocatable!(2))
+        );
+        assert_eq!(
+            dictionary.get(&mayberelocatable!(2), &mut vm),
+            Some(&mayberelocatable!(7))
+        );
+    }
+
+    #[test]
+    fn dictionary_peek_simple() {
+        let mut dictionary = Dictionary::SimpleDictionary(HashMap::new());
+        dictionary.insert(&mayberelocatable!(1), &mayberelocatable!(2));
+        assert_eq!(
+            dictionary.peek(&mayberelocatable!(1)),
+            Some(&mayberelocatable!(2))
+        );
+        assert_eq!(dictionary.peek(&mayberelocatable!(2)), None);
+    }
+
+    #[test]
+    fn peek_value_default_dict_doesnt_insert() {
+        let mut vm = vm!();
+        let mut tracker =
+            DictTracker::new_default_dict(&relocatable!(1, 0), &mayberelocatable!(7), None);
+        tracker.insert_value(&mayberelocatable!(1), &mayberelocatable!(2));
+        let dict_before = tracker.get_dictionary_copy();
+        assert_eq!(
+            tracker.peek_value(&mayberelocatable!(1)),
+            Some(mayberelocatable!(2))
+        );
+        assert_eq!(
+            tracker.peek_value(&mayberelocatable!(5)),
+            Some(mayberelocatable!(7))
+        );
+        assert_eq!(tracker.get_dictionary_copy(), dict_before);
+        assert_eq!(
+            tracker.get_value(&mayberelocatable!(5), &mut vm),
+            Ok(&mayberelocatable!(7))
+        );
+        assert_ne!(tracker.get_dictionary_copy(), dict_before);
+    }
+
+    #[test]
+    fn dict_manager_serializable_json_round_trip() {
+        let mut vm = vm!();
+        let mut dict_manager = DictManager::new();
+        let mut initial_dict = HashMap::<MaybeRelocatable, MaybeRelocatable>::new();
+        initial_dict.insert(mayberelocatable!(5), mayberelocatable!(-1));
+        initial_dict.insert(mayberelocatable!(1), mayberelocatable!(10));
+        dict_manager.new_dict(&mut vm, initial_dict).unwrap();
+        dict_manager
+            .new_default_dict(&mut vm, &mayberelocatable!(17), None)
+            .unwrap();
+        dict_manager
+            .trackers
+            .get_mut(&1)
+            .unwrap()
+            .insert_value(&mayberelocatable!(3), &mayberelocatable!(4));
+        dict_manager.trackers.get_mut(&1).unwrap().current_ptr = relocatable!(1, 3);
+
+        let json = serde_json::to_string(&dict_manager.to_serializable()).unwrap();
+        assert!(json.contains(r#""key":"0x5""#));
+        let deserialized: SerializableDictManager = serde_json::from_str(&json).unwrap();
+        assert_eq!(DictManager::from_serializable(deserialized), dict_manager);
+    }
+
+    #[test]
+    fn dict_manager_serializable_json_round_trip_relocatable_values() {
+        let mut vm = vm!();
+        let mut dict_manager = DictManager::new();
+        let initial_dict = HashMap::from([
+            (mayberelocatable!(2), mayberelocatable!(3, 4)),
+            (mayberelocatable!(1, 7), mayberelocatable!(6)),
+        ]);
+        dict_manager.new_dict(&mut vm, initial_dict).unwrap();
+        dict_manager
+            .new_default_dict(&mut vm, &mayberelocatable!(4, 0), None)
+            .unwrap();
+
+        let json = serde_json::to_string(&dict_manager.to_serializable()).unwrap();
+        assert!(json.contains(r#""value":{"segment_index":3,"offset":4}"#));
+        let deserialized: SerializableDictManager = serde_json::from_str(&json).unwrap();
+        assert_eq!(DictManager::from_serializable(deserialized), dict_manager);
+    }
+
+    #[test]
+    fn dict_manager_serializable_json_round_trip_segment_default_dict() {
+        let mut vm = vm!();
+        let mut dict_manager = DictManager::new();
+        dict_manager
+            .new_segment_default_dict(&mut vm, None)
+            .unwrap();
+        dict_manager
+            .trackers
+            .get_mut(&0)
+            .unwrap()
+            .get_value(&mayberelocatable!(9), &mut vm)
+            .unwrap();
+
+        let json = serde_json::to_string(&dict_manager.to_serializable()).unwrap();
+        assert!(json.contains(r#""type":"segment_default_dictionary""#));
+        let deserialized: SerializableDictManager = serde_json::from_str(&json).unwrap();
+        assert_eq!(DictManager::from_serializable(deserialized), dict_manager);
+    }
+
+    #[test]
+    fn dict_manager_to_serializable() {
+        let mut dict_manager = DictManager::new();
+        let mut tracker =
+            DictTracker::new_default_dict(&relocatable!(2, 6), &mayberelocatable!(7), None);
+        tracker.insert_value(&mayberelocatable!(2), &mayberelocatable!(3));
+        dict_manager.trackers.insert(2, tracker);
+        assert_eq!(
+            dict_manager.to_serializable(),
+            SerializableDictManager {
+                trackers: vec![SerializableDictTracker {
+                    data: SerializableDictionary::DefaultDictionary {
+                        entries: vec![SerializableDictEntry {
+                            key: SerializableDictValue::Int(Felt::new(2)),
+                            value: SerializableDictValue::Int(Felt::new(3))
+                        }],
+                        default_value: SerializableDictValue::Int(Felt::new(7))
+                    },
+                    current_ptr: relocatable!(2, 6)
+                }]
+            }
+        );
+    }
+
+    #[test]
+    fn dict_manager_iter_sorted() {
+        let mut dict_manager = DictManager::new();
+        for segment_index in [4, 0, 7, 2] {
+            dict_manager.trackers.insert(
+                segment_index,
+                DictTracker::new_empty(&relocatable!(segment_index, 0)),
+            );
+        }
+        assert_eq!(dict_manager.segment_indices(), vec![0, 2, 4, 7]);
+        let sorted: Vec<(isize, Relocatable)> = dict_manager
+            .iter_sorted()
+            .map(|(segment_index, tracker)| (*segment_index, tracker.current_ptr))
+            .collect();
+        assert_eq!(
+            sorted,
+            vec![
+                (0, relocatable!(0, 0)),
+                (2, relocatable!(2, 0)),
+                (4, relocatable!(4, 0)),
+                (7, relocatable!(7, 0))
+            ]
+        );
+    }
+
+    #[test]
+    fn dict_manager_iter_sorted_empty() {
+        let dict_manager = DictManager::new();
+        assert_eq!(dict_manager.iter_sorted().count(), 0);
+        assert_eq!(dict_manager.segment_indices(), Vec::<isize>::new());
     }
 }
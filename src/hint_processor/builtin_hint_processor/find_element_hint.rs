@@ -5,17 +5,14 @@ use crate::{
             insert_value_from_var_name,
         },
         hint_processor_definition::HintReference,
-        hint_processor_utils::felt_to_usize,
     },
+    math_utils::FeltToPrimitive,
     serde::deserialize_program::ApTracking,
-    types::exec_scope::ExecutionScopes,
-    vm::{
-        errors::{hint_errors::HintError, vm_errors::VirtualMachineError},
-        vm_core::VirtualMachine,
-    },
+    types::{exec_scope::ExecutionScopes, relocatable::MaybeRelocatable},
+    vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
 use felt::{Felt, NewFelt};
-use num_traits::{Signed, ToPrimitive};
+use num_traits::Signed;
 use std::collections::HashMap;
 
 pub fn find_element(
@@ -29,17 +26,18 @@ pub fn find_element(
     let n_elms = get_integer_from_var_name("n_elms", vm, ids_data, ap_tracking)?;
     let array_start = get_ptr_from_var_name("array_ptr", vm, ids_data, ap_tracking)?;
     let find_element_index = exec_scopes.get::<Felt>("find_element_index").ok();
-    let elm_size = elm_size_bigint
-        .to_usize()
-        .ok_or_else(|| HintError::ValueOutOfRange(elm_size_bigint.as_ref().clone()))?;
-    if elm_size == 0 {
+    if !elm_size_bigint.is_positive() {
         return Err(HintError::ValueOutOfRange(elm_size_bigint.into_owned()));
     }
+    // The element size and the indices fit in a usize, so their products can't wrap around the
+    // prime, and the checked addition fails if the offset of an element doesn't fit either
+    let elm_size = Felt::new(elm_size_bigint.to_usize_checked()?);
 
     if let Some(find_element_index_value) = find_element_index {
-        let find_element_index_usize = felt_to_usize(&find_element_index_value)?;
+        let index = Felt::new(find_element_index_value.to_usize_checked()?);
+        let elm_addr = (array_start + &(&elm_size * &index))?;
         let found_key = vm
-            .get_integer(&(array_start + (elm_size * find_element_index_usize)))
+            .get_integer(&elm_addr)
             .map_err(|_| HintError::KeyNotFound)?;
 
         if found_key.as_ref() != key.as_ref() {
@@ -65,13 +63,12 @@ pub fn find_element(
                 ));
             }
         }
-        let n_elms_iter: i32 = n_elms
-            .to_i32()
-            .ok_or_else(|| VirtualMachineError::OffsetExceeded(n_elms.into_owned()))?;
+        let n_elms_iter = n_elms.to_usize_checked()?;
 
         for i in 0..n_elms_iter {
+            let elm_addr = (array_start + &(&elm_size * &Felt::new(i)))?;
             let iter_key = vm
-                .get_integer(&(array_start + (elm_size * i as usize)))
+                .get_integer(&elm_addr)
                 .map_err(|_| HintError::KeyNotFound)?;
 
             if iter_key.as_ref() == key.as_ref() {
@@ -85,7 +82,9 @@ pub fn find_element(
             }
         }
 
-        Err(HintError::NoValueForKey(key.into_owned()))
+        Err(HintError::NoValueForKey(MaybeRelocatable::from(
+            key.into_owned(),
+        )))
     }
 }
 
@@ -118,18 +117,25 @@ pub fn search_sorted_lower(
         }
     }
 
-    let mut array_iter = vm.get_relocatable(&rel_array_ptr)?;
-    let n_elms_usize = n_elms.to_usize().ok_or(HintError::KeyNotFound)?;
-    let elm_size_usize = elm_size.to_usize().ok_or(HintError::KeyNotFound)?;
-
-    for i in 0..n_elms_usize {
-        let value = vm.get_integer(&array_iter)?;
+    let array_start = vm.get_relocatable(&rel_array_ptr)?;
+    let n_elms_usize = n_elms.to_usize_checked()?;
+    // Both factors of the element offsets fit in a usize, so they can't wrap around the prime
+    let elm_size = Felt::new(elm_size.to_usize_checked()?);
+
+    // The array is sorted, so the first element >= key can be found with a binary search.
+    // If there is no such element, the resulting index is n_elms
+    let (mut low, mut high) = (0, n_elms_usize);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let elm_addr = (array_start + &(&elm_size * &Felt::new(mid)))?;
+        let value = vm.get_integer(&elm_addr)?;
         if value.as_ref() >= key.as_ref() {
-            return insert_value_from_var_name("index", Felt::new(i), vm, ids_data, ap_tracking);
+            high = mid;
+        } else {
+            low = mid + 1;
         }
-        array_iter.offset += elm_size_usize;
     }
-    insert_value_from_var_name("index", n_elms.into_owned(), vm, ids_data, ap_tracking)
+    insert_value_from_var_name("index", Felt::new(low), vm, ids_data, ap_tracking)
 }
 
 #[cfg(test)]
@@ -144,9 +150,13 @@ mod tests {
             },
             hint_processor_definition::HintProcessor,
         },
+        relocatable,
         types::relocatable::MaybeRelocatable,
         utils::test_utils::*,
-        vm::vm_core::VirtualMachine,
+        vm::{
+            errors::{math_errors::MathError, vm_errors::VirtualMachineError},
+            vm_core::VirtualMachine,
+        },
     };
     use num_traits::{One, Zero};
     use std::any::Any;
@@ -246,7 +256,9 @@ mod tests {
         )]));
         assert_eq!(
             run_hint!(vm, ids_data, hint_code::FIND_ELEMENT),
-            Err(HintError::NoValueForKey(Felt::new(7)))
+            Err(HintError::NoValueForKey(MaybeRelocatable::from(Felt::new(
+                7
+            ))))
         );
     }
 
@@ -311,6 +323,63 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn find_elm_elm_size_above_usize_max() {
+        let elm_size = Felt::new(usize::MAX as u128 + 1);
+        let (mut vm, ids_data) = init_vm_ids_data(HashMap::from([(
+            "elm_size".to_string(),
+            MaybeRelocatable::Int(elm_size.clone()),
+        )]));
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::FIND_ELEMENT),
+            Err(HintError::Math(MathError::FeltToUsizeConversion(elm_size)))
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn find_elm_oracle_index_above_usize_max() {
+        let index = Felt::new(usize::MAX as u128 + 1);
+        let (mut vm, ids_data) = init_vm_ids_data(HashMap::new());
+        let mut exec_scopes = scope![("find_element_index", index.clone())];
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::FIND_ELEMENT, &mut exec_scopes),
+            Err(HintError::Math(MathError::FeltToUsizeConversion(index)))
+        );
+    }
+
+    #[test]
+    fn find_elm_oracle_index_offset_overflow() {
+        // elm_size * index fits in a usize, but adding it to array_ptr overflows the offset
+        let (mut vm, ids_data) = init_vm_ids_data(HashMap::from([(
+            "array_ptr".to_string(),
+            MaybeRelocatable::from((2, 2)),
+        )]));
+        let mut exec_scopes = scope![("find_element_index", Felt::new(usize::MAX / 2))];
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::FIND_ELEMENT, &mut exec_scopes),
+            Err(HintError::Math(MathError::RelocatableAddOffsetOverflow(
+                relocatable!(2, 2),
+                usize::MAX - 1
+            )))
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn find_elm_oracle_index_offset_above_usize_max() {
+        // Both elm_size = 2 and the index fit in a usize, but their product doesn't
+        let (mut vm, ids_data) = init_vm_ids_data(HashMap::new());
+        let mut exec_scopes = scope![("find_element_index", Felt::new(usize::MAX / 2 + 1))];
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::FIND_ELEMENT, &mut exec_scopes),
+            Err(HintError::Math(MathError::FeltToUsizeConversion(
+                Felt::new(usize::MAX as u128 + 1)
+            )))
+        );
+    }
+
     #[test]
     fn find_elm_not_int_n_elms() {
         let relocatable = MaybeRelocatable::from((1, 2));
@@ -453,6 +522,20 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn search_sorted_lower_n_elms_above_usize_max() {
+        let n_elms = Felt::new(usize::MAX as u128 + 1);
+        let (mut vm, ids_data) = init_vm_ids_data(HashMap::from([(
+            "n_elms".to_string(),
+            MaybeRelocatable::Int(n_elms.clone()),
+        )]));
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::SEARCH_SORTED_LOWER),
+            Err(HintError::Math(MathError::FeltToUsizeConversion(n_elms)))
+        );
+    }
+
     #[test]
     fn search_sorted_lower_empty_scope() {
         let (mut vm, ids_data) = init_vm_ids_data(HashMap::new());
@@ -476,4 +559,48 @@ mod tests {
             Err(HintError::FindElemMaxSize(Felt::one(), Felt::new(2)))
         );
     }
+
+    #[test]
+    fn search_sorted_lower_boundary_keys() {
+        // array_ptr -> [1, 2, 3, 4] when reading it with elm_size = 1
+        for (key, expected_index) in [(0, 0), (1, 0), (3, 2), (4, 3), (5, 4)] {
+            let (mut vm, ids_data) = init_vm_ids_data(HashMap::from([
+                ("elm_size".to_string(), MaybeRelocatable::from(Felt::one())),
+                ("n_elms".to_string(), MaybeRelocatable::from(Felt::new(4))),
+                ("key".to_string(), MaybeRelocatable::from(Felt::new(key))),
+            ]));
+            assert_eq!(
+                run_hint!(vm, ids_data, hint_code::SEARCH_SORTED_LOWER),
+                Ok(())
+            );
+            check_memory![vm.memory, ((1, 3), expected_index)];
+        }
+    }
+
+    #[test]
+    fn search_sorted_lower_empty_array() {
+        let (mut vm, ids_data) = init_vm_ids_data(HashMap::from([(
+            "n_elms".to_string(),
+            MaybeRelocatable::from(Felt::zero()),
+        )]));
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::SEARCH_SORTED_LOWER),
+            Ok(())
+        );
+        check_memory![vm.memory, ((1, 3), 0)];
+    }
+
+    #[test]
+    fn search_sorted_lower_not_int_key() {
+        let (mut vm, ids_data) = init_vm_ids_data(HashMap::from([(
+            "key".to_string(),
+            MaybeRelocatable::from((2, 0)),
+        )]));
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::SEARCH_SORTED_LOWER),
+            Err(HintError::Internal(VirtualMachineError::ExpectedInteger(
+                MaybeRelocatable::from((1, 4))
+            )))
+        );
+    }
 }
@@ -1,7 +1,10 @@
 use crate::{
     hint_processor::{
         hint_processor_definition::HintReference,
-        hint_processor_utils::{compute_addr_from_reference, get_integer_from_reference},
+        hint_processor_utils::{
+            compute_addr_from_reference, get_integer_from_reference,
+            get_maybe_relocatable_from_reference,
+        },
     },
     serde::deserialize_program::ApTracking,
     types::relocatable::{MaybeRelocatable, Relocatable},
@@ -93,6 +96,17 @@ pub fn get_integer_from_var_name<'a>(
     get_integer_from_reference(vm, reference, ap_tracking)
 }
 
+//Gets the value of the given ids variable, which can be either an integer or a relocatable
+pub fn get_maybe_relocatable_from_var_name<'a>(
+    var_name: &str,
+    vm: &'a VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<Cow<'a, MaybeRelocatable>, HintError> {
+    let reference = get_reference_from_var_name(var_name, ids_data)?;
+    get_maybe_relocatable_from_reference(vm, reference, ap_tracking)
+}
+
 pub fn get_reference_from_var_name<'a>(
     var_name: &str,
     ids_data: &'a HashMap<String, HintReference>,
@@ -150,15 +150,8 @@ pub fn unsafe_keccak_finalize(
         offset: keccak_state_ptr.offset + 1,
     })?;
 
-    // this is not very nice code, we should consider adding the sub() method for Relocatable's
+    let n_elems = (end_ptr - start_ptr).map_err(VirtualMachineError::MathError)?;
     let maybe_rel_start_ptr = MaybeRelocatable::RelocatableValue(start_ptr);
-    let maybe_rel_end_ptr = MaybeRelocatable::RelocatableValue(end_ptr);
-
-    let n_elems = maybe_rel_end_ptr
-        .sub(&maybe_rel_start_ptr)?
-        .get_int_ref()?
-        .to_usize()
-        .ok_or(VirtualMachineError::BigintToUsizeFail)?;
 
     let mut keccak_input = Vec::new();
     let range = vm
@@ -172,6 +165,10 @@ pub fn unsafe_keccak_finalize(
         let word = word.get_int_ref()?;
 
         let mut bytes = word.to_bytes_be();
+        // word.to_bytes(16, 'big') fails for words that don't fit in 16 bytes
+        if bytes.len() > 16 {
+            return Err(HintError::InvalidWordSize(word.clone()));
+        }
         let mut bytes = {
             let n_word_bytes = &bytes.len();
             left_pad(&mut bytes, 16 - n_word_bytes)
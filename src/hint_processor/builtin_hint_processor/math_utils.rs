@@ -68,7 +68,54 @@ pub fn is_nn_out_of_range(
 //        assert a <= b, f'a = {a} is not less than or equal to b = {b}.'
 //        ids.small_inputs = int(
 //            a < range_check_builtin.bound and (b - a) < range_check_builtin.bound)
+// This is the assert_le_felt hint of cairo-lang versions before 0.10
+pub fn assert_le_felt_v_0_6(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let a = get_integer_from_var_name("a", vm, ids_data, ap_tracking)?.into_owned();
+    let b = get_integer_from_var_name("b", vm, ids_data, ap_tracking)?.into_owned();
+
+    if a > b {
+        return Err(HintError::NonLeFelt(a, b));
+    }
 
+    let small_inputs = match &vm.get_range_check_builtin()?._bound {
+        Some(bound) => &a < bound && &(&b - &a) < bound,
+        None => true,
+    };
+    let small_inputs = if small_inputs {
+        Felt::one()
+    } else {
+        Felt::zero()
+    };
+    insert_value_from_var_name("small_inputs", small_inputs, vm, ids_data, ap_tracking)
+}
+
+/* Implements hint:
+import itertools
+
+from starkware.cairo.common.math_utils import assert_integer
+assert_integer(ids.a)
+assert_integer(ids.b)
+a = ids.a % PRIME
+b = ids.b % PRIME
+assert a <= b, f'a = {a} is not less than or equal to b = {b}.'
+
+# Find an arc less than PRIME / 3, and another less than PRIME / 2.
+lengths_and_indices = [(a, 0), (b - a, 1), (PRIME - 1 - b, 2)]
+lengths_and_indices.sort()
+assert lengths_and_indices[0][0] <= PRIME // 3 and lengths_and_indices[1][0] <= PRIME // 2
+excluded = lengths_and_indices[2][1]
+
+memory[ids.range_check_ptr + 1], memory[ids.range_check_ptr + 0] = (
+    divmod(lengths_and_indices[0][0], ids.PRIME_OVER_3_HIGH))
+memory[ids.range_check_ptr + 3], memory[ids.range_check_ptr + 2] = (
+    divmod(lengths_and_indices[1][0], ids.PRIME_OVER_2_HIGH))
+*/
+// This is the assert_le_felt hint of cairo-lang 0.10 and later, followed by the
+// ASSERT_LE_FELT_EXCLUDED hints, which read the `excluded` arc stored in the scope
 pub fn assert_le_felt(
     vm: &mut VirtualMachine,
     exec_scopes: &mut ExecutionScopes,
@@ -101,14 +148,14 @@ pub fn assert_le_felt(
     let arc2 = Felt::zero() - Felt::one() - b;
     let mut lengths_and_indices = vec![(a, 0_i32), (&arc1, 1_i32), (&arc2, 2_i32)];
     lengths_and_indices.sort();
-    if lengths_and_indices[0].0 > &div_prime_by_bound(Felt::new(3_i32))?
-        || lengths_and_indices[1].0 > &div_prime_by_bound(Felt::new(2_i32))?
+    if lengths_and_indices[0].0 > &div_prime_by_bound(Felt::three())?
+        || lengths_and_indices[1].0 > &div_prime_by_bound(Felt::two())?
     {
         return Err(HintError::ArcTooBig(
             lengths_and_indices[0].0.clone(),
-            div_prime_by_bound(Felt::new(3_i32))?,
+            div_prime_by_bound(Felt::three())?,
             lengths_and_indices[1].0.clone(),
-            div_prime_by_bound(Felt::new(3_i32))?,
+            div_prime_by_bound(Felt::two())?,
         ));
     }
 
@@ -128,7 +175,7 @@ pub fn assert_le_felt(
 pub fn assert_le_felt_excluded_2(exec_scopes: &mut ExecutionScopes) -> Result<(), HintError> {
     let excluded: Felt = exec_scopes.get("excluded")?;
 
-    if excluded != Felt::new(2_i32) {
+    if excluded != Felt::two() {
         Err(HintError::ExcludedNot2(excluded))
     } else {
         Ok(())
@@ -196,33 +243,31 @@ pub fn assert_not_equal(
     //Check that the ids are in memory
     match (vm.get_maybe(&a_addr), vm.get_maybe(&b_addr)) {
         (Ok(Some(maybe_rel_a)), Ok(Some(maybe_rel_b))) => {
-            let maybe_rel_a = maybe_rel_a;
-            let maybe_rel_b = maybe_rel_b;
-            match (maybe_rel_a, maybe_rel_b) {
+            match (maybe_rel_a.as_ref(), maybe_rel_b.as_ref()) {
                 (MaybeRelocatable::Int(a), MaybeRelocatable::Int(b)) => {
-                    if (&a - &b).is_zero() {
+                    if (a - b).is_zero() {
                         return Err(HintError::AssertNotEqualFail(
-                            MaybeRelocatable::Int(a),
-                            MaybeRelocatable::Int(b),
+                            MaybeRelocatable::Int(a.clone()),
+                            MaybeRelocatable::Int(b.clone()),
                         ));
                     };
                     Ok(())
                 }
                 (MaybeRelocatable::RelocatableValue(a), MaybeRelocatable::RelocatableValue(b)) => {
                     if a.segment_index != b.segment_index {
-                        Err(VirtualMachineError::DiffIndexComp(a, b))?;
+                        Err(VirtualMachineError::DiffIndexComp(*a, *b))?;
                     };
                     if a.offset == b.offset {
                         return Err(HintError::AssertNotEqualFail(
-                            MaybeRelocatable::RelocatableValue(a),
-                            MaybeRelocatable::RelocatableValue(b),
+                            MaybeRelocatable::RelocatableValue(*a),
+                            MaybeRelocatable::RelocatableValue(*b),
                         ));
                     };
                     Ok(())
                 }
                 (maybe_rel_a, maybe_rel_b) => Err(VirtualMachineError::DiffTypeComparison(
-                    maybe_rel_a,
-                    maybe_rel_b,
+                    maybe_rel_a.clone(),
+                    maybe_rel_b.clone(),
                 ))?,
             }
         }
@@ -337,6 +382,11 @@ pub fn is_positive(
 
 //Implements hint:
 // %{
+//     ids.low = ids.value & ((1 << 128) - 1)
+//     ids.high = ids.value >> 128
+// %}
+//and hint:
+// %{
 //     from starkware.cairo.common.math_utils import assert_integer
 //     assert ids.MAX_HIGH < 2**128 and ids.MAX_LOW < 2**128
 //     assert PRIME - 1 == ids.MAX_HIGH * 2**128 + ids.MAX_LOW
@@ -406,10 +456,10 @@ pub fn signed_div_rem(
                 builtin_bound.clone(),
             ));
         }
-        Some(builtin_bound) if bound.as_ref() > &builtin_bound.shr(1) => {
+        Some(builtin_bound) if bound.as_ref() > &builtin_bound.shr(1_u32) => {
             return Err(HintError::OutOfValidRange(
                 bound.into_owned(),
-                builtin_bound.shr(1),
+                builtin_bound.shr(1_u32),
             ));
         }
         None if div.is_zero() => {
@@ -502,6 +552,64 @@ pub fn assert_250_bit(
     insert_value_from_var_name("low", low, vm, ids_data, ap_tracking)
 }
 
+/* Implements hint:
+%{
+    # Verify the assumptions on the relationship between 2**250, ADDR_BOUND and PRIME.
+    ADDR_BOUND = ids.ADDR_BOUND % PRIME
+    assert (2**250 < ADDR_BOUND <= 2**251) and (2 * 2**250 < PRIME) and (
+            ADDR_BOUND * 2 > PRIME), \
+        'normalize_address() cannot be used with the current constants.'
+    ids.is_small = 1 if ids.addr < ADDR_BOUND else 0
+%}
+*/
+pub fn is_addr_bounded(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    constants: &HashMap<String, Felt>,
+) -> Result<(), HintError> {
+    const ADDR_BOUND: &str = "starkware.starknet.common.storage.ADDR_BOUND";
+
+    let addr_bound = constants
+        .get(ADDR_BOUND)
+        .ok_or(HintError::MissingConstant(ADDR_BOUND))?;
+    let addr = get_integer_from_var_name("addr", vm, ids_data, ap_tracking)?;
+
+    // 2 * 2**250 < PRIME always holds, and as PRIME is odd,
+    // ADDR_BOUND * 2 > PRIME is equivalent to ADDR_BOUND > (PRIME - 1) / 2
+    let half_prime = (Felt::zero() - Felt::one()).shr(1_u32);
+    let is_valid_bound = addr_bound > &Felt::one().shl(250_u32)
+        && addr_bound <= &Felt::one().shl(251_u32)
+        && addr_bound > &half_prime;
+    if !is_valid_bound {
+        return Err(HintError::AssertionFailed(String::from(
+            "normalize_address() cannot be used with the current constants.",
+        )));
+    }
+
+    let is_small = if addr.as_ref() < addr_bound {
+        Felt::one()
+    } else {
+        Felt::zero()
+    };
+    insert_value_from_var_name("is_small", is_small, vm, ids_data, ap_tracking)
+}
+
+//Implements hint: ids.is_250 = 1 if ids.addr < 2**250 else 0
+pub fn is_250_bits(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let addr = get_integer_from_var_name("addr", vm, ids_data, ap_tracking)?;
+    let is_250 = if addr.bits() <= 250 {
+        Felt::one()
+    } else {
+        Felt::zero()
+    };
+    insert_value_from_var_name("is_250", is_250, vm, ids_data, ap_tracking)
+}
+
 /*
 Implements hint:
 %{
@@ -544,7 +652,10 @@ mod tests {
         any_box,
         hint_processor::builtin_hint_processor::{
             builtin_hint_processor_definition::{BuiltinHintProcessor, HintProcessorData},
-            hint_code::ASSERT_LE_FELT,
+            hint_code::{
+                ASSERT_LE_FELT, ASSERT_LE_FELT_EXCLUDED_0, ASSERT_LE_FELT_EXCLUDED_1,
+                ASSERT_LE_FELT_V_0_6, IS_250_BITS, IS_ADDR_BOUNDED, SPLIT_128,
+            },
         },
         hint_processor::hint_processor_definition::HintProcessor,
         relocatable,
@@ -719,6 +830,164 @@ mod tests {
         //Hint would return an error if the assertion fails
     }
 
+    fn assert_le_felt_constants() -> HashMap<String, Felt> {
+        HashMap::from([
+            (
+                "starkware.cairo.common.math.assert_le_felt.PRIME_OVER_3_HIGH".to_string(),
+                felt_str!("2AAAAAAAAAAAAB05555555555555556", 16),
+            ),
+            (
+                "starkware.cairo.common.math.assert_le_felt.PRIME_OVER_2_HIGH".to_string(),
+                felt_str!("4000000000000088000000000000001", 16),
+            ),
+        ])
+    }
+
+    // Runs ASSERT_LE_FELT with ids.a at (1, 0), ids.b at (1, 1) and range_check_ptr = (2, 0)
+    fn run_assert_le_felt(
+        a: Felt,
+        b: Felt,
+        exec_scopes: &mut ExecutionScopes,
+    ) -> (VirtualMachine, Result<(), HintError>) {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 3;
+        vm.memory = memory![((1, 2), (2, 0))];
+        vm.insert_value(&relocatable!(1, 0), a).unwrap();
+        vm.insert_value(&relocatable!(1, 1), b).unwrap();
+        add_segments!(vm, 1);
+        let ids_data = ids_data!["a", "b", "range_check_ptr"];
+        let result = run_hint!(
+            vm,
+            ids_data,
+            ASSERT_LE_FELT,
+            exec_scopes,
+            &assert_le_felt_constants()
+        );
+        (vm, result)
+    }
+
+    #[test]
+    fn run_assert_le_felt_equal_values() {
+        let mut exec_scopes = ExecutionScopes::new();
+        let (vm, result) = run_assert_le_felt(Felt::new(5), Felt::new(5), &mut exec_scopes);
+        assert_eq!(result, Ok(()));
+        // The arcs are [(5, 0), (0, 1), (PRIME - 6, 2)], so the last one is excluded
+        check_scope!(&exec_scopes, [("excluded", Felt::new(2))]);
+        check_memory![
+            vm.memory,
+            ((2, 0), 0),
+            ((2, 1), 0),
+            ((2, 2), 5),
+            ((2, 3), 0)
+        ];
+    }
+
+    #[test]
+    fn run_assert_le_felt_prime_minus_one() {
+        let mut exec_scopes = ExecutionScopes::new();
+        let max = Felt::zero() - Felt::one();
+        let (vm, result) = run_assert_le_felt(max.clone(), max, &mut exec_scopes);
+        assert_eq!(result, Ok(()));
+        // The arcs are [(PRIME - 1, 0), (0, 1), (0, 2)], so the first one is excluded
+        check_scope!(&exec_scopes, [("excluded", Felt::zero())]);
+        check_memory![
+            vm.memory,
+            ((2, 0), 0),
+            ((2, 1), 0),
+            ((2, 2), 0),
+            ((2, 3), 0)
+        ];
+    }
+
+    #[test]
+    fn run_assert_le_felt_big_arcs() {
+        let mut exec_scopes = ExecutionScopes::new();
+        // a = 2 * PRIME_OVER_3_HIGH * 2**128 + 7 leaves PRIME - 1 - b as the smallest arc
+        let a = felt_str!("2AAAAAAAAAAAAB05555555555555556", 16).shl(129_u32) + Felt::new(7);
+        let b = a.clone();
+        let (vm, result) = run_assert_le_felt(a.clone(), b, &mut exec_scopes);
+        assert_eq!(result, Ok(()));
+        check_scope!(&exec_scopes, [("excluded", Felt::zero())]);
+        let prime_over_2_high = felt_str!("4000000000000088000000000000001", 16);
+        let (q_1, r_1) = (Felt::zero() - Felt::one() - &a).div_mod_floor(&prime_over_2_high);
+        check_memory![vm.memory, ((2, 0), 0), ((2, 1), 0)];
+        assert_eq!(vm.get_integer(&relocatable!(2, 2)).unwrap().as_ref(), &r_1);
+        assert_eq!(vm.get_integer(&relocatable!(2, 3)).unwrap().as_ref(), &q_1);
+    }
+
+    #[test]
+    fn run_assert_le_felt_b_is_zero() {
+        let mut exec_scopes = ExecutionScopes::new();
+        let (_, result) = run_assert_le_felt(Felt::one(), Felt::zero(), &mut exec_scopes);
+        assert_eq!(result, Err(HintError::NonLeFelt(Felt::one(), Felt::zero())));
+        // Nothing is stored for the follow-up hints
+        assert!(exec_scopes.get::<Felt>("excluded").is_err());
+    }
+
+    #[test]
+    fn run_assert_le_felt_excluded_hints() {
+        let mut exec_scopes = ExecutionScopes::new();
+        let max = Felt::zero() - Felt::one();
+        let (mut vm, result) = run_assert_le_felt(Felt::new(3), max, &mut exec_scopes);
+        assert_eq!(result, Ok(()));
+        // The arcs are [(3, 0), (PRIME - 4, 1), (0, 2)], the second one is excluded
+        check_scope!(&exec_scopes, [("excluded", Felt::one())]);
+
+        vm.run_context.ap = 5;
+        assert_eq!(
+            run_hint!(
+                vm,
+                HashMap::new(),
+                ASSERT_LE_FELT_EXCLUDED_0,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        vm.run_context.ap = 6;
+        assert_eq!(
+            run_hint!(
+                vm,
+                HashMap::new(),
+                ASSERT_LE_FELT_EXCLUDED_1,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_memory![vm.memory, ((1, 5), 1), ((1, 6), 0)];
+    }
+
+    #[test]
+    fn run_assert_le_felt_v_0_6_small_inputs() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 3;
+        vm.memory = memory![((1, 0), 1), ((1, 1), 2)];
+        let ids_data = ids_data!["a", "b", "small_inputs"];
+        assert_eq!(run_hint!(vm, ids_data, ASSERT_LE_FELT_V_0_6), Ok(()));
+        check_memory![vm.memory, ((1, 2), 1)];
+    }
+
+    #[test]
+    fn run_assert_le_felt_v_0_6_big_inputs() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 3;
+        vm.memory = memory![((1, 0), 1), ((1, 1), (-1))];
+        let ids_data = ids_data!["a", "b", "small_inputs"];
+        assert_eq!(run_hint!(vm, ids_data, ASSERT_LE_FELT_V_0_6), Ok(()));
+        check_memory![vm.memory, ((1, 2), 0)];
+    }
+
+    #[test]
+    fn run_assert_le_felt_v_0_6_invalid() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 3;
+        vm.memory = memory![((1, 0), 1), ((1, 1), 0)];
+        let ids_data = ids_data!["a", "b", "small_inputs"];
+        assert_eq!(
+            run_hint!(vm, ids_data, ASSERT_LE_FELT_V_0_6),
+            Err(HintError::NonLeFelt(Felt::one(), Felt::zero()))
+        );
+    }
+
     #[test]
     fn is_le_felt_hint_true() {
         let hint_code = "memory[ap] = 0 if (ids.a % PRIME) <= (ids.b % PRIME) else 1";
@@ -1636,6 +1905,101 @@ mod tests {
         );
     }
 
+    fn addr_bound_constants(addr_bound: Felt) -> HashMap<String, Felt> {
+        HashMap::from([(
+            "starkware.starknet.common.storage.ADDR_BOUND".to_string(),
+            addr_bound,
+        )])
+    }
+
+    // ADDR_BOUND = 2**251 - 256
+    fn addr_bound() -> Felt {
+        Felt::one().shl(251_u32) - Felt::new(256)
+    }
+
+    fn run_is_addr_bounded(
+        addr: Felt,
+        addr_bound: Felt,
+    ) -> (VirtualMachine, Result<(), HintError>) {
+        let mut vm = vm!();
+        vm.run_context.fp = 2;
+        add_segments!(vm, 2);
+        vm.insert_value(&relocatable!(1, 0), addr).unwrap();
+        let ids_data = ids_data!["addr", "is_small"];
+        let result = run_hint!(
+            vm,
+            ids_data,
+            IS_ADDR_BOUNDED,
+            exec_scopes_ref!(),
+            &addr_bound_constants(addr_bound)
+        );
+        (vm, result)
+    }
+
+    #[test]
+    fn run_is_addr_bounded_below_bound() {
+        let (vm, result) = run_is_addr_bounded(addr_bound() - Felt::one(), addr_bound());
+        assert_eq!(result, Ok(()));
+        check_memory![vm.memory, ((1, 1), 1)];
+    }
+
+    #[test]
+    fn run_is_addr_bounded_at_bound() {
+        let (vm, result) = run_is_addr_bounded(addr_bound(), addr_bound());
+        assert_eq!(result, Ok(()));
+        check_memory![vm.memory, ((1, 1), 0)];
+    }
+
+    #[test]
+    fn run_is_addr_bounded_invalid_bound() {
+        // ADDR_BOUND must be in (2**250, 2**251] and greater than PRIME / 2
+        for addr_bound in [
+            Felt::one().shl(250_u32),
+            Felt::one().shl(250_u32) + Felt::one(),
+            Felt::one().shl(251_u32) + Felt::one(),
+        ] {
+            let (_, result) = run_is_addr_bounded(Felt::one(), addr_bound);
+            assert_eq!(
+                result,
+                Err(HintError::AssertionFailed(String::from(
+                    "normalize_address() cannot be used with the current constants."
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn run_is_addr_bounded_missing_constant() {
+        let mut vm = vm!();
+        vm.run_context.fp = 2;
+        add_segments!(vm, 2);
+        vm.insert_value(&relocatable!(1, 0), Felt::one()).unwrap();
+        let ids_data = ids_data!["addr", "is_small"];
+        assert_eq!(
+            run_hint!(vm, ids_data, IS_ADDR_BOUNDED),
+            Err(HintError::MissingConstant(
+                "starkware.starknet.common.storage.ADDR_BOUND"
+            ))
+        );
+    }
+
+    #[test]
+    fn run_is_250_bits() {
+        for (addr, is_250) in [
+            (Felt::one().shl(250_u32) - Felt::one(), 1),
+            (Felt::one().shl(250_u32), 0),
+            (addr_bound(), 0),
+        ] {
+            let mut vm = vm!();
+            vm.run_context.fp = 2;
+            add_segments!(vm, 2);
+            vm.insert_value(&relocatable!(1, 0), addr).unwrap();
+            let ids_data = ids_data!["addr", "is_250"];
+            assert_eq!(run_hint!(vm, ids_data, IS_250_BITS), Ok(()));
+            check_memory![vm.memory, ((1, 1), is_250)];
+        }
+    }
+
     #[test]
     fn run_split_felt_ok() {
         let hint_code =
@@ -1664,6 +2028,23 @@ mod tests {
         ];
     }
 
+    #[test]
+    fn run_split_128_ok() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 3;
+        vm.memory = memory![((1, 0), (2, 0))];
+        let value = Felt::new(3).shl(128_u32) + Felt::new(5);
+        vm.insert_value(&relocatable!(1, 2), value).unwrap();
+        add_segments!(vm, 1);
+        let ids_data = HashMap::from([
+            ("low".to_string(), HintReference::new(-3, 0, true, true)),
+            ("high".to_string(), HintReference::new(-3, 1, true, true)),
+            ("value".to_string(), HintReference::new_simple(-1)),
+        ]);
+        assert_eq!(run_hint!(vm, ids_data, SPLIT_128), Ok(()));
+        check_memory![vm.memory, ((2, 0), 5), ((2, 1), 3)];
+    }
+
     #[test]
     fn run_split_felt_incorrect_ids() {
         let hint_code =
@@ -9,8 +9,8 @@ use crate::{
     types::exec_scope::ExecutionScopes,
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
-use felt::Felt;
-use num_traits::{One, Zero};
+use felt::{Felt, NewFelt};
+use num_traits::{One, Signed};
 use std::{any::Any, collections::HashMap};
 
 //Implements hint: memory[ap] = segments.add()
@@ -56,19 +56,18 @@ pub fn memcpy_continue_copying(
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
 ) -> Result<(), HintError> {
-    // get `n` variable from vm scope
-    let n = exec_scopes.get_ref::<Felt>("n")?;
-    // this variable will hold the value of `n - 1`
-    let new_n = n - 1;
-    // if it is positive, insert 1 in the address of `continue_copying`
-    // else, insert 0
-    if new_n.is_zero() {
-        insert_value_from_var_name("continue_copying", &new_n, vm, ids_data, ap_tracking)?;
-    } else {
-        insert_value_from_var_name("continue_copying", Felt::one(), vm, ids_data, ap_tracking)?;
-    }
-    exec_scopes.insert_value("n", new_n);
-    Ok(())
+    // `n` is only looked up in the innermost scope, the one entered by memcpy_enter_scope,
+    // so nested copies never decrement the counter of an outer one
+    let n = exec_scopes.get_mut_ref::<Felt>("n")?;
+    *n -= Felt::one();
+    let continue_copying = Felt::new(n.is_positive() as i32);
+    insert_value_from_var_name(
+        "continue_copying",
+        continue_copying,
+        vm,
+        ids_data,
+        ap_tracking,
+    )
 }
 
 #[cfg(test)]
@@ -82,7 +81,6 @@ mod tests {
             vm_memory::memory::Memory,
         },
     };
-    use felt::NewFelt;
 
     #[test]
     fn get_integer_from_var_name_valid() {
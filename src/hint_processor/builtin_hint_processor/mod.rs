@@ -16,6 +16,7 @@ pub mod secp;
 pub mod segments;
 pub mod set;
 pub mod sha256_utils;
+pub mod squash_dict_scope;
 pub mod squash_dict_utils;
 pub mod uint256_utils;
 pub mod usort;
@@ -2,19 +2,119 @@ use crate::{
     hint_processor::{
         builtin_hint_processor::{
             hint_utils::{get_relocatable_from_var_name, insert_value_from_var_name},
-            secp::secp_utils::{split, BASE_86},
+            secp::secp_utils::BASE_86,
         },
         hint_processor_definition::HintReference,
     },
     serde::deserialize_program::ApTracking,
-    types::{exec_scope::ExecutionScopes, relocatable::MaybeRelocatable},
+    types::{
+        exec_scope::ExecutionScopes,
+        relocatable::{MaybeRelocatable, Relocatable},
+    },
     vm::{
         errors::{hint_errors::HintError, vm_errors::VirtualMachineError},
         vm_core::VirtualMachine,
     },
 };
-use felt::{Felt, NewFelt};
-use std::collections::HashMap;
+use felt::{Felt, FeltOps, NewFelt};
+use num_bigint::{BigInt, BigUint};
+use num_traits::{Signed, Zero};
+use std::{borrow::Cow, collections::HashMap, ops::Shl};
+
+/// The limbs of a `BigInt3` (base 2**86) or `Uint384` (base 2**128) struct read from memory.
+/// Both structs are range checked by the Cairo code using them, so each limb has to fit in
+/// 128 bits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BigInt3<'a> {
+    pub d0: Cow<'a, Felt>,
+    pub d1: Cow<'a, Felt>,
+    pub d2: Cow<'a, Felt>,
+}
+
+pub type Uint384<'a> = BigInt3<'a>;
+
+impl BigInt3<'_> {
+    /// Reads the limbs of the struct at `addr`, failing with `BigIntLimbOutOfRange` if one of
+    /// them doesn't fit in 128 bits.
+    pub fn from_base_addr(addr: Relocatable, vm: &VirtualMachine) -> Result<BigInt3, HintError> {
+        Ok(BigInt3 {
+            d0: get_limb(vm, addr)?,
+            d1: get_limb(vm, addr + 1_usize)?,
+            d2: get_limb(vm, addr + 2_usize)?,
+        })
+    }
+
+    /// Reads the limbs of the struct referenced by `ids.name`.
+    pub fn from_var_name<'a>(
+        name: &str,
+        vm: &'a VirtualMachine,
+        ids_data: &HashMap<String, HintReference>,
+        ap_tracking: &ApTracking,
+    ) -> Result<BigInt3<'a>, HintError> {
+        let addr = get_relocatable_from_var_name(name, vm, ids_data, ap_tracking)?;
+        BigInt3::from_base_addr(addr, vm)
+    }
+
+    /// Returns d0 + d1 * 2**86 + d2 * 2**172, the value of a `BigInt3`.
+    pub fn pack86(&self) -> BigInt {
+        pack(&self.d0, &self.d1, &self.d2)
+    }
+
+    /// Returns d0 + d1 * 2**128 + d2 * 2**256, the value of a `Uint384`.
+    pub fn pack128(&self) -> BigUint {
+        [&self.d0, &self.d1, &self.d2]
+            .into_iter()
+            .enumerate()
+            .map(|(idx, limb)| limb.to_biguint().shl(idx * 128))
+            .sum()
+    }
+}
+
+fn get_limb(vm: &VirtualMachine, addr: Relocatable) -> Result<Cow<Felt>, HintError> {
+    let limb = vm.get_integer(&addr)?;
+    if limb.bits() > 128 {
+        return Err(HintError::BigIntLimbOutOfRange(limb.into_owned()));
+    }
+    Ok(limb)
+}
+
+/*
+Takes a 256-bit integer and returns its canonical representation as:
+d0 + BASE * d1 + BASE**2 * d2,
+where BASE = 2**86.
+*/
+pub fn split(integer: &BigInt) -> Result<[Felt; 3], HintError> {
+    if integer.is_negative() {
+        return Err(HintError::SecpSplitNegative(integer.clone()));
+    }
+    let base_86_max = (BigUint::from(1_u32) << 86_u32) - 1_u32;
+
+    let mut num = integer.magnitude().clone();
+    let mut canonical_repr: [Felt; 3] = Default::default();
+    for item in &mut canonical_repr {
+        *item = Felt::new(&num & &base_86_max);
+        num >>= 86_usize;
+    }
+
+    if !num.is_zero() {
+        return Err(HintError::SecpSplitOutOfRange(integer.magnitude().clone()));
+    }
+    Ok(canonical_repr)
+}
+
+/*
+Takes an UnreducedFelt3 struct which represents a triple of limbs (d0, d1, d2) of field
+elements and reconstructs the corresponding 256-bit integer (see split()).
+Note that the limbs do not have to be in the range [0, BASE).
+*/
+pub fn pack(d0: &Felt, d1: &Felt, d2: &Felt) -> BigInt {
+    [d0, d1, d2]
+        .into_iter()
+        .enumerate()
+        .map(|(idx, value)| value.to_bigint().shl(idx * 86))
+        .sum()
+}
+
 /*
 Implements hint:
 %{
@@ -28,16 +128,12 @@ pub fn nondet_bigint3(
     exec_scopes: &mut ExecutionScopes,
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
-    constants: &HashMap<String, Felt>,
 ) -> Result<(), HintError> {
     let res_reloc = get_relocatable_from_var_name("res", vm, ids_data, ap_tracking)?;
-    let value = exec_scopes
-        .get_ref::<num_bigint::BigInt>("value")?
-        .to_biguint()
-        .ok_or(HintError::BigIntToBigUintFail)?;
-    let arg: Vec<MaybeRelocatable> = split(&value, constants)?
+    let value = exec_scopes.get_ref::<BigInt>("value")?;
+    let arg: Vec<MaybeRelocatable> = split(value)?
         .into_iter()
-        .map(|n| MaybeRelocatable::from(Felt::new(n)))
+        .map(MaybeRelocatable::from)
         .collect();
     vm.write_arg(&res_reloc, &arg)
         .map_err(VirtualMachineError::MemoryError)?;
@@ -78,6 +174,7 @@ mod tests {
     use crate::utils::test_utils::*;
     use crate::vm::runners::builtin_runner::RangeCheckBuiltinRunner;
     use crate::vm::vm_core::VirtualMachine;
+    use felt::felt_str;
     use num_traits::One;
     use std::any::Any;
     use std::ops::Shl;
@@ -144,7 +241,143 @@ mod tests {
         let ids_data = non_continuous_ids_data![("res", 5)];
         assert_eq!(
             run_hint!(vm, ids_data, hint_code, &mut exec_scopes),
-            Err(HintError::BigIntToBigUintFail)
+            Err(HintError::SecpSplitNegative(bigint!(-1)))
+        );
+    }
+
+    #[test]
+    fn secp_split() {
+        assert_eq!(split(&BigInt::zero()), Ok(Default::default()));
+        assert_eq!(
+            split(&bigint!(999992)),
+            Ok([Felt::new(999992), Felt::zero(), Felt::zero()])
+        );
+        assert_eq!(
+            split(&bigint_str!(
+                "7737125245533626718119526477371252455336267181195264773712524553362"
+            )),
+            Ok([
+                felt_str!("773712524553362"),
+                felt_str!("57408430697461422066401280"),
+                felt_str!("1292469707114105")
+            ])
+        );
+        let too_big = bigint_str!(
+            "773712524553362671811952647737125245533626718119526477371252455336267181195264"
+        );
+        assert_eq!(
+            split(&too_big),
+            Err(HintError::SecpSplitOutOfRange(too_big.magnitude().clone()))
+        );
+    }
+
+    #[test]
+    fn split_negative_value() {
+        assert_eq!(
+            split(&bigint!(-5)),
+            Err(HintError::SecpSplitNegative(bigint!(-5)))
+        );
+    }
+
+    #[test]
+    fn split_at_limb_boundaries() {
+        let base = BigInt::one() << 86_u32;
+        assert_eq!(
+            split(&(&base - 1_u32)),
+            Ok([Felt::new(&base_max()), Felt::zero(), Felt::zero()])
+        );
+        assert_eq!(split(&base), Ok([Felt::zero(), Felt::one(), Felt::zero()]));
+        // 2**258 - 1 is the largest value that fits in three limbs
+        let max = (BigInt::one() << 258_u32) - 1_u32;
+        assert_eq!(
+            split(&max),
+            Ok([
+                Felt::new(&base_max()),
+                Felt::new(&base_max()),
+                Felt::new(&base_max())
+            ])
+        );
+        assert_eq!(
+            split(&(&max + 1_u32)),
+            Err(HintError::SecpSplitOutOfRange(BigUint::one() << 258_u32))
+        );
+    }
+
+    fn base_max() -> BigUint {
+        (BigUint::one() << 86_u32) - 1_u32
+    }
+
+    #[test]
+    fn split_pack_round_trip() {
+        for value in [
+            BigInt::zero(),
+            bigint!(1),
+            (BigInt::one() << 86_u32) + 1_u32,
+            bigint_str!("7737125245533626718119526477371252455336267181195264773712524553362"),
+            (BigInt::one() << 256_u32) - 1_u32,
+        ] {
+            let [d0, d1, d2] = split(&value).unwrap();
+            assert_eq!(pack(&d0, &d1, &d2), value);
+        }
+    }
+
+    #[test]
+    fn secp_pack() {
+        let pack_1 = pack(&Felt::new(10_i32), &Felt::new(10_i32), &Felt::new(10_i32));
+        assert_eq!(
+            pack_1,
+            bigint_str!("59863107065073783529622931521771477038469668772249610")
+        );
+
+        let pack_2 = pack(
+            &felt_str!("773712524553362"),
+            &felt_str!("57408430697461422066401280"),
+            &felt_str!("1292469707114105"),
+        );
+        assert_eq!(
+            pack_2,
+            bigint_str!("7737125245533626718119526477371252455336267181195264773712524553362")
+        );
+    }
+
+    #[test]
+    fn read_bigint3_from_memory() {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), 1), ((1, 1), 2), ((1, 2), 3)];
+        let bigint3 = BigInt3::from_base_addr(relocatable!(1, 0), &vm).unwrap();
+        assert_eq!(*bigint3.d1, Felt::new(2));
+        assert_eq!(
+            bigint3.pack86(),
+            bigint!(1) + (bigint!(2) << 86_u32) + (bigint!(3) << 172_u32)
+        );
+        assert_eq!(
+            bigint3.pack128(),
+            BigUint::from(1_u32)
+                + (BigUint::from(2_u32) << 128_u32)
+                + (BigUint::from(3_u32) << 256_u32)
+        );
+    }
+
+    #[test]
+    fn read_bigint3_limb_out_of_range() {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), 1), ((1, 1), 2), ((1, 2), (-1))];
+        assert_eq!(
+            BigInt3::from_base_addr(relocatable!(1, 0), &vm),
+            Err(HintError::BigIntLimbOutOfRange(Felt::new(-1)))
+        );
+    }
+
+    #[test]
+    fn read_uint384_from_var_name() {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), 1), ((1, 1), 0), ((1, 2), 1)];
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["x"];
+        let uint384 = Uint384::from_var_name("x", &vm, &ids_data, &ApTracking::default()).unwrap();
+        assert_eq!(
+            uint384.pack128(),
+            BigUint::from(1_u32) + (BigUint::from(1_u32) << 256_u32)
         );
     }
 }
@@ -4,7 +4,7 @@ use crate::{
             hint_utils::{
                 get_integer_from_var_name, get_relocatable_from_var_name, insert_value_into_ap,
             },
-            secp::secp_utils::{pack, pack_from_relocatable, SECP_REM},
+            secp::secp_utils::{pack, pack_from_relocatable, SECP256R1_ALPHA, SECP256R1_P},
         },
         hint_processor_definition::HintReference,
     },
@@ -13,14 +13,11 @@ use crate::{
     types::exec_scope::ExecutionScopes,
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
-use felt::{Felt, FeltOps};
+use felt::Felt;
 use num_bigint::BigInt;
 use num_integer::Integer;
-use num_traits::{One, Zero};
-use std::{
-    collections::HashMap,
-    ops::{BitAnd, Shl},
-};
+use num_traits::One;
+use std::{collections::HashMap, ops::BitAnd};
 
 /*
 Implements hint:
@@ -31,25 +28,19 @@ Implements hint:
     # The modulo operation in python always returns a nonnegative number.
     value = (-y) % SECP_P
 %}
+The secp256r1 version of this hint doesn't import SECP_P, and takes it from the exec scopes instead.
 */
 pub fn ec_negate(
     vm: &mut VirtualMachine,
     exec_scopes: &mut ExecutionScopes,
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
-    constants: &HashMap<String, Felt>,
+    secp_p: &BigInt,
 ) -> Result<(), HintError> {
-    let secp_p = num_bigint::BigInt::one().shl(256u32)
-        - constants
-            .get(SECP_REM)
-            .ok_or(HintError::MissingConstant(SECP_REM))?
-            .clone()
-            .to_bigint();
-
     //ids.point
     let point_y = get_relocatable_from_var_name("point", vm, ids_data, ap_tracking)? + 3i32;
     let y = pack_from_relocatable(point_y, vm)?;
-    let value = (-y).mod_floor(&secp_p);
+    let value = (-y).mod_floor(secp_p);
     exec_scopes.insert_value("value", value);
     Ok(())
 }
@@ -65,20 +56,16 @@ Implements hint:
     y = pack(ids.point.y, PRIME)
     value = slope = ec_double_slope(point=(x, y), alpha=0, p=SECP_P)
 %}
+The secp256r1 version of this hint takes SECP_P and ALPHA from the exec scopes instead.
 */
 pub fn compute_doubling_slope(
     vm: &mut VirtualMachine,
     exec_scopes: &mut ExecutionScopes,
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
-    constants: &HashMap<String, Felt>,
+    secp_p: &BigInt,
+    alpha: &BigInt,
 ) -> Result<(), HintError> {
-    let secp_p = num_bigint::BigInt::one().shl(256usize)
-        - constants
-            .get(SECP_REM)
-            .ok_or(HintError::MissingConstant(SECP_REM))?
-            .to_bigint();
-
     //ids.point
     let point_reloc = get_relocatable_from_var_name("point", vm, ids_data, ap_tracking)?;
 
@@ -96,8 +83,8 @@ pub fn compute_doubling_slope(
             pack(x_d0.as_ref(), x_d1.as_ref(), x_d2.as_ref()),
             pack(y_d0.as_ref(), y_d1.as_ref(), y_d2.as_ref()),
         ),
-        &BigInt::zero(),
-        &secp_p,
+        alpha,
+        secp_p,
     );
     exec_scopes.insert_value("value", value.clone());
     exec_scopes.insert_value("slope", value);
@@ -117,20 +104,15 @@ Implements hint:
     y1 = pack(ids.point1.y, PRIME)
     value = slope = line_slope(point1=(x0, y0), point2=(x1, y1), p=SECP_P)
 %}
+The secp256r1 version of this hint takes SECP_P from the exec scopes instead.
 */
 pub fn compute_slope(
     vm: &mut VirtualMachine,
     exec_scopes: &mut ExecutionScopes,
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
-    constants: &HashMap<String, Felt>,
+    secp_p: &BigInt,
 ) -> Result<(), HintError> {
-    let secp_p = BigInt::one().shl(256usize)
-        - constants
-            .get(SECP_REM)
-            .ok_or(HintError::MissingConstant(SECP_REM))?
-            .to_bigint();
-
     //ids.point0
     let point0_reloc = get_relocatable_from_var_name("point0", vm, ids_data, ap_tracking)?;
 
@@ -180,7 +162,7 @@ pub fn compute_slope(
                 point1_y_d2.as_ref(),
             ),
         ),
-        &secp_p,
+        secp_p,
     );
     exec_scopes.insert_value("value", value.clone());
     exec_scopes.insert_value("slope", value);
@@ -198,20 +180,15 @@ Implements hint:
 
     value = new_x = (pow(slope, 2, SECP_P) - 2 * x) % SECP_P
 %}
+The secp256r1 version of this hint takes SECP_P from the exec scopes instead.
 */
 pub fn ec_double_assign_new_x(
     vm: &mut VirtualMachine,
     exec_scopes: &mut ExecutionScopes,
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
-    constants: &HashMap<String, Felt>,
+    secp_p: &BigInt,
 ) -> Result<(), HintError> {
-    let secp_p = BigInt::one().shl(256usize)
-        - constants
-            .get(SECP_REM)
-            .ok_or(HintError::MissingConstant(SECP_REM))?
-            .to_bigint();
-
     //ids.slope
     let slope_reloc = get_relocatable_from_var_name("slope", vm, ids_data, ap_tracking)?;
 
@@ -237,9 +214,11 @@ pub fn ec_double_assign_new_x(
     let x = pack(x_d0.as_ref(), x_d1.as_ref(), x_d2.as_ref());
     let y = pack(y_d0.as_ref(), y_d1.as_ref(), y_d2.as_ref());
 
-    let value = ((&slope).pow(2) - (&x << 1u32)).mod_floor(&secp_p);
+    let value = ((&slope).pow(2) - (&x << 1u32)).mod_floor(secp_p);
 
     //Assign variables to vm scope
+    //SECP_P is also stored, as it is used by the hint that computes new_y
+    exec_scopes.insert_value("SECP_P", secp_p.clone());
     exec_scopes.insert_value("slope", slope);
     exec_scopes.insert_value("x", x);
     exec_scopes.insert_value("y", y);
@@ -254,14 +233,8 @@ Implements hint:
 */
 pub fn ec_double_assign_new_y(
     exec_scopes: &mut ExecutionScopes,
-    constants: &HashMap<String, Felt>,
+    secp_p: &BigInt,
 ) -> Result<(), HintError> {
-    let secp_p = BigInt::one().shl(256usize)
-        - constants
-            .get(SECP_REM)
-            .ok_or(HintError::MissingConstant(SECP_REM))?
-            .to_bigint();
-
     //Get variables from vm scope
     let (slope, x, new_x, y) = (
         exec_scopes.get::<BigInt>("slope")?,
@@ -270,7 +243,7 @@ pub fn ec_double_assign_new_y(
         exec_scopes.get::<BigInt>("y")?,
     );
 
-    let value = (slope * (x - new_x) - y).mod_floor(&secp_p);
+    let value = (slope * (x - new_x) - y).mod_floor(secp_p);
     exec_scopes.insert_value("value", value.clone());
     exec_scopes.insert_value("new_y", value);
     Ok(())
@@ -288,20 +261,15 @@ Implements hint:
 
     value = new_x = (pow(slope, 2, SECP_P) - x0 - x1) % SECP_P
 %}
+The secp256r1 version of this hint takes SECP_P from the exec scopes instead.
 */
 pub fn fast_ec_add_assign_new_x(
     vm: &mut VirtualMachine,
     exec_scopes: &mut ExecutionScopes,
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
-    constants: &HashMap<String, Felt>,
+    secp_p: &BigInt,
 ) -> Result<(), HintError> {
-    let secp_p = BigInt::one().shl(256usize)
-        - constants
-            .get(SECP_REM)
-            .ok_or(HintError::MissingConstant(SECP_REM))?
-            .to_bigint();
-
     //ids.slope
     let slope_reloc = get_relocatable_from_var_name("slope", vm, ids_data, ap_tracking)?;
 
@@ -349,8 +317,10 @@ pub fn fast_ec_add_assign_new_x(
         point0_y_d2.as_ref(),
     );
 
-    let value = (&slope * &slope - &x0 - &x1).mod_floor(&secp_p);
+    let value = (&slope * &slope - &x0 - &x1).mod_floor(secp_p);
     //Assign variables to vm scope
+    //SECP_P is also stored, as it is used by the hint that computes new_y
+    exec_scopes.insert_value("SECP_P", secp_p.clone());
     exec_scopes.insert_value("slope", slope);
     exec_scopes.insert_value("x0", x0);
     exec_scopes.insert_value("y0", y0);
@@ -366,14 +336,8 @@ Implements hint:
 */
 pub fn fast_ec_add_assign_new_y(
     exec_scopes: &mut ExecutionScopes,
-    constants: &HashMap<String, Felt>,
+    secp_p: &BigInt,
 ) -> Result<(), HintError> {
-    let secp_p = BigInt::one().shl(256usize)
-        - constants
-            .get(SECP_REM)
-            .ok_or(HintError::MissingConstant(SECP_REM))?
-            .to_bigint();
-
     //Get variables from vm scope
     let (slope, x0, new_x, y0) = (
         exec_scopes.get::<BigInt>("slope")?,
@@ -381,13 +345,31 @@ pub fn fast_ec_add_assign_new_y(
         exec_scopes.get::<BigInt>("new_x")?,
         exec_scopes.get::<BigInt>("y0")?,
     );
-    let value = (slope * (x0 - new_x) - y0).mod_floor(&secp_p);
+    let value = (slope * (x0 - new_x) - y0).mod_floor(secp_p);
     exec_scopes.insert_value("value", value.clone());
     exec_scopes.insert_value("new_y", value);
 
     Ok(())
 }
 
+/*
+Implements hint:
+%{ from starkware.cairo.common.cairo_secp.secp256r1_utils import SECP256R1_P as SECP_P %}
+*/
+pub fn import_secp256r1_p(exec_scopes: &mut ExecutionScopes) -> Result<(), HintError> {
+    exec_scopes.insert_value("SECP_P", SECP256R1_P.clone());
+    Ok(())
+}
+
+/*
+Implements hint:
+%{ from starkware.cairo.common.cairo_secp.secp256r1_utils import SECP256R1_ALPHA as ALPHA %}
+*/
+pub fn import_secp256r1_alpha(exec_scopes: &mut ExecutionScopes) -> Result<(), HintError> {
+    exec_scopes.insert_value("ALPHA", SECP256R1_ALPHA.clone());
+    Ok(())
+}
+
 /*
 Implements hint:
 %{ memory[ap] = (ids.scalar % PRIME) % 2 %}
@@ -410,8 +392,10 @@ mod tests {
     use crate::{
         any_box,
         hint_processor::{
-            builtin_hint_processor::builtin_hint_processor_definition::{
-                BuiltinHintProcessor, HintProcessorData,
+            builtin_hint_processor::{
+                builtin_hint_processor_definition::{BuiltinHintProcessor, HintProcessorData},
+                hint_code,
+                secp::secp_utils::SECP_REM,
             },
             hint_processor_definition::HintProcessor,
         },
@@ -425,7 +409,7 @@ mod tests {
             vm_core::VirtualMachine, vm_memory::memory::Memory,
         },
     };
-    use std::any::Any;
+    use std::{any::Any, ops::Shl};
 
     #[test]
     fn run_ec_negate_ok() {
@@ -922,4 +906,116 @@ mod tests {
         //Check hint memory inserts
         check_memory![&vm.memory, ((1, 2), 0)];
     }
+
+    #[test]
+    fn run_secp256r1_point_doubling_ok() {
+        let mut vm = vm_with_range_check!();
+        //Insert ids.point (the secp256r1 generator point) and ids.slope into memory
+        vm.memory = memory![
+            ((1, 0), 52227620040540588600771222_i128),
+            ((1, 1), 33347259622618539004134583_i128),
+            ((1, 2), 8091721874918813684698062_i128),
+            ((1, 3), 59685082318776612195095029_i128),
+            ((1, 4), 54599710628478995760242092_i128),
+            ((1, 5), 6036146923926000695307902_i128),
+            ((1, 6), 18381657395733761514123165_i128),
+            ((1, 7), 60250936249481871670557728_i128),
+            ((1, 8), 12262137276504867214243183_i128)
+        ];
+        //Initialize fp
+        vm.run_context.fp = 9;
+        let ids_data = HashMap::from([
+            ("point".to_string(), HintReference::new_simple(-9)),
+            ("slope".to_string(), HintReference::new_simple(-3)),
+        ]);
+        let mut exec_scopes = ExecutionScopes::new();
+
+        assert_eq!(
+            run_hint!(
+                vm,
+                HashMap::new(),
+                hint_code::IMPORT_SECP256R1_P,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            run_hint!(
+                vm,
+                HashMap::new(),
+                hint_code::IMPORT_SECP256R1_ALPHA,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data.clone(),
+                hint_code::EC_DOUBLE_SCOPE_SECP256R1,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [(
+                "slope",
+                bigint_str!(
+                    "73404963663004311880882944372748989162084677934852963787452504780932599885725"
+                )
+            )]
+        );
+
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_DOUBLE_ASSIGN_NEW_X_SECP256R1,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        //The constants don't include SECP_REM, so SECP_P has to be taken from the scope
+        assert_eq!(
+            run_hint!(
+                vm,
+                HashMap::new(),
+                hint_code::EC_DOUBLE_ASSIGN_NEW_Y,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        //2 * G
+        check_scope!(
+            &exec_scopes,
+            [
+                (
+                    "new_x",
+                    bigint_str!(
+                        "56515219790691171413109057904011688695424810155802929973526481321309856242040"
+                    )
+                ),
+                (
+                    "new_y",
+                    bigint_str!(
+                        "3377031843712258259223711451491452598088675519751548567112458094635497583569"
+                    )
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn run_ec_negate_secp256r1_without_secp_p() {
+        let mut vm = vm_with_range_check!();
+        vm.memory = memory![((1, 3), 2645i32), ((1, 4), 454i32), ((1, 5), 206i32)];
+        //Initialize fp
+        vm.run_context.fp = 1;
+        let ids_data = ids_data!["point"];
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::EC_NEGATE_SECP256R1),
+            Err(HintError::VariableNotInScopeError("SECP_P".to_string()))
+        );
+    }
 }
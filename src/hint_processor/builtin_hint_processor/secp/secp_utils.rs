@@ -4,15 +4,18 @@ use crate::{
         hint_processor_definition::HintReference,
     },
     serde::deserialize_program::ApTracking,
-    types::relocatable::Relocatable,
+    types::{exec_scope::ExecutionScopes, relocatable::Relocatable},
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
 use felt::{Felt, FeltOps};
+use lazy_static::lazy_static;
 use num_bigint::BigInt;
-use num_traits::Zero;
+use num_traits::One;
 use std::collections::HashMap;
 use std::ops::Shl;
 
+pub use super::bigint_utils::pack;
+
 // Constants in package "starkware.cairo.common.cairo_secp.constants".
 pub const BASE_86: &str = "starkware.cairo.common.cairo_secp.constants.BASE";
 pub const BETA: &str = "starkware.cairo.common.cairo_secp.constants.BETA";
@@ -24,47 +27,43 @@ pub const P1: &str = "starkware.cairo.common.cairo_secp.constants.P1";
 pub const P2: &str = "starkware.cairo.common.cairo_secp.constants.P2";
 pub const SECP_REM: &str = "starkware.cairo.common.cairo_secp.constants.SECP_REM";
 
+// Constants in package "starkware.cairo.common.cairo_secp.secp256r1_utils".
+lazy_static! {
+    pub static ref SECP256R1_P: BigInt = BigInt::parse_bytes(
+        b"FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFF",
+        16
+    )
+    .unwrap();
+    pub static ref SECP256R1_ALPHA: BigInt = BigInt::parse_bytes(
+        b"FFFFFFFF00000001000000000000000000000000FFFFFFFFFFFFFFFFFFFFFFFC",
+        16
+    )
+    .unwrap();
+}
+
 /*
-Takes a 256-bit integer and returns its canonical representation as:
-d0 + BASE * d1 + BASE**2 * d2,
-where BASE = 2**86.
+Returns SECP_P = 2**256 - SECP_REM, the secp256k1 prime.
 */
-pub fn split(
-    integer: &num_bigint::BigUint,
-    constants: &HashMap<String, Felt>,
-) -> Result<[num_bigint::BigUint; 3], HintError> {
-    let base_86_max = constants
-        .get(BASE_86)
-        .ok_or(HintError::MissingConstant(BASE_86))?
-        .to_biguint()
-        - 1_u32;
-
-    let mut canonical_repr: [num_bigint::BigUint; 3] = Default::default();
-    let mut num = integer.clone();
-    for item in &mut canonical_repr {
-        *item = &num & &base_86_max;
-        num >>= 86_usize;
-    }
-
-    if !num.is_zero() {
-        return Err(HintError::SecpSplitOutOfRange(integer.clone()));
-    }
-    Ok(canonical_repr)
+pub fn get_secp_p(constants: &HashMap<String, Felt>) -> Result<BigInt, HintError> {
+    Ok(BigInt::one().shl(256_u32)
+        - constants
+            .get(SECP_REM)
+            .ok_or(HintError::MissingConstant(SECP_REM))?
+            .to_bigint())
 }
 
 /*
-Takes an UnreducedFelt3 struct which represents a triple of limbs (d0, d1, d2) of field
-elements and reconstructs the corresponding 256-bit integer (see split()).
-Note that the limbs do not have to be in the range [0, BASE).
+Returns the SECP_P variable set in the exec scopes by a previous hint, falling back to the
+secp256k1 prime if it hasn't been set.
 */
-pub fn pack(d0: &Felt, d1: &Felt, d2: &Felt) -> num_bigint::BigInt {
-    let unreduced_big_int_3 = vec![d0, d1, d2];
-
-    unreduced_big_int_3
-        .into_iter()
-        .enumerate()
-        .map(|(idx, value)| value.to_bigint().shl(idx * 86))
-        .sum()
+pub fn get_secp_p_from_scope_or_constants(
+    exec_scopes: &ExecutionScopes,
+    constants: &HashMap<String, Felt>,
+) -> Result<BigInt, HintError> {
+    match exec_scopes.get::<BigInt>("SECP_P") {
+        Err(HintError::VariableNotInScopeError(_)) => get_secp_p(constants),
+        result => result,
+    }
 }
 
 pub fn pack_from_var_name(
@@ -88,99 +87,3 @@ pub fn pack_from_relocatable(rel: Relocatable, vm: &VirtualMachine) -> Result<Bi
 
     Ok(pack(d0.as_ref(), d1.as_ref(), d2.as_ref()))
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::utils::test_utils::*;
-    use felt::{felt_str, NewFelt};
-    use num_bigint::BigUint;
-    use num_traits::One;
-
-    #[test]
-    fn secp_split() {
-        let mut constants = HashMap::new();
-        constants.insert(BASE_86.to_string(), Felt::one() << 86_usize);
-
-        let array_1 = split(&BigUint::zero(), &constants);
-        let array_2 = split(
-            &bigint!(999992)
-                .to_biguint()
-                .expect("Couldn't convert to BigUint"),
-            &constants,
-        );
-        let array_3 = split(
-            &bigint_str!("7737125245533626718119526477371252455336267181195264773712524553362")
-                .to_biguint()
-                .expect("Couldn't convert to BigUint"),
-            &constants,
-        );
-        //TODO, Check SecpSplitutOfRange limit
-        let array_4 = split(
-            &bigint_str!(
-                "773712524553362671811952647737125245533626718119526477371252455336267181195264"
-            )
-            .to_biguint()
-            .expect("Couldn't convert to BigUint"),
-            &constants,
-        );
-
-        assert_eq!(
-            array_1,
-            Ok([BigUint::zero(), BigUint::zero(), BigUint::zero()])
-        );
-        assert_eq!(
-            array_2,
-            Ok([
-                bigint!(999992)
-                    .to_biguint()
-                    .expect("Couldn't convert to BigUint"),
-                BigUint::zero(),
-                BigUint::zero()
-            ])
-        );
-        assert_eq!(
-            array_3,
-            Ok([
-                bigint_str!("773712524553362")
-                    .to_biguint()
-                    .expect("Couldn't convert to BigUint"),
-                bigint_str!("57408430697461422066401280")
-                    .to_biguint()
-                    .expect("Couldn't convert to BigUint"),
-                bigint_str!("1292469707114105")
-                    .to_biguint()
-                    .expect("Couldn't convert to BigUint")
-            ])
-        );
-        assert_eq!(
-            array_4,
-            Err(HintError::SecpSplitOutOfRange(
-                bigint_str!(
-                "773712524553362671811952647737125245533626718119526477371252455336267181195264"
-            )
-                .to_biguint()
-                .expect("Couldn't convert to BigUint")
-            ))
-        );
-    }
-
-    #[test]
-    fn secp_pack() {
-        let pack_1 = pack(&Felt::new(10_i32), &Felt::new(10_i32), &Felt::new(10_i32));
-        assert_eq!(
-            pack_1,
-            bigint_str!("59863107065073783529622931521771477038469668772249610")
-        );
-
-        let pack_2 = pack(
-            &felt_str!("773712524553362"),
-            &felt_str!("57408430697461422066401280"),
-            &felt_str!("1292469707114105"),
-        );
-        assert_eq!(
-            pack_2,
-            bigint_str!("7737125245533626718119526477371252455336267181195264773712524553362")
-        );
-    }
-}
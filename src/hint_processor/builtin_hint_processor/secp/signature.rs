@@ -9,13 +9,13 @@ use crate::{
     math_utils::{div_mod, safe_div_bigint},
     serde::deserialize_program::ApTracking,
     types::exec_scope::ExecutionScopes,
-    vm::errors::hint_errors::HintError,
+    vm::errors::{hint_errors::HintError, vm_errors::VirtualMachineError},
     vm::vm_core::VirtualMachine,
 };
 use felt::{Felt, FeltOps};
 use num_bigint::BigInt;
 use num_integer::Integer;
-use num_traits::One;
+use num_traits::{One, Zero};
 use std::{
     collections::HashMap,
     ops::{Shl, Shr},
@@ -105,6 +105,19 @@ pub fn div_mod_n_safe_div(
     Ok(())
 }
 
+/* Implements hint:
+from starkware.cairo.common.cairo_secp.secp_utils import SECP_P, pack
+
+x_cube_int = pack(ids.x_cube, PRIME) % SECP_P
+y_square_int = (x_cube_int + ids.BETA) % SECP_P
+y = pow(y_square_int, (SECP_P + 1) // 4, SECP_P)
+
+# We need to decide whether to take y or SECP_P - y.
+if ids.v % 2 == y % 2:
+    value = y
+else:
+    value = (-y) % SECP_P
+*/
 pub fn get_point_from_x(
     vm: &mut VirtualMachine,
     exec_scopes: &mut ExecutionScopes,
@@ -125,18 +138,118 @@ pub fn get_point_from_x(
     let x_cube_int = pack_from_var_name("x_cube", vm, ids_data, ap_tracking)?.mod_floor(&secp_p);
     //.mod_floor(&BigInt::from_biguint(num_bigint::Sign::Plus, secp_p.clone()))
     //.to_biguint().ok_or(VirtualMachineError::BigIntToBigUintFail)?;
-    let y_cube_int = (x_cube_int + beta).mod_floor(&secp_p);
+    let y_square_int = (x_cube_int + beta).mod_floor(&secp_p);
     // Divide by 4
-    let mut y = y_cube_int.modpow(&(&secp_p + 1_u32).shr(2_u32), &secp_p);
+    let mut y = y_square_int.modpow(&(&secp_p + 1_u32).shr(2_u32), &secp_p);
 
     let v = get_integer_from_var_name("v", vm, ids_data, ap_tracking)?.to_biguint();
     if v.is_even() != y.is_even() {
-        y = &secp_p - y;
+        y = (-y).mod_floor(&secp_p);
     }
     exec_scopes.insert_value("value", y);
     Ok(())
 }
 
+/* Implements hint:
+from starkware.cairo.common.cairo_secp.secp_utils import pack
+from starkware.python.math_utils import div_mod, safe_div
+
+N = pack(ids.n, PRIME)
+x = pack(ids.x, PRIME) % N
+s = pack(ids.s, PRIME) % N
+value = res = div_mod(x, s, N)
+*/
+pub fn ec_recover_divmod_n_packed(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let n = pack_from_var_name("n", vm, ids_data, ap_tracking)?;
+    if n.is_zero() {
+        return Err(VirtualMachineError::DividedByZero.into());
+    }
+    let x = pack_from_var_name("x", vm, ids_data, ap_tracking)?.mod_floor(&n);
+    let s = pack_from_var_name("s", vm, ids_data, ap_tracking)?.mod_floor(&n);
+
+    let value = div_mod(&x, &s, &n);
+    exec_scopes.insert_value("value", value.clone());
+    exec_scopes.insert_value("res", value);
+    Ok(())
+}
+
+/* Implements hint:
+from starkware.cairo.common.cairo_secp.secp_utils import pack
+from starkware.python.math_utils import div_mod, safe_div
+
+a = pack(ids.a, PRIME)
+b = pack(ids.b, PRIME)
+
+value = res = a - b
+*/
+pub fn ec_recover_sub_a_b(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let a = pack_from_var_name("a", vm, ids_data, ap_tracking)?;
+    let b = pack_from_var_name("b", vm, ids_data, ap_tracking)?;
+
+    let value = a - b;
+    exec_scopes.insert_value("value", value.clone());
+    exec_scopes.insert_value("res", value);
+    Ok(())
+}
+
+/* Implements hint:
+from starkware.cairo.common.cairo_secp.secp_utils import pack
+from starkware.python.math_utils import div_mod, safe_div
+
+a = pack(ids.a, PRIME)
+b = pack(ids.b, PRIME)
+product = a * b
+m = pack(ids.m, PRIME)
+
+value = res = product % m
+*/
+pub fn ec_recover_product_mod(
+    vm: &mut VirtualMachine,
+    exec_scopes: &mut ExecutionScopes,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let a = pack_from_var_name("a", vm, ids_data, ap_tracking)?;
+    let b = pack_from_var_name("b", vm, ids_data, ap_tracking)?;
+    let m = pack_from_var_name("m", vm, ids_data, ap_tracking)?;
+    if m.is_zero() {
+        return Err(VirtualMachineError::DividedByZero.into());
+    }
+
+    let product = a * b;
+    let value = product.mod_floor(&m);
+    exec_scopes.insert_value("product", product);
+    exec_scopes.insert_value("m", m);
+    exec_scopes.insert_value("value", value.clone());
+    exec_scopes.insert_value("res", value);
+    Ok(())
+}
+
+// Implements hint:
+// value = k = product // m
+pub fn ec_recover_product_div_m(exec_scopes: &mut ExecutionScopes) -> Result<(), HintError> {
+    let product = exec_scopes.get_ref::<BigInt>("product")?;
+    let m = exec_scopes.get_ref::<BigInt>("m")?;
+    if m.is_zero() {
+        return Err(VirtualMachineError::DividedByZero.into());
+    }
+
+    let value = product.div_floor(m);
+    exec_scopes.insert_value("k", value.clone());
+    exec_scopes.insert_value("value", value);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -156,6 +269,12 @@ mod tests {
             vm_memory::memory::Memory,
         },
     };
+    use crate::{
+        hint_processor::builtin_hint_processor::secp::secp_utils::{
+            get_secp_p, pack_from_relocatable,
+        },
+        math_utils::{ec_add, ec_double},
+    };
     use felt::NewFelt;
     use num_traits::Zero;
     use std::{any::Any, ops::Shl};
@@ -310,4 +429,283 @@ mod tests {
             )]
         );
     }
+
+    fn secp256k1_constants() -> HashMap<String, Felt> {
+        [
+            (BASE_86, Felt::one().shl(86_u32)),
+            (BETA, Felt::new(7)),
+            (N0, Felt::new(10428087374290690730508609u128)),
+            (N1, Felt::new(77371252455330678278691517u128)),
+            (N2, Felt::new(19342813113834066795298815u128)),
+            (SECP_REM, Felt::new(4294968273_u64)),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect()
+    }
+
+    // Computes scalar * point by double and add, None being the point at infinity
+    fn ec_mul(
+        mut point: (BigInt, BigInt),
+        mut scalar: BigInt,
+        prime: &BigInt,
+    ) -> Option<(BigInt, BigInt)> {
+        let mut result = None;
+        while !scalar.is_zero() {
+            if scalar.is_odd() {
+                result = Some(match result {
+                    None => point.clone(),
+                    Some(acc) => ec_add(acc, point.clone(), prime),
+                });
+            }
+            point = ec_double(point, &BigInt::zero(), prime);
+            scalar >>= 1_u32;
+        }
+        result
+    }
+
+    #[test]
+    fn recover_public_key_from_signature() {
+        // secp256k1 signature of keccak256("hello world"), with v = 0, by the private key
+        // 0xc85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4
+        let mut vm = vm!();
+        vm.memory = memory![
+            // v
+            ((1, 0), 0),
+            // x_cube = r ** 3 % SECP_P
+            ((1, 1), 23420558026587981756428745_i128),
+            ((1, 2), 53852785926475032796856120_i128),
+            ((1, 3), 8312425759479565787768505_i128),
+            // msg_hash
+            ((1, 4), 45846486525469159007985581_i128),
+            ((1, 5), 76284455722753535030574346_i128),
+            ((1, 6), 5371454974383298793826674_i128),
+            // r
+            ((1, 7), 41181127072592870240214779_i128),
+            ((1, 8), 19646448161111585339453224_i128),
+            ((1, 9), 1893262843563236966233600_i128),
+            // s
+            ((1, 10), 41930436500267318436522170_i128),
+            ((1, 11), 9809013868265390246960989_i128),
+            ((1, 12), 1748397770081909680213382_i128)
+        ];
+        let constants = secp256k1_constants();
+        let secp_p = get_secp_p(&constants).unwrap();
+        let mut exec_scopes = ExecutionScopes::new();
+
+        // The point whose x coordinate is r, with the parity given by v
+        let ids_data = non_continuous_ids_data![("v", 0), ("x_cube", 1)];
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::GET_POINT_FROM_X,
+                &mut exec_scopes,
+                &constants
+            ),
+            Ok(())
+        );
+        let r = pack_from_relocatable((1, 7).into(), &vm).unwrap();
+        let r_point = (r, exec_scopes.get::<BigInt>("value").unwrap());
+        assert_eq!(
+            r_point.1,
+            bigint_str!(
+                "619dc12f89431da8ebe3d9c5c157ae7c4b47eeeba09cd52860c1d4cc22f1d95e",
+                16
+            )
+        );
+
+        // u1 = msg_hash / r and u2 = s / r, modulo N
+        let mut div_mod_n = |a: i32| {
+            let ids_data = non_continuous_ids_data![("a", a), ("b", 7)];
+            assert_eq!(
+                run_hint!(
+                    vm,
+                    ids_data,
+                    hint_code::DIV_MOD_N_PACKED_DIVMOD,
+                    &mut exec_scopes,
+                    &constants
+                ),
+                Ok(())
+            );
+            exec_scopes.get::<BigInt>("res").unwrap()
+        };
+        let u1 = div_mod_n(4);
+        let u2 = div_mod_n(10);
+
+        // public_key = u2 * R - u1 * G
+        let generator = (
+            bigint_str!(
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                16
+            ),
+            bigint_str!(
+                "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+                16
+            ),
+        );
+        let point1 = ec_mul(generator, u1, &secp_p).unwrap();
+        let minus_point1 = (point1.0, (-point1.1).mod_floor(&secp_p));
+        let point2 = ec_mul(r_point, u2, &secp_p).unwrap();
+        let public_key = ec_add(minus_point1, point2, &secp_p);
+        assert_eq!(
+            public_key,
+            (
+                bigint_str!(
+                    "0947751e3022ecf3016be03ec77ab0ce3c2662b4843898cb068d74f698ccc8ad",
+                    16
+                ),
+                bigint_str!(
+                    "75aa17564ae80a20bb044ee7a6d903e8e8df624b089c95d66a0570f051e5a05b",
+                    16
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn ec_recover_divmod_n_packed_ok() {
+        let mut vm = vm!();
+        vm.memory = memory![
+            ((1, 0), 7),
+            ((1, 1), 0),
+            ((1, 2), 0),
+            ((1, 3), 10),
+            ((1, 4), 0),
+            ((1, 5), 0),
+            ((1, 6), 2),
+            ((1, 7), 0),
+            ((1, 8), 0)
+        ];
+        vm.run_context.fp = 9;
+        let ids_data = non_continuous_ids_data![("n", -9), ("x", -6), ("s", -3)];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_RECOVER_DIV_MOD_N_PACKED,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        // 10 % 7 = 3 and 2 * 5 = 3 (mod 7)
+        check_scope!(
+            &exec_scopes,
+            [("value", BigInt::from(5)), ("res", BigInt::from(5))]
+        );
+    }
+
+    #[test]
+    fn ec_recover_divmod_n_packed_zero_n() {
+        let mut vm = vm!();
+        vm.memory = memory![
+            ((1, 0), 0),
+            ((1, 1), 0),
+            ((1, 2), 0),
+            ((1, 3), 10),
+            ((1, 4), 0),
+            ((1, 5), 0),
+            ((1, 6), 2),
+            ((1, 7), 0),
+            ((1, 8), 0)
+        ];
+        vm.run_context.fp = 9;
+        let ids_data = non_continuous_ids_data![("n", -9), ("x", -6), ("s", -3)];
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::EC_RECOVER_DIV_MOD_N_PACKED),
+            Err(HintError::Internal(VirtualMachineError::DividedByZero))
+        );
+    }
+
+    #[test]
+    fn ec_recover_sub_a_b_ok() {
+        let mut vm = vm!();
+        vm.memory = memory![
+            ((1, 0), 5),
+            ((1, 1), 1),
+            ((1, 2), 0),
+            ((1, 3), 10),
+            ((1, 4), 0),
+            ((1, 5), 0)
+        ];
+        vm.run_context.fp = 6;
+        let ids_data = non_continuous_ids_data![("a", -6), ("b", -3)];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_RECOVER_SUB_A_B,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        let expected = BigInt::one().shl(86_u32) - 5;
+        check_scope!(
+            &exec_scopes,
+            [("value", expected.clone()), ("res", expected)]
+        );
+    }
+
+    #[test]
+    fn ec_recover_product_mod_and_div_m() {
+        let mut vm = vm!();
+        vm.memory = memory![
+            ((1, 0), 20),
+            ((1, 1), 0),
+            ((1, 2), 0),
+            ((1, 3), 30),
+            ((1, 4), 0),
+            ((1, 5), 0),
+            ((1, 6), 7),
+            ((1, 7), 0),
+            ((1, 8), 0)
+        ];
+        vm.run_context.fp = 9;
+        let ids_data = non_continuous_ids_data![("a", -9), ("b", -6), ("m", -3)];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_RECOVER_PRODUCT_MOD,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [
+                ("product", BigInt::from(600)),
+                ("m", BigInt::from(7)),
+                ("value", BigInt::from(5)),
+                ("res", BigInt::from(5))
+            ]
+        );
+
+        let ids_data = HashMap::new();
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                hint_code::EC_RECOVER_PRODUCT_DIV_M,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_scope!(
+            &exec_scopes,
+            [("value", BigInt::from(85)), ("k", BigInt::from(85))]
+        );
+    }
+
+    #[test]
+    fn ec_recover_product_div_m_missing_product() {
+        let mut exec_scopes = scope![("m", BigInt::from(7))];
+        assert_eq!(
+            ec_recover_product_div_m(&mut exec_scopes),
+            Err(HintError::VariableNotInScopeError("product".to_string()))
+        );
+    }
 }
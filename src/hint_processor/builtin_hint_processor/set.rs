@@ -42,7 +42,10 @@ pub fn set_add(
         ));
     }
 
-    let range_limit = set_end_ptr.sub(&set_ptr)?;
+    let range_limit = (set_end_ptr - set_ptr).map_err(VirtualMachineError::MathError)?;
+    if range_limit % elm_size != 0 {
+        return Err(HintError::InvalidSetLength(range_limit, elm_size));
+    }
 
     for i in (0..range_limit).step_by(elm_size) {
         let set_iter = vm
@@ -182,4 +185,51 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn set_add_empty_set() {
+        let (mut vm, ids_data) = init_vm_ids_data(Some((2, 2)), None, Some(1), Some(3));
+        assert_eq!(run_hint!(vm, ids_data, HINT_CODE), Ok(()));
+        check_memory![vm.memory, ((1, 0), 0)];
+        assert_eq!(vm.memory.get(&MaybeRelocatable::from((1, 1))), Ok(None));
+    }
+
+    #[test]
+    fn set_add_already_exists_in_middle() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 6;
+        vm.memory = memory![
+            ((1, 2), (2, 0)),
+            ((1, 3), 2),
+            ((1, 4), (3, 0)),
+            ((1, 5), (2, 6)),
+            ((2, 0), 1),
+            ((2, 1), 3),
+            ((2, 2), 5),
+            ((2, 3), 7),
+            ((2, 4), 9),
+            ((2, 5), 11),
+            ((3, 0), 5),
+            ((3, 1), 7)
+        ];
+        let ids_data = ids_data![
+            "is_elm_in_set",
+            "index",
+            "set_ptr",
+            "elm_size",
+            "elm_ptr",
+            "set_end_ptr"
+        ];
+        assert_eq!(run_hint!(vm, ids_data, HINT_CODE), Ok(()));
+        check_memory![vm.memory, ((1, 0), 1), ((1, 1), 1)];
+    }
+
+    #[test]
+    fn set_length_not_multiple_of_elm_size() {
+        let (mut vm, ids_data) = init_vm_ids_data(None, Some(3), None, None);
+        assert_eq!(
+            run_hint!(vm, ids_data, HINT_CODE),
+            Err(HintError::InvalidSetLength(2, 3))
+        );
+    }
 }
@@ -0,0 +1,175 @@
+use crate::{types::exec_scope::ExecutionScopes, vm::errors::hint_errors::HintError};
+use felt::Felt;
+use std::collections::HashMap;
+
+// The squash_dict hints keep these variables in the execution scope. Lists that are popped
+// from are kept in descending order, so that popping yields their smallest element, just as
+// cairo-lang's `sorted(...)[::-1]` followed by `pop()` does.
+const ACCESS_INDICES: &str = "access_indices";
+const KEYS: &str = "keys";
+const CURRENT_ACCESS_INDICES: &str = "current_access_indices";
+
+fn sort_descending(values: &mut [Felt]) {
+    values.sort_unstable_by(|a, b| b.cmp(a));
+}
+
+/// Stores the map from each key to the indices of the accesses to it.
+pub fn set_access_indices(
+    exec_scopes: &mut ExecutionScopes,
+    access_indices: HashMap<Felt, Vec<Felt>>,
+) {
+    exec_scopes.insert_value(ACCESS_INDICES, access_indices);
+}
+
+/// Returns the map from each key to the indices of the accesses to it.
+pub fn get_access_indices(
+    exec_scopes: &ExecutionScopes,
+) -> Result<&HashMap<Felt, Vec<Felt>>, HintError> {
+    exec_scopes.get_ref::<HashMap<Felt, Vec<Felt>>>(ACCESS_INDICES)
+}
+
+/// Returns the indices of the accesses to `key`, failing with `NoKeyInAccessIndices`
+/// if the key was never accessed.
+pub fn get_access_indices_at_key<'a>(
+    exec_scopes: &'a ExecutionScopes,
+    key: &Felt,
+) -> Result<&'a Vec<Felt>, HintError> {
+    get_access_indices(exec_scopes)?
+        .get(key)
+        .ok_or_else(|| HintError::NoKeyInAccessIndices(key.clone()))
+}
+
+/// Pushes keys into the list of keys left to squash, creating it if needed.
+pub fn push_keys(
+    exec_scopes: &mut ExecutionScopes,
+    new_keys: impl IntoIterator<Item = Felt>,
+) -> Result<(), HintError> {
+    match exec_scopes.get_mut_list_ref::<Felt>(KEYS) {
+        Ok(keys) => {
+            keys.extend(new_keys);
+            sort_descending(keys);
+        }
+        Err(HintError::VariableNotInScopeError(_)) => {
+            let mut keys: Vec<Felt> = new_keys.into_iter().collect();
+            sort_descending(&mut keys);
+            exec_scopes.insert_value(KEYS, keys);
+        }
+        Err(error) => return Err(error),
+    }
+    Ok(())
+}
+
+/// Returns the keys left to squash, in descending order.
+pub fn get_keys(exec_scopes: &ExecutionScopes) -> Result<&Vec<Felt>, HintError> {
+    exec_scopes.get_list_ref::<Felt>(KEYS)
+}
+
+/// Pops the smallest key left to squash, failing with `EmptyKeys` if there is none.
+pub fn pop_key(exec_scopes: &mut ExecutionScopes) -> Result<Felt, HintError> {
+    exec_scopes
+        .get_mut_list_ref::<Felt>(KEYS)?
+        .pop()
+        .ok_or(HintError::EmptyKeys)
+}
+
+/// Stores the indices of the accesses to the key being squashed.
+pub fn set_current_access_indices(exec_scopes: &mut ExecutionScopes, mut indices: Vec<Felt>) {
+    sort_descending(&mut indices);
+    exec_scopes.insert_value(CURRENT_ACCESS_INDICES, indices);
+}
+
+/// Returns the access indices of the current key that weren't popped yet, in descending order.
+pub fn get_current_access_indices(exec_scopes: &ExecutionScopes) -> Result<&Vec<Felt>, HintError> {
+    exec_scopes.get_list_ref::<Felt>(CURRENT_ACCESS_INDICES)
+}
+
+/// Pops the smallest access index of the current key, failing with `EmptyCurrentAccessIndices`
+/// if there is none.
+pub fn pop_current_access_index(exec_scopes: &mut ExecutionScopes) -> Result<Felt, HintError> {
+    exec_scopes
+        .get_mut_list_ref::<Felt>(CURRENT_ACCESS_INDICES)?
+        .pop()
+        .ok_or(HintError::EmptyCurrentAccessIndices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use felt::NewFelt;
+
+    fn felts(values: &[i32]) -> Vec<Felt> {
+        values.iter().map(|value| Felt::new(*value)).collect()
+    }
+
+    #[test]
+    fn pop_key_yields_ascending_keys() {
+        let mut exec_scopes = ExecutionScopes::new();
+        push_keys(&mut exec_scopes, felts(&[5, 1, 7])).unwrap();
+        push_keys(&mut exec_scopes, felts(&[3])).unwrap();
+        assert_eq!(get_keys(&exec_scopes), Ok(&felts(&[7, 5, 3, 1])));
+
+        for expected in [1, 3, 5, 7] {
+            assert_eq!(pop_key(&mut exec_scopes), Ok(Felt::new(expected)));
+        }
+        assert_eq!(pop_key(&mut exec_scopes), Err(HintError::EmptyKeys));
+    }
+
+    #[test]
+    fn pop_current_access_index_yields_ascending_indices() {
+        let mut exec_scopes = ExecutionScopes::new();
+        set_current_access_indices(&mut exec_scopes, felts(&[4, 0, 9, 2]));
+        assert_eq!(
+            get_current_access_indices(&exec_scopes),
+            Ok(&felts(&[9, 4, 2, 0]))
+        );
+
+        for expected in [0, 2, 4, 9] {
+            assert_eq!(
+                pop_current_access_index(&mut exec_scopes),
+                Ok(Felt::new(expected))
+            );
+        }
+        assert_eq!(
+            pop_current_access_index(&mut exec_scopes),
+            Err(HintError::EmptyCurrentAccessIndices)
+        );
+    }
+
+    #[test]
+    fn helpers_fail_outside_squash_dict_scope() {
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_eq!(
+            pop_key(&mut exec_scopes),
+            Err(HintError::VariableNotInScopeError("keys".to_string()))
+        );
+        assert_eq!(
+            pop_current_access_index(&mut exec_scopes),
+            Err(HintError::VariableNotInScopeError(
+                "current_access_indices".to_string()
+            ))
+        );
+        assert_eq!(
+            get_access_indices(&exec_scopes),
+            Err(HintError::VariableNotInScopeError(
+                "access_indices".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn get_access_indices_at_missing_key() {
+        let mut exec_scopes = ExecutionScopes::new();
+        set_access_indices(
+            &mut exec_scopes,
+            HashMap::from([(Felt::new(1), felts(&[0, 1]))]),
+        );
+        assert_eq!(
+            get_access_indices_at_key(&exec_scopes, &Felt::new(1)),
+            Ok(&felts(&[0, 1]))
+        );
+        assert_eq!(
+            get_access_indices_at_key(&exec_scopes, &Felt::new(2)),
+            Err(HintError::NoKeyInAccessIndices(Felt::new(2)))
+        );
+    }
+}
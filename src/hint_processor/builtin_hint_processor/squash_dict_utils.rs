@@ -6,6 +6,11 @@ use crate::{
                 get_integer_from_var_name, get_ptr_from_var_name, get_relocatable_from_var_name,
                 insert_value_from_var_name,
             },
+            squash_dict_scope::{
+                get_access_indices_at_key, get_current_access_indices, get_keys,
+                pop_current_access_index, pop_key, push_keys, set_access_indices,
+                set_current_access_indices,
+            },
         },
         hint_processor_definition::HintReference,
     },
@@ -21,21 +26,6 @@ use num_integer::Integer;
 use num_traits::{One, ToPrimitive, Zero};
 use std::collections::HashMap;
 
-fn get_access_indices(
-    exec_scopes: &mut ExecutionScopes,
-) -> Result<&HashMap<Felt, Vec<Felt>>, HintError> {
-    let mut access_indices: Option<&HashMap<Felt, Vec<Felt>>> = None;
-    if let Some(variable) = exec_scopes
-        .get_local_variables_mut()?
-        .get_mut("access_indices")
-    {
-        if let Some(py_access_indices) = variable.downcast_mut::<HashMap<Felt, Vec<Felt>>>() {
-            access_indices = Some(py_access_indices);
-        }
-    }
-    access_indices.ok_or_else(|| HintError::VariableNotInScopeError("access_indices".to_string()))
-}
-
 /*Implements hint:
     current_access_indices = sorted(access_indices[key])[::-1]
     current_access_index = current_access_indices.pop()
@@ -50,20 +40,12 @@ pub fn squash_dict_inner_first_iteration(
     //Check that access_indices and key are in scope
     let key = exec_scopes.get::<Felt>("key")?;
     let range_check_ptr = get_ptr_from_var_name("range_check_ptr", vm, ids_data, ap_tracking)?;
-    let access_indices = get_access_indices(exec_scopes)?;
     //Get current_indices from access_indices
-    let mut current_access_indices = access_indices
-        .get(&key)
-        .ok_or_else(|| HintError::NoKeyInAccessIndices(key.clone()))?
-        .clone();
-    current_access_indices.sort();
-    current_access_indices.reverse();
+    let current_access_indices = get_access_indices_at_key(exec_scopes, &key)?.clone();
+    set_current_access_indices(exec_scopes, current_access_indices);
     //Get current_access_index
-    let first_val = current_access_indices
-        .pop()
-        .ok_or(HintError::EmptyCurrentAccessIndices)?;
+    let first_val = pop_current_access_index(exec_scopes)?;
     //Store variables in scope
-    exec_scopes.insert_value("current_access_indices", current_access_indices);
     exec_scopes.insert_value("current_access_index", first_val.clone());
     //Insert current_accesss_index into range_check_ptr
     vm.insert_value(&range_check_ptr, first_val)
@@ -78,7 +60,7 @@ pub fn squash_dict_inner_skip_loop(
     ap_tracking: &ApTracking,
 ) -> Result<(), HintError> {
     //Check that current_access_indices is in scope
-    let current_access_indices = exec_scopes.get_list_ref::<Felt>("current_access_indices")?;
+    let current_access_indices = get_current_access_indices(exec_scopes)?;
     //Main Logic
     let should_skip_loop = if current_access_indices.is_empty() {
         Felt::one()
@@ -107,11 +89,8 @@ pub fn squash_dict_inner_check_access_index(
 ) -> Result<(), HintError> {
     //Check that current_access_indices and current_access_index are in scope
     let current_access_index = exec_scopes.get::<Felt>("current_access_index")?;
-    let current_access_indices = exec_scopes.get_mut_list_ref::<Felt>("current_access_indices")?;
     //Main Logic
-    let new_access_index = current_access_indices
-        .pop()
-        .ok_or(HintError::EmptyCurrentAccessIndices)?;
+    let new_access_index = pop_current_access_index(exec_scopes)?;
     let index_delta_minus1 = new_access_index.clone() - current_access_index - Felt::one();
     //loop_temps.delta_minus1 = loop_temps + 0 as it is the first field of the struct
     //Insert loop_temps.delta_minus1 into memory
@@ -132,7 +111,7 @@ pub fn squash_dict_inner_continue_loop(
     //Get addr for ids variables
     let loop_temps_addr = get_relocatable_from_var_name("loop_temps", vm, ids_data, ap_tracking)?;
     //Check that current_access_indices is in scope
-    let current_access_indices = exec_scopes.get_list_ref::<Felt>("current_access_indices")?;
+    let current_access_indices = get_current_access_indices(exec_scopes)?;
     //Main Logic
     let should_continue = if current_access_indices.is_empty() {
         Felt::zero()
@@ -149,7 +128,7 @@ pub fn squash_dict_inner_continue_loop(
 // Implements Hint: assert len(current_access_indices) == 0
 pub fn squash_dict_inner_len_assert(exec_scopes: &mut ExecutionScopes) -> Result<(), HintError> {
     //Check that current_access_indices is in scope
-    let current_access_indices = exec_scopes.get_list_ref::<Felt>("current_access_indices")?;
+    let current_access_indices = get_current_access_indices(exec_scopes)?;
     if !current_access_indices.is_empty() {
         return Err(HintError::CurrentAccessIndicesNotEmpty);
     }
@@ -165,11 +144,8 @@ pub fn squash_dict_inner_used_accesses_assert(
 ) -> Result<(), HintError> {
     let key = exec_scopes.get::<Felt>("key")?;
     let n_used_accesses = get_integer_from_var_name("n_used_accesses", vm, ids_data, ap_tracking)?;
-    let access_indices = get_access_indices(exec_scopes)?;
     //Main Logic
-    let access_indices_at_key = access_indices
-        .get(&key)
-        .ok_or_else(|| HintError::NoKeyInAccessIndices(key.clone()))?;
+    let access_indices_at_key = get_access_indices_at_key(exec_scopes, &key)?;
 
     if n_used_accesses.as_ref() != &Felt::new(access_indices_at_key.len()) {
         return Err(HintError::NumUsedAccessesAssertFail(
@@ -186,7 +162,7 @@ pub fn squash_dict_inner_assert_len_keys(
     exec_scopes: &mut ExecutionScopes,
 ) -> Result<(), HintError> {
     //Check that current_access_indices is in scope
-    let keys = exec_scopes.get_list_ref::<Felt>("keys")?;
+    let keys = get_keys(exec_scopes)?;
     if !keys.is_empty() {
         return Err(HintError::KeysNotEmpty);
     };
@@ -203,8 +179,7 @@ pub fn squash_dict_inner_next_key(
     ap_tracking: &ApTracking,
 ) -> Result<(), HintError> {
     //Check that current_access_indices is in scope
-    let keys = exec_scopes.get_mut_list_ref::<Felt>("keys")?;
-    let next_key = keys.pop().ok_or(HintError::EmptyKeys)?;
+    let next_key = pop_key(exec_scopes)?;
     //Insert next_key into ids.next_keys
     insert_value_from_var_name("next_key", next_key.clone(), vm, ids_data, ap_tracking)?;
     //Update local variables
@@ -250,8 +225,12 @@ pub fn squash_dict(
     if ptr_diff.mod_floor(&Felt::new(DICT_ACCESS_SIZE)) != Felt::zero() {
         return Err(HintError::PtrDiffNotDivisibleByDictAccessSize);
     }
-    let squash_dict_max_size = exec_scopes.get::<Felt>("__squash_dict_max_size");
-    if let Ok(max_size) = squash_dict_max_size {
+    let squash_dict_max_size = match exec_scopes.get::<Felt>("__squash_dict_max_size") {
+        Ok(max_size) => Some(max_size),
+        Err(HintError::VariableNotInScopeError(_)) => None,
+        Err(error) => return Err(error),
+    };
+    if let Some(max_size) = squash_dict_max_size {
         if n_accesses.as_ref() > &max_size {
             return Err(HintError::SquashDictMaxSizeExceeded(
                 max_size,
@@ -274,22 +253,20 @@ pub fn squash_dict(
             .or_insert(Vec::<Felt>::new())
             .push(Felt::new(i));
     }
-    //Descending list of keys.
-    let mut keys: Vec<Felt> = access_indices.keys().cloned().collect();
-    keys.sort();
-    keys.reverse();
+    //Descending list of keys, replacing the ones of any previous squash in this scope.
+    exec_scopes.delete_variable("keys");
+    push_keys(exec_scopes, access_indices.keys().cloned())?;
+    set_access_indices(exec_scopes, access_indices);
     //Are the keys used bigger than the range_check bound.
-    let big_keys = if keys[0] >= range_check_bound.unwrap() {
+    let biggest_key = get_keys(exec_scopes)?.first().ok_or(HintError::EmptyKeys)?;
+    let big_keys = if biggest_key >= &range_check_bound.unwrap() {
         Felt::one()
     } else {
         Felt::zero()
     };
     insert_value_from_var_name("big_keys", big_keys, vm, ids_data, ap_tracking)?;
-    let key = keys.pop().ok_or(HintError::EmptyKeys)?;
+    let key = pop_key(exec_scopes)?;
     insert_value_from_var_name("first_key", key.clone(), vm, ids_data, ap_tracking)?;
-    //Insert local variables into scope
-    exec_scopes.insert_value("access_indices", access_indices);
-    exec_scopes.insert_value("keys", keys);
     exec_scopes.insert_value("key", key);
     Ok(())
 }
@@ -778,6 +755,101 @@ mod tests {
         check_memory![vm.memory, ((1, 1), 0), ((1, 2), 1)];
     }
 
+    #[test]
+    fn squash_dict_pops_keys_and_indices_in_ascending_order() {
+        let mut vm = vm_with_range_check!();
+        vm.run_context.fp = 5;
+        //Accesses to the keys 5, 1, 5 and 3, in that order
+        vm.memory = memory![
+            ((1, 0), (2, 0)),
+            ((1, 3), 12),
+            ((1, 4), 4),
+            ((1, 7), (2, 12)),
+            ((2, 0), 5),
+            ((2, 1), 0),
+            ((2, 2), 0),
+            ((2, 3), 1),
+            ((2, 4), 0),
+            ((2, 5), 0),
+            ((2, 6), 5),
+            ((2, 7), 0),
+            ((2, 8), 0),
+            ((2, 9), 3),
+            ((2, 10), 0),
+            ((2, 11), 0)
+        ];
+        let ids_data = ids_data![
+            "dict_accesses",
+            "big_keys",
+            "first_key",
+            "ptr_diff",
+            "n_accesses"
+        ];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_eq!(
+            run_hint!(vm, ids_data, SQUASH_DICT, &mut exec_scopes),
+            Ok(())
+        );
+        check_memory![vm.memory, ((1, 2), 1)];
+        check_scope!(
+            &exec_scopes,
+            [
+                ("keys", vec![Felt::new(5), Felt::new(3)]),
+                ("key", Felt::one())
+            ]
+        );
+
+        //The remaining keys are popped from the smallest one
+        vm.run_context.fp = 6;
+        let ids_data = ids_data!["next_key"];
+        assert_eq!(
+            run_hint!(vm, ids_data, SQUASH_DICT_INNER_NEXT_KEY, &mut exec_scopes),
+            Ok(())
+        );
+        vm.run_context.fp = 7;
+        let ids_data = ids_data!["next_key"];
+        assert_eq!(
+            run_hint!(vm, ids_data, SQUASH_DICT_INNER_NEXT_KEY, &mut exec_scopes),
+            Ok(())
+        );
+        check_memory![vm.memory, ((1, 5), 3), ((1, 6), 5)];
+        check_scope!(&exec_scopes, [("key", Felt::new(5))]);
+
+        //The accesses to key 5 are popped from the first one
+        vm.run_context.fp = 8;
+        let ids_data = ids_data!["range_check_ptr"];
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                SQUASH_DICT_INNER_FIRST_ITERATION,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        check_memory![vm.memory, ((2, 12), 0)];
+        vm.run_context.fp = 9;
+        let ids_data = ids_data!["loop_temps"];
+        assert_eq!(
+            run_hint!(
+                vm,
+                ids_data,
+                SQUASH_DICT_INNER_CHECK_ACCESS_INDEX,
+                &mut exec_scopes
+            ),
+            Ok(())
+        );
+        //index_delta_minus1 = 2 - 0 - 1
+        check_memory![vm.memory, ((1, 8), 1)];
+        check_scope!(
+            &exec_scopes,
+            [
+                ("current_access_indices", Vec::<Felt>::new()),
+                ("current_access_index", Felt::new(2))
+            ]
+        );
+    }
+
     #[test]
     fn squash_dict_valid_two_key_dict_no_max_size() {
         //Dict = {1: (1,1), 1: (1,2), 2: (10,10), 2: (10,20)}
@@ -9,6 +9,7 @@ use crate::{
     vm::{errors::hint_errors::HintError, vm_core::VirtualMachine},
 };
 use felt::{Felt, FeltOps, NewFelt};
+use num_bigint::BigUint;
 use num_integer::div_rem;
 use num_traits::{One, Signed, Zero};
 use std::{
@@ -32,14 +33,10 @@ pub fn uint256_add(
     let shift = Felt::new(1_u32) << 128_u32;
     let a_relocatable = get_relocatable_from_var_name("a", vm, ids_data, ap_tracking)?;
     let b_relocatable = get_relocatable_from_var_name("b", vm, ids_data, ap_tracking)?;
-    let a_low = vm.get_integer(&a_relocatable)?;
-    let a_high = vm.get_integer(&(a_relocatable + 1_usize))?;
-    let b_low = vm.get_integer(&b_relocatable)?;
-    let b_high = vm.get_integer(&(b_relocatable + 1_usize))?;
-    let a_low = a_low.as_ref();
-    let a_high = a_high.as_ref();
-    let b_low = b_low.as_ref();
-    let b_high = b_high.as_ref();
+    let a_limbs = vm.get_integer_range(&a_relocatable, 2)?;
+    let b_limbs = vm.get_integer_range(&b_relocatable, 2)?;
+    let (a_low, a_high) = (a_limbs[0].as_ref(), a_limbs[1].as_ref());
+    let (b_low, b_high) = (b_limbs[0].as_ref(), b_limbs[1].as_ref());
 
     //Main logic
     //sum_low = ids.a.low + ids.b.low
@@ -104,10 +101,8 @@ pub fn uint256_sqrt(
 ) -> Result<(), HintError> {
     let n_addr = get_relocatable_from_var_name("n", vm, ids_data, ap_tracking)?;
     let root_addr = get_relocatable_from_var_name("root", vm, ids_data, ap_tracking)?;
-    let n_low = vm.get_integer(&n_addr)?;
-    let n_high = vm.get_integer(&(n_addr + 1_usize))?;
-    let n_low = n_low.as_ref();
-    let n_high = n_high.as_ref();
+    let n_limbs = vm.get_integer_range(&n_addr, 2)?;
+    let (n_low, n_high) = (n_limbs[0].as_ref(), n_limbs[1].as_ref());
 
     //Main logic
     //from starkware.python.math_utils import isqrt
@@ -168,20 +163,47 @@ pub fn uint256_unsigned_div_rem(
     vm: &mut VirtualMachine,
     ids_data: &HashMap<String, HintReference>,
     ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    uint256_offseted_unsigned_div_rem(vm, ids_data, ap_tracking, 0, 1)
+}
+
+/*
+Implements hint:
+%{
+    a = (ids.a.high << 128) + ids.a.low
+    div = (ids.div.b23 << 128) + ids.div.b01
+    quotient, remainder = divmod(a, div)
+
+    ids.quotient.low = quotient & ((1 << 128) - 1)
+    ids.quotient.high = quotient >> 128
+    ids.remainder.low = remainder & ((1 << 128) - 1)
+    ids.remainder.high = remainder >> 128
+%}
+*/
+pub fn uint256_expanded_unsigned_div_rem(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    //ids.div is an Uint256Expanded {B0, b01, b12, b23, b3}
+    uint256_offseted_unsigned_div_rem(vm, ids_data, ap_tracking, 1, 3)
+}
+
+fn uint256_offseted_unsigned_div_rem(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    div_offset_low: usize,
+    div_offset_high: usize,
 ) -> Result<(), HintError> {
     let a_addr = get_relocatable_from_var_name("a", vm, ids_data, ap_tracking)?;
     let div_addr = get_relocatable_from_var_name("div", vm, ids_data, ap_tracking)?;
     let quotient_addr = get_relocatable_from_var_name("quotient", vm, ids_data, ap_tracking)?;
     let remainder_addr = get_relocatable_from_var_name("remainder", vm, ids_data, ap_tracking)?;
 
-    let a_low = vm.get_integer(&a_addr)?;
-    let a_high = vm.get_integer(&(a_addr + 1_usize))?;
-    let div_low = vm.get_integer(&div_addr)?;
-    let div_high = vm.get_integer(&(div_addr + 1_usize))?;
-    let a_low = a_low.as_ref();
-    let a_high = a_high.as_ref();
-    let div_low = div_low.as_ref();
-    let div_high = div_high.as_ref();
+    let a_limbs = vm.get_integer_range(&a_addr, 2)?;
+    let div_low = vm.get_integer(&(div_addr + div_offset_low))?;
+    let div_high = vm.get_integer(&(div_addr + div_offset_high))?;
 
     //Main logic
     //a = (ids.a.high << 128) + ids.a.low
@@ -193,16 +215,16 @@ pub fn uint256_unsigned_div_rem(
     //ids.remainder.low = remainder & ((1 << 128) - 1)
     //ids.remainder.high = remainder >> 128
 
-    let a = &a_high.shl(128_usize) + a_low;
-    let div = &div_high.shl(128_usize) + div_low;
+    let a = pack_uint256(a_limbs[0].as_ref(), a_limbs[1].as_ref());
+    let div = pack_uint256(div_low.as_ref(), div_high.as_ref());
+    if div.is_zero() {
+        return Err(HintError::Uint256DivByZero);
+    }
     //a and div will always be positive numbers
     //Then, Rust div_rem equals Python divmod
     let (quotient, remainder) = div_rem(a, div);
-    let quotient_low = &quotient & &Felt::new(u128::MAX);
-    let quotient_high = quotient.shr(128);
-
-    let remainder_low = &remainder & &Felt::new(u128::MAX);
-    let remainder_high = remainder.shr(128);
+    let (quotient_low, quotient_high) = split_uint256(&quotient);
+    let (remainder_low, remainder_high) = split_uint256(&remainder);
 
     //Insert ids.quotient.low
     vm.insert_value(&quotient_addr, quotient_low)?;
@@ -215,14 +237,90 @@ pub fn uint256_unsigned_div_rem(
     Ok(())
 }
 
+/*
+Implements hint:
+%{
+    a = (ids.a.high << 128) + ids.a.low
+    b = (ids.b.high << 128) + ids.b.low
+    div = (ids.div.high << 128) + ids.div.low
+    quotient, remainder = divmod(a * b, div)
+
+    ids.quotient_low.low = quotient & ((1 << 128) - 1)
+    ids.quotient_low.high = (quotient >> 128) & ((1 << 128) - 1)
+    ids.quotient_high.low = (quotient >> 256) & ((1 << 128) - 1)
+    ids.quotient_high.high = quotient >> 384
+    ids.remainder.low = remainder & ((1 << 128) - 1)
+    ids.remainder.high = remainder >> 128
+%}
+*/
+pub fn uint256_mul_div_mod(
+    vm: &mut VirtualMachine,
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+) -> Result<(), HintError> {
+    let a_addr = get_relocatable_from_var_name("a", vm, ids_data, ap_tracking)?;
+    let b_addr = get_relocatable_from_var_name("b", vm, ids_data, ap_tracking)?;
+    let div_addr = get_relocatable_from_var_name("div", vm, ids_data, ap_tracking)?;
+    let quotient_low_addr =
+        get_relocatable_from_var_name("quotient_low", vm, ids_data, ap_tracking)?;
+    let quotient_high_addr =
+        get_relocatable_from_var_name("quotient_high", vm, ids_data, ap_tracking)?;
+    let remainder_addr = get_relocatable_from_var_name("remainder", vm, ids_data, ap_tracking)?;
+
+    let a_limbs = vm.get_integer_range(&a_addr, 2)?;
+    let b_limbs = vm.get_integer_range(&b_addr, 2)?;
+    let div_limbs = vm.get_integer_range(&div_addr, 2)?;
+
+    let a = pack_uint256(a_limbs[0].as_ref(), a_limbs[1].as_ref());
+    let b = pack_uint256(b_limbs[0].as_ref(), b_limbs[1].as_ref());
+    let div = pack_uint256(div_limbs[0].as_ref(), div_limbs[1].as_ref());
+    if div.is_zero() {
+        return Err(HintError::Uint256DivByZero);
+    }
+    //The product can be up to 512 bits long, so the quotient is split into two Uint256
+    let (quotient, remainder) = div_rem(a * b, div);
+    let (quotient_low_low, quotient_low_high) = split_uint256(&quotient);
+    let (quotient_high_low, quotient_high_high) = split_uint256(&(quotient >> 256_u32));
+    let (remainder_low, remainder_high) = split_uint256(&remainder);
+
+    //Insert ids.quotient_low.low
+    vm.insert_value(&quotient_low_addr, quotient_low_low)?;
+    //Insert ids.quotient_low.high
+    vm.insert_value(&(quotient_low_addr + 1_i32), quotient_low_high)?;
+    //Insert ids.quotient_high.low
+    vm.insert_value(&quotient_high_addr, quotient_high_low)?;
+    //Insert ids.quotient_high.high
+    vm.insert_value(&(quotient_high_addr + 1_i32), quotient_high_high)?;
+    //Insert ids.remainder.low
+    vm.insert_value(&remainder_addr, remainder_low)?;
+    //Insert ids.remainder.high
+    vm.insert_value(&(remainder_addr + 1_i32), remainder_high)?;
+    Ok(())
+}
+
+//Computes (high << 128) + low without reducing it modulo PRIME
+fn pack_uint256(low: &Felt, high: &Felt) -> BigUint {
+    (high.to_biguint() << 128_u32) + low.to_biguint()
+}
+
+//Returns (value & ((1 << 128) - 1), (value >> 128) & ((1 << 128) - 1))
+fn split_uint256(value: &BigUint) -> (Felt, Felt) {
+    let mask = BigUint::from(u128::MAX);
+    (
+        Felt::from(value & &mask),
+        Felt::from((value >> 128_u32) & mask),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
         any_box,
         hint_processor::{
-            builtin_hint_processor::builtin_hint_processor_definition::{
-                BuiltinHintProcessor, HintProcessorData,
+            builtin_hint_processor::{
+                builtin_hint_processor_definition::{BuiltinHintProcessor, HintProcessorData},
+                hint_code,
             },
             hint_processor_definition::HintProcessor,
         },
@@ -542,4 +640,187 @@ mod tests {
             )))
         );
     }
+
+    #[test]
+    fn run_expanded_unsigned_div_rem_ok() {
+        let mut vm = vm_with_range_check!();
+        //Initialize fp
+        vm.run_context.fp = 10;
+        //Create hint_data
+        let ids_data =
+            non_continuous_ids_data![("a", -7), ("div", -5), ("quotient", 0), ("remainder", 2)];
+        //Insert ids into memory
+        //ids.div = Uint256Expanded {B0, b01, b12, b23, b3}
+        vm.memory = memory![
+            ((1, 3), 89),
+            ((1, 4), 72),
+            ((1, 5), 0),
+            ((1, 6), 3),
+            ((1, 7), 0),
+            ((1, 8), 7),
+            ((1, 9), 0)
+        ];
+        //Execute the hint
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::UINT256_EXPANDED_UNSIGNED_DIV_REM),
+            Ok(())
+        );
+        //Check hint memory inserts
+        //ids.quotient.low, ids.quotient.high, ids.remainder.low, ids.remainder.high
+        check_memory![
+            &vm.memory,
+            ((1, 10), 10),
+            ((1, 11), 0),
+            ((1, 12), 59),
+            ((1, 13), 2)
+        ];
+    }
+
+    #[test]
+    fn run_expanded_unsigned_div_rem_div_by_zero() {
+        let mut vm = vm_with_range_check!();
+        //Initialize fp
+        vm.run_context.fp = 10;
+        //Create hint_data
+        let ids_data =
+            non_continuous_ids_data![("a", -7), ("div", -5), ("quotient", 0), ("remainder", 2)];
+        //Insert ids into memory
+        vm.memory = memory![
+            ((1, 3), 89),
+            ((1, 4), 72),
+            ((1, 5), 0),
+            ((1, 6), 0),
+            ((1, 7), 0),
+            ((1, 8), 0),
+            ((1, 9), 0)
+        ];
+        //Execute the hint
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::UINT256_EXPANDED_UNSIGNED_DIV_REM),
+            Err(HintError::Uint256DivByZero)
+        );
+    }
+
+    #[test]
+    fn run_mul_div_mod_ok() {
+        let mut vm = vm_with_range_check!();
+        //Initialize fp
+        vm.run_context.fp = 10;
+        //Create hint_data
+        let ids_data = non_continuous_ids_data![
+            ("a", -8),
+            ("b", -6),
+            ("div", -4),
+            ("quotient_low", 0),
+            ("quotient_high", 2),
+            ("remainder", 4)
+        ];
+        //Insert ids into memory
+        vm.memory = memory![
+            ((1, 2), 456),
+            ((1, 3), 123),
+            ((1, 4), 1011),
+            ((1, 5), 789),
+            ((1, 6), 7),
+            ((1, 7), 3)
+        ];
+        //Execute the hint
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::UINT256_MUL_DIV_MOD),
+            Ok(())
+        );
+        //Check hint memory inserts
+        check_memory![
+            &vm.memory,
+            ((1, 10), 85897),
+            ((1, 11), 32349),
+            ((1, 12), 0),
+            ((1, 13), 0),
+            ((1, 15), 2)
+        ];
+        assert_eq!(
+            vm.get_integer(&Relocatable::from((1, 14)))
+                .unwrap()
+                .as_ref(),
+            &felt_str!("340282366920938463463374607431768071193")
+        );
+    }
+
+    #[test]
+    fn run_mul_div_mod_quotient_bigger_than_256_bits() {
+        let mut vm = vm_with_range_check!();
+        //Initialize fp
+        vm.run_context.fp = 10;
+        //Create hint_data
+        let ids_data = non_continuous_ids_data![
+            ("a", -8),
+            ("b", -6),
+            ("div", -4),
+            ("quotient_low", 0),
+            ("quotient_high", 2),
+            ("remainder", 4)
+        ];
+        //Insert ids into memory
+        //a = 2**256 - 1, b = 2**255 + 3, div = 2**130 + 5
+        vm.memory = memory![((1, 4), 3), ((1, 6), 5), ((1, 7), 4)];
+        vm.insert_value(&Relocatable::from((1, 2)), Felt::new(u128::MAX))
+            .unwrap();
+        vm.insert_value(&Relocatable::from((1, 3)), Felt::new(u128::MAX))
+            .unwrap();
+        vm.insert_value(&Relocatable::from((1, 5)), Felt::new(1_u128 << 127))
+            .unwrap();
+        //Execute the hint
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::UINT256_MUL_DIV_MOD),
+            Ok(())
+        );
+        //Check hint memory inserts
+        //ids.quotient_low.low, ids.quotient_low.high, ids.quotient_high.low, ids.quotient_high.high
+        //ids.remainder.low, ids.remainder.high
+        assert_eq!(
+            vm.get_integer_range(&Relocatable::from((1, 10)), 6)
+                .unwrap()
+                .into_iter()
+                .map(|x| x.into_owned())
+                .collect::<Vec<Felt>>(),
+            vec![
+                felt_str!("279137879114832333309799482658872360958"),
+                felt_str!("287113247089541828547222325020554428416"),
+                felt_str!("42535295865117307932921825928971026431"),
+                Felt::zero(),
+                felt_str!("305722439030530650767875623864479252487"),
+                Felt::new(3),
+            ]
+        );
+    }
+
+    #[test]
+    fn run_mul_div_mod_div_by_zero() {
+        let mut vm = vm_with_range_check!();
+        //Initialize fp
+        vm.run_context.fp = 10;
+        //Create hint_data
+        let ids_data = non_continuous_ids_data![
+            ("a", -8),
+            ("b", -6),
+            ("div", -4),
+            ("quotient_low", 0),
+            ("quotient_high", 2),
+            ("remainder", 4)
+        ];
+        //Insert ids into memory
+        vm.memory = memory![
+            ((1, 2), 456),
+            ((1, 3), 123),
+            ((1, 4), 1011),
+            ((1, 5), 789),
+            ((1, 6), 0),
+            ((1, 7), 0)
+        ];
+        //Execute the hint
+        assert_eq!(
+            run_hint!(vm, ids_data, hint_code::UINT256_MUL_DIV_MOD),
+            Err(HintError::Uint256DivByZero)
+        );
+    }
 }
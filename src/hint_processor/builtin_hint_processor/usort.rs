@@ -14,14 +14,16 @@ use num_traits::{ToPrimitive, Zero};
 use std::{any::Any, collections::HashMap};
 
 pub fn usort_enter_scope(exec_scopes: &mut ExecutionScopes) -> Result<(), HintError> {
-    if let Ok(usort_max_size) = exec_scopes.get::<Felt>("usort_max_size") {
-        let boxed_max_size: Box<dyn Any> = Box::new(usort_max_size);
-        exec_scopes.enter_scope(HashMap::from([(
-            "usort_max_size".to_string(),
-            boxed_max_size,
-        )]));
-    } else {
-        exec_scopes.enter_scope(HashMap::new());
+    match exec_scopes.get::<u64>("usort_max_size") {
+        Ok(usort_max_size) => {
+            let boxed_max_size: Box<dyn Any> = Box::new(usort_max_size);
+            exec_scopes.enter_scope(HashMap::from([(
+                "usort_max_size".to_string(),
+                boxed_max_size,
+            )]));
+        }
+        Err(HintError::VariableNotInScopeError(_)) => exec_scopes.enter_scope(HashMap::new()),
+        Err(error) => return Err(error),
     }
     Ok(())
 }
@@ -33,11 +35,15 @@ pub fn usort_body(
     ap_tracking: &ApTracking,
 ) -> Result<(), HintError> {
     let input_ptr = get_ptr_from_var_name("input", vm, ids_data, ap_tracking)?;
-    let usort_max_size = exec_scopes.get::<u64>("usort_max_size");
+    let usort_max_size = match exec_scopes.get::<u64>("usort_max_size") {
+        Ok(usort_max_size) => Some(usort_max_size),
+        Err(HintError::VariableNotInScopeError(_)) => None,
+        Err(error) => return Err(error),
+    };
     let input_len = get_integer_from_var_name("input_len", vm, ids_data, ap_tracking)?;
     let input_len_u64 = input_len.to_u64().ok_or(HintError::BigintToUsizeFail)?;
 
-    if let Ok(usort_max_size) = usort_max_size {
+    if let Some(usort_max_size) = usort_max_size {
         if input_len_u64 > usort_max_size {
             return Err(HintError::UsortOutOfRange(
                 usort_max_size,
@@ -151,11 +157,26 @@ mod tests {
             vm_core::VirtualMachine, vm_memory::memory::Memory,
         },
     };
+    use num_traits::One;
 
     #[test]
     fn usort_with_max_size() {
         let mut exec_scopes = scope![("usort_max_size", 1_u64)];
         assert_eq!(usort_enter_scope(&mut exec_scopes), Ok(()));
+        assert_eq!(exec_scopes.data.len(), 2);
+        check_scope!(&exec_scopes, [("usort_max_size", 1_u64)]);
+    }
+
+    #[test]
+    fn usort_max_size_wrong_type() {
+        let mut exec_scopes = scope![("usort_max_size", Felt::one())];
+        assert_eq!(
+            usort_enter_scope(&mut exec_scopes),
+            Err(HintError::WrongScopeTypeError(
+                "usort_max_size".to_string(),
+                "u64".to_string()
+            ))
+        );
     }
 
     #[test]
@@ -172,4 +193,126 @@ mod tests {
             Err(HintError::UsortOutOfRange(1, Felt::new(5_i32)))
         );
     }
+
+    #[test]
+    fn usort_body_repeated_values() {
+        let mut vm = vm!();
+        vm.run_context.fp = 5;
+        add_segments!(vm, 3);
+        vm.memory = memory![
+            ((1, 0), (2, 0)),
+            ((1, 1), 5),
+            ((2, 0), 3),
+            ((2, 1), 1),
+            ((2, 2), 3),
+            ((2, 3), 3),
+            ((2, 4), 1)
+        ];
+        let ids_data = ids_data![
+            "input",
+            "input_len",
+            "output",
+            "output_len",
+            "multiplicities"
+        ];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_eq!(
+            run_hint!(vm, ids_data, USORT_BODY, &mut exec_scopes),
+            Ok(())
+        );
+        check_memory![
+            vm.memory,
+            ((1, 2), (3, 0)),
+            ((1, 3), 2),
+            ((1, 4), (4, 0)),
+            ((3, 0), 1),
+            ((3, 1), 3),
+            ((4, 0), 2),
+            ((4, 1), 3)
+        ];
+        assert_eq!(
+            exec_scopes.get::<HashMap<Felt, Vec<u64>>>("positions_dict"),
+            Ok(HashMap::from([
+                (Felt::one(), vec![1, 4]),
+                (Felt::new(3), vec![0, 2, 3])
+            ]))
+        );
+    }
+
+    #[test]
+    fn usort_body_empty_input() {
+        let mut vm = vm!();
+        vm.run_context.fp = 5;
+        add_segments!(vm, 3);
+        vm.memory = memory![((1, 0), (2, 0)), ((1, 1), 0)];
+        let ids_data = ids_data![
+            "input",
+            "input_len",
+            "output",
+            "output_len",
+            "multiplicities"
+        ];
+        let mut exec_scopes = ExecutionScopes::new();
+        assert_eq!(
+            run_hint!(vm, ids_data, USORT_BODY, &mut exec_scopes),
+            Ok(())
+        );
+        check_memory![vm.memory, ((1, 2), (3, 0)), ((1, 3), 0), ((1, 4), (4, 0))];
+        assert!(vm.memory.data[3].is_empty());
+        assert!(vm.memory.data[4].is_empty());
+        assert_eq!(
+            exec_scopes.get::<HashMap<Felt, Vec<u64>>>("positions_dict"),
+            Ok(HashMap::new())
+        );
+    }
+
+    #[test]
+    fn verify_usort_and_multiplicity() {
+        let mut vm = vm!();
+        vm.run_context.fp = 1;
+        add_segments!(vm, 2);
+        vm.memory = memory![((1, 0), 3)];
+        let mut exec_scopes = scope![(
+            "positions_dict",
+            HashMap::from([(Felt::new(3), vec![0_u64, 2, 3])])
+        )];
+        assert_eq!(
+            verify_usort(
+                &mut vm,
+                &mut exec_scopes,
+                &ids_data!["value"],
+                &ApTracking::new()
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            verify_multiplicity_assert(&mut exec_scopes),
+            Err(HintError::PositionsLengthNotZero)
+        );
+
+        // Each position is written to its own next_item_index cell
+        for fp in 2..5 {
+            vm.run_context.fp = fp;
+            assert_eq!(
+                verify_multiplicity_body(
+                    &mut vm,
+                    &mut exec_scopes,
+                    &ids_data!["next_item_index"],
+                    &ApTracking::new()
+                ),
+                Ok(())
+            );
+        }
+        check_memory![vm.memory, ((1, 1), 0), ((1, 2), 1), ((1, 3), 0)];
+        assert_eq!(verify_multiplicity_assert(&mut exec_scopes), Ok(()));
+        assert_eq!(
+            verify_multiplicity_body(
+                &mut vm,
+                &mut exec_scopes,
+                &ids_data!["next_item_index"],
+                &ApTracking::new()
+            ),
+            Err(HintError::CouldntPopPositions)
+        );
+    }
 }
@@ -4,6 +4,7 @@ use crate::{
     serde::deserialize_program::{ApTracking, OffsetValue},
     types::{exec_scope::ExecutionScopes, instruction::Register},
     vm::errors::{hint_errors::HintError, vm_errors::VirtualMachineError},
+    vm::runners::cairo_runner::RunResources,
     vm::vm_core::VirtualMachine,
 };
 use felt::Felt;
@@ -23,6 +24,9 @@ pub trait HintProcessor {
         hint_data: &Box<dyn Any>,
         //Constant values extracted from the program specification.
         constants: &HashMap<String, Felt>,
+        //Step budget of the current run. The VM consumes one step per executed hint, processors
+        //may consume more for expensive hints
+        run_resources: &mut RunResources,
     ) -> Result<(), HintError>;
 
     //Transforms hint data outputed by the VM into whichever format will be later used by execute_hint
@@ -38,11 +42,11 @@ pub trait HintProcessor {
         //List of all references (key corresponds to element of the previous dictionary)
         references: &HashMap<usize, HintReference>,
     ) -> Result<Box<dyn Any>, VirtualMachineError> {
-        Ok(any_box!(HintProcessorData {
-            code: hint_code.to_string(),
-            ap_tracking: ap_tracking_data.clone(),
-            ids_data: get_ids_data(reference_ids, references)?,
-        }))
+        Ok(any_box!(HintProcessorData::new(
+            hint_code,
+            ap_tracking_data.clone(),
+            get_ids_data(reference_ids, references)?,
+        )))
     }
 }
 
@@ -72,6 +76,9 @@ pub struct HintReference {
     pub offset1: OffsetValue,
     pub offset2: OffsetValue,
     pub dereference: bool,
+    // Whether offset1 + offset2 is dereferenced before applying `dereference`,
+    // as in `[cast([[fp] + 1], felt*)]`
+    pub inner_dereference: bool,
     pub ap_tracking_data: Option<ApTracking>,
     pub cairo_type: Option<String>,
 }
@@ -83,16 +90,18 @@ impl HintReference {
             offset2: OffsetValue::Value(0),
             ap_tracking_data: None,
             dereference: true,
+            inner_dereference: false,
             cairo_type: None,
         }
     }
 
-    pub fn new(offset1: i32, offset2: i32, inner_dereference: bool, dereference: bool) -> Self {
+    pub fn new(offset1: i32, offset2: i32, offset1_dereference: bool, dereference: bool) -> Self {
         HintReference {
-            offset1: OffsetValue::Reference(Register::FP, offset1, inner_dereference),
+            offset1: OffsetValue::Reference(Register::FP, offset1, offset1_dereference),
             offset2: OffsetValue::Value(offset2),
             ap_tracking_data: None,
             dereference,
+            inner_dereference: false,
             cairo_type: None,
         }
     }
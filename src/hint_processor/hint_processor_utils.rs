@@ -9,10 +9,10 @@ use crate::{
         vm_core::VirtualMachine,
     },
 };
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashMap};
 
 use super::hint_processor_definition::HintReference;
-use felt::Felt;
+use felt::{Felt, NewFelt};
 use num_traits::ToPrimitive;
 
 ///Inserts value into the address of the given ids variable
@@ -40,10 +40,52 @@ pub fn get_integer_from_reference<'a>(
         return Ok(Cow::Borrowed(int_1));
     }
 
+    if is_felt_expression(hint_reference) {
+        return compute_felt_from_reference(hint_reference, vm, ap_tracking).map(Cow::Owned);
+    }
+
     let var_addr = compute_addr_from_reference(hint_reference, vm, ap_tracking)?;
     vm.get_integer(&var_addr).map_err(HintError::Internal)
 }
 
+///Returns the value stored in the given ids variable, which can be either an integer or a relocatable
+pub fn get_maybe_relocatable_from_reference<'a>(
+    vm: &'a VirtualMachine,
+    hint_reference: &HintReference,
+    ap_tracking: &ApTracking,
+) -> Result<Cow<'a, MaybeRelocatable>, HintError> {
+    //If the reference is an immediate value, return it as an integer
+    if let OffsetValue::Immediate(num) = &hint_reference.offset1 {
+        return Ok(Cow::Owned(MaybeRelocatable::from(num)));
+    }
+    if is_felt_expression(hint_reference) {
+        return compute_felt_from_reference(hint_reference, vm, ap_tracking)
+            .map(|num| Cow::Owned(MaybeRelocatable::from(num)));
+    }
+    let var_addr = compute_addr_from_reference(hint_reference, vm, ap_tracking)?;
+    vm.get_maybe(&var_addr)
+        .map_err(VirtualMachineError::MemoryError)?
+        .ok_or_else(|| VirtualMachineError::MemoryGet(var_addr.into()).into())
+}
+
+///Returns the value of every ids variable, or None for the ones that can't be read, e.g. because
+///their memory cell wasn't written yet
+pub fn get_ids_data_snapshot(
+    ids_data: &HashMap<String, HintReference>,
+    ap_tracking: &ApTracking,
+    vm: &VirtualMachine,
+) -> HashMap<String, Option<MaybeRelocatable>> {
+    ids_data
+        .iter()
+        .map(|(name, hint_reference)| {
+            let value = get_maybe_relocatable_from_reference(vm, hint_reference, ap_tracking)
+                .ok()
+                .map(Cow::into_owned);
+            (name.clone(), value)
+        })
+        .collect()
+}
+
 ///Returns the Relocatable value stored in the given ids variable
 pub fn get_ptr_from_reference(
     vm: &VirtualMachine,
@@ -65,6 +107,21 @@ pub fn compute_addr_from_reference(
     vm: &VirtualMachine,
     //ApTracking of the Hint itself
     hint_ap_tracking: &ApTracking,
+) -> Result<Relocatable, HintError> {
+    let addr = compute_offsets_sum(hint_reference, vm, hint_ap_tracking)?;
+    if hint_reference.inner_dereference {
+        // A reference like `[cast([[fp] + 1], felt*)]` points to the address stored at [fp] + 1
+        Ok(vm.get_relocatable(&addr)?)
+    } else {
+        Ok(addr)
+    }
+}
+
+// Computes offset1 + offset2 as a Relocatable, without applying the inner dereference
+fn compute_offsets_sum(
+    hint_reference: &HintReference,
+    vm: &VirtualMachine,
+    hint_ap_tracking: &ApTracking,
 ) -> Result<Relocatable, HintError> {
     let offset1 =
         if let OffsetValue::Reference(_register, _offset, _deref) = &hint_reference.offset1 {
@@ -101,6 +158,56 @@ pub fn compute_addr_from_reference(
     }
 }
 
+// A felt reference that isn't dereferenced, like `cast([fp + (-3)] + 2, felt)`,
+// holds the value of its expression instead of the address of a memory cell
+fn is_felt_expression(hint_reference: &HintReference) -> bool {
+    !hint_reference.dereference && hint_reference.cairo_type.as_deref() == Some("felt")
+}
+
+// Evaluates the expression of a felt reference that isn't dereferenced
+fn compute_felt_from_reference(
+    hint_reference: &HintReference,
+    vm: &VirtualMachine,
+    hint_ap_tracking: &ApTracking,
+) -> Result<Felt, HintError> {
+    if hint_reference.inner_dereference {
+        let addr = compute_offsets_sum(hint_reference, vm, hint_ap_tracking)?;
+        return Ok(vm.get_integer(&addr)?.into_owned());
+    }
+    let offset1 = get_offset_value_felt(
+        vm,
+        hint_reference,
+        hint_ap_tracking,
+        &hint_reference.offset1,
+    )?;
+    let offset2 = get_offset_value_felt(
+        vm,
+        hint_reference,
+        hint_ap_tracking,
+        &hint_reference.offset2,
+    )?;
+    Ok(offset1 + offset2)
+}
+
+fn get_offset_value_felt(
+    vm: &VirtualMachine,
+    hint_reference: &HintReference,
+    hint_ap_tracking: &ApTracking,
+    offset_value: &OffsetValue,
+) -> Result<Felt, HintError> {
+    match offset_value {
+        OffsetValue::Immediate(num) => Ok(num.clone()),
+        OffsetValue::Value(num) => Ok(Felt::new(*num)),
+        OffsetValue::Reference(_register, _offset, _deref) => {
+            Ok(
+                get_offset_value_reference(vm, hint_reference, hint_ap_tracking, offset_value)?
+                    .get_int_ref()?
+                    .clone(),
+            )
+        }
+    }
+}
+
 fn apply_ap_tracking_correction(
     ap: &Relocatable,
     ref_ap_tracking: &ApTracking,
@@ -114,7 +221,7 @@ fn apply_ap_tracking_correction(
         ));
     }
     let ap_diff = hint_ap_tracking.offset - ref_ap_tracking.offset;
-    ap.sub_usize(ap_diff).map_err(HintError::Internal)
+    ap.sub_usize(ap_diff).map_err(HintError::from)
 }
 
 //Tries to convert a Felt value to usize
@@ -163,7 +270,8 @@ fn get_offset_value_reference(
         Ok(vm
             .get_maybe(&(base_addr + *offset))
             .map_err(|_| HintError::FailedToGetIds)?
-            .ok_or(HintError::FailedToGetIds)?)
+            .ok_or(HintError::FailedToGetIds)?
+            .into_owned())
     } else {
         Ok((base_addr + *offset).into())
     }
@@ -266,6 +374,108 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_ptr_from_reference_with_double_dereference() {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), (2, 0)), ((2, 3), (3, 5)), ((3, 5), (4, 1))];
+        // cast([[fp] + 3], felt*)
+        let mut hint_ref = HintReference::new(0, 3, true, false);
+        hint_ref.inner_dereference = true;
+        hint_ref.cairo_type = Some("felt*".to_string());
+
+        assert_eq!(
+            get_ptr_from_reference(&vm, &hint_ref, &ApTracking::new()),
+            Ok(relocatable!(3, 5))
+        );
+
+        // [cast([[fp] + 3], felt**)]
+        hint_ref.dereference = true;
+        assert_eq!(
+            get_ptr_from_reference(&vm, &hint_ref, &ApTracking::new()),
+            Ok(relocatable!(4, 1))
+        );
+    }
+
+    #[test]
+    fn get_integer_from_reference_with_double_dereference() {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), (2, 0)), ((2, 2), 7)];
+        // cast([[fp] + 2], felt)
+        let mut hint_ref = HintReference::new(0, 2, true, false);
+        hint_ref.inner_dereference = true;
+        hint_ref.cairo_type = Some("felt".to_string());
+
+        assert_eq!(
+            get_integer_from_reference(&vm, &hint_ref, &ApTracking::new())
+                .map(|value| value.into_owned()),
+            Ok(Felt::new(7))
+        );
+    }
+
+    #[test]
+    fn get_integer_from_reference_with_double_dereference_and_ap_tracking() {
+        let mut vm = vm!();
+        vm.run_context.ap = 3;
+        vm.memory = memory![((1, 1), (2, 0)), ((2, 1), 9)];
+        // cast([[ap + (-1)] + 1], felt), where ap was increased by one since the reference
+        let hint_ref = HintReference {
+            offset1: OffsetValue::Reference(Register::AP, -1, true),
+            offset2: OffsetValue::Value(1),
+            dereference: false,
+            inner_dereference: true,
+            ap_tracking_data: Some(ApTracking {
+                group: 0,
+                offset: 1,
+            }),
+            cairo_type: Some("felt".to_string()),
+        };
+        let hint_ap_tracking = ApTracking {
+            group: 0,
+            offset: 2,
+        };
+
+        assert_eq!(
+            get_integer_from_reference(&vm, &hint_ref, &hint_ap_tracking)
+                .map(|value| value.into_owned()),
+            Ok(Felt::new(9))
+        );
+    }
+
+    #[test]
+    fn get_integer_from_reference_with_immediate_addition() {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), 5)];
+        // cast([fp] + 2, felt)
+        let mut hint_ref = HintReference::new(0, 0, true, false);
+        hint_ref.offset2 = OffsetValue::Immediate(Felt::new(2));
+        hint_ref.cairo_type = Some("felt".to_string());
+
+        assert_eq!(
+            get_integer_from_reference(&vm, &hint_ref, &ApTracking::new())
+                .map(|value| value.into_owned()),
+            Ok(Felt::new(7))
+        );
+        assert_eq!(
+            get_maybe_relocatable_from_reference(&vm, &hint_ref, &ApTracking::new())
+                .map(Cow::into_owned),
+            Ok(MaybeRelocatable::from(Felt::new(7)))
+        );
+    }
+
+    #[test]
+    fn get_integer_from_reference_with_immediate_addition_to_pointer() {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), (2, 0))];
+        let mut hint_ref = HintReference::new(0, 0, true, false);
+        hint_ref.offset2 = OffsetValue::Immediate(Felt::new(2));
+        hint_ref.cairo_type = Some("felt".to_string());
+
+        assert!(matches!(
+            get_integer_from_reference(&vm, &hint_ref, &ApTracking::new()),
+            Err(HintError::Internal(VirtualMachineError::ExpectedInteger(_)))
+        ));
+    }
+
     #[test]
     fn tracking_correction_invalid_group() {
         let mut ref_ap_tracking = ApTracking::new();
@@ -278,4 +488,21 @@ mod tests {
             Err(HintError::InvalidTrackingGroup(1, 2))
         );
     }
+
+    #[test]
+    fn get_ids_data_snapshot_with_missing_value() {
+        let mut vm = vm!();
+        vm.run_context.fp = 3;
+        vm.memory = memory![((1, 0), 5), ((1, 1), (2, 3))];
+        let ids_data = ids_data!["a", "b_ptr", "c"];
+
+        assert_eq!(
+            get_ids_data_snapshot(&ids_data, &ApTracking::new(), &vm),
+            HashMap::from([
+                ("a".to_string(), Some(mayberelocatable!(5))),
+                ("b_ptr".to_string(), Some(mayberelocatable!(2, 3))),
+                ("c".to_string(), None),
+            ])
+        );
+    }
 }
@@ -5,7 +5,7 @@ use cairo_rs::vm::errors::cairo_run_errors::CairoRunError;
 use cairo_rs::vm::errors::runner_errors::RunnerError;
 use cairo_rs::vm::errors::trace_errors::TraceError;
 use clap::{Parser, ValueHint};
-use std::path::PathBuf;
+use std::{path::PathBuf, process::ExitCode};
 
 #[cfg(feature = "with_mimalloc")]
 use mimalloc::MiMalloc;
@@ -32,6 +32,8 @@ struct Args {
     layout: String,
     #[structopt(long = "--proof_mode")]
     proof_mode: bool,
+    #[structopt(long = "--secure_run")]
+    secure_run: bool,
 }
 
 fn validate_layout(value: &str) -> Result<(), String> {
@@ -43,25 +45,19 @@ fn validate_layout(value: &str) -> Result<(), String> {
     }
 }
 
-fn main() -> Result<(), CairoRunError> {
-    let args = Args::parse();
+fn run(args: Args) -> Result<(), CairoRunError> {
     let trace_enabled = args.trace_file.is_some();
     let mut hint_executor = BuiltinHintProcessor::new_empty();
-    let cairo_runner = match cairo_run::cairo_run(
+    let cairo_runner = cairo_run::cairo_run(
         &args.filename,
         &args.entrypoint,
         trace_enabled,
         args.print_output,
         &args.layout,
         args.proof_mode,
+        args.secure_run,
         &mut hint_executor,
-    ) {
-        Ok(runner) => runner,
-        Err(error) => {
-            println!("{}", error);
-            return Err(error);
-        }
-    };
+    )?;
 
     if let Some(trace_path) = args.trace_file {
         let relocated_trace = cairo_runner
@@ -84,6 +80,17 @@ fn main() -> Result<(), CairoRunError> {
     Ok(())
 }
 
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            // VmExceptions include the location of the error and the Cairo traceback
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
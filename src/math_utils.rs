@@ -1,10 +1,27 @@
-use crate::vm::errors::vm_errors::VirtualMachineError;
+use crate::vm::errors::{math_errors::MathError, vm_errors::VirtualMachineError};
 use felt::Felt;
 use num_bigint::{BigInt, BigUint};
 use num_integer::Integer;
 use num_traits::{One, Signed, Zero};
 use std::ops::Shr;
 
+/// Checked conversions of felts into primitive integers, whose errors hold the felt that
+/// didn't fit.
+pub trait FeltToPrimitive {
+    fn to_usize_checked(&self) -> Result<usize, MathError>;
+    fn to_u64_checked(&self) -> Result<u64, MathError>;
+}
+
+impl FeltToPrimitive for Felt {
+    fn to_usize_checked(&self) -> Result<usize, MathError> {
+        usize::try_from(self).map_err(|err| MathError::FeltToUsizeConversion(err.0))
+    }
+
+    fn to_u64_checked(&self) -> Result<u64, MathError> {
+        u64::try_from(self).map_err(|err| MathError::FeltToU64Conversion(err.0))
+    }
+}
+
 ///Returns the integer square root of the nonnegative integer n.
 ///This is the floor of the exact square root of n.
 ///Unlike math.sqrt(), this function doesn't have rounding error issues.
@@ -182,6 +199,33 @@ mod tests {
     use felt::NewFelt;
     use num_traits::Num;
 
+    #[test]
+    fn felt_to_usize_checked() {
+        assert_eq!(Felt::new(usize::MAX).to_usize_checked(), Ok(usize::MAX));
+        assert_eq!(
+            Felt::new(-1).to_u64_checked(),
+            Err(MathError::FeltToU64Conversion(Felt::new(-1)))
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn felt_to_usize_checked_just_above_max() {
+        let too_big = Felt::new(usize::MAX as u128 + 1);
+        assert_eq!(
+            too_big.to_usize_checked(),
+            Err(MathError::FeltToUsizeConversion(too_big.clone()))
+        );
+        assert_eq!(
+            too_big.to_u64_checked(),
+            Err(MathError::FeltToU64Conversion(too_big.clone()))
+        );
+        assert_eq!(
+            too_big.to_usize_checked().unwrap_err().to_string(),
+            "Felt 18446744073709551616 doesn't fit in a usize"
+        );
+    }
+
     #[test]
     fn calculate_divmod_a() {
         let a = bigint_str!(
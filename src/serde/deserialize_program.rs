@@ -1,14 +1,19 @@
 use crate::{
     serde::deserialize_utils,
     types::{
-        errors::program_errors::ProgramError, instruction::Register, program::Program,
+        errors::program_errors::ProgramError,
+        instruction::Register,
+        program::{extract_constants, normalize_builtin_names, Program},
         relocatable::MaybeRelocatable,
     },
 };
-use felt::{Felt, FeltOps, PRIME_STR};
-use serde::{de, de::MapAccess, de::SeqAccess, Deserialize, Deserializer};
+use felt::{Felt, FeltOps, NewFelt, PRIME_STR};
+use lazy_static::lazy_static;
+use num_bigint::BigInt;
+use num_traits::Bounded;
+use serde::{de, de::MapAccess, de::SeqAccess, Deserialize, Deserializer, Serializer};
 use serde_json::Number;
-use std::{collections::HashMap, fmt, io::Read};
+use std::{collections::HashMap, fmt, io::Read, sync::Arc};
 
 #[derive(Deserialize, Debug)]
 pub struct ProgramJson {
@@ -21,6 +26,8 @@ pub struct ProgramJson {
     pub reference_manager: ReferenceManager,
     pub attributes: Vec<Attribute>,
     pub debug_info: Option<DebugInfo>,
+    // Only recorded by cairo-lang 0.10 and later
+    pub compiler_version: Option<String>,
 }
 
 #[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -83,6 +90,8 @@ pub struct Attribute {
     pub start_pc: usize,
     pub end_pc: usize,
     pub value: String,
+    #[serde(default)]
+    pub flow_tracking_data: Option<FlowTrackingData>,
 }
 
 #[derive(Deserialize, Clone, Debug, PartialEq, Eq)]
@@ -117,12 +126,27 @@ pub struct HintLocation {
     pub n_prefix_newlines: u32,
 }
 
+lazy_static! {
+    // The prime as a signed integer, to check the range of the constant values
+    static ref SIGNED_PRIME: BigInt = Felt::max_value().to_bigint() + 1_u32;
+}
+
 fn felt_from_number<'de, D>(deserializer: D) -> Result<Option<Felt>, D::Error>
 where
     D: Deserializer<'de>,
 {
     let n = Number::deserialize(deserializer)?;
-    Ok(Felt::parse_bytes(n.to_string().as_bytes(), 10))
+    let value = match BigInt::parse_bytes(n.to_string().as_bytes(), 10) {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    // Constant values are written as integers in the range [-PRIME, PRIME)
+    if value >= *SIGNED_PRIME || value < -&*SIGNED_PRIME {
+        return Err(de::Error::custom(format!(
+            "Constant value {n} is out of the field range"
+        )));
+    }
+    Ok(Some(Felt::new(value)))
 }
 
 #[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -152,6 +176,8 @@ pub struct ValueAddress {
     pub offset1: OffsetValue,
     pub offset2: OffsetValue,
     pub dereference: bool,
+    // Whether offset1 + offset2 is dereferenced before the cast, as in `cast([[fp] + 1], felt*)`
+    pub inner_dereference: bool,
     pub value_type: String,
 }
 
@@ -168,6 +194,7 @@ impl ValueAddress {
             offset1: OffsetValue::Value(99),
             offset2: OffsetValue::Value(99),
             dereference: false,
+            inner_dereference: false,
             value_type: String::from("felt"),
         }
     }
@@ -288,6 +315,15 @@ pub fn deserialize_felt_hex<'de, D: Deserializer<'de>>(d: D) -> Result<Felt, D::
     d.deserialize_str(FeltVisitor)
 }
 
+// Formats a Felt as a 0x-prefixed hex string, the format read by deserialize_felt_hex
+pub(crate) fn felt_to_hex(value: &Felt) -> String {
+    format!("0x{}", value.to_str_radix(16))
+}
+
+pub fn serialize_felt_hex<S: Serializer>(value: &Felt, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&felt_to_hex(value))
+}
+
 pub fn deserialize_array_of_bigint_hex<'de, D: Deserializer<'de>>(
     d: D,
 ) -> Result<Vec<MaybeRelocatable>, D::Error> {
@@ -316,11 +352,48 @@ pub fn deserialize_program(
     entrypoint: Option<&str>,
 ) -> Result<Program, ProgramError> {
     let program_json: ProgramJson = deserialize_program_json(reader)?;
+    parse_program_json(program_json, entrypoint)
+}
 
+/// Deserializes a compiled program held in memory, e.g. one embedded with `include_bytes!`.
+pub fn deserialize_and_parse_program(
+    bytes: &[u8],
+    entrypoint: Option<&str>,
+) -> Result<Program, ProgramError> {
+    let program_json: ProgramJson = serde_json::from_slice(bytes)?;
+    parse_program_json(program_json, entrypoint)
+}
+
+/// Builds a `Program` from its compiled JSON. Without an entrypoint the program is loaded as
+/// a library: `main` is left unset and its functions can be run with `run_from_entrypoint`.
+pub fn parse_program_json(
+    program_json: ProgramJson,
+    entrypoint: Option<&str>,
+) -> Result<Program, ProgramError> {
     if PRIME_STR != program_json.prime {
         return Err(ProgramError::PrimeDiffers(program_json.prime));
     }
+    build_program(program_json, entrypoint)
+}
+
+/// Builds a `Program` from its compiled JSON like `parse_program_json`, but accepts programs
+/// compiled for any prime, which is kept in `Program::prime`.
+///
+/// **Warning**: this doesn't change the field of the VM. Every felt operation is still done
+/// modulo `felt::PRIME_STR`, so a program relying on the arithmetic of its own prime, e.g.
+/// one that checks a value against it, runs with wrong results. Meant for research use.
+#[cfg(feature = "with_prime")]
+pub fn parse_program_json_with_prime(
+    program_json: ProgramJson,
+    entrypoint: Option<&str>,
+) -> Result<Program, ProgramError> {
+    build_program(program_json, entrypoint)
+}
 
+fn build_program(
+    program_json: ProgramJson,
+    entrypoint: Option<&str>,
+) -> Result<Program, ProgramError> {
     let entrypoint_pc = match entrypoint {
         Some(entrypoint) => match program_json
             .identifiers
@@ -342,27 +415,14 @@ pub fn deserialize_program(
     };
 
     Ok(Program {
-        builtins: program_json.builtins,
-        prime: PRIME_STR.to_string(),
+        builtins: normalize_builtin_names(program_json.builtins)?,
+        prime: program_json.prime,
         data: program_json.data,
-        constants: {
-            let mut constants = HashMap::new();
-            for (key, value) in program_json.identifiers.iter() {
-                if value.type_.as_deref() == Some("const") {
-                    let value = value
-                        .value
-                        .clone()
-                        .ok_or_else(|| ProgramError::ConstWithoutValue(key.to_owned()))?;
-                    constants.insert(key.to_owned(), value);
-                }
-            }
-
-            constants
-        },
+        constants: extract_constants(&program_json.identifiers)?,
         main: entrypoint_pc,
         start,
         end,
-        hints: program_json.hints,
+        hints: Arc::new(program_json.hints),
         reference_manager: program_json.reference_manager,
         identifiers: program_json.identifiers,
         error_message_attributes: program_json
@@ -373,6 +433,7 @@ pub fn deserialize_program(
         instruction_locations: program_json
             .debug_info
             .map(|debug_info| debug_info.instruction_locations),
+        compiler_version: program_json.compiler_version,
     })
 }
 
@@ -592,6 +653,7 @@ mod tests {
                         offset1: OffsetValue::Reference(Register::FP, -4, false),
                         offset2: OffsetValue::Value(0),
                         dereference: true,
+                        inner_dereference: false,
                         value_type: "felt".to_string(),
                     },
                 },
@@ -605,6 +667,7 @@ mod tests {
                         offset1: OffsetValue::Reference(Register::FP, -3, false),
                         offset2: OffsetValue::Value(0),
                         dereference: true,
+                        inner_dereference: false,
                         value_type: "felt".to_string(),
                     },
                 },
@@ -618,6 +681,7 @@ mod tests {
                         offset1: OffsetValue::Reference(Register::FP, -3, true),
                         offset2: OffsetValue::Immediate(Felt::new(2)),
                         dereference: false,
+                        inner_dereference: false,
                         value_type: "felt".to_string(),
                     },
                 },
@@ -631,6 +695,7 @@ mod tests {
                         offset1: OffsetValue::Reference(Register::FP, 0, false),
                         offset2: OffsetValue::Value(0),
                         dereference: true,
+                        inner_dereference: false,
                         value_type: "felt*".to_string(),
                     },
                 },
@@ -778,7 +843,7 @@ mod tests {
         assert_eq!(program.builtins, builtins);
         assert_eq!(program.data, data);
         assert_eq!(program.main, Some(0));
-        assert_eq!(program.hints, hints);
+        assert_eq!(*program.hints, hints);
     }
 
     /// Deserialize a program without an entrypoint.
@@ -841,7 +906,107 @@ mod tests {
         assert_eq!(program.builtins, builtins);
         assert_eq!(program.data, data);
         assert_eq!(program.main, None);
-        assert_eq!(program.hints, hints);
+        assert_eq!(*program.hints, hints);
+    }
+
+    #[test]
+    fn deserialize_program_compiled_without_version() {
+        let file =
+            File::open("cairo_programs/manually_compiled/program_cairo_lang_0_9.json").unwrap();
+        let program = deserialize_program(BufReader::new(file), Some("main")).unwrap();
+
+        assert_eq!(program.builtins, vec!["output", "range_check"]);
+        assert_eq!(program.data.len(), 3);
+        assert_eq!(program.main, Some(0));
+        assert_eq!(program.reference_manager.references.len(), 2);
+        assert_eq!(
+            program.constants,
+            HashMap::from([(String::from("__main__.main.SIZEOF_LOCALS"), Felt::zero())])
+        );
+        assert_eq!(program.compiler_version, None);
+    }
+
+    #[test]
+    fn deserialize_program_compiled_with_version() {
+        let file =
+            File::open("cairo_programs/manually_compiled/program_cairo_lang_0_11.json").unwrap();
+        let program = deserialize_program(BufReader::new(file), Some("main")).unwrap();
+
+        assert_eq!(program.builtins, vec!["output", "range_check"]);
+        assert_eq!(program.data.len(), 6);
+        assert_eq!(program.main, Some(0));
+        assert_eq!(program.compiler_version.as_deref(), Some("0.11.0.2"));
+    }
+
+    #[test]
+    fn deserialize_program_with_complex_references() {
+        let file =
+            File::open("cairo_programs/manually_compiled/program_with_complex_references.json")
+                .unwrap();
+        let program = deserialize_program(BufReader::new(file), Some("main")).unwrap();
+
+        let value_addresses: Vec<ValueAddress> = program
+            .reference_manager
+            .references
+            .into_iter()
+            .map(|reference| reference.value_address)
+            .collect();
+        assert_eq!(
+            value_addresses,
+            vec![
+                ValueAddress {
+                    offset1: OffsetValue::Reference(Register::FP, -3, true),
+                    offset2: OffsetValue::Value(2),
+                    dereference: true,
+                    inner_dereference: true,
+                    value_type: "felt".to_string(),
+                },
+                ValueAddress {
+                    offset1: OffsetValue::Reference(Register::AP, -1, true),
+                    offset2: OffsetValue::Value(1),
+                    dereference: false,
+                    inner_dereference: true,
+                    value_type: "felt".to_string(),
+                },
+                ValueAddress {
+                    offset1: OffsetValue::Reference(Register::AP, -2, true),
+                    offset2: OffsetValue::Immediate(Felt::new(5)),
+                    dereference: false,
+                    inner_dereference: false,
+                    value_type: "felt".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn deserialize_program_with_unsupported_builtin() {
+        let file = File::open("cairo_programs/manually_compiled/unsupported_builtin_program.json")
+            .unwrap();
+        let result = deserialize_program(BufReader::new(file), Some("main"));
+
+        assert!(
+            matches!(result, Err(ProgramError::UnsupportedBuiltin(name)) if name == "segment_arena")
+        );
+    }
+
+    #[test]
+    fn deserialize_program_with_different_prime() {
+        let file = File::open("cairo_programs/manually_compiled/wrong_prime_program.json").unwrap();
+        let result = deserialize_program(BufReader::new(file), Some("main"));
+
+        assert!(matches!(result, Err(ProgramError::PrimeDiffers(prime)) if prime == "0x7fffffff"));
+    }
+
+    #[cfg(feature = "with_prime")]
+    #[test]
+    fn parse_program_json_with_different_prime() {
+        let file = File::open("cairo_programs/manually_compiled/wrong_prime_program.json").unwrap();
+        let program_json = deserialize_program_json(BufReader::new(file)).unwrap();
+        let program = parse_program_json_with_prime(program_json, Some("main")).unwrap();
+
+        assert_eq!(program.prime, "0x7fffffff");
+        assert_eq!(program.main, Some(0));
     }
 
     #[test]
@@ -1044,12 +1209,26 @@ mod tests {
                 start_pc: 379,
                 end_pc: 381,
                 value: String::from("SafeUint256: addition overflow"),
+                flow_tracking_data: Some(FlowTrackingData {
+                    ap_tracking: ApTracking {
+                        group: 14,
+                        offset: 35,
+                    },
+                    reference_ids: HashMap::new(),
+                }),
             },
             Attribute {
                 name: String::from("error_message"),
                 start_pc: 402,
                 end_pc: 404,
                 value: String::from("SafeUint256: subtraction overflow"),
+                flow_tracking_data: Some(FlowTrackingData {
+                    ap_tracking: ApTracking {
+                        group: 15,
+                        offset: 60,
+                    },
+                    reference_ids: HashMap::new(),
+                }),
             },
         ];
 
@@ -1263,4 +1442,33 @@ mod tests {
 
         assert_eq!(program_json.debug_info, Some(debug_info));
     }
+
+    #[test]
+    fn deserialize_constant_in_field_range() {
+        let identifier: Identifier = serde_json::from_str(
+            r#"{"type": "const", "value": -3618502788666131213697322783095070105623107215331596699973092056135872020481}"#,
+        )
+        .unwrap();
+        assert_eq!(identifier.value, Some(Felt::zero()));
+
+        let identifier: Identifier = serde_json::from_str(
+            r#"{"type": "const", "value": 3618502788666131213697322783095070105623107215331596699973092056135872020480}"#,
+        )
+        .unwrap();
+        assert_eq!(identifier.value, Some(Felt::zero() - Felt::new(1)));
+    }
+
+    #[test]
+    fn deserialize_constant_out_of_field_range() {
+        for value in [
+            "3618502788666131213697322783095070105623107215331596699973092056135872020481",
+            "-3618502788666131213697322783095070105623107215331596699973092056135872020482",
+        ] {
+            let json = format!(r#"{{"type": "const", "value": {value}}}"#);
+            let error = serde_json::from_str::<Identifier>(&json).unwrap_err();
+            assert!(error
+                .to_string()
+                .starts_with(&format!("Constant value {value} is out of the field range")));
+        }
+    }
 }
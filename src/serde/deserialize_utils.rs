@@ -92,6 +92,16 @@ fn take_cast_first_arg(input: &str) -> IResult<&str, &str> {
     take_until(",")(rem_input).map(|(rem_input, res)| (res, rem_input))
 }
 
+// Checks if the first argument of the cast is dereferenced as a whole, as in
+// `cast([[fp + 2] + 3], felt*)`. This is used to set the `inner_dereference` field of
+// ValueAddress. A single dereference of a register, like `[fp + 2]`, is left to the offset parsers.
+fn inner_brackets(input: &str) -> IResult<&str, bool> {
+    match delimited(tag("["), take_until_unbalanced('[', ']'), tag("]"))(input) {
+        Ok(("", inner)) if inner.contains('[') => Ok((inner, true)),
+        _ => Ok((input, false)),
+    }
+}
+
 fn register(input: &str) -> IResult<&str, Option<Register>> {
     opt(alt((
         value(Register::AP, tag("ap")),
@@ -163,12 +173,14 @@ fn no_inner_dereference(input: &str) -> IResult<&str, OffsetValue> {
 }
 
 pub fn parse_value(input: &str) -> IResult<&str, ValueAddress> {
-    let (rem_input, (dereference, second_arg, fst_offset, snd_offset)) = tuple((
-        outer_brackets,
-        take_cast_first_arg,
-        opt(alt((inner_dereference, no_inner_dereference))),
-        opt(alt((inner_dereference, no_inner_dereference))),
-    ))(input)?;
+    let (rem_input, (dereference, second_arg, inner_dereference, fst_offset, snd_offset)) =
+        tuple((
+            outer_brackets,
+            take_cast_first_arg,
+            inner_brackets,
+            opt(alt((inner_dereference, no_inner_dereference))),
+            opt(alt((inner_dereference, no_inner_dereference))),
+        ))(input)?;
 
     let (indirection_level, (_, struct_)) =
         tuple((tag(", "), take_till(|c: char| c == '*')))(second_arg)?;
@@ -182,29 +194,32 @@ pub fn parse_value(input: &str) -> IResult<&str, ValueAddress> {
     let fst_offset = fst_offset.unwrap_or(OffsetValue::Value(0));
     let snd_offset = snd_offset.unwrap_or(OffsetValue::Value(0));
 
-    // cast to big int if necessary
-    let (offset1, offset2) = if struct_ == "felt" && indirection_level.is_empty() {
-        let offset1 = match fst_offset {
-            OffsetValue::Immediate(imm) => OffsetValue::Immediate(imm),
-            OffsetValue::Value(val) => OffsetValue::Immediate(Felt::new(val)),
-            OffsetValue::Reference(reg, val, refe) => OffsetValue::Reference(reg, val, refe),
-        };
-
-        let offset2 = match snd_offset {
-            OffsetValue::Immediate(imm) => OffsetValue::Immediate(imm),
-            OffsetValue::Value(val) => OffsetValue::Immediate(Felt::new(val)),
-            OffsetValue::Reference(reg, val, refe) => OffsetValue::Reference(reg, val, refe),
+    // cast to big int if necessary. The offsets of a double dereference make up an address,
+    // whatever the type of the value stored there.
+    let (offset1, offset2) =
+        if struct_ == "felt" && indirection_level.is_empty() && !inner_dereference {
+            let offset1 = match fst_offset {
+                OffsetValue::Immediate(imm) => OffsetValue::Immediate(imm),
+                OffsetValue::Value(val) => OffsetValue::Immediate(Felt::new(val)),
+                OffsetValue::Reference(reg, val, refe) => OffsetValue::Reference(reg, val, refe),
+            };
+
+            let offset2 = match snd_offset {
+                OffsetValue::Immediate(imm) => OffsetValue::Immediate(imm),
+                OffsetValue::Value(val) => OffsetValue::Immediate(Felt::new(val)),
+                OffsetValue::Reference(reg, val, refe) => OffsetValue::Reference(reg, val, refe),
+            };
+
+            (offset1, offset2)
+        } else {
+            (fst_offset, snd_offset)
         };
 
-        (offset1, offset2)
-    } else {
-        (fst_offset, snd_offset)
-    };
-
     let value_address = ValueAddress {
         offset1,
         offset2,
         dereference,
+        inner_dereference,
         value_type: type_,
     };
 
@@ -313,6 +328,7 @@ mod tests {
                     offset2: OffsetValue::Value(2),
                     offset1: OffsetValue::Reference(Register::FP, -1_i32, true),
                     dereference: true,
+                    inner_dereference: false,
                     value_type: "felt".to_string(),
                 }
             ))
@@ -332,6 +348,7 @@ mod tests {
                     offset1: OffsetValue::Reference(Register::AP, 2_i32, false),
                     offset2: OffsetValue::Value(0),
                     dereference: false,
+                    inner_dereference: false,
                     value_type: "felt".to_string(),
                 }
             ))
@@ -350,6 +367,7 @@ mod tests {
                     offset1: OffsetValue::Value(825323),
                     offset2: OffsetValue::Value(0),
                     dereference: false,
+                    inner_dereference: false,
                     value_type: "felt".to_string(),
                 }
             ))
@@ -369,6 +387,7 @@ mod tests {
                     offset1: OffsetValue::Reference(Register::AP, 0_i32, false),
                     offset2: OffsetValue::Value(-1),
                     dereference: true,
+                    inner_dereference: false,
                     value_type: "felt".to_string(),
                 }
             ))
@@ -388,6 +407,7 @@ mod tests {
                     offset1: OffsetValue::Reference(Register::AP, 0_i32, true),
                     offset2: OffsetValue::Value(1),
                     dereference: true,
+                    inner_dereference: false,
                     value_type: "__main__.felt".to_string(),
                 }
             ))
@@ -407,6 +427,7 @@ mod tests {
                     offset1: OffsetValue::Reference(Register::AP, 0_i32, true),
                     offset2: OffsetValue::Immediate(Felt::one()),
                     dereference: true,
+                    inner_dereference: false,
                     value_type: "felt".to_string(),
                 }
             ))
@@ -426,6 +447,7 @@ mod tests {
                     offset1: OffsetValue::Reference(Register::AP, 1_i32, true),
                     offset2: OffsetValue::Value(1),
                     dereference: true,
+                    inner_dereference: false,
                     value_type: "felt".to_string(),
                 }
             ))
@@ -445,6 +467,7 @@ mod tests {
                     offset1: OffsetValue::Reference(Register::AP, 0_i32, true),
                     offset2: OffsetValue::Reference(Register::FP, 1_i32, true),
                     dereference: true,
+                    inner_dereference: false,
                     value_type: "__main__.felt".to_string(),
                 }
             ))
@@ -464,6 +487,7 @@ mod tests {
                     offset1: OffsetValue::Reference(Register::AP, 1_i32, true),
                     offset2: OffsetValue::Reference(Register::FP, 1_i32, true),
                     dereference: true,
+                    inner_dereference: false,
                     value_type: "__main__.felt".to_string(),
                 }
             ))
@@ -483,6 +507,7 @@ mod tests {
                     offset1: OffsetValue::Immediate(Felt::new(825323_i32)),
                     offset2: OffsetValue::Immediate(Felt::zero()),
                     dereference: false,
+                    inner_dereference: false,
                     value_type: "felt".to_string(),
                 }
             ))
@@ -502,6 +527,7 @@ mod tests {
                     offset1: OffsetValue::Reference(Register::AP, 0_i32, true),
                     offset2: OffsetValue::Value(1),
                     dereference: true,
+                    inner_dereference: false,
                     value_type: "starkware.cairo.common.cairo_secp.ec.EcPoint".to_string(),
                 }
             ))
@@ -521,6 +547,7 @@ mod tests {
                     offset1: OffsetValue::Reference(Register::AP, 0_i32, true),
                     offset2: OffsetValue::Value(1),
                     dereference: true,
+                    inner_dereference: false,
                     value_type: "starkware.cairo.common.cairo_secp.ec.EcPoint*".to_string(),
                 }
             ))
@@ -540,6 +567,7 @@ mod tests {
                     offset1: OffsetValue::Reference(Register::AP, 0_i32, true),
                     offset2: OffsetValue::Reference(Register::AP, 0_i32, true),
                     dereference: true,
+                    inner_dereference: false,
                     value_type: "felt".to_string(),
                 }
             ))
@@ -559,6 +587,75 @@ mod tests {
                     offset1: OffsetValue::Reference(Register::AP, 1_i32, true),
                     offset2: OffsetValue::Reference(Register::AP, 2_i32, true),
                     dereference: true,
+                    inner_dereference: false,
+                    value_type: "felt".to_string(),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn inner_brackets_test() {
+        assert_eq!(inner_brackets("[[fp + 2] + 3]"), Ok(("[fp + 2] + 3", true)));
+        assert_eq!(inner_brackets("[fp + 2]"), Ok(("[fp + 2]", false)));
+        assert_eq!(inner_brackets("[fp] + [ap]"), Ok(("[fp] + [ap]", false)));
+        assert_eq!(inner_brackets("ap + 1"), Ok(("ap + 1", false)));
+    }
+
+    #[test]
+    fn parse_value_with_double_dereference() {
+        let value = "[cast([[fp + 2] + 3], felt*)]";
+        let parsed = parse_value(value);
+
+        assert_eq!(
+            parsed,
+            Ok((
+                "",
+                ValueAddress {
+                    offset1: OffsetValue::Reference(Register::FP, 2_i32, true),
+                    offset2: OffsetValue::Value(3),
+                    dereference: true,
+                    inner_dereference: true,
+                    value_type: "felt".to_string(),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_value_with_double_dereference_to_felt() {
+        let value = "cast([[ap + (-1)] + 2], felt)";
+        let parsed = parse_value(value);
+
+        assert_eq!(
+            parsed,
+            Ok((
+                "",
+                ValueAddress {
+                    offset1: OffsetValue::Reference(Register::AP, -1_i32, true),
+                    offset2: OffsetValue::Value(2),
+                    dereference: false,
+                    inner_dereference: true,
+                    value_type: "felt".to_string(),
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn parse_value_with_immediate_addition() {
+        let value = "cast([fp + (-3)] + 2, felt)";
+        let parsed = parse_value(value);
+
+        assert_eq!(
+            parsed,
+            Ok((
+                "",
+                ValueAddress {
+                    offset1: OffsetValue::Reference(Register::FP, -3_i32, true),
+                    offset2: OffsetValue::Immediate(Felt::new(2)),
+                    dereference: false,
+                    inner_dereference: false,
                     value_type: "felt".to_string(),
                 }
             ))
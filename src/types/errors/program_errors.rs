@@ -1,3 +1,4 @@
+use crate::types::program::BUILTIN_NAMES;
 use felt::PRIME_STR;
 use std::io;
 use thiserror::Error;
@@ -14,6 +15,10 @@ pub enum ProgramError {
     ConstWithoutValue(String),
     #[error("Expected prime {PRIME_STR}, got {0}")]
     PrimeDiffers(String),
+    #[error("Pc {0} is out of bounds for a program of length {1}")]
+    PcOutOfBounds(usize, usize),
+    #[error("Unsupported builtin {0}, the supported builtins are: {}", BUILTIN_NAMES.join(", "))]
+    UnsupportedBuiltin(String),
 }
 
 #[cfg(test)]
@@ -26,4 +31,13 @@ mod tests {
         let formatted_error = format!("{}", error);
         assert_eq!(formatted_error, "Entrypoint my_function not found");
     }
+
+    #[test]
+    fn format_unsupported_builtin_error() {
+        let error = ProgramError::UnsupportedBuiltin(String::from("sha256_builtin"));
+        assert_eq!(
+            error.to_string(),
+            "Unsupported builtin sha256_builtin, the supported builtins are: output, pedersen, range_check, ecdsa, bitwise, ec_op, keccak, poseidon"
+        );
+    }
 }
@@ -3,7 +3,12 @@ use crate::{
     hint_processor::builtin_hint_processor::dict_manager::DictManager,
     vm::errors::{exec_scope_errors::ExecScopeError, hint_errors::HintError},
 };
-use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+    any::{type_name, Any},
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+};
 
 pub struct ExecutionScopes {
     pub data: Vec<HashMap<String, Box<dyn Any>>>,
@@ -61,35 +66,21 @@ impl ExecutionScopes {
 
     ///Returns the value in the current execution scope that matches the name and is of the given generic type
     pub fn get<T: Any + Clone>(&self, name: &str) -> Result<T, HintError> {
-        let mut val: Option<T> = None;
-        if let Some(variable) = self.get_local_variables()?.get(name) {
-            if let Some(int) = variable.downcast_ref::<T>() {
-                val = Some(int.clone());
-            }
-        }
-        val.ok_or_else(|| HintError::VariableNotInScopeError(name.to_string()))
+        self.get_ref::<T>(name).cloned()
     }
 
     ///Returns a reference to the value in the current execution scope that matches the name and is of the given generic type
     pub fn get_ref<T: Any>(&self, name: &str) -> Result<&T, HintError> {
-        let mut val: Option<&T> = None;
-        if let Some(variable) = self.get_local_variables()?.get(name) {
-            if let Some(int) = variable.downcast_ref::<T>() {
-                val = Some(int);
-            }
-        }
-        val.ok_or_else(|| HintError::VariableNotInScopeError(name.to_string()))
+        self.get_any_boxed_ref(name)?
+            .downcast_ref::<T>()
+            .ok_or_else(|| wrong_scope_type::<T>(name))
     }
 
     ///Returns a mutable reference to the value in the current execution scope that matches the name and is of the given generic type
     pub fn get_mut_ref<T: Any>(&mut self, name: &str) -> Result<&mut T, HintError> {
-        let mut val: Option<&mut T> = None;
-        if let Some(variable) = self.get_local_variables_mut()?.get_mut(name) {
-            if let Some(int) = variable.downcast_mut::<T>() {
-                val = Some(int);
-            }
-        }
-        val.ok_or_else(|| HintError::VariableNotInScopeError(name.to_string()))
+        self.get_any_boxed_mut(name)?
+            .downcast_mut::<T>()
+            .ok_or_else(|| wrong_scope_type::<T>(name))
     }
 
     ///Returns the value in the current execution scope that matches the name
@@ -110,46 +101,22 @@ impl ExecutionScopes {
 
     ///Returns the value in the current execution scope that matches the name and is of type List
     pub fn get_list<T: Any + Clone>(&self, name: &str) -> Result<Vec<T>, HintError> {
-        let mut val: Option<Vec<T>> = None;
-        if let Some(variable) = self.get_local_variables()?.get(name) {
-            if let Some(list) = variable.downcast_ref::<Vec<T>>() {
-                val = Some(list.clone());
-            }
-        }
-        val.ok_or_else(|| HintError::VariableNotInScopeError(name.to_string()))
+        self.get::<Vec<T>>(name)
     }
 
     ///Returns a reference to the value in the current execution scope that matches the name and is of type List
     pub fn get_list_ref<T: Any>(&self, name: &str) -> Result<&Vec<T>, HintError> {
-        let mut val: Option<&Vec<T>> = None;
-        if let Some(variable) = self.get_local_variables()?.get(name) {
-            if let Some(list) = variable.downcast_ref::<Vec<T>>() {
-                val = Some(list);
-            }
-        }
-        val.ok_or_else(|| HintError::VariableNotInScopeError(name.to_string()))
+        self.get_ref::<Vec<T>>(name)
     }
 
     ///Returns a mutable reference to the value in the current execution scope that matches the name and is of type List
     pub fn get_mut_list_ref<T: Any>(&mut self, name: &str) -> Result<&mut Vec<T>, HintError> {
-        let mut val: Option<&mut Vec<T>> = None;
-        if let Some(variable) = self.get_local_variables_mut()?.get_mut(name) {
-            if let Some(list) = variable.downcast_mut::<Vec<T>>() {
-                val = Some(list);
-            }
-        }
-        val.ok_or_else(|| HintError::VariableNotInScopeError(name.to_string()))
+        self.get_mut_ref::<Vec<T>>(name)
     }
 
     ///Returns the value in the dict manager
     pub fn get_dict_manager(&self) -> Result<Rc<RefCell<DictManager>>, HintError> {
-        let mut val: Option<Rc<RefCell<DictManager>>> = None;
-        if let Some(variable) = self.get_local_variables()?.get("dict_manager") {
-            if let Some(dict_manager) = variable.downcast_ref::<Rc<RefCell<DictManager>>>() {
-                val = Some(dict_manager.clone());
-            }
-        }
-        val.ok_or_else(|| HintError::VariableNotInScopeError("dict_manager".to_string()))
+        self.get::<Rc<RefCell<DictManager>>>("dict_manager")
     }
 
     ///Returns a mutable reference to the value in the current execution scope that matches the name and is of the given type
@@ -157,13 +124,7 @@ impl ExecutionScopes {
         &mut self,
         name: &str,
     ) -> Result<&mut HashMap<K, V>, HintError> {
-        let mut val: Option<&mut HashMap<K, V>> = None;
-        if let Some(variable) = self.get_local_variables_mut()?.get_mut(name) {
-            if let Some(dict) = variable.downcast_mut::<HashMap<K, V>>() {
-                val = Some(dict);
-            }
-        }
-        val.ok_or_else(|| HintError::VariableNotInScopeError(name.to_string()))
+        self.get_mut_ref::<HashMap<K, V>>(name)
     }
 
     ///Inserts the boxed value into the current scope
@@ -177,6 +138,11 @@ impl ExecutionScopes {
     }
 }
 
+// Error for a scope variable that exists but can't be downcasted to T
+fn wrong_scope_type<T: Any>(name: &str) -> HintError {
+    HintError::WrongScopeTypeError(name.to_string(), type_name::<T>().to_string())
+}
+
 impl Default for ExecutionScopes {
     fn default() -> Self {
         Self::new()
@@ -447,4 +413,45 @@ mod tests {
         assert!(scopes.get_any_boxed_mut("no_variable").is_err());
         assert!(scopes.get_any_boxed_ref("no_variable").is_err());
     }
+
+    #[test]
+    fn get_wrong_type_test() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_value("u64", 9_u64);
+
+        let wrong_type = |expected: &str| {
+            HintError::WrongScopeTypeError("u64".to_string(), expected.to_string())
+        };
+        assert_eq!(
+            scopes.get::<Felt>("u64"),
+            Err(wrong_type(type_name::<Felt>()))
+        );
+        assert_eq!(
+            scopes.get_ref::<Felt>("u64"),
+            Err(wrong_type(type_name::<Felt>()))
+        );
+        assert_eq!(
+            scopes.get_mut_ref::<Felt>("u64"),
+            Err(wrong_type(type_name::<Felt>()))
+        );
+        assert_eq!(
+            scopes.get_list_ref::<u64>("u64"),
+            Err(wrong_type(type_name::<Vec<u64>>()))
+        );
+        assert_eq!(
+            scopes.get_mut_dict_ref::<u64, u64>("u64"),
+            Err(wrong_type(type_name::<HashMap<u64, u64>>()))
+        );
+    }
+
+    #[test]
+    fn wrong_scope_type_error_message() {
+        let mut scopes = ExecutionScopes::new();
+        scopes.insert_value("n", Felt::one());
+
+        assert_eq!(
+            scopes.get::<u64>("n").unwrap_err().to_string(),
+            "Variable n in current execution scope is not of type u64"
+        );
+    }
 }
@@ -1,8 +1,10 @@
 use super::{
     bitwise_instance_def::BitwiseInstanceDef, ec_op_instance_def::EcOpInstanceDef,
     ecdsa_instance_def::EcdsaInstanceDef, keccak_instance_def::KeccakInstanceDef,
-    pedersen_instance_def::PedersenInstanceDef, range_check_instance_def::RangeCheckInstanceDef,
+    pedersen_instance_def::PedersenInstanceDef, poseidon_instance_def::PoseidonInstanceDef,
+    range_check_instance_def::RangeCheckInstanceDef,
 };
+use crate::types::layout::DynamicLayoutParams;
 
 #[derive(Debug, PartialEq)]
 pub(crate) struct BuiltinsInstanceDef {
@@ -13,6 +15,7 @@ pub(crate) struct BuiltinsInstanceDef {
     pub(crate) bitwise: Option<BitwiseInstanceDef>,
     pub(crate) ec_op: Option<EcOpInstanceDef>,
     pub(crate) keccak: Option<KeccakInstanceDef>,
+    pub(crate) poseidon: Option<PoseidonInstanceDef>,
 }
 
 impl BuiltinsInstanceDef {
@@ -25,6 +28,7 @@ impl BuiltinsInstanceDef {
             bitwise: None,
             ec_op: None,
             keccak: None,
+            poseidon: None,
         }
     }
 
@@ -37,6 +41,7 @@ impl BuiltinsInstanceDef {
             bitwise: None,
             ec_op: None,
             keccak: None,
+            poseidon: None,
         }
     }
 
@@ -49,6 +54,7 @@ impl BuiltinsInstanceDef {
             bitwise: None,
             ec_op: None,
             keccak: None,
+            poseidon: None,
         }
     }
 
@@ -61,6 +67,7 @@ impl BuiltinsInstanceDef {
             bitwise: Some(BitwiseInstanceDef::new(64)),
             ec_op: Some(EcOpInstanceDef::new(1024)),
             keccak: None,
+            poseidon: None,
         }
     }
 
@@ -73,6 +80,7 @@ impl BuiltinsInstanceDef {
             bitwise: Some(BitwiseInstanceDef::new(8)),
             ec_op: None,
             keccak: None,
+            poseidon: None,
         }
     }
 
@@ -85,6 +93,7 @@ impl BuiltinsInstanceDef {
             bitwise: Some(BitwiseInstanceDef::new(16)),
             ec_op: None,
             keccak: Some(KeccakInstanceDef::new(2048)),
+            poseidon: None,
         }
     }
 
@@ -97,6 +106,24 @@ impl BuiltinsInstanceDef {
             bitwise: Some(BitwiseInstanceDef::default()),
             ec_op: Some(EcOpInstanceDef::default()),
             keccak: None,
+            poseidon: Some(PoseidonInstanceDef::default()),
+        }
+    }
+
+    pub(crate) fn dynamic(params: &DynamicLayoutParams) -> BuiltinsInstanceDef {
+        BuiltinsInstanceDef {
+            _output: params.output,
+            pedersen: params
+                .pedersen_ratio
+                .map(|ratio| PedersenInstanceDef::new(ratio, 1)),
+            range_check: params
+                .range_check_ratio
+                .map(|ratio| RangeCheckInstanceDef::new(ratio, 8)),
+            _ecdsa: params.ecdsa_ratio.map(EcdsaInstanceDef::new),
+            bitwise: params.bitwise_ratio.map(BitwiseInstanceDef::new),
+            ec_op: params.ec_op_ratio.map(EcOpInstanceDef::new),
+            keccak: params.keccak_ratio.map(KeccakInstanceDef::new),
+            poseidon: params.poseidon_ratio.map(PoseidonInstanceDef::new),
         }
     }
 }
@@ -114,6 +141,7 @@ mod tests {
         assert!(builtins._ecdsa.is_none());
         assert!(builtins.bitwise.is_none());
         assert!(builtins.ec_op.is_none());
+        assert!(builtins.poseidon.is_none());
     }
 
     #[test]
@@ -125,6 +153,7 @@ mod tests {
         assert!(builtins._ecdsa.is_some());
         assert!(builtins.bitwise.is_none());
         assert!(builtins.ec_op.is_none());
+        assert!(builtins.poseidon.is_none());
     }
 
     #[test]
@@ -136,6 +165,7 @@ mod tests {
         assert!(builtins._ecdsa.is_some());
         assert!(builtins.bitwise.is_none());
         assert!(builtins.ec_op.is_none());
+        assert!(builtins.poseidon.is_none());
     }
 
     #[test]
@@ -147,6 +177,7 @@ mod tests {
         assert!(builtins._ecdsa.is_some());
         assert!(builtins.bitwise.is_some());
         assert!(builtins.ec_op.is_some());
+        assert!(builtins.poseidon.is_none());
     }
 
     #[test]
@@ -158,6 +189,7 @@ mod tests {
         assert!(builtins._ecdsa.is_some());
         assert!(builtins.bitwise.is_some());
         assert!(builtins.ec_op.is_none());
+        assert!(builtins.poseidon.is_none());
     }
 
     #[test]
@@ -169,6 +201,7 @@ mod tests {
         assert!(builtins._ecdsa.is_none());
         assert!(builtins.bitwise.is_some());
         assert!(builtins.ec_op.is_none());
+        assert!(builtins.poseidon.is_none());
     }
 
     #[test]
@@ -180,5 +213,29 @@ mod tests {
         assert!(builtins._ecdsa.is_some());
         assert!(builtins.bitwise.is_some());
         assert!(builtins.ec_op.is_some());
+        assert!(builtins.poseidon.is_some());
+    }
+
+    #[test]
+    fn get_builtins_dynamic() {
+        let builtins = BuiltinsInstanceDef::dynamic(&DynamicLayoutParams {
+            output: false,
+            pedersen_ratio: Some(5),
+            range_check_ratio: Some(3),
+            ecdsa_ratio: None,
+            bitwise_ratio: Some(7),
+            ec_op_ratio: None,
+            keccak_ratio: None,
+            poseidon_ratio: None,
+            memory_units_per_step: 16,
+        });
+        assert!(!builtins._output);
+        assert_eq!(builtins.pedersen, Some(PedersenInstanceDef::new(5, 1)));
+        assert_eq!(builtins.range_check, Some(RangeCheckInstanceDef::new(3, 8)));
+        assert!(builtins._ecdsa.is_none());
+        assert_eq!(builtins.bitwise, Some(BitwiseInstanceDef::new(7)));
+        assert!(builtins.ec_op.is_none());
+        assert!(builtins.keccak.is_none());
+        assert!(builtins.poseidon.is_none());
     }
 }
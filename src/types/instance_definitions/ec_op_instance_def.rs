@@ -1,11 +1,23 @@
+use felt::{Felt, NewFelt};
+use num_traits::One;
+
 pub(crate) const CELLS_PER_EC_OP: u32 = 7;
 pub(crate) const INPUT_CELLS_PER_EC_OP: u32 = 5;
 
+// Beta coefficient of the STARK curve, y^2 = x^3 + alpha * x + beta
+fn stark_curve_beta() -> Felt {
+    let beta_low = Felt::new(0x609ad26c15c915c1f4cdfcb99cee9e89_u128);
+    let beta_high = Felt::new(0x6f21413efbe40de150e596d72f7a8c5_u128);
+    (beta_high << 128_usize) + beta_low
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub(crate) struct EcOpInstanceDef {
     pub(crate) ratio: u32,
     pub(crate) scalar_height: u32,
     pub(crate) _scalar_bits: u32,
+    pub(crate) alpha: Felt,
+    pub(crate) beta: Felt,
 }
 
 impl EcOpInstanceDef {
@@ -14,6 +26,8 @@ impl EcOpInstanceDef {
             ratio: 256,
             scalar_height: 256,
             _scalar_bits: 252,
+            alpha: Felt::one(),
+            beta: stark_curve_beta(),
         }
     }
 
@@ -22,6 +36,8 @@ impl EcOpInstanceDef {
             ratio,
             scalar_height: 256,
             _scalar_bits: 252,
+            alpha: Felt::one(),
+            beta: stark_curve_beta(),
         }
     }
 
@@ -56,6 +72,8 @@ mod tests {
             ratio: 8,
             scalar_height: 256,
             _scalar_bits: 252,
+            alpha: Felt::one(),
+            beta: stark_curve_beta(),
         };
         assert_eq!(EcOpInstanceDef::new(8), builtin_instance);
     }
@@ -66,6 +84,8 @@ mod tests {
             ratio: 256,
             scalar_height: 256,
             _scalar_bits: 252,
+            alpha: Felt::one(),
+            beta: stark_curve_beta(),
         };
         assert_eq!(EcOpInstanceDef::default(), builtin_instance);
     }
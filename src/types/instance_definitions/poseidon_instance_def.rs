@@ -0,0 +1,56 @@
+pub(crate) const CELLS_PER_POSEIDON: u32 = 6;
+pub(crate) const INPUT_CELLS_PER_POSEIDON: u32 = 3;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct PoseidonInstanceDef {
+    pub(crate) ratio: u32,
+}
+
+impl Default for PoseidonInstanceDef {
+    fn default() -> Self {
+        PoseidonInstanceDef { ratio: 256 }
+    }
+}
+
+impl PoseidonInstanceDef {
+    pub(crate) fn new(ratio: u32) -> Self {
+        PoseidonInstanceDef { ratio }
+    }
+
+    pub(crate) fn _cells_per_builtin(&self) -> u32 {
+        CELLS_PER_POSEIDON
+    }
+
+    pub(crate) fn _range_check_units_per_builtin(&self) -> u32 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_range_check_units_per_builtin() {
+        let builtin_instance = PoseidonInstanceDef::default();
+        assert_eq!(builtin_instance._range_check_units_per_builtin(), 0);
+    }
+
+    #[test]
+    fn get_cells_per_builtin() {
+        let builtin_instance = PoseidonInstanceDef::default();
+        assert_eq!(builtin_instance._cells_per_builtin(), 6);
+    }
+
+    #[test]
+    fn test_new() {
+        let builtin_instance = PoseidonInstanceDef { ratio: 32 };
+        assert_eq!(PoseidonInstanceDef::new(32), builtin_instance);
+    }
+
+    #[test]
+    fn test_default() {
+        let builtin_instance = PoseidonInstanceDef { ratio: 256 };
+        assert_eq!(PoseidonInstanceDef::default(), builtin_instance);
+    }
+}
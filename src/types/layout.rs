@@ -2,9 +2,49 @@ use super::instance_definitions::{
     builtins_instance_def::BuiltinsInstanceDef, cpu_instance_def::CpuInstanceDef,
     diluted_pool_instance_def::DilutedPoolInstanceDef,
 };
+use std::collections::HashMap;
+
+/// The parameters of a dynamic layout. Builtins whose ratio is None aren't part of the layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DynamicLayoutParams {
+    pub output: bool,
+    pub pedersen_ratio: Option<u32>,
+    pub range_check_ratio: Option<u32>,
+    pub ecdsa_ratio: Option<u32>,
+    pub bitwise_ratio: Option<u32>,
+    pub ec_op_ratio: Option<u32>,
+    pub keccak_ratio: Option<u32>,
+    pub poseidon_ratio: Option<u32>,
+    pub memory_units_per_step: u32,
+}
+
+impl DynamicLayoutParams {
+    /// Returns the parameters as named in the public input of the prover
+    pub(crate) fn to_public_input_params(&self) -> HashMap<String, u64> {
+        let ratios = [
+            ("pedersen_ratio", self.pedersen_ratio),
+            ("range_check_ratio", self.range_check_ratio),
+            ("ecdsa_ratio", self.ecdsa_ratio),
+            ("bitwise_ratio", self.bitwise_ratio),
+            ("ec_op_ratio", self.ec_op_ratio),
+            ("keccak_ratio", self.keccak_ratio),
+            ("poseidon_ratio", self.poseidon_ratio),
+        ];
+        let mut params: HashMap<String, u64> = ratios
+            .iter()
+            .filter_map(|(name, ratio)| Some((name.to_string(), (*ratio)? as u64)))
+            .collect();
+        params.insert("uses_output_builtin".to_string(), self.output as u64);
+        params.insert(
+            "memory_units_per_step".to_string(),
+            self.memory_units_per_step as u64,
+        );
+        params
+    }
+}
 
 #[derive(Debug)]
-pub(crate) struct CairoLayout {
+pub struct CairoLayout {
     pub(crate) _name: String,
     pub(crate) _cpu_component_step: u32,
     pub(crate) rc_units: u32,
@@ -14,6 +54,7 @@ pub(crate) struct CairoLayout {
     pub(crate) diluted_pool_instance_def: Option<DilutedPoolInstanceDef>,
     pub(crate) _n_trace_colums: u32,
     pub(crate) _cpu_instance_def: CpuInstanceDef,
+    pub(crate) dynamic_params: Option<DynamicLayoutParams>,
 }
 
 impl CairoLayout {
@@ -28,6 +69,7 @@ impl CairoLayout {
             diluted_pool_instance_def: None,
             _n_trace_colums: 8,
             _cpu_instance_def: CpuInstanceDef::default(),
+            dynamic_params: None,
         }
     }
 
@@ -42,6 +84,7 @@ impl CairoLayout {
             diluted_pool_instance_def: None,
             _n_trace_colums: 25,
             _cpu_instance_def: CpuInstanceDef::default(),
+            dynamic_params: None,
         }
     }
 
@@ -56,6 +99,7 @@ impl CairoLayout {
             diluted_pool_instance_def: None,
             _n_trace_colums: 22,
             _cpu_instance_def: CpuInstanceDef::default(),
+            dynamic_params: None,
         }
     }
 
@@ -70,6 +114,7 @@ impl CairoLayout {
             diluted_pool_instance_def: Some(DilutedPoolInstanceDef::new(2, 4, 16)),
             _n_trace_colums: 10,
             _cpu_instance_def: CpuInstanceDef::default(),
+            dynamic_params: None,
         }
     }
 
@@ -84,6 +129,7 @@ impl CairoLayout {
             diluted_pool_instance_def: Some(DilutedPoolInstanceDef::default()),
             _n_trace_colums: 10,
             _cpu_instance_def: CpuInstanceDef::default(),
+            dynamic_params: None,
         }
     }
 
@@ -98,6 +144,7 @@ impl CairoLayout {
             diluted_pool_instance_def: Some(DilutedPoolInstanceDef::default()),
             _n_trace_colums: 11,
             _cpu_instance_def: CpuInstanceDef::default(),
+            dynamic_params: None,
         }
     }
 
@@ -112,6 +159,23 @@ impl CairoLayout {
             diluted_pool_instance_def: Some(DilutedPoolInstanceDef::default()),
             _n_trace_colums: 27,
             _cpu_instance_def: CpuInstanceDef::default(),
+            dynamic_params: None,
+        }
+    }
+
+    /// A layout whose builtin ratios and memory units per step are given by `params`
+    pub fn dynamic(params: DynamicLayoutParams) -> CairoLayout {
+        CairoLayout {
+            _name: String::from("dynamic"),
+            _cpu_component_step: 1,
+            rc_units: 4,
+            builtins: BuiltinsInstanceDef::dynamic(&params),
+            _public_memory_fraction: 8,
+            _memory_units_per_step: params.memory_units_per_step,
+            diluted_pool_instance_def: Some(DilutedPoolInstanceDef::default()),
+            _n_trace_colums: 73,
+            _cpu_instance_def: CpuInstanceDef::default(),
+            dynamic_params: Some(params),
         }
     }
 }
@@ -236,4 +300,50 @@ mod tests {
         assert_eq!(layout._n_trace_colums, 27);
         assert_eq!(layout._cpu_instance_def, CpuInstanceDef::default());
     }
+
+    fn dynamic_params() -> DynamicLayoutParams {
+        DynamicLayoutParams {
+            output: true,
+            pedersen_ratio: Some(3),
+            range_check_ratio: Some(16),
+            ecdsa_ratio: None,
+            bitwise_ratio: None,
+            ec_op_ratio: None,
+            keccak_ratio: None,
+            poseidon_ratio: None,
+            memory_units_per_step: 16,
+        }
+    }
+
+    #[test]
+    fn get_dynamic_instance() {
+        let layout = CairoLayout::dynamic(dynamic_params());
+        let builtins = BuiltinsInstanceDef::dynamic(&dynamic_params());
+        assert_eq!(&layout._name, "dynamic");
+        assert_eq!(layout._cpu_component_step, 1);
+        assert_eq!(layout.rc_units, 4);
+        assert_eq!(layout.builtins, builtins);
+        assert_eq!(layout._public_memory_fraction, 8);
+        assert_eq!(layout._memory_units_per_step, 16);
+        assert_eq!(
+            layout.diluted_pool_instance_def,
+            Some(DilutedPoolInstanceDef::default())
+        );
+        assert_eq!(layout._n_trace_colums, 73);
+        assert_eq!(layout._cpu_instance_def, CpuInstanceDef::default());
+        assert_eq!(layout.dynamic_params, Some(dynamic_params()));
+    }
+
+    #[test]
+    fn dynamic_params_to_public_input_params() {
+        assert_eq!(
+            dynamic_params().to_public_input_params(),
+            HashMap::from([
+                ("pedersen_ratio".to_string(), 3),
+                ("range_check_ratio".to_string(), 16),
+                ("uses_output_builtin".to_string(), 1),
+                ("memory_units_per_step".to_string(), 16),
+            ])
+        );
+    }
 }
@@ -1,7 +1,9 @@
+#[cfg(feature = "with_prime")]
+use crate::serde::deserialize_program::parse_program_json_with_prime;
 use crate::{
     serde::deserialize_program::{
-        deserialize_program, Attribute, HintParams, Identifier, InstructionLocation,
-        ReferenceManager,
+        deserialize_and_parse_program, deserialize_program, Attribute, HintParams, Identifier,
+        InstructionLocation, Reference, ReferenceManager,
     },
     types::{errors::program_errors::ProgramError, relocatable::MaybeRelocatable},
 };
@@ -9,6 +11,7 @@ use felt::{Felt, PRIME_STR};
 use std::{
     fs::File,
     io::{BufReader, Read},
+    sync::Arc,
     {collections::HashMap, path::Path},
 };
 
@@ -22,11 +25,14 @@ pub struct Program {
     //start and end labels will only be used in proof-mode
     pub start: Option<usize>,
     pub end: Option<usize>,
-    pub hints: HashMap<usize, Vec<HintParams>>,
+    // Hints indexed by pc. Shared so that cloning a program doesn't copy them.
+    pub hints: Arc<HashMap<usize, Vec<HintParams>>>,
     pub reference_manager: ReferenceManager,
     pub identifiers: HashMap<String, Identifier>,
     pub error_message_attributes: Vec<Attribute>,
     pub instruction_locations: Option<HashMap<usize, InstructionLocation>>,
+    // The version of cairo-lang that compiled the program, if it was recorded
+    pub compiler_version: Option<String>,
 }
 
 impl Program {
@@ -42,32 +48,24 @@ impl Program {
         error_message_attributes: Vec<Attribute>,
         instruction_locations: Option<HashMap<usize, InstructionLocation>>,
     ) -> Result<Program, ProgramError> {
+        // Felt arithmetic is always done modulo PRIME_STR
+        if prime != PRIME_STR {
+            return Err(ProgramError::PrimeDiffers(prime));
+        }
         Ok(Self {
-            builtins,
+            builtins: normalize_builtin_names(builtins)?,
             prime,
             data,
-            constants: {
-                let mut constants = HashMap::new();
-                for (key, value) in identifiers.iter() {
-                    if value.type_.as_deref() == Some("const") {
-                        let value = value
-                            .value
-                            .clone()
-                            .ok_or_else(|| ProgramError::ConstWithoutValue(key.to_owned()))?;
-                        constants.insert(key.to_owned(), value);
-                    }
-                }
-
-                constants
-            },
+            constants: extract_constants(&identifiers)?,
             main,
             start: None,
             end: None,
-            hints,
+            hints: Arc::new(hints),
             reference_manager,
             identifiers,
             error_message_attributes,
             instruction_locations,
+            compiler_version: None,
         })
     }
 
@@ -84,6 +82,65 @@ impl Program {
     ) -> Result<Program, ProgramError> {
         deserialize_program(reader, entrypoint)
     }
+
+    /// Deserializes a compiled program from memory, so that it can be embedded with
+    /// `include_bytes!` or loaded where there is no filesystem. Passing no entrypoint loads
+    /// programs without a `main` function.
+    pub fn from_bytes(bytes: &[u8], entrypoint: Option<&str>) -> Result<Program, ProgramError> {
+        deserialize_and_parse_program(bytes, entrypoint)
+    }
+
+    /// Deserializes a compiled program from memory like `from_bytes`, but accepts programs
+    /// compiled for any prime. See `parse_program_json_with_prime` for why the results of
+    /// running them can't be trusted.
+    #[cfg(feature = "with_prime")]
+    pub fn from_bytes_with_prime(
+        bytes: &[u8],
+        entrypoint: Option<&str>,
+    ) -> Result<Program, ProgramError> {
+        let program_json = serde_json::from_slice(bytes)?;
+        parse_program_json_with_prime(program_json, entrypoint)
+    }
+
+    /// Returns the identifier with the given full name, e.g. `__main__.main`. Names that
+    /// aren't found are also looked up in the `__main__` module, so `main` finds the same
+    /// identifier.
+    pub fn get_identifier(&self, name: &str) -> Option<&Identifier> {
+        self.identifiers
+            .get(name)
+            .or_else(|| self.identifiers.get(&format!("__main__.{name}")))
+    }
+
+    /// Iterates over the program's identifiers and their full names, in no particular order.
+    pub fn iter_identifiers(&self) -> impl Iterator<Item = (&str, &Identifier)> {
+        self.identifiers
+            .iter()
+            .map(|(name, identifier)| (name.as_str(), identifier))
+    }
+
+    /// Returns the values of the program's `const` identifiers, keyed by full name. These are
+    /// the constants the hints receive.
+    pub fn get_constants(&self) -> &HashMap<String, Felt> {
+        &self.constants
+    }
+}
+
+/// Collects the values of the identifiers of type "const", failing with `ConstWithoutValue`
+/// if one of them has no value.
+pub(crate) fn extract_constants(
+    identifiers: &HashMap<String, Identifier>,
+) -> Result<HashMap<String, Felt>, ProgramError> {
+    identifiers
+        .iter()
+        .filter(|(_, identifier)| identifier.type_.as_deref() == Some("const"))
+        .map(|(name, identifier)| {
+            let value = identifier
+                .value
+                .clone()
+                .ok_or_else(|| ProgramError::ConstWithoutValue(name.to_owned()))?;
+            Ok((name.to_owned(), value))
+        })
+        .collect()
 }
 
 impl Default for Program {
@@ -96,19 +153,140 @@ impl Default for Program {
             main: None,
             start: None,
             end: None,
-            hints: HashMap::new(),
+            hints: Arc::new(HashMap::new()),
             reference_manager: ReferenceManager {
                 references: Vec::new(),
             },
             identifiers: HashMap::new(),
             error_message_attributes: Vec::new(),
             instruction_locations: None,
+            compiler_version: None,
         }
     }
 }
+
+// Names of the builtins supported by the runner, in the order in which they have to be declared
+pub(crate) const BUILTIN_NAMES: [&str; 8] = [
+    "output",
+    "pedersen",
+    "range_check",
+    "ecdsa",
+    "bitwise",
+    "ec_op",
+    "keccak",
+    "poseidon",
+];
+
+/// Returns the name of a builtin without the `_builtin` suffix, which some versions of
+/// cairo-lang append to the names listed in the compiled program.
+pub(crate) fn normalize_builtin_name(name: &str) -> &str {
+    name.strip_suffix("_builtin").unwrap_or(name)
+}
+
+/// Normalizes the builtin names of a program, failing with `UnsupportedBuiltin` if one of them
+/// isn't supported by the runner.
+pub(crate) fn normalize_builtin_names(builtins: Vec<String>) -> Result<Vec<String>, ProgramError> {
+    builtins
+        .into_iter()
+        .map(|name| {
+            let normalized = normalize_builtin_name(&name);
+            if BUILTIN_NAMES.contains(&normalized) {
+                Ok(normalized.to_string())
+            } else {
+                Err(ProgramError::UnsupportedBuiltin(name))
+            }
+        })
+        .collect()
+}
+
+/// Builds a [`Program`] in code, without going through its JSON representation.
+///
+/// The resulting program uses the Cairo prime, and its consistency is checked by
+/// [`ProgramBuilder::build`]: the main offset and the hint pcs have to point inside the
+/// bytecode, and the builtins have to be supported by the runner.
+#[derive(Clone, Debug, Default)]
+pub struct ProgramBuilder {
+    builtins: Vec<String>,
+    data: Vec<MaybeRelocatable>,
+    main: Option<usize>,
+    hints: HashMap<usize, Vec<HintParams>>,
+    references: Vec<Reference>,
+    identifiers: HashMap<String, Identifier>,
+}
+
+impl ProgramBuilder {
+    pub fn new() -> ProgramBuilder {
+        ProgramBuilder::default()
+    }
+
+    /// Sets the program's bytecode.
+    pub fn data(mut self, data: Vec<MaybeRelocatable>) -> ProgramBuilder {
+        self.data = data;
+        self
+    }
+
+    /// Sets the offset of the main function within the bytecode.
+    pub fn main(mut self, main: usize) -> ProgramBuilder {
+        self.main = Some(main);
+        self
+    }
+
+    /// Sets the builtins used by the program.
+    pub fn builtins(mut self, builtins: Vec<String>) -> ProgramBuilder {
+        self.builtins = builtins;
+        self
+    }
+
+    /// Adds a hint to be executed before the instruction at `pc`.
+    /// Hints added at the same pc are executed in insertion order.
+    pub fn hint(mut self, pc: usize, hint: HintParams) -> ProgramBuilder {
+        self.hints.entry(pc).or_default().push(hint);
+        self
+    }
+
+    /// Adds an entry to the reference manager. The hints refer to it by its position.
+    pub fn reference(mut self, reference: Reference) -> ProgramBuilder {
+        self.references.push(reference);
+        self
+    }
+
+    /// Adds an identifier. Identifiers of type "const" are also added to the program constants.
+    pub fn identifier(mut self, name: &str, identifier: Identifier) -> ProgramBuilder {
+        self.identifiers.insert(name.to_string(), identifier);
+        self
+    }
+
+    pub fn build(self) -> Result<Program, ProgramError> {
+        let data_len = self.data.len();
+        if let Some(main) = self.main {
+            if main >= data_len {
+                return Err(ProgramError::PcOutOfBounds(main, data_len));
+            }
+        }
+        if let Some(pc) = self.hints.keys().find(|pc| **pc >= data_len) {
+            return Err(ProgramError::PcOutOfBounds(*pc, data_len));
+        }
+
+        Program::new(
+            self.builtins,
+            PRIME_STR.to_string(),
+            self.data,
+            self.main,
+            self.hints,
+            ReferenceManager {
+                references: self.references,
+            },
+            self.identifiers,
+            Vec::new(),
+            None,
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::serde::deserialize_program::{ApTracking, FlowTrackingData};
     use crate::utils::test_utils::mayberelocatable;
     use felt::{felt_str, NewFelt};
     use num_traits::Zero;
@@ -366,6 +544,13 @@ mod tests {
             start_pc: 379,
             end_pc: 381,
             value: String::from("SafeUint256: addition overflow"),
+            flow_tracking_data: Some(FlowTrackingData {
+                ap_tracking: ApTracking {
+                    group: 14,
+                    offset: 35,
+                },
+                reference_ids: HashMap::new(),
+            }),
         }];
 
         let data: Vec<MaybeRelocatable> = vec![
@@ -476,6 +661,26 @@ mod tests {
         assert_eq!(program.constants, constants);
     }
 
+    #[test]
+    fn clone_program_shares_hints() {
+        let hint = HintParams {
+            code: "memory[ap] = 1".to_string(),
+            accessible_scopes: Vec::new(),
+            flow_tracking_data: FlowTrackingData {
+                ap_tracking: ApTracking::default(),
+                reference_ids: HashMap::new(),
+            },
+        };
+        let program = Program {
+            hints: Arc::new(HashMap::from([(0, vec![hint])])),
+            ..Default::default()
+        };
+
+        let cloned = program.clone();
+        assert!(Arc::ptr_eq(&program.hints, &cloned.hints));
+        assert_eq!(program, cloned);
+    }
+
     #[test]
     fn default_program() {
         let program = Program {
@@ -486,7 +691,7 @@ mod tests {
             main: None,
             start: None,
             end: None,
-            hints: HashMap::new(),
+            hints: Arc::new(HashMap::new()),
             reference_manager: ReferenceManager {
                 references: Vec::new(),
             },
@@ -497,4 +702,296 @@ mod tests {
 
         assert_eq!(program, Program::default())
     }
+
+    fn hint_params(code: &str) -> HintParams {
+        HintParams {
+            code: code.to_string(),
+            accessible_scopes: vec![String::from("__main__.main")],
+            flow_tracking_data: FlowTrackingData {
+                ap_tracking: ApTracking::new(),
+                reference_ids: HashMap::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn program_builder() {
+        let data = vec![
+            mayberelocatable!(5189976364521848832),
+            mayberelocatable!(1000),
+            mayberelocatable!(2345108766317314046),
+        ];
+        let program = ProgramBuilder::new()
+            .data(data.clone())
+            .main(0)
+            .builtins(vec![String::from("output")])
+            .hint(0, hint_params("memory[ap] = 1"))
+            .hint(0, hint_params("memory[ap + 1] = 2"))
+            .hint(2, hint_params("memory[ap] = 3"))
+            .identifier(
+                "__main__.main.SIZEOF_LOCALS",
+                Identifier {
+                    pc: None,
+                    type_: Some(String::from("const")),
+                    value: Some(Felt::zero()),
+                    full_name: None,
+                    members: None,
+                },
+            )
+            .build()
+            .unwrap();
+
+        assert_eq!(program.data, data);
+        assert_eq!(program.main, Some(0));
+        assert_eq!(program.prime, PRIME_STR);
+        assert_eq!(program.builtins, vec![String::from("output")]);
+        assert_eq!(
+            *program.hints,
+            HashMap::from([
+                (
+                    0,
+                    vec![
+                        hint_params("memory[ap] = 1"),
+                        hint_params("memory[ap + 1] = 2")
+                    ]
+                ),
+                (2, vec![hint_params("memory[ap] = 3")]),
+            ])
+        );
+        assert_eq!(
+            program.constants,
+            HashMap::from([(String::from("__main__.main.SIZEOF_LOCALS"), Felt::zero())])
+        );
+    }
+
+    #[test]
+    fn new_program_with_different_prime() {
+        let result = Program::new(
+            Vec::new(),
+            String::from("0x7fffffff"),
+            Vec::new(),
+            None,
+            HashMap::new(),
+            ReferenceManager {
+                references: Vec::new(),
+            },
+            HashMap::new(),
+            Vec::new(),
+            None,
+        );
+        assert!(matches!(result, Err(ProgramError::PrimeDiffers(prime)) if prime == "0x7fffffff"));
+    }
+
+    #[test]
+    fn new_program_normalizes_builtin_names() {
+        let program = Program::new(
+            vec![String::from("output_builtin"), String::from("range_check")],
+            felt::PRIME_STR.to_string(),
+            Vec::new(),
+            None,
+            HashMap::new(),
+            ReferenceManager {
+                references: Vec::new(),
+            },
+            HashMap::new(),
+            Vec::new(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(program.builtins, vec!["output", "range_check"]);
+    }
+
+    #[test]
+    fn new_program_with_unsupported_builtin() {
+        let result = Program::new(
+            vec![String::from("segment_arena")],
+            felt::PRIME_STR.to_string(),
+            Vec::new(),
+            None,
+            HashMap::new(),
+            ReferenceManager {
+                references: Vec::new(),
+            },
+            HashMap::new(),
+            Vec::new(),
+            None,
+        );
+        assert!(
+            matches!(result, Err(ProgramError::UnsupportedBuiltin(name)) if name == "segment_arena")
+        );
+    }
+
+    #[cfg(feature = "with_prime")]
+    #[test]
+    fn from_bytes_with_different_prime() {
+        let bytes =
+            include_bytes!("../../cairo_programs/manually_compiled/wrong_prime_program.json");
+        assert!(matches!(
+            Program::from_bytes(bytes, Some("main")),
+            Err(ProgramError::PrimeDiffers(prime)) if prime == "0x7fffffff"
+        ));
+
+        let program = Program::from_bytes_with_prime(bytes, Some("main")).unwrap();
+        assert_eq!(program.prime, "0x7fffffff");
+    }
+
+    #[test]
+    fn program_builder_main_out_of_bounds() {
+        let result = ProgramBuilder::new()
+            .data(vec![mayberelocatable!(2345108766317314046)])
+            .main(1)
+            .build();
+        assert!(matches!(result, Err(ProgramError::PcOutOfBounds(1, 1))));
+    }
+
+    #[test]
+    fn program_builder_hint_out_of_bounds() {
+        let result = ProgramBuilder::new()
+            .data(vec![mayberelocatable!(2345108766317314046)])
+            .hint(3, hint_params("memory[ap] = 1"))
+            .build();
+        assert!(matches!(result, Err(ProgramError::PcOutOfBounds(3, 1))));
+    }
+
+    #[test]
+    fn program_builder_unknown_builtin() {
+        let result = ProgramBuilder::new()
+            .builtins(vec![String::from("output"), String::from("sha256")])
+            .build();
+        assert!(matches!(result, Err(ProgramError::UnsupportedBuiltin(name)) if name == "sha256"));
+    }
+
+    #[test]
+    fn program_builder_normalizes_builtin_names() {
+        let program = ProgramBuilder::new()
+            .builtins(vec![
+                String::from("output_builtin"),
+                String::from("pedersen"),
+            ])
+            .build()
+            .unwrap();
+        assert_eq!(program.builtins, vec!["output", "pedersen"]);
+    }
+
+    #[test]
+    fn from_bytes_matches_from_file() {
+        let path = Path::new("cairo_programs/manually_compiled/valid_program_a.json");
+        let bytes = include_bytes!("../../cairo_programs/manually_compiled/valid_program_a.json");
+
+        assert_eq!(
+            Program::from_bytes(bytes, Some("main")).unwrap(),
+            Program::from_file(path, Some("main")).unwrap()
+        );
+    }
+
+    #[test]
+    fn from_bytes_without_entrypoint() {
+        let bytes = include_bytes!("../../cairo_programs/manually_compiled/library_program.json");
+        let program = Program::from_bytes(bytes, None).unwrap();
+
+        assert_eq!(program.main, None);
+        assert_eq!(program.data.len(), 2);
+        assert_eq!(program.get_identifier("add").unwrap().pc, Some(0));
+    }
+
+    #[test]
+    fn from_bytes_with_missing_entrypoint() {
+        let bytes = include_bytes!("../../cairo_programs/manually_compiled/library_program.json");
+
+        assert!(matches!(
+            Program::from_bytes(bytes, Some("main")),
+            Err(ProgramError::EntrypointNotFound(entrypoint)) if entrypoint == "main"
+        ));
+    }
+
+    #[test]
+    fn from_bytes_invalid_json() {
+        assert!(matches!(
+            Program::from_bytes(b"{\"data\": [", None),
+            Err(ProgramError::Parse(_))
+        ));
+    }
+
+    #[test]
+    fn get_identifier_by_full_or_main_name() {
+        let program = Program::from_file(
+            Path::new("cairo_programs/manually_compiled/valid_program_a.json"),
+            Some("main"),
+        )
+        .unwrap();
+
+        let main = program.get_identifier("__main__.main").unwrap();
+        assert_eq!(main.pc, Some(0));
+        assert_eq!(main.type_.as_deref(), Some("function"));
+        assert_eq!(program.get_identifier("main"), Some(main));
+        assert_eq!(
+            program.get_identifier("main.Args"),
+            program.get_identifier("__main__.main.Args")
+        );
+        assert_eq!(program.get_identifier("__main__.missing"), None);
+        assert_eq!(program.get_identifier("missing"), None);
+    }
+
+    #[test]
+    fn iter_identifiers() {
+        let program = Program::from_file(
+            Path::new("cairo_programs/manually_compiled/valid_program_a.json"),
+            Some("main"),
+        )
+        .unwrap();
+
+        let mut names: Vec<&str> = program.iter_identifiers().map(|(name, _)| name).collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            [
+                "__main__.main",
+                "__main__.main.Args",
+                "__main__.main.ImplicitArgs",
+                "__main__.main.Return",
+                "__main__.main.SIZEOF_LOCALS",
+            ]
+        );
+        assert!(program
+            .iter_identifiers()
+            .all(|(name, identifier)| program.get_identifier(name) == Some(identifier)));
+    }
+
+    #[test]
+    fn get_constants_from_fixture() {
+        let program = Program::from_file(
+            Path::new("cairo_programs/manually_compiled/deserialize_constant_test.json"),
+            Some("main"),
+        )
+        .unwrap();
+
+        let constants = program.get_constants();
+        assert_eq!(constants.len(), 5);
+        assert_eq!(
+            constants.get("starkware.cairo.common.uint256.SHIFT"),
+            Some(&felt_str!("340282366920938463463374607431768211456"))
+        );
+        assert_eq!(
+            constants.get("starkware.cairo.common.cairo_keccak.packed_keccak.BLOCK_SIZE"),
+            Some(&Felt::new(3))
+        );
+    }
+
+    #[test]
+    fn program_builder_const_identifier_without_value() {
+        let identifier = Identifier {
+            pc: None,
+            type_: Some(String::from("const")),
+            value: None,
+            full_name: None,
+            members: None,
+        };
+        let result = ProgramBuilder::new()
+            .identifier("__main__.X", identifier)
+            .build();
+        assert!(matches!(
+            result,
+            Err(ProgramError::ConstWithoutValue(name)) if name == "__main__.X"
+        ));
+    }
 }
@@ -1,22 +1,26 @@
 use crate::{
+    math_utils::FeltToPrimitive,
     relocatable,
-    vm::errors::{memory_errors::MemoryError, vm_errors::VirtualMachineError},
+    vm::errors::{
+        math_errors::MathError, memory_errors::MemoryError, vm_errors::VirtualMachineError,
+    },
 };
 use felt::{Felt, NewFelt};
 use num_integer::Integer;
 use num_traits::{FromPrimitive, ToPrimitive};
+use serde::{Deserialize, Serialize};
 use std::{
     fmt::{self, Display},
-    ops::Add,
+    ops::{Add, Sub},
 };
 
-#[derive(Eq, Hash, PartialEq, PartialOrd, Clone, Copy, Debug)]
+#[derive(Eq, Hash, PartialEq, PartialOrd, Ord, Clone, Copy, Debug, Serialize, Deserialize)]
 pub struct Relocatable {
     pub segment_index: isize,
     pub offset: usize,
 }
 
-#[derive(Eq, Hash, PartialEq, PartialOrd, Clone, Debug)]
+#[derive(Eq, Hash, PartialEq, PartialOrd, Ord, Clone, Debug)]
 pub enum MaybeRelocatable {
     RelocatableValue(Relocatable),
     Int(Felt),
@@ -138,20 +142,16 @@ impl TryFrom<&MaybeRelocatable> for Relocatable {
 }
 
 impl Relocatable {
-    pub fn sub_usize(&self, other: usize) -> Result<Self, VirtualMachineError> {
-        if self.offset < other {
-            return Err(VirtualMachineError::CantSubOffset(self.offset, other));
-        }
-        let new_offset = self.offset - other;
-        Ok(relocatable!(self.segment_index, new_offset))
+    /// Subtracts a usize from the offset of self, like `Sub<usize>`
+    pub fn sub_usize(&self, other: usize) -> Result<Self, MathError> {
+        *self - other
     }
 
     ///Adds a Felt to self
-    pub fn add_int(&self, other: &Felt) -> Result<Relocatable, VirtualMachineError> {
-        let big_offset = other + self.offset;
-        let new_offset = big_offset
+    pub fn add_int(&self, other: &Felt) -> Result<Relocatable, MathError> {
+        let new_offset = (other + self.offset)
             .to_usize()
-            .ok_or(VirtualMachineError::OffsetExceeded(big_offset))?;
+            .ok_or_else(|| MathError::RelocatableAddFeltOffsetOverflow(*self, other.clone()))?;
         Ok(Relocatable {
             segment_index: self.segment_index,
             offset: new_offset,
@@ -165,46 +165,76 @@ impl Relocatable {
             .get_int_ref()
             .map_err(|_| VirtualMachineError::RelocatableAdd)?;
 
-        let big_offset: Felt = num_ref + self.offset;
-        let new_offset = big_offset
-            .to_usize()
-            .ok_or(VirtualMachineError::OffsetExceeded(big_offset))?;
-        Ok(Relocatable {
-            segment_index: self.segment_index,
-            offset: new_offset,
-        })
+        self.add_int(num_ref).map_err(VirtualMachineError::from)
     }
 
-    pub fn sub(&self, other: &Self) -> Result<usize, VirtualMachineError> {
+    /// Returns the distance between two addresses of the same segment
+    pub fn sub(&self, other: &Self) -> Result<usize, MathError> {
         if self.segment_index != other.segment_index {
-            return Err(VirtualMachineError::DiffIndexSub);
+            return Err(MathError::RelocatableSubDifferentSegments(*self, *other));
         }
-        if self.offset < other.offset {
-            return Err(VirtualMachineError::CantSubOffset(
-                self.offset,
-                other.offset,
-            ));
-        }
-        let result = self.offset - other.offset;
-        Ok(result)
+        self.offset
+            .checked_sub(other.offset)
+            .ok_or(MathError::RelocatableSubNegOffset(*self, other.offset))
+    }
+
+    /// Adds a usize to self, returning None if the offset overflows
+    pub fn checked_add(&self, other: usize) -> Option<Relocatable> {
+        Some(relocatable!(
+            self.segment_index,
+            self.offset.checked_add(other)?
+        ))
+    }
+
+    /// Adds a Felt to the offset of self, keeping its segment.
+    /// Fails if the felt doesn't fit in a usize or the resulting offset overflows
+    pub fn checked_add_felt(&self, other: &Felt) -> Result<Relocatable, MathError> {
+        let other = other.to_usize_checked()?;
+        self.checked_add(other)
+            .ok_or(MathError::RelocatableAddOffsetOverflow(*self, other))
+    }
+
+    /// Subtracts a Felt from the offset of self, keeping its segment.
+    /// Fails if the felt doesn't fit in a usize or the resulting offset is negative
+    pub fn sub_felt(&self, other: &Felt) -> Result<Relocatable, MathError> {
+        *self - other.to_usize_checked()?
+    }
+}
+
+impl Add<&Felt> for Relocatable {
+    type Output = Result<Relocatable, MathError>;
+    fn add(self, other: &Felt) -> Result<Relocatable, MathError> {
+        self.checked_add_felt(other)
+    }
+}
+
+impl Sub<Relocatable> for Relocatable {
+    type Output = Result<usize, MathError>;
+    fn sub(self, other: Relocatable) -> Result<usize, MathError> {
+        Relocatable::sub(&self, &other)
+    }
+}
+
+impl Sub<usize> for Relocatable {
+    type Output = Result<Relocatable, MathError>;
+    fn sub(self, other: usize) -> Result<Relocatable, MathError> {
+        let new_offset = self
+            .offset
+            .checked_sub(other)
+            .ok_or(MathError::RelocatableSubNegOffset(self, other))?;
+        Ok(relocatable!(self.segment_index, new_offset))
     }
 }
 
 impl MaybeRelocatable {
     /// Adds a Felt to self
-    pub fn add_int(&self, other: &Felt) -> Result<MaybeRelocatable, VirtualMachineError> {
+    /// Relocatable values keep their segment and fail if the offset overflows
+    pub fn add_int(&self, other: &Felt) -> Result<MaybeRelocatable, MathError> {
         match *self {
             MaybeRelocatable::Int(ref value) => Ok(MaybeRelocatable::Int(value + other)),
-            MaybeRelocatable::RelocatableValue(ref rel) => {
-                let big_offset = other + rel.offset;
-                let new_offset = big_offset
-                    .to_usize()
-                    .ok_or(VirtualMachineError::OffsetExceeded(big_offset))?;
-                Ok(MaybeRelocatable::RelocatableValue(Relocatable {
-                    segment_index: rel.segment_index,
-                    offset: new_offset,
-                }))
-            }
+            MaybeRelocatable::RelocatableValue(ref rel) => Ok(MaybeRelocatable::RelocatableValue(
+                rel.checked_add_felt(other)?,
+            )),
         }
     }
 
@@ -232,16 +262,8 @@ impl MaybeRelocatable {
                 Err(VirtualMachineError::RelocatableAdd)
             }
             (&MaybeRelocatable::RelocatableValue(ref rel), &MaybeRelocatable::Int(ref num_ref))
-            | (&MaybeRelocatable::Int(ref num_ref), &MaybeRelocatable::RelocatableValue(ref rel)) =>
-            {
-                let big_offset: Felt = num_ref + rel.offset;
-                let new_offset = big_offset
-                    .to_usize()
-                    .ok_or(VirtualMachineError::OffsetExceeded(big_offset))?;
-                Ok(MaybeRelocatable::RelocatableValue(Relocatable {
-                    segment_index: rel.segment_index,
-                    offset: new_offset,
-                }))
+            | (&MaybeRelocatable::Int(ref num_ref), &MaybeRelocatable::RelocatableValue(ref rel)) => {
+                Ok(MaybeRelocatable::RelocatableValue(rel.add_int(num_ref)?))
             }
         }
     }
@@ -257,20 +279,13 @@ impl MaybeRelocatable {
             (
                 MaybeRelocatable::RelocatableValue(rel_a),
                 MaybeRelocatable::RelocatableValue(rel_b),
-            ) => {
-                if rel_a.segment_index == rel_b.segment_index {
-                    return Ok(MaybeRelocatable::from(Felt::new(
-                        rel_a.offset - rel_b.offset,
-                    )));
-                }
-                Err(VirtualMachineError::DiffIndexSub)
-            }
+            ) => Ok(MaybeRelocatable::from(Felt::new(rel_a.sub(rel_b)?))),
             (MaybeRelocatable::RelocatableValue(rel_a), MaybeRelocatable::Int(ref num_b)) => {
                 Ok(MaybeRelocatable::from((
                     rel_a.segment_index,
-                    (rel_a.offset - num_b)
-                        .to_usize()
-                        .ok_or_else(|| VirtualMachineError::OffsetExceeded(rel_a.offset - num_b))?,
+                    (rel_a.offset - num_b).to_usize().ok_or_else(|| {
+                        MathError::RelocatableSubFeltNegOffset(*rel_a, num_b.clone())
+                    })?,
                 )))
             }
             _ => Err(VirtualMachineError::NotImplemented),
@@ -394,13 +409,13 @@ mod tests {
         let error = addr.add_int(&felt_str!("18446744073709551616"));
         assert_eq!(
             error,
-            Err(VirtualMachineError::OffsetExceeded(felt_str!(
+            Err(MathError::FeltToUsizeConversion(felt_str!(
                 "18446744073709551616"
             )))
         );
         assert_eq!(
             error.unwrap_err().to_string(),
-            "Offset 18446744073709551616 exeeds maximum offset value"
+            "Felt 18446744073709551616 doesn't fit in a usize"
         );
     }
 
@@ -433,6 +448,86 @@ mod tests {
         );
     }
 
+    #[test]
+    fn add_int_to_relocatable_keeps_segment() {
+        let addr = MaybeRelocatable::from((3, 4));
+        assert_eq!(
+            addr.add_int(&Felt::new(usize::MAX - 4)),
+            Ok(MaybeRelocatable::from((3, usize::MAX)))
+        );
+    }
+
+    #[test]
+    fn add_felt_to_relocatable() {
+        assert_eq!(relocatable!(2, 5) + &Felt::new(3), Ok(relocatable!(2, 8)));
+        assert_eq!(
+            relocatable!(2, 5).checked_add_felt(&Felt::zero()),
+            Ok(relocatable!(2, 5))
+        );
+    }
+
+    #[test]
+    fn add_felt_to_relocatable_exact_max_offset() {
+        assert_eq!(
+            relocatable!(1, 1) + &Felt::new(usize::MAX - 1),
+            Ok(relocatable!(1, usize::MAX))
+        );
+    }
+
+    #[test]
+    fn add_felt_to_relocatable_offset_overflow() {
+        assert_eq!(
+            relocatable!(1, 1) + &Felt::new(usize::MAX),
+            Err(MathError::RelocatableAddOffsetOverflow(
+                relocatable!(1, 1),
+                usize::MAX
+            ))
+        );
+    }
+
+    #[test]
+    fn add_felt_above_usize_max_to_relocatable() {
+        let big_felt = Felt::new(usize::MAX) + Felt::one();
+        assert_eq!(
+            relocatable!(1, 0) + &big_felt,
+            Err(MathError::FeltToUsizeConversion(big_felt))
+        );
+        // A negative felt is a huge field element, it doesn't wrap the offset back
+        assert_eq!(
+            relocatable!(1, 5) + &Felt::new(-1),
+            Err(MathError::FeltToUsizeConversion(Felt::new(-1)))
+        );
+    }
+
+    #[test]
+    fn sub_felt_from_relocatable() {
+        assert_eq!(
+            relocatable!(2, 5).sub_felt(&Felt::new(3)),
+            Ok(relocatable!(2, 2))
+        );
+        assert_eq!(
+            relocatable!(2, 5).sub_felt(&Felt::new(5)),
+            Ok(relocatable!(2, 0))
+        );
+    }
+
+    #[test]
+    fn sub_felt_from_relocatable_neg_offset() {
+        assert_eq!(
+            relocatable!(2, 5).sub_felt(&Felt::new(6)),
+            Err(MathError::RelocatableSubNegOffset(relocatable!(2, 5), 6))
+        );
+    }
+
+    #[test]
+    fn sub_felt_above_usize_max_from_relocatable() {
+        let big_felt = Felt::new(usize::MAX) + Felt::one();
+        assert_eq!(
+            relocatable!(2, usize::MAX).sub_felt(&big_felt),
+            Err(MathError::FeltToUsizeConversion(big_felt))
+        );
+    }
+
     #[test]
     fn add_int_to_int() {
         let addr_a = &MaybeRelocatable::from(felt_str!(
@@ -493,9 +588,12 @@ mod tests {
         let error = addr.add(&MaybeRelocatable::from(felt_str!("18446744073709551616")));
         assert_eq!(
             error,
-            Err(VirtualMachineError::OffsetExceeded(felt_str!(
-                "18446744073709551616"
-            )))
+            Err(VirtualMachineError::MathError(
+                MathError::RelocatableAddFeltOffsetOverflow(
+                    relocatable!(0, 0),
+                    felt_str!("18446744073709551616")
+                )
+            ))
         );
     }
 
@@ -509,9 +607,12 @@ mod tests {
         let error = addr.add(&MaybeRelocatable::RelocatableValue(relocatable));
         assert_eq!(
             error,
-            Err(VirtualMachineError::OffsetExceeded(felt_str!(
-                "18446744073709551616"
-            )))
+            Err(VirtualMachineError::MathError(
+                MathError::RelocatableAddFeltOffsetOverflow(
+                    relocatable!(0, 0),
+                    felt_str!("18446744073709551616")
+                )
+            ))
         );
     }
 
@@ -536,10 +637,39 @@ mod tests {
         let addr_a = &MaybeRelocatable::from((7, 17));
         let addr_b = &MaybeRelocatable::from((8, 7));
         let error = addr_a.sub(addr_b);
-        assert_eq!(error, Err(VirtualMachineError::DiffIndexSub));
+        assert_eq!(
+            error,
+            Err(VirtualMachineError::MathError(
+                MathError::RelocatableSubDifferentSegments(relocatable!(7, 17), relocatable!(8, 7))
+            ))
+        );
         assert_eq!(
             error.unwrap_err().to_string(),
-            "Can only subtract two relocatable values of the same segment"
+            "Can only subtract two relocatable values of the same segment, got 7:17 and 8:7"
+        );
+    }
+
+    #[test]
+    fn sub_relocatable_from_relocatable_neg_offset() {
+        let addr_a = &MaybeRelocatable::from((7, 7));
+        let addr_b = &MaybeRelocatable::from((7, 17));
+        assert_eq!(
+            addr_a.sub(addr_b),
+            Err(VirtualMachineError::MathError(
+                MathError::RelocatableSubNegOffset(relocatable!(7, 7), 17)
+            ))
+        );
+    }
+
+    #[test]
+    fn sub_int_from_relocatable_offset_exceeded() {
+        let addr_a = &MaybeRelocatable::from((7, 3));
+        let addr_b = &MaybeRelocatable::from(Felt::new(5_i32));
+        assert_eq!(
+            addr_a.sub(addr_b),
+            Err(VirtualMachineError::MathError(
+                MathError::RelocatableSubFeltNegOffset(relocatable!(7, 3), Felt::new(5_i32))
+            ))
         );
     }
 
@@ -643,7 +773,8 @@ mod tests {
     #[test]
     fn relocatable_add_int_mod_offset_exceeded_error() {
         assert_eq!(
-            Err(VirtualMachineError::OffsetExceeded(
+            Err(MathError::RelocatableAddFeltOffsetOverflow(
+                relocatable!(0, 0),
                 Felt::new(usize::MAX) + 1_usize
             )),
             relocatable!(0, 0).add_int(&(Felt::new(usize::MAX) + 1_usize))
@@ -680,10 +811,11 @@ mod tests {
     fn relocatable_sub_rel_test() {
         let reloc = relocatable!(7, 6);
 
-        assert_eq!(Ok(1), reloc.sub(&relocatable!(7, 5)));
+        assert_eq!(Ok(1), reloc - relocatable!(7, 5));
+        assert_eq!(Ok(0), reloc - relocatable!(7, 6));
         assert_eq!(
-            Err(VirtualMachineError::CantSubOffset(6, 9)),
-            reloc.sub(&relocatable!(7, 9))
+            Err(MathError::RelocatableSubNegOffset(reloc, 9)),
+            reloc - relocatable!(7, 9)
         );
     }
 
@@ -692,7 +824,32 @@ mod tests {
         let a = relocatable!(7, 6);
         let b = relocatable!(8, 6);
 
-        assert_eq!(Err(VirtualMachineError::DiffIndexSub), a.sub(&b));
+        assert_eq!(Err(MathError::RelocatableSubDifferentSegments(a, b)), a - b);
+    }
+
+    #[test]
+    fn relocatable_sub_usize() {
+        let reloc = relocatable!(7, 6);
+
+        assert_eq!(Ok(relocatable!(7, 2)), reloc - 4);
+        assert_eq!(Ok(relocatable!(7, 0)), reloc - 6);
+        assert_eq!(Err(MathError::RelocatableSubNegOffset(reloc, 7)), reloc - 7);
+        assert_eq!(
+            Err(MathError::RelocatableSubNegOffset(reloc, 7)),
+            reloc.sub_usize(7)
+        );
+    }
+
+    #[test]
+    fn relocatable_checked_add() {
+        let reloc = relocatable!(7, 6);
+
+        assert_eq!(Some(relocatable!(7, 10)), reloc.checked_add(4));
+        assert_eq!(None, reloc.checked_add(usize::MAX));
+        assert_eq!(
+            Some(relocatable!(0, usize::MAX)),
+            relocatable!(0, 0).checked_add(usize::MAX)
+        );
     }
 
     #[test]
@@ -731,8 +888,11 @@ mod tests {
     #[test]
     fn add_maybe_mod_offset_exceeded_error() {
         assert_eq!(
-            Err(VirtualMachineError::OffsetExceeded(
-                Felt::new(usize::MAX) + 1_usize
+            Err(VirtualMachineError::MathError(
+                MathError::RelocatableAddFeltOffsetOverflow(
+                    relocatable!(1, 0),
+                    Felt::new(usize::MAX) + 1_usize
+                )
             )),
             relocatable!(1, 0).add_maybe(&mayberelocatable!(usize::MAX as i128 + 1),)
         );
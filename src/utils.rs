@@ -156,6 +156,32 @@ pub mod test_utils {
     }
     pub(crate) use check_memory;
 
+    // Asserts that two memories are equal, printing up to max_entries (20 by default) of the
+    // differing cells otherwise
+    macro_rules! assert_memory_eq {
+        ($lhs: expr, $rhs: expr) => {
+            assert_memory_eq!($lhs, $rhs, 20)
+        };
+        ($lhs: expr, $rhs: expr, $max_entries: expr) => {
+            let diffs = $lhs.diff(&$rhs);
+            if !diffs.is_empty() {
+                let shown = diffs
+                    .iter()
+                    .take($max_entries)
+                    .map(|diff| diff.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                panic!(
+                    "memories differ in {} cells (showing at most {}):\n{}",
+                    diffs.len(),
+                    $max_entries,
+                    shown
+                );
+            }
+        };
+    }
+    pub(crate) use assert_memory_eq;
+
     macro_rules! check_memory_address {
         ($mem:expr, ($si:expr, $off:expr), ($sival:expr, $offval: expr)) => {
             assert_eq!(
@@ -242,13 +268,14 @@ pub mod test_utils {
                 main: None,
                 start: None,
                 end: None,
-                hints: HashMap::new(),
+                hints: std::sync::Arc::new(HashMap::new()),
                 reference_manager: ReferenceManager {
                     references: Vec::new(),
                 },
                 identifiers: HashMap::new(),
                 error_message_attributes: Vec::new(),
                 instruction_locations: None,
+                compiler_version: None,
             }
         };
         // Custom program definition
@@ -349,7 +376,13 @@ pub mod test_utils {
         ($vm:expr, $ids_data:expr, $hint_code:expr, $exec_scopes:expr, $constants:expr) => {{
             let hint_data = HintProcessorData::new_default($hint_code.to_string(), $ids_data);
             let mut hint_processor = BuiltinHintProcessor::new_empty();
-            hint_processor.execute_hint(&mut $vm, $exec_scopes, &any_box!(hint_data), $constants)
+            hint_processor.execute_hint(
+                &mut $vm,
+                $exec_scopes,
+                &any_box!(hint_data),
+                $constants,
+                &mut $crate::vm::runners::cairo_runner::RunResources::default(),
+            )
         }};
         ($vm:expr, $ids_data:expr, $hint_code:expr, $exec_scopes:expr) => {{
             let hint_data = HintProcessorData::new_default($hint_code.to_string(), $ids_data);
@@ -359,6 +392,7 @@ pub mod test_utils {
                 $exec_scopes,
                 &any_box!(hint_data),
                 &HashMap::new(),
+                &mut $crate::vm::runners::cairo_runner::RunResources::default(),
             )
         }};
         ($vm:expr, $ids_data:expr, $hint_code:expr) => {{
@@ -369,6 +403,7 @@ pub mod test_utils {
                 exec_scopes_ref!(),
                 &any_box!(hint_data),
                 &HashMap::new(),
+                &mut $crate::vm::runners::cairo_runner::RunResources::default(),
             )
         }};
     }
@@ -415,12 +450,12 @@ pub mod test_utils {
                     $exec_scopes
                         .get_dict_manager()
                         .unwrap()
-                        .borrow_mut()
+                        .borrow()
                         .trackers
-                        .get_mut(&$tracker_num)
+                        .get(&$tracker_num)
                         .unwrap()
-                        .get_value(&Felt::new($key)),
-                    Ok(&Felt::new($val))
+                        .peek_value(&$crate::types::relocatable::MaybeRelocatable::from(Felt::new($key))),
+                    Some($crate::types::relocatable::MaybeRelocatable::from(Felt::new($val)))
                 );
             )*
         };
@@ -448,7 +483,7 @@ pub mod test_utils {
         ($exec_scopes:expr, $tracker_num:expr, $( ($key:expr, $val:expr )),* ) => {
             let mut tracker = DictTracker::new_empty(&relocatable!($tracker_num, 0));
             $(
-            tracker.insert_value(&Felt::new($key), &Felt::new($val));
+            tracker.insert_value(&$crate::types::relocatable::MaybeRelocatable::from(Felt::new($key)), &$crate::types::relocatable::MaybeRelocatable::from(Felt::new($val)));
             )*
             let mut dict_manager = DictManager::new();
             dict_manager.trackers.insert(2, tracker);
@@ -466,9 +501,9 @@ pub mod test_utils {
 
     macro_rules! dict_manager_default {
         ($exec_scopes:expr, $tracker_num:expr,$default:expr, $( ($key:expr, $val:expr )),* ) => {
-            let mut tracker = DictTracker::new_default_dict(&relocatable!($tracker_num, 0), &Felt::new($default), None);
+            let mut tracker = DictTracker::new_default_dict(&relocatable!($tracker_num, 0), &$crate::types::relocatable::MaybeRelocatable::from(Felt::new($default)), None);
             $(
-            tracker.insert_value(&Felt::new($key), &Felt::new($val));
+            tracker.insert_value(&$crate::types::relocatable::MaybeRelocatable::from(Felt::new($key)), &$crate::types::relocatable::MaybeRelocatable::from(Felt::new($val)));
 
             )*
             let mut dict_manager = DictManager::new();
@@ -476,7 +511,7 @@ pub mod test_utils {
             $exec_scopes.insert_value("dict_manager", Rc::new(RefCell::new(dict_manager)))
         };
         ($exec_scopes:expr, $tracker_num:expr,$default:expr) => {
-            let tracker = DictTracker::new_default_dict(&relocatable!($tracker_num, 0), &Felt::new($default), None);
+            let tracker = DictTracker::new_default_dict(&relocatable!($tracker_num, 0), &$crate::types::relocatable::MaybeRelocatable::from(Felt::new($default)), None);
             let mut dict_manager = DictManager::new();
             dict_manager.trackers.insert(2, tracker);
             $exec_scopes.insert_value("dict_manager", Rc::new(RefCell::new(dict_manager)))
@@ -531,7 +566,7 @@ mod test {
     };
     use felt::{Felt, NewFelt};
     use num_traits::One;
-    use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+    use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc, sync::Arc};
 
     use super::*;
 
@@ -580,6 +615,19 @@ mod test {
         check_memory![memory, ((1, 1), (1, 0)), ((1, 2), 1)];
     }
 
+    #[test]
+    fn assert_memory_eq_macro_test() {
+        let memory = memory![((1, 2), 1), ((1, 1), (1, 0))];
+        assert_memory_eq!(memory, memory![((1, 1), (1, 0)), ((1, 2), 1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "memories differ in 2 cells (showing at most 1):\n0:0: 1 != 2")]
+    fn assert_memory_eq_macro_limits_output() {
+        let memory = memory![((0, 0), 1), ((1, 0), 3)];
+        assert_memory_eq!(memory, memory![((0, 0), 2), ((1, 0), 4)], 1);
+    }
+
     #[test]
     fn check_memory_address_macro_test() {
         let mut memory = Memory::new();
@@ -858,13 +906,14 @@ mod test {
             main: None,
             start: None,
             end: None,
-            hints: HashMap::new(),
+            hints: Arc::new(HashMap::new()),
             reference_manager: ReferenceManager {
                 references: Vec::new(),
             },
             identifiers: HashMap::new(),
             error_message_attributes: Vec::new(),
             instruction_locations: None,
+            compiler_version: None,
         };
 
         assert_eq!(program, program!())
@@ -880,13 +929,14 @@ mod test {
             main: None,
             start: None,
             end: None,
-            hints: HashMap::new(),
+            hints: Arc::new(HashMap::new()),
             reference_manager: ReferenceManager {
                 references: Vec::new(),
             },
             identifiers: HashMap::new(),
             error_message_attributes: Vec::new(),
             instruction_locations: None,
+            compiler_version: None,
         };
 
         assert_eq!(program, program!["range_check"])
@@ -902,13 +952,14 @@ mod test {
             main: Some(2),
             start: None,
             end: None,
-            hints: HashMap::new(),
+            hints: Arc::new(HashMap::new()),
             reference_manager: ReferenceManager {
                 references: Vec::new(),
             },
             identifiers: HashMap::new(),
             error_message_attributes: Vec::new(),
             instruction_locations: None,
+            compiler_version: None,
         };
 
         assert_eq!(
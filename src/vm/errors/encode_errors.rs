@@ -0,0 +1,16 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum EncodeError {
+    #[error(transparent)]
+    IO(#[from] io::Error),
+    #[error("Memory must be relocated before it is encoded")]
+    MemoryNotRelocated,
+    #[error("Encoded memory ends with an incomplete cell of {0} bytes")]
+    TruncatedCell(usize),
+    #[error("Encoded memory address {0} isn't greater than the previous one")]
+    UnsortedAddress(u64),
+    #[error("Encoded memory value at address {0} isn't a valid felt")]
+    InvalidValue(u64),
+}
@@ -1,10 +1,13 @@
-use felt::Felt;
+use felt::{Felt, SignedFeltDisplay};
 use num_bigint::{BigInt, BigUint};
+use std::{collections::HashMap, fmt};
 use thiserror::Error;
 
 use crate::types::relocatable::{MaybeRelocatable, Relocatable};
 
-use super::{exec_scope_errors::ExecScopeError, vm_errors::VirtualMachineError};
+use super::{
+    exec_scope_errors::ExecScopeError, math_errors::MathError, vm_errors::VirtualMachineError,
+};
 
 #[derive(Debug, PartialEq, Error)]
 pub enum HintError {
@@ -20,28 +23,33 @@ pub enum HintError {
     MissingConstant(&'static str),
     #[error("Fail to get constants for hint execution")]
     FailedToGetConstant,
-    #[error("Arc too big, {0} must be <= {1} and {2} <= {3}")]
+    #[error("Arc too big, {} must be <= {} and {} <= {}", SignedFeltDisplay(.0), SignedFeltDisplay(.1), SignedFeltDisplay(.2), SignedFeltDisplay(.3))]
     ArcTooBig(Felt, Felt, Felt, Felt),
-    #[error("Excluded is supposed to be 2, got {0}")]
+    #[error("Excluded is supposed to be 2, got {}", SignedFeltDisplay(.0))]
     ExcludedNot2(Felt),
-    #[error("Value: {0} is outside of the range [0, 2**250)")]
+    #[error("Value: {} is outside of the range [0, 2**250)", SignedFeltDisplay(.0))]
     ValueOutside250BitRange(Felt),
     #[error("Failed to get scope variables")]
     ScopeError,
     #[error("Variable {0} not present in current execution scope")]
     VariableNotInScopeError(String),
+    #[error("Variable {0} in current execution scope is not of type {1}")]
+    WrongScopeTypeError(String, String),
     #[error("DictManagerError: Tried to create tracker for a dictionary on segment: {0} when there is already a tracker for a dictionary on this segment")]
     CantCreateDictionaryOnTakenSegment(isize),
     #[error("Dict Error: No dict tracker found for segment {0}")]
     NoDictTracker(isize),
     #[error("Dict Error: No value found for key: {0}")]
-    NoValueForKey(Felt),
-    #[error("Assertion failed, a = {0} % PRIME is not less than b = {1} % PRIME")]
+    NoValueForKey(MaybeRelocatable),
+    #[error("Assertion failed, a = {} % PRIME is not less than b = {} % PRIME", SignedFeltDisplay(.0), SignedFeltDisplay(.1))]
     AssertLtFelt(Felt, Felt),
-    #[error("find_element() can only be used with n_elms <= {0}.\nGot: n_elms = {1}")]
+    #[error("find_element() can only be used with n_elms <= {}.\nGot: n_elms = {}", SignedFeltDisplay(.0), SignedFeltDisplay(.1))]
     FindElemMaxSize(Felt, Felt),
     #[error(
-        "Invalid index found in find_element_index. Index: {0}.\nExpected key: {1}, found_key {2}"
+        "Invalid index found in find_element_index. Index: {}.\nExpected key: {}, found_key {}",
+        SignedFeltDisplay(.0),
+        SignedFeltDisplay(.1),
+        SignedFeltDisplay(.2)
     )]
     InvalidIndex(Felt, Felt, Felt),
     #[error("Found Key is None")]
@@ -54,7 +62,7 @@ pub enum HintError {
     InvalidApValue(MaybeRelocatable),
     #[error("Dict Error: Tried to create a dict whithout an initial dict")]
     NoInitialDict,
-    #[error("squash_dict_inner fail: couldnt find key {0} in accesses_indices")]
+    #[error("squash_dict_inner fail: couldnt find key {} in accesses_indices", SignedFeltDisplay(.0))]
     NoKeyInAccessIndices(Felt),
     #[error("squash_dict_inner fail: local accessed_indices is empty")]
     EmptyAccessIndices,
@@ -63,8 +71,8 @@ pub enum HintError {
     #[error("squash_dict_inner fail: local current_accessed_indices not empty, loop ended with remaining unaccounted elements")]
     CurrentAccessIndicesNotEmpty,
     #[error("Dict Error: Got the wrong value for dict_update, expected value: {0}, got: {1} for key: {2}")]
-    WrongPrevValue(Felt, Felt, Felt),
-    #[error("squash_dict_inner fail: Number of used accesses:{0} doesnt match the lengh: {1} of the access_indices at key: {2}")]
+    WrongPrevValue(MaybeRelocatable, MaybeRelocatable, MaybeRelocatable),
+    #[error("squash_dict_inner fail: Number of used accesses:{} doesnt match the lengh: {} of the access_indices at key: {}", SignedFeltDisplay(.0), .1, SignedFeltDisplay(.2))]
     NumUsedAccessesAssertFail(Felt, usize, Felt),
     #[error("squash_dict_inner fail: local keys is not empty")]
     KeysNotEmpty,
@@ -72,15 +80,17 @@ pub enum HintError {
     EmptyKeys,
     #[error("squash_dict fail: Accesses array size must be divisible by DictAccess.SIZE")]
     PtrDiffNotDivisibleByDictAccessSize,
-    #[error("squash_dict() can only be used with n_accesses<={0}. ' \nGot: n_accesses={1}")]
+    #[error("squash_dict() can only be used with n_accesses<={}. ' \nGot: n_accesses={}", SignedFeltDisplay(.0), SignedFeltDisplay(.1))]
     SquashDictMaxSizeExceeded(Felt, Felt),
-    #[error("squash_dict fail: n_accesses: {0} is too big to be converted into an iterator")]
+    #[error("squash_dict fail: n_accesses: {} is too big to be converted into an iterator", SignedFeltDisplay(.0))]
     NAccessesTooBig(Felt),
     #[error(transparent)]
     Internal(#[from] VirtualMachineError),
+    #[error(transparent)]
+    Math(#[from] MathError),
     #[error("Couldn't convert BigInt to usize")]
     BigintToUsizeFail,
-    #[error("usort() can only be used with input_len<={0}. Got: input_len={1}.")]
+    #[error("usort() can only be used with input_len<={}. Got: input_len={}.", .0, SignedFeltDisplay(.1))]
     UsortOutOfRange(u64, Felt),
     #[error("unexpected usort fail: positions_dict or key value pair not found")]
     UnexpectedPositionsDictFail,
@@ -94,6 +104,8 @@ pub enum HintError {
     LastPosNotFound,
     #[error("Set's starting point {0} is bigger it's ending point {1}")]
     InvalidSetRange(MaybeRelocatable, MaybeRelocatable),
+    #[error("Set's length {0} is not a multiple of the element size {1}")]
+    InvalidSetLength(usize, usize),
     #[error("Failed to construct a fixed size array of size: {0}")]
     FixedSizeArrayFail(usize),
     #[error("{0}")]
@@ -106,11 +118,11 @@ pub enum HintError {
     SecpSplitOutOfRange(BigUint),
     #[error("verify_zero: Invalid input {0}")]
     SecpVerifyZero(BigInt),
-    #[error("unsafe_keccak() can only be used with length<={0}. Got: length={1}")]
+    #[error("unsafe_keccak() can only be used with length<={}. Got: length={}", SignedFeltDisplay(.0), SignedFeltDisplay(.1))]
     KeccakMaxSize(Felt, Felt),
-    #[error("Invalid word size: {0}")]
+    #[error("Invalid word size: {}", SignedFeltDisplay(.0))]
     InvalidWordSize(Felt),
-    #[error("Invalid input length, Got: length={0}")]
+    #[error("Invalid input length, Got: length={}", SignedFeltDisplay(.0))]
     InvalidKeccakInputLength(Felt),
     #[error(transparent)]
     FromScopeError(#[from] ExecScopeError),
@@ -118,28 +130,57 @@ pub enum HintError {
     AssertNotEqualFail(MaybeRelocatable, MaybeRelocatable),
     #[error("split_int(): value is out of range")]
     SplitIntNotZero,
-    #[error("split_int(): Limb {0} is out of range.")]
+    #[error("split_int(): Limb {} is out of range.", SignedFeltDisplay(.0))]
     SplitIntLimbOutOfRange(Felt),
-    #[error("Expected size to be in the range from [0, 100), got: {0}")]
+    #[error("Expected size to be in the range from [0, 100), got: {}", SignedFeltDisplay(.0))]
     InvalidKeccakStateSizeFelts(Felt),
-    #[error("Expected size to be in range from [0, 10), got: {0}")]
+    #[error("Expected size to be in range from [0, 10), got: {}", SignedFeltDisplay(.0))]
     InvalidBlockSize(Felt),
+    #[error("Keccak state word at {0} must be a 64-bit value, got: {}", SignedFeltDisplay(.1))]
+    InvalidKeccakStateWord(Relocatable, Felt),
     #[error("Couldn't convert BigInt to u32")]
     BigintToU32Fail,
     #[error("BigInt to BigUint failed, BigInt is negative")]
     BigIntToBigUintFail,
-    #[error("Assertion failed, 0 <= ids.a % PRIME < range_check_builtin.bound \n a = {0} is out of range")]
+    #[error("Assertion failed, 0 <= ids.a % PRIME < range_check_builtin.bound \n a = {} is out of range", SignedFeltDisplay(.0))]
     ValueOutOfRange(Felt),
-    #[error("Assertion failed, 0 <= ids.a % PRIME < range_check_builtin.bound \n a = {0} is out of range")]
+    #[error("Assertion failed, 0 <= ids.a % PRIME < range_check_builtin.bound \n a = {} is out of range", SignedFeltDisplay(.0))]
     AssertNNValueOutOfRange(Felt),
-    #[error("Assertion failed, {0} % {1} is equal to 0")]
+    #[error("Assertion failed, {} % {} is equal to 0", SignedFeltDisplay(.0), .1)]
     AssertNotZero(Felt, String),
-    #[error("Div out of range: 0 < {0} <= {1}")]
+    #[error("Div out of range: 0 < {} <= {}", SignedFeltDisplay(.0), SignedFeltDisplay(.1))]
     OutOfValidRange(Felt, Felt),
-    #[error("Value: {0} is outside valid range")]
+    #[error("Value: {} is outside valid range", SignedFeltDisplay(.0))]
     ValueOutsideValidRange(Felt),
-    #[error("Assertion failed, {0}, is not less or equal to {1}")]
+    #[error("Assertion failed, {}, is not less or equal to {}", SignedFeltDisplay(.0), SignedFeltDisplay(.1))]
     NonLeFelt(Felt, Felt),
+    #[error("Uint256 division by zero")]
+    Uint256DivByZero,
+    #[error("Uint256 limb {0} is out of range [0, 2**128)")]
+    Uint256LimbOutOfRange(Felt),
+    #[error("BigInt3 limb {0} is out of range [0, 2**128)")]
+    BigIntLimbOutOfRange(Felt),
     #[error("Unknown Hint: {0}")]
     UnknownHint(String),
+    #[error("{0}\n{}", IdsSnapshotDisplay(.1))]
+    WithIdsSnapshot(Box<HintError>, HashMap<String, Option<MaybeRelocatable>>),
+}
+
+// Renders the ids of a failed hint as a table sorted by name, marking the unreadable ones
+struct IdsSnapshotDisplay<'a>(&'a HashMap<String, Option<MaybeRelocatable>>);
+
+impl fmt::Display for IdsSnapshotDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut ids: Vec<_> = self.0.iter().collect();
+        ids.sort_by(|(a, _), (b, _)| a.cmp(b));
+        let width = ids.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+        write!(f, "ids:")?;
+        for (name, value) in ids {
+            match value {
+                Some(value) => write!(f, "\n    {name:<width$} | {value}")?,
+                None => write!(f, "\n    {name:<width$} | <unknown>")?,
+            }
+        }
+        Ok(())
+    }
 }
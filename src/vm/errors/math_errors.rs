@@ -0,0 +1,21 @@
+use crate::types::relocatable::Relocatable;
+use felt::{Felt, SignedFeltDisplay};
+use thiserror::Error;
+
+#[derive(Debug, PartialEq, Error)]
+pub enum MathError {
+    #[error("Can only subtract two relocatable values of the same segment, got {0} and {1}")]
+    RelocatableSubDifferentSegments(Relocatable, Relocatable),
+    #[error("Cant subtract {1} from relocatable {0}, offsets cant be negative")]
+    RelocatableSubNegOffset(Relocatable, usize),
+    #[error("Adding {1} to relocatable {0} overflows its offset")]
+    RelocatableAddOffsetOverflow(Relocatable, usize),
+    #[error("Adding {} to relocatable {} overflows its offset", SignedFeltDisplay(.1), .0)]
+    RelocatableAddFeltOffsetOverflow(Relocatable, Felt),
+    #[error("Cant subtract {} from relocatable {}, offsets cant be negative", SignedFeltDisplay(.1), .0)]
+    RelocatableSubFeltNegOffset(Relocatable, Felt),
+    #[error("Felt {0} doesn't fit in a usize")]
+    FeltToUsizeConversion(Felt),
+    #[error("Felt {0} doesn't fit in a u64")]
+    FeltToU64Conversion(Felt),
+}
@@ -18,6 +18,8 @@ pub enum MemoryError {
     EffectiveSizesNotCalled,
     #[error("Inconsistent Relocation")]
     Relocation,
+    #[error("Can't relocate the value {1} stored at {0}: {2}")]
+    ValueRelocation(Relocatable, Relocatable, Box<MemoryError>),
     #[error("Could not cast arguments")]
     WriteArg,
     #[error("Memory addresses mustn't be in a TemporarySegment, segment: {0}")]
@@ -36,6 +38,8 @@ pub enum MemoryError {
     MissingSegmentUsedSizes,
     #[error("Segment at index {0} either doesn't exist or is not finalized.")]
     SegmentNotFinalized(usize),
+    #[error("Segment at index {0} uses {1} cells but was finalized with size {2}")]
+    SegmentSizeMismatch(usize, usize, usize),
     #[error("Invalid memory value at address {0:?}: {1:?}")]
     InvalidMemoryValue(Relocatable, MaybeRelocatable),
     #[error("Found a memory gap when calling get_continuous_range")]
@@ -44,6 +48,8 @@ pub enum MemoryError {
     ErrorCalculatingMemoryUnits,
     #[error("Number of steps is insufficient in the builtin.")]
     InsufficientAllocatedCells,
+    #[error("The {0} builtin uses {1} cells, but only {2} are allocated. The number of steps is insufficient")]
+    InsufficientAllocatedCellsError(&'static str, usize, usize),
     #[error("Missing memory cells for builtin {0}")]
     MissingMemoryCells(&'static str),
     #[error("Missing memory cells for builtin {0}: {1:?}")]
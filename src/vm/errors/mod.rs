@@ -1,8 +1,11 @@
 pub mod cairo_run_errors;
+pub mod encode_errors;
 pub mod exec_scope_errors;
 pub mod hint_errors;
+pub mod math_errors;
 pub mod memory_errors;
 pub mod runner_errors;
+pub mod security_errors;
 pub mod trace_errors;
 pub mod vm_errors;
 pub mod vm_exception;
@@ -1,7 +1,7 @@
 use std::collections::HashSet;
 
 use super::memory_errors::MemoryError;
-use crate::types::relocatable::MaybeRelocatable;
+use crate::types::relocatable::{MaybeRelocatable, Relocatable};
 use felt::Felt;
 use thiserror::Error;
 
@@ -13,8 +13,10 @@ pub enum RunnerError {
     NoExecBaseForEntrypoint,
     #[error("Initialization failure: No program base")]
     NoProgBase,
-    #[error("Missing main()")]
+    #[error("Missing main(), programs loaded without an entrypoint must be run with run_from_entrypoint")]
     MissingMain,
+    #[error("Can't run a program that wasn't initialized with an entrypoint")]
+    NoEntrypoint,
     #[error("Uninitialized base for builtin")]
     UninitializedBase,
     #[error("Base for builtin is not finished")]
@@ -105,4 +107,22 @@ pub enum RunnerError {
     SafeDivFailUsize(usize, usize),
     #[error(transparent)]
     MemoryError(#[from] MemoryError),
+    #[error("Entrypoint {0} not found in the program identifiers")]
+    EntrypointNotFound(String),
+    #[error("Output page {0} is already in use")]
+    OutputPageIdInUse(usize),
+    #[error("Output page start {0} is not in the output segment {1}")]
+    OutputPageNotInSegment(Relocatable, isize),
+    #[error("Output page {0} overlaps with output page {1}")]
+    OutputPagesOverlap(usize, usize),
+    #[error("Output page {0} of size {1} overflows the output segment")]
+    OutputPageSizeOverflow(usize, usize),
+    #[error("Output page {0} exceeds the {1} used cells of the output segment")]
+    OutputPageOutOfBounds(usize, usize),
+    #[error("Expected the return fp and pc to point to the start of their segments")]
+    InvalidReturnPointers,
+    #[error("Expected a relocated, non-empty trace")]
+    NoRelocatedTrace,
+    #[error("Public memory address {0} is not in the relocated memory")]
+    PublicMemoryCellNotFound(usize),
 }
@@ -0,0 +1,26 @@
+use thiserror::Error;
+
+use crate::types::relocatable::{MaybeRelocatable, Relocatable};
+use crate::vm::errors::{
+    memory_errors::MemoryError, runner_errors::RunnerError, vm_errors::VirtualMachineError,
+};
+
+#[derive(Debug, PartialEq, Error)]
+pub enum SecurityError {
+    #[error("Out of bounds access to builtin segment {0} at {1:?}")]
+    OutOfBoundsBuiltinSegmentAccess(&'static str, Relocatable),
+    #[error("Out of bounds access to program segment at {0:?}")]
+    OutOfBoundsProgramSegmentAccess(Relocatable),
+    #[error("Out of bounds access at {0:?}, the segment was finalized with size {1}")]
+    OutOfBoundsSegmentAccess(Relocatable, usize),
+    #[error("Program memory at {0:?} was modified during execution, expected {1:?}, got {2:?}")]
+    ModifiedProgramSegment(Relocatable, MaybeRelocatable, Option<MaybeRelocatable>),
+    #[error("Invalid memory value at address {0:?}: {1:?}")]
+    InvalidMemoryValue(Relocatable, MaybeRelocatable),
+    #[error(transparent)]
+    Runner(#[from] RunnerError),
+    #[error(transparent)]
+    Memory(#[from] MemoryError),
+    #[error(transparent)]
+    VirtualMachine(#[from] Box<VirtualMachineError>),
+}
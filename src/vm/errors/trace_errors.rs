@@ -11,6 +11,8 @@ pub enum TraceError {
     RegNotRelocatable,
     #[error("No relocation found for this segment")]
     NoRelocationFound,
+    #[error("Failed to write trace entry at position {0}: {1}")]
+    WriteFail(usize, String),
     #[error(transparent)]
     MemoryError(#[from] MemoryError),
 }
@@ -1,11 +1,12 @@
 use crate::{
     types::relocatable::{MaybeRelocatable, Relocatable},
     vm::errors::{
-        exec_scope_errors::ExecScopeError, hint_errors::HintError, memory_errors::MemoryError,
-        runner_errors::RunnerError, trace_errors::TraceError,
+        exec_scope_errors::ExecScopeError, hint_errors::HintError, math_errors::MathError,
+        memory_errors::MemoryError, runner_errors::RunnerError, security_errors::SecurityError,
+        trace_errors::TraceError,
     },
 };
-use felt::Felt;
+use felt::{Felt, SignedFeltDisplay};
 use num_bigint::{BigInt, BigUint};
 use thiserror::Error;
 
@@ -49,12 +50,8 @@ pub enum VirtualMachineError {
     InvalidOpcode(i64),
     #[error("Cannot add two relocatable values")]
     RelocatableAdd,
-    #[error("Offset {0} exeeds maximum offset value")]
-    OffsetExceeded(Felt),
     #[error("This is not implemented")]
     NotImplemented,
-    #[error("Can only subtract two relocatable values of the same segment")]
-    DiffIndexSub,
     #[error("Inconsistent auto-deduction for builtin {0}, expected {1}, got {2:?}")]
     InconsistentAutoDeduction(String, MaybeRelocatable, Option<MaybeRelocatable>),
     #[error(transparent)]
@@ -63,6 +60,10 @@ pub enum VirtualMachineError {
     InvalidHintEncoding(MaybeRelocatable),
     #[error(transparent)]
     MemoryError(#[from] MemoryError),
+    #[error(transparent)]
+    MathError(#[from] MathError),
+    #[error(transparent)]
+    SecurityError(#[from] SecurityError),
     #[error("Expected range_check builtin to be present")]
     NoRangeCheckBuiltin,
     #[error("Expected ecdsa builtin to be present")]
@@ -73,9 +74,9 @@ pub enum VirtualMachineError {
     ExpectedInteger(MaybeRelocatable),
     #[error("Expected relocatable at address {0}")]
     ExpectedRelocatable(MaybeRelocatable),
-    #[error("Value: {0} should be positive")]
+    #[error("Value: {} should be positive", SignedFeltDisplay(.0))]
     ValueNotPositive(Felt),
-    #[error("Div out of range: 0 < {0} <= {1}")]
+    #[error("Div out of range: 0 < {} <= {}", SignedFeltDisplay(.0), SignedFeltDisplay(.1))]
     OutOfValidRange(Felt, Felt),
     #[error("Failed to compare {0} and {1}, cant compare a relocatable to an integer value")]
     DiffTypeComparison(MaybeRelocatable, MaybeRelocatable),
@@ -91,9 +92,9 @@ pub enum VirtualMachineError {
     NoneInMemoryRange,
     #[error("Couldn't convert usize to u32")]
     UsizeToU32Fail,
-    #[error("Can't calculate the square root of negative number: {0})")]
+    #[error("Can't calculate the square root of negative number: {})", SignedFeltDisplay(.0))]
     SqrtNegative(Felt),
-    #[error("{0} is not divisible by {1}")]
+    #[error("{} is not divisible by {}", SignedFeltDisplay(.0), SignedFeltDisplay(.1))]
     SafeDivFail(Felt, Felt),
     #[error("{0} is not divisible by {1}")]
     SafeDivFailBigInt(BigInt, BigInt),
@@ -115,8 +116,6 @@ pub enum VirtualMachineError {
     CompileHintFail(String),
     #[error("op1_addr is Op1Addr.IMM, but no immediate was given")]
     NoImm,
-    #[error("Cant substract {0} from offset {1}, offsets cant be negative")]
-    CantSubOffset(usize, usize),
     #[error("Execution reached the end of the program. Requested remaining steps: {0}.")]
     EndOfProgram(usize),
     #[error(transparent)]
@@ -125,6 +124,8 @@ pub enum VirtualMachineError {
     MainScopeError(#[from] ExecScopeError),
     #[error("Current run is not finished")]
     RunNotFinished,
+    #[error("Execution ran out of steps before reaching the end of the program")]
+    UnfinishedExecution,
     #[error("Invalid argument count, expected {0} but got {1}")]
     InvalidArgCount(usize, usize),
     #[error("Couldn't parse prime: {0}")]
@@ -133,6 +134,10 @@ pub enum VirtualMachineError {
     ErrorMessageAttribute(String, Box<VirtualMachineError>),
     #[error("Got an exception while executing a hint: {1}")]
     Hint(usize, Box<HintError>),
+    #[error("Execution aborted by hook: {0}")]
+    Hook(String),
+    #[error("Execution cancelled at pc {0} after {1} steps")]
+    Cancelled(Relocatable, usize),
     #[error("Unexpected Failure")]
     Unexpected,
 }
@@ -186,6 +186,7 @@ mod test {
     use crate::types::program::Program;
     use crate::types::relocatable::Relocatable;
     use crate::utils::test_utils::*;
+    use crate::vm::runners::cairo_runner::RunResources;
 
     use super::*;
     #[test]
@@ -392,6 +393,7 @@ mod test {
             start_pc: 1,
             end_pc: 5,
             value: String::from("Invalid hash"),
+            flow_tracking_data: None,
         }];
         let program = program!(error_message_attributes = attributes,);
         let runner = cairo_runner!(program);
@@ -408,6 +410,7 @@ mod test {
             start_pc: 1,
             end_pc: 5,
             value: String::from("Invalid hash"),
+            flow_tracking_data: None,
         }];
         let program = program!(error_message_attributes = attributes,);
         let runner = cairo_runner!(program);
@@ -510,7 +513,12 @@ mod test {
 
         let end = cairo_runner.initialize(&mut vm).unwrap();
         assert!(cairo_runner
-            .run_until_pc(end, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            )
             .is_err());
         let expected_traceback = String::from("Cairo traceback (most recent call last):\ncairo_programs/bad_programs/bad_dict_update.cairo:10:5: (pc=0:34)\n    dict_update{dict_ptr=my_dict}(key=2, prev_value=3, new_value=4);\n    ^*************************************************************^\n");
         assert_eq!(get_traceback(&vm, &cairo_runner), Some(expected_traceback));
@@ -530,7 +538,12 @@ mod test {
 
         let end = cairo_runner.initialize(&mut vm).unwrap();
         assert!(cairo_runner
-            .run_until_pc(end, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            )
             .is_err());
         let expected_traceback = String::from("Cairo traceback (most recent call last):\ncairo_programs/bad_programs/bad_usort.cairo:91:48: (pc=0:97)\n    let (output_len, output, multiplicities) = usort(input_len=3, input=input_array);\n                                               ^***********************************^\ncairo_programs/bad_programs/bad_usort.cairo:36:5: (pc=0:30)\n    verify_usort{output=output}(\n    ^**************************^\ncairo_programs/bad_programs/bad_usort.cairo:64:5: (pc=0:60)\n    verify_multiplicity(multiplicity=multiplicity, input_len=input_len, input=input, value=value);\n    ^*******************************************************************************************^\n");
         assert_eq!(get_traceback(&vm, &cairo_runner), Some(expected_traceback));
@@ -665,7 +678,12 @@ cairo_programs/bad_programs/bad_range_check.cairo:11:5: (pc=0:6)
 
         let end = cairo_runner.initialize(&mut vm).unwrap();
         let error = cairo_runner
-            .run_until_pc(end, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap_err();
         let vm_excepction = VmException::from_vm_error(&cairo_runner, &vm, error);
         assert_eq!(vm_excepction.to_string(), expected_error_string);
@@ -700,9 +718,182 @@ cairo_programs/bad_programs/bad_usort.cairo:64:5: (pc=0:60)
 
         let end = cairo_runner.initialize(&mut vm).unwrap();
         let error = cairo_runner
-            .run_until_pc(end, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap_err();
         let vm_excepction = VmException::from_vm_error(&cairo_runner, &vm, error);
         assert_eq!(vm_excepction.to_string(), expected_error_string);
     }
+
+    #[test]
+    fn run_failing_assert_and_check_error_message_attribute() {
+        let program_json = r#"
+            {
+                "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+                "attributes": [
+                    {
+                        "accessible_scopes": ["__main__", "__main__.main"],
+                        "end_pc": 4,
+                        "flow_tracking_data": {
+                            "ap_tracking": {
+                                "group": 0,
+                                "offset": 1
+                            },
+                            "reference_ids": {}
+                        },
+                        "name": "error_message",
+                        "start_pc": 2,
+                        "value": "x must be 3"
+                    }
+                ],
+                "builtins": [],
+                "data": [
+                    "0x480680017fff8000",
+                    "0x2",
+                    "0x400680017fff7fff",
+                    "0x3",
+                    "0x208b7fff7fff7ffe"
+                ],
+                "identifiers": {
+                    "__main__.main": {
+                        "decorators": [],
+                        "pc": 0,
+                        "type": "function"
+                    }
+                },
+                "hints": {},
+                "reference_manager": {
+                    "references": []
+                }
+            }"#;
+        let program = Program::from_reader(program_json.as_bytes(), Some("main"))
+            .expect("Call to `Program::from_reader()` failed.");
+        assert_eq!(program.error_message_attributes.len(), 1);
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = VirtualMachine::new(false, program.error_message_attributes.clone());
+
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        let error = cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap_err();
+        let vm_exception = VmException::from_vm_error(&cairo_runner, &vm, error);
+        assert_eq!(vm_exception.pc, 2);
+        assert_eq!(
+            vm_exception.error_attr_value,
+            Some(String::from("Error message: x must be 3\n"))
+        );
+        assert_eq!(
+            vm_exception.inner_exc,
+            VirtualMachineError::ErrorMessageAttribute(
+                String::from("x must be 3"),
+                Box::new(VirtualMachineError::DiffAssertValues(
+                    mayberelocatable!(2),
+                    mayberelocatable!(3)
+                ))
+            )
+        );
+        assert!(vm_exception
+            .to_string()
+            .starts_with("Error message: x must be 3\nError at pc=0:2:\n"));
+    }
+
+    #[test]
+    fn run_divide_by_zero_and_check_error_displayed() {
+        // main calls div_by_zero, which tries to deduce x from 5 = 0 * x
+        let program_json = r#"
+            {
+                "prime": "0x800000000000011000000000000000000000000000000000000000000000001",
+                "attributes": [],
+                "builtins": [],
+                "data": [
+                    "0x1104800180018000",
+                    "0x3",
+                    "0x208b7fff7fff7ffe",
+                    "0x480680017fff8000",
+                    "0x0",
+                    "0x480680017fff8000",
+                    "0x5",
+                    "0x405080007ffe7fff",
+                    "0x208b7fff7fff7ffe"
+                ],
+                "debug_info": {
+                    "instruction_locations": {
+                        "0": {
+                            "hints": [],
+                            "inst": {
+                                "end_col": 19,
+                                "end_line": 8,
+                                "input_file": {
+                                    "filename": "divide_by_zero.cairo"
+                                },
+                                "start_col": 5,
+                                "start_line": 8
+                            }
+                        },
+                        "7": {
+                            "hints": [],
+                            "inst": {
+                                "end_col": 24,
+                                "end_line": 3,
+                                "input_file": {
+                                    "filename": "divide_by_zero.cairo"
+                                },
+                                "start_col": 5,
+                                "start_line": 3
+                            }
+                        }
+                    }
+                },
+                "identifiers": {
+                    "__main__.main": {
+                        "decorators": [],
+                        "pc": 0,
+                        "type": "function"
+                    },
+                    "__main__.div_by_zero": {
+                        "decorators": [],
+                        "pc": 3,
+                        "type": "function"
+                    }
+                },
+                "hints": {},
+                "reference_manager": {
+                    "references": []
+                }
+            }"#;
+        let expected_error_string = r#"divide_by_zero.cairo:3:5: Error at pc=0:7:
+Couldn't compute operand op1 at address 1:6
+Cairo traceback (most recent call last):
+divide_by_zero.cairo:8:5: (pc=0:0)
+"#;
+        let program = Program::from_reader(program_json.as_bytes(), Some("main"))
+            .expect("Call to `Program::from_reader()` failed.");
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        let error = cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap_err();
+        let vm_exception = VmException::from_vm_error(&cairo_runner, &vm, error);
+        assert_eq!(vm_exception.to_string(), expected_error_string);
+    }
 }
@@ -0,0 +1,205 @@
+//! Callbacks to observe the execution of a [`VirtualMachine`], available with the `hooks` feature.
+//!
+//! Every callback receives the current pc, ap and fp. Returning an error aborts the run with
+//! [`VirtualMachineError::Hook`].
+
+use crate::{
+    types::relocatable::{MaybeRelocatable, Relocatable},
+    vm::{errors::vm_errors::VirtualMachineError, vm_core::VirtualMachine},
+};
+
+/// Receives the pc, ap and fp of the VM.
+pub type StepHook = Box<dyn FnMut(Relocatable, Relocatable, Relocatable) -> Result<(), String>>;
+
+/// Receives the pc, ap and fp of the VM, followed by the address and the value being written.
+pub type MemoryWriteHook = Box<
+    dyn FnMut(
+        Relocatable,
+        Relocatable,
+        Relocatable,
+        Relocatable,
+        &MaybeRelocatable,
+    ) -> Result<(), String>,
+>;
+
+#[derive(Default)]
+pub struct Hooks {
+    /// Called before decoding each instruction.
+    pub pre_step: Option<StepHook>,
+    /// Called after each instruction has been executed and the registers updated.
+    pub post_step: Option<StepHook>,
+    /// Called before the VM writes a value into memory, either a deduced operand or a value
+    /// inserted through `VirtualMachine::insert_value`.
+    pub on_memory_write: Option<MemoryWriteHook>,
+}
+
+impl VirtualMachine {
+    pub fn set_hooks(&mut self, hooks: Hooks) {
+        self.hooks = hooks;
+    }
+
+    pub(crate) fn execute_pre_step_hook(&mut self) -> Result<(), VirtualMachineError> {
+        let (pc, ap, fp) = (*self.get_pc(), self.get_ap(), self.get_fp());
+        if let Some(hook) = self.hooks.pre_step.as_mut() {
+            hook(pc, ap, fp).map_err(VirtualMachineError::Hook)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn execute_post_step_hook(&mut self) -> Result<(), VirtualMachineError> {
+        let (pc, ap, fp) = (*self.get_pc(), self.get_ap(), self.get_fp());
+        if let Some(hook) = self.hooks.post_step.as_mut() {
+            hook(pc, ap, fp).map_err(VirtualMachineError::Hook)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn execute_memory_write_hook(
+        &mut self,
+        address: &Relocatable,
+        value: &MaybeRelocatable,
+    ) -> Result<(), VirtualMachineError> {
+        let (pc, ap, fp) = (*self.get_pc(), self.get_ap(), self.get_fp());
+        if let Some(hook) = self.hooks.on_memory_write.as_mut() {
+            hook(pc, ap, fp, *address, value).map_err(VirtualMachineError::Hook)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
+    use crate::types::program::Program;
+    use crate::utils::test_utils::*;
+    use crate::vm::runners::cairo_runner::{CairoRunner, RunResources};
+    use std::{cell::RefCell, path::Path, rc::Rc};
+
+    fn fibonacci_runner() -> CairoRunner {
+        let program = Program::from_file(Path::new("cairo_programs/fibonacci.json"), Some("main"))
+            .expect("Call to `Program::from_file()` failed.");
+        cairo_runner!(program)
+    }
+
+    #[test]
+    fn pre_step_hook_sees_trace_pcs() {
+        let mut cairo_runner = fibonacci_runner();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut vm = vm!(true);
+
+        let pcs = Rc::new(RefCell::new(Vec::new()));
+        let hook_pcs = pcs.clone();
+        vm.set_hooks(Hooks {
+            pre_step: Some(Box::new(move |pc, _ap, _fp| {
+                hook_pcs.borrow_mut().push(pc);
+                Ok(())
+            })),
+            ..Default::default()
+        });
+
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap();
+
+        let trace_pcs: Vec<Relocatable> = vm
+            .trace
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|entry| entry.pc)
+            .collect();
+        assert!(!trace_pcs.is_empty());
+        assert_eq!(*pcs.borrow(), trace_pcs);
+    }
+
+    #[test]
+    fn post_step_hook_called_once_per_step() {
+        let mut cairo_runner = fibonacci_runner();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut vm = vm!();
+
+        let steps = Rc::new(RefCell::new(0_usize));
+        let hook_steps = steps.clone();
+        vm.set_hooks(Hooks {
+            post_step: Some(Box::new(move |_pc, _ap, _fp| {
+                *hook_steps.borrow_mut() += 1;
+                Ok(())
+            })),
+            ..Default::default()
+        });
+
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap();
+
+        assert_eq!(*steps.borrow(), vm.current_step);
+    }
+
+    #[test]
+    fn memory_write_hook_sees_inserted_value() {
+        let mut vm = vm!();
+        vm.segments.add(&mut vm.memory);
+
+        let writes = Rc::new(RefCell::new(Vec::new()));
+        let hook_writes = writes.clone();
+        vm.set_hooks(Hooks {
+            on_memory_write: Some(Box::new(move |_pc, _ap, _fp, address, value| {
+                hook_writes.borrow_mut().push((address, value.clone()));
+                Ok(())
+            })),
+            ..Default::default()
+        });
+
+        vm.insert_value(&Relocatable::from((0, 3)), MaybeRelocatable::from((0, 1)))
+            .unwrap();
+        assert_eq!(
+            *writes.borrow(),
+            vec![(Relocatable::from((0, 3)), MaybeRelocatable::from((0, 1)))]
+        );
+    }
+
+    #[test]
+    fn pre_step_hook_error_aborts_run() {
+        let mut cairo_runner = fibonacci_runner();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut vm = vm!();
+
+        let mut steps = 0;
+        vm.set_hooks(Hooks {
+            pre_step: Some(Box::new(move |_pc, _ap, _fp| {
+                steps += 1;
+                if steps == 3 {
+                    Err(String::from("breakpoint"))
+                } else {
+                    Ok(())
+                }
+            })),
+            ..Default::default()
+        });
+
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        assert_eq!(
+            cairo_runner.run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            ),
+            Err(VirtualMachineError::Hook(String::from("breakpoint")))
+        );
+        assert_eq!(vm.current_step, 2);
+    }
+}
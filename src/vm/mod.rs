@@ -1,6 +1,8 @@
 pub mod context;
 pub mod decoding;
 pub mod errors;
+#[cfg(feature = "hooks")]
+pub mod hooks;
 pub mod runners;
 pub mod security;
 pub mod trace;
@@ -0,0 +1,196 @@
+use crate::serde::deserialize_program::felt_to_hex;
+use felt::Felt;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use std::collections::HashMap;
+
+/// The inputs of a single builtin instance, as expected by the prover.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PrivateInput {
+    // range_check
+    Value {
+        index: usize,
+        value: Felt,
+    },
+    // pedersen and bitwise
+    Pair {
+        index: usize,
+        x: Felt,
+        y: Felt,
+    },
+    EcOp {
+        index: usize,
+        p_x: Felt,
+        p_y: Felt,
+        m: Felt,
+        q_x: Felt,
+        q_y: Felt,
+    },
+    // keccak and poseidon, serialized as input_s0, input_s1, ...
+    State {
+        index: usize,
+        inputs: Vec<Felt>,
+    },
+    // ecdsa, where w is the inverse of s modulo the curve order
+    Signature {
+        index: usize,
+        pubkey: Felt,
+        msg: Felt,
+        r: Felt,
+        w: Felt,
+    },
+}
+
+/// The private input of a proof mode run: the paths of the trace and memory files
+/// and the inputs of every builtin instance, keyed by builtin name.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct AirPrivateInput {
+    pub trace_path: String,
+    pub memory_path: String,
+    #[serde(flatten)]
+    pub builtins: HashMap<String, Vec<PrivateInput>>,
+}
+
+impl AirPrivateInput {
+    pub fn serialize_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl Serialize for PrivateInput {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            PrivateInput::Value { index, value } => {
+                map.serialize_entry("index", index)?;
+                map.serialize_entry("value", &felt_to_hex(value))?;
+            }
+            PrivateInput::Pair { index, x, y } => {
+                map.serialize_entry("index", index)?;
+                map.serialize_entry("x", &felt_to_hex(x))?;
+                map.serialize_entry("y", &felt_to_hex(y))?;
+            }
+            PrivateInput::EcOp {
+                index,
+                p_x,
+                p_y,
+                m,
+                q_x,
+                q_y,
+            } => {
+                map.serialize_entry("index", index)?;
+                map.serialize_entry("p_x", &felt_to_hex(p_x))?;
+                map.serialize_entry("p_y", &felt_to_hex(p_y))?;
+                map.serialize_entry("m", &felt_to_hex(m))?;
+                map.serialize_entry("q_x", &felt_to_hex(q_x))?;
+                map.serialize_entry("q_y", &felt_to_hex(q_y))?;
+            }
+            PrivateInput::State { index, inputs } => {
+                map.serialize_entry("index", index)?;
+                for (i, input) in inputs.iter().enumerate() {
+                    map.serialize_entry(&format!("input_s{i}"), &felt_to_hex(input))?;
+                }
+            }
+            PrivateInput::Signature {
+                index,
+                pubkey,
+                msg,
+                r,
+                w,
+            } => {
+                map.serialize_entry("index", index)?;
+                map.serialize_entry("pubkey", &felt_to_hex(pubkey))?;
+                map.serialize_entry("msg", &felt_to_hex(msg))?;
+                let signature_input = [("r", felt_to_hex(r)), ("w", felt_to_hex(w))];
+                map.serialize_entry("signature_input", &HashMap::from(signature_input))?;
+            }
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use felt::NewFelt;
+
+    #[test]
+    fn serialize_value_input() {
+        let input = PrivateInput::Value {
+            index: 3,
+            value: Felt::new(255),
+        };
+        assert_eq!(
+            serde_json::to_string(&input).unwrap(),
+            r#"{"index":3,"value":"0xff"}"#
+        );
+    }
+
+    #[test]
+    fn serialize_ec_op_input() {
+        let input = PrivateInput::EcOp {
+            index: 0,
+            p_x: Felt::new(1),
+            p_y: Felt::new(2),
+            m: Felt::new(3),
+            q_x: Felt::new(4),
+            q_y: Felt::new(5),
+        };
+        assert_eq!(
+            serde_json::to_string(&input).unwrap(),
+            r#"{"index":0,"p_x":"0x1","p_y":"0x2","m":"0x3","q_x":"0x4","q_y":"0x5"}"#
+        );
+    }
+
+    #[test]
+    fn serialize_state_input() {
+        let input = PrivateInput::State {
+            index: 1,
+            inputs: vec![Felt::new(10), Felt::new(11), Felt::new(12)],
+        };
+        assert_eq!(
+            serde_json::to_string(&input).unwrap(),
+            r#"{"index":1,"input_s0":"0xa","input_s1":"0xb","input_s2":"0xc"}"#
+        );
+    }
+
+    #[test]
+    fn serialize_signature_input() {
+        let input = PrivateInput::Signature {
+            index: 0,
+            pubkey: Felt::new(1),
+            msg: Felt::new(2),
+            r: Felt::new(3),
+            w: Felt::new(4),
+        };
+        let value = serde_json::to_value(&input).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "index": 0,
+                "pubkey": "0x1",
+                "msg": "0x2",
+                "signature_input": {"r": "0x3", "w": "0x4"}
+            })
+        );
+    }
+
+    #[test]
+    fn serialize_private_input_flattens_builtins() {
+        let private_input = AirPrivateInput {
+            trace_path: "trace.bin".to_string(),
+            memory_path: "memory.bin".to_string(),
+            builtins: HashMap::from([(
+                "pedersen".to_string(),
+                vec![PrivateInput::Pair {
+                    index: 0,
+                    x: Felt::new(1),
+                    y: Felt::new(2),
+                }],
+            )]),
+        };
+        assert_eq!(
+            serde_json::to_string(&private_input).unwrap(),
+            r#"{"trace_path":"trace.bin","memory_path":"memory.bin","pedersen":[{"index":0,"x":"0x1","y":"0x2"}]}"#
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use crate::serde::deserialize_program::serialize_felt_hex;
+use felt::Felt;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+/// A public memory cell, at its relocated address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PublicMemoryEntry {
+    pub address: usize,
+    #[serde(serialize_with = "serialize_felt_hex")]
+    pub value: Felt,
+    pub page: usize,
+}
+
+/// The relocated first and past-the-end addresses of a memory segment.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct MemorySegmentAddresses {
+    pub begin_addr: usize,
+    pub stop_ptr: usize,
+}
+
+/// The public input of a proof mode run, in the JSON schema expected by the prover.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct PublicInput {
+    pub layout: String,
+    pub rc_min: isize,
+    pub rc_max: isize,
+    pub n_steps: usize,
+    // Keyed by segment name: "program", "execution" and the builtin names, sorted so that the
+    // serialized segments don't depend on the order in which they were added
+    pub memory_segments: BTreeMap<String, MemorySegmentAddresses>,
+    pub public_memory: Vec<PublicMemoryEntry>,
+    // Only set by dynamic layouts
+    pub dynamic_params: Option<HashMap<String, u64>>,
+}
+
+impl PublicInput {
+    pub fn serialize_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use felt::NewFelt;
+
+    #[test]
+    fn serialize_public_input() {
+        let public_input = PublicInput {
+            layout: "small".to_string(),
+            rc_min: 32763,
+            rc_max: 32769,
+            n_steps: 16,
+            memory_segments: BTreeMap::from([(
+                "program".to_string(),
+                MemorySegmentAddresses {
+                    begin_addr: 1,
+                    stop_ptr: 5,
+                },
+            )]),
+            public_memory: vec![PublicMemoryEntry {
+                address: 1,
+                value: Felt::new(0x40780017fff7fff_u64),
+                page: 0,
+            }],
+            dynamic_params: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&public_input).unwrap(),
+            r#"{"layout":"small","rc_min":32763,"rc_max":32769,"n_steps":16,"memory_segments":{"program":{"begin_addr":1,"stop_ptr":5}},"public_memory":[{"address":1,"value":"0x40780017fff7fff","page":0}],"dynamic_params":null}"#
+        );
+    }
+
+    #[test]
+    fn serialize_public_input_memory_segments_in_name_order() {
+        let segments = [
+            ("range_check", 24, 26),
+            ("program", 1, 5),
+            ("output", 22, 23),
+            ("execution", 6, 21),
+            ("pedersen", 23, 23),
+        ];
+        let public_input = PublicInput {
+            layout: "small".to_string(),
+            rc_min: 32763,
+            rc_max: 32769,
+            n_steps: 16,
+            memory_segments: segments
+                .iter()
+                .map(|&(name, begin_addr, stop_ptr)| {
+                    (
+                        name.to_string(),
+                        MemorySegmentAddresses {
+                            begin_addr,
+                            stop_ptr,
+                        },
+                    )
+                })
+                .collect(),
+            public_memory: Vec::new(),
+            dynamic_params: Some(HashMap::from([("pedersen_ratio".to_string(), 8)])),
+        };
+        assert_eq!(
+            serde_json::to_string(&public_input).unwrap(),
+            r#"{"layout":"small","rc_min":32763,"rc_max":32769,"n_steps":16,"memory_segments":{"execution":{"begin_addr":6,"stop_ptr":21},"output":{"begin_addr":22,"stop_ptr":23},"pedersen":{"begin_addr":23,"stop_ptr":23},"program":{"begin_addr":1,"stop_ptr":5},"range_check":{"begin_addr":24,"stop_ptr":26}},"public_memory":[],"dynamic_params":{"pedersen_ratio":8}}"#
+        );
+    }
+}
@@ -150,7 +150,9 @@ impl BitwiseBuiltinRunner {
                 * safe_div_usize(vm.current_step, ratio)
                     .map_err(|_| MemoryError::InsufficientAllocatedCells)?;
             if used > size {
-                return Err(MemoryError::InsufficientAllocatedCells);
+                return Err(MemoryError::InsufficientAllocatedCellsError(
+                    "bitwise", used, size,
+                ));
             }
             Ok((used, size))
         }
@@ -220,9 +222,12 @@ mod tests {
     use crate::vm::{runners::builtin_runner::BuiltinRunner, vm_core::VirtualMachine};
     use crate::{
         hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
-        types::program::Program, utils::test_utils::*, vm::runners::cairo_runner::CairoRunner,
+        types::program::Program,
+        utils::test_utils::*,
+        vm::runners::cairo_runner::{CairoRunner, RunResources},
     };
     use felt::{Felt, NewFelt};
+    use num_traits::One;
 
     #[test]
     fn get_used_instances() {
@@ -374,7 +379,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_used_cells_and_allocated_size(&vm), Ok((0, 5)));
@@ -417,7 +427,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(5));
@@ -463,6 +478,54 @@ mod tests {
         assert_eq!(result, Ok(None));
     }
 
+    #[test]
+    fn deduce_memory_cell_bitwise_outputs_in_any_order() {
+        let memory = memory![((0, 5), 10), ((0, 6), 12)];
+        let builtin = BitwiseBuiltinRunner::new(&BitwiseInstanceDef::default(), true);
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((0, 9)), &memory),
+            Ok(Some(MaybeRelocatable::from(Felt::new(14))))
+        );
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((0, 8)), &memory),
+            Ok(Some(MaybeRelocatable::from(Felt::new(6))))
+        );
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((0, 7)), &memory),
+            Ok(Some(MaybeRelocatable::from(Felt::new(8))))
+        );
+    }
+
+    #[test]
+    fn deduce_memory_cell_bitwise_missing_input() {
+        let memory = memory![((0, 5), 10)];
+        let builtin = BitwiseBuiltinRunner::new(&BitwiseInstanceDef::default(), true);
+        let result = builtin.deduce_memory_cell(&Relocatable::from((0, 9)), &memory);
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn deduce_memory_cell_bitwise_input_bigger_than_total_n_bits() {
+        let mut memory = memory![((0, 5), 10)];
+        let big_value = Felt::one() << 251_u32;
+        memory
+            .insert(
+                &MaybeRelocatable::from((0, 6)),
+                &MaybeRelocatable::from(big_value.clone()),
+            )
+            .unwrap();
+        let builtin = BitwiseBuiltinRunner::new(&BitwiseInstanceDef::default(), true);
+        let result = builtin.deduce_memory_cell(&Relocatable::from((0, 7)), &memory);
+        assert_eq!(
+            result,
+            Err(RunnerError::IntegerBiggerThanPowerOfTwo(
+                MaybeRelocatable::from((0, 6)),
+                251,
+                big_value
+            ))
+        );
+    }
+
     #[test]
     fn get_memory_segment_addresses() {
         let builtin = BitwiseBuiltinRunner::new(&BitwiseInstanceDef::default(), true);
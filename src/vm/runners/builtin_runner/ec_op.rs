@@ -116,10 +116,9 @@ impl EcOpBuiltinRunner {
         //Constant values declared here
         const EC_POINT_INDICES: [(usize, usize); 3] = [(0, 1), (2, 3), (5, 6)];
         const OUTPUT_INDICES: (usize, usize) = EC_POINT_INDICES[2];
-        let alpha: Felt = Felt::one();
-        let beta_low: Felt = Felt::new(0x609ad26c15c915c1f4cdfcb99cee9e89_u128);
-        let beta_high: Felt = Felt::new(0x6f21413efbe40de150e596d72f7a8c5_u128);
-        let beta: Felt = (beta_high << 128_usize) + beta_low;
+        const M_INDEX: usize = 4;
+        let alpha = &self.ec_op_builtin.alpha;
+        let beta = &self.ec_op_builtin.beta;
 
         let index = address
             .offset
@@ -147,20 +146,20 @@ impl EcOpBuiltinRunner {
                 }
             };
         }
-        //Assert that m is under the limit defined by scalar_limit.
-        /*if input_cells[M_INDEX].as_ref() >= &self.ec_op_builtin.scalar_limit {
+        //Assert that m is under the limit defined by scalar_limit (2**scalar_height).
+        if input_cells[M_INDEX].bits() > self.ec_op_builtin.scalar_height as u64 {
             return Err(RunnerError::EcOpBuiltinScalarLimit(
-                self.ec_op_builtin.scalar_limit.clone(),
+                Felt::one() << self.ec_op_builtin.scalar_height as usize,
             ));
-        }*/
+        }
 
-        // Assert that if the current address is part of a point, the point is on the curve
-        for pair in &EC_POINT_INDICES[0..1] {
+        // Assert that P and Q are on the curve
+        for pair in &EC_POINT_INDICES[0..2] {
             if !EcOpBuiltinRunner::point_on_curve(
                 input_cells[pair.0].as_ref(),
                 input_cells[pair.1].as_ref(),
-                &alpha,
-                &beta,
+                alpha,
+                beta,
             ) {
                 return Err(RunnerError::PointNotOnCurve(*pair));
             };
@@ -176,8 +175,8 @@ impl EcOpBuiltinRunner {
                 input_cells[2].to_owned().into_owned(),
                 input_cells[3].to_owned().into_owned(),
             ),
-            input_cells[4].as_ref(),
-            &(&alpha).to_bigint(),
+            input_cells[M_INDEX].as_ref(),
+            &alpha.to_bigint(),
             &prime,
             self.ec_op_builtin.scalar_height,
         )?;
@@ -223,7 +222,9 @@ impl EcOpBuiltinRunner {
                 * safe_div_usize(vm.current_step, ratio)
                     .map_err(|_| MemoryError::InsufficientAllocatedCells)?;
             if used > size {
-                return Err(MemoryError::InsufficientAllocatedCells);
+                return Err(MemoryError::InsufficientAllocatedCellsError(
+                    "ec_op", used, size,
+                ));
             }
             Ok((used, size))
         }
@@ -275,7 +276,7 @@ mod tests {
     use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
     use crate::types::program::Program;
     use crate::utils::test_utils::*;
-    use crate::vm::runners::cairo_runner::CairoRunner;
+    use crate::vm::runners::cairo_runner::{CairoRunner, RunResources};
     use crate::vm::{
         errors::{memory_errors::MemoryError, runner_errors::RunnerError},
         runners::builtin_runner::BuiltinRunner,
@@ -431,7 +432,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_used_cells_and_allocated_size(&vm), Ok((0, 7)));
@@ -474,7 +480,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(7));
@@ -889,13 +900,192 @@ mod tests {
         ];
         let builtin = EcOpBuiltinRunner::new(&EcOpInstanceDef::default(), true);
 
-        let _error = builtin.deduce_memory_cell(&Relocatable::from((3, 6)), &memory);
-        /*assert_eq!(
-            error,
-            Err(RunnerError::EcOpBuiltinScalarLimit(
-                builtin.ec_op_builtin.scalar_limit
-            ))
-        );*/
+        // PRIME + 1 wraps around to 1, which is under the default limit of 2**256
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((3, 6)), &memory),
+            Ok(Some(MaybeRelocatable::from(felt_str!(
+                "1953920282798264903374921629826896621772963845696406305734323863516759859513"
+            ))))
+        );
+    }
+
+    #[test]
+    fn deduce_memory_cell_ec_op_m_over_custom_scalar_height() {
+        let memory = memory![
+            (
+                (3, 0),
+                (
+                    "2962412995502985605007699495352191122971573493113767820301112397466445942584",
+                    10
+                )
+            ),
+            (
+                (3, 1),
+                (
+                    "214950771763870898744428659242275426967582168179217139798831865603966154129",
+                    10
+                )
+            ),
+            (
+                (3, 2),
+                (
+                    "874739451078007766457464989774322083649278607533249481151382481072868806602",
+                    10
+                )
+            ),
+            (
+                (3, 3),
+                (
+                    "152666792071518830868575557812948353041420400780739481342941381225525861407",
+                    10
+                )
+            ),
+            ((3, 4), 34)
+        ];
+        let mut instance_def = EcOpInstanceDef::default();
+        instance_def.scalar_height = 5;
+        let builtin = EcOpBuiltinRunner::new(&instance_def, true);
+
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((3, 5)), &memory),
+            Err(RunnerError::EcOpBuiltinScalarLimit(Felt::new(32)))
+        );
+    }
+
+    #[test]
+    fn deduce_memory_cell_ec_op_m_within_custom_scalar_height() {
+        let memory = memory![
+            (
+                (3, 0),
+                (
+                    "2962412995502985605007699495352191122971573493113767820301112397466445942584",
+                    10
+                )
+            ),
+            (
+                (3, 1),
+                (
+                    "214950771763870898744428659242275426967582168179217139798831865603966154129",
+                    10
+                )
+            ),
+            (
+                (3, 2),
+                (
+                    "874739451078007766457464989774322083649278607533249481151382481072868806602",
+                    10
+                )
+            ),
+            (
+                (3, 3),
+                (
+                    "152666792071518830868575557812948353041420400780739481342941381225525861407",
+                    10
+                )
+            ),
+            ((3, 4), 34)
+        ];
+        let mut instance_def = EcOpInstanceDef::default();
+        instance_def.scalar_height = 6;
+        let builtin = EcOpBuiltinRunner::new(&instance_def, true);
+
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((3, 5)), &memory),
+            Ok(Some(MaybeRelocatable::from(felt_str!(
+                "2778063437308421278851140253538604815869848682781135193774472480292420096757"
+            ))))
+        );
+    }
+
+    #[test]
+    fn deduce_memory_cell_ec_op_large_scalar() {
+        // m = 2**200 + 12345
+        let memory = memory![
+            (
+                (3, 0),
+                (
+                    "2962412995502985605007699495352191122971573493113767820301112397466445942584",
+                    10
+                )
+            ),
+            (
+                (3, 1),
+                (
+                    "214950771763870898744428659242275426967582168179217139798831865603966154129",
+                    10
+                )
+            ),
+            (
+                (3, 2),
+                (
+                    "874739451078007766457464989774322083649278607533249481151382481072868806602",
+                    10
+                )
+            ),
+            (
+                (3, 3),
+                (
+                    "152666792071518830868575557812948353041420400780739481342941381225525861407",
+                    10
+                )
+            ),
+            (
+                (3, 4),
+                (
+                    "1606938044258990275541962092341162602522202993782792835313721",
+                    10
+                )
+            )
+        ];
+        let builtin = EcOpBuiltinRunner::new(&EcOpInstanceDef::default(), true);
+
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((3, 5)), &memory),
+            Ok(Some(MaybeRelocatable::from(felt_str!(
+                "2716385580186682981561548656432464638633986724205746520750121383111925534009"
+            ))))
+        );
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((3, 6)), &memory),
+            Ok(Some(MaybeRelocatable::from(felt_str!(
+                "65482527610429387355936186572257499266773287057805126081365815746574949054"
+            ))))
+        );
+    }
+
+    #[test]
+    fn deduce_memory_cell_ec_op_q_not_on_curve() {
+        let memory = memory![
+            (
+                (3, 0),
+                (
+                    "2962412995502985605007699495352191122971573493113767820301112397466445942584",
+                    10
+                )
+            ),
+            (
+                (3, 1),
+                (
+                    "214950771763870898744428659242275426967582168179217139798831865603966154129",
+                    10
+                )
+            ),
+            (
+                (3, 2),
+                (
+                    "874739451078007766457464989774322083649278607533249481151382481072868806602",
+                    10
+                )
+            ),
+            ((3, 3), 1),
+            ((3, 4), 34)
+        ];
+        let builtin = EcOpBuiltinRunner::new(&EcOpInstanceDef::default(), true);
+
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((3, 5)), &memory),
+            Err(RunnerError::PointNotOnCurve((2, 3)))
+        );
     }
 
     #[test]
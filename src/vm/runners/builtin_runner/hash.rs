@@ -1,4 +1,5 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 
 use crate::math_utils::safe_div_usize;
 use crate::types::instance_definitions::pedersen_instance_def::{
@@ -7,12 +8,27 @@ use crate::types::instance_definitions::pedersen_instance_def::{
 use crate::types::relocatable::{MaybeRelocatable, Relocatable};
 use crate::vm::errors::memory_errors::MemoryError;
 use crate::vm::errors::runner_errors::RunnerError;
+use crate::vm::runners::cairo_pie::BuiltinAdditionalData;
 use crate::vm::vm_core::VirtualMachine;
 use crate::vm::vm_memory::memory::Memory;
 use crate::vm::vm_memory::memory_segments::MemorySegmentManager;
 use felt::{Felt, FeltOps};
 use num_integer::{div_ceil, Integer};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 use starknet_crypto::{pedersen_hash, FieldElement};
+#[cfg(feature = "parallel")]
+use std::collections::HashSet;
+
+/// Default number of input pairs whose hash is kept by the pedersen builtin.
+pub const DEFAULT_HASH_CACHE_CAPACITY: usize = 1 << 16;
+
+/// Hit and miss counters of the pedersen builtin's hash cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HashCacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
 
 #[derive(Debug, Clone)]
 pub struct HashBuiltinRunner {
@@ -26,10 +42,21 @@ pub struct HashBuiltinRunner {
     // This act as a cache to optimize calls to deduce_memory_cell
     // Therefore need interior mutability
     pub(self) verified_addresses: RefCell<Vec<Relocatable>>,
+    // Hashes of already seen (x, y) input pairs. Once it holds cache_capacity
+    // entries, new pairs are hashed without being cached.
+    cache: RefCell<HashMap<(Felt, Felt), Felt>>,
+    cache_capacity: usize,
+    cache_stats: Cell<HashCacheStats>,
 }
 
 impl HashBuiltinRunner {
     pub fn new(ratio: u32, included: bool) -> Self {
+        HashBuiltinRunner::new_with_cache_capacity(ratio, included, DEFAULT_HASH_CACHE_CAPACITY)
+    }
+
+    /// Creates a builtin that caches the hashes of at most `cache_capacity` input pairs.
+    /// A capacity of 0 disables the cache.
+    pub fn new_with_cache_capacity(ratio: u32, included: bool, cache_capacity: usize) -> Self {
         HashBuiltinRunner {
             base: 0,
             ratio,
@@ -39,6 +66,33 @@ impl HashBuiltinRunner {
             verified_addresses: RefCell::new(Vec::new()),
             _included: included,
             instances_per_component: 1,
+            cache: RefCell::new(HashMap::new()),
+            cache_capacity,
+            cache_stats: Cell::new(HashCacheStats::default()),
+        }
+    }
+
+    /// Returns the hit and miss counters of the hash cache.
+    pub fn stats(&self) -> HashCacheStats {
+        self.cache_stats.get()
+    }
+
+    // Returns the cached hash of the pair, counting the lookup as a hit or a miss
+    fn get_cached_hash(&self, key: &(Felt, Felt)) -> Option<Felt> {
+        let mut stats = self.cache_stats.get();
+        let hash = self.cache.borrow().get(key).cloned();
+        match hash {
+            Some(_) => stats.hits += 1,
+            None => stats.misses += 1,
+        }
+        self.cache_stats.set(stats);
+        hash
+    }
+
+    fn cache_hash(&self, key: (Felt, Felt), hash: Felt) {
+        let mut cache = self.cache.borrow_mut();
+        if cache.len() < self.cache_capacity {
+            cache.insert(key, hash);
         }
     }
 
@@ -97,27 +151,65 @@ impl HashBuiltinRunner {
             num_b.as_ref().map(|x| x.as_ref().map(|x| x.as_ref())),
         ) {
             self.verified_addresses.borrow_mut().push(*address);
-
-            //Convert MaybeRelocatable to FieldElement
-            let a_string = num_a.to_str_radix(10);
-            let b_string = num_b.to_str_radix(10);
-            let (y, x) = match (
-                FieldElement::from_dec_str(&a_string),
-                FieldElement::from_dec_str(&b_string),
-            ) {
-                (Ok(field_element_a), Ok(field_element_b)) => (field_element_a, field_element_b),
-                _ => return Err(RunnerError::FailedStringConversion),
+            let key = (num_a.clone(), num_b.clone());
+            let result = match self.get_cached_hash(&key) {
+                Some(result) => result,
+                None => {
+                    let result = compute_pedersen_hash(num_a, num_b)?;
+                    self.cache_hash(key, result.clone());
+                    result
+                }
             };
-            //Compute pedersen Hash
-            let fe_result = pedersen_hash(&x, &y);
-            //Convert result from FieldElement to MaybeRelocatable
-            let r_byte_slice = fe_result.to_bytes_be();
-            let result = Felt::from_bytes_be(&r_byte_slice);
             return Ok(Some(MaybeRelocatable::from(result)));
         }
         Ok(None)
     }
 
+    /// Deduces every hash output of the builtin segment at once, computing the hashes in
+    /// parallel. The deduced cells are returned in offset order, and are the ones that
+    /// calling `deduce_memory_cell` on each cell of the segment would have produced.
+    #[cfg(feature = "parallel")]
+    pub fn deduce_memory_cells(
+        &self,
+        segment: &[Option<MaybeRelocatable>],
+    ) -> Result<Vec<(usize, MaybeRelocatable)>, RunnerError> {
+        let verified_addresses: HashSet<Relocatable> =
+            self.verified_addresses.borrow().iter().copied().collect();
+        let mut inputs = Vec::new();
+        for offset in (0..segment.len())
+            .filter(|offset| offset.mod_floor(&(self.cells_per_instance as usize)) == 2)
+        {
+            let address = Relocatable::from((self.base, offset));
+            if verified_addresses.contains(&address) {
+                continue;
+            }
+            if let (Some(MaybeRelocatable::Int(num_a)), Some(MaybeRelocatable::Int(num_b))) =
+                (&segment[offset - 1], &segment[offset - 2])
+            {
+                self.verified_addresses.borrow_mut().push(address);
+                let key = (num_a.clone(), num_b.clone());
+                let cached = self.get_cached_hash(&key);
+                inputs.push((offset, key, cached));
+            }
+        }
+        let hashes: Vec<Result<Felt, RunnerError>> = inputs
+            .par_iter()
+            .map(|(_, (num_a, num_b), cached)| match cached {
+                Some(hash) => Ok(hash.clone()),
+                None => compute_pedersen_hash(num_a, num_b),
+            })
+            .collect();
+        let mut deduced = Vec::with_capacity(inputs.len());
+        for ((offset, key, cached), hash) in inputs.into_iter().zip(hashes) {
+            let hash = hash?;
+            if cached.is_none() {
+                self.cache_hash(key, hash.clone());
+            }
+            deduced.push((offset, MaybeRelocatable::from(hash)));
+        }
+        Ok(deduced)
+    }
+
     pub fn get_allocated_memory_units(&self, vm: &VirtualMachine) -> Result<usize, MemoryError> {
         let value = safe_div_usize(vm.current_step, self.ratio as usize)
             .map_err(|_| MemoryError::ErrorCalculatingMemoryUnits)?;
@@ -128,6 +220,12 @@ impl HashBuiltinRunner {
         ("pedersen", (self.base, self.stop_ptr))
     }
 
+    pub fn get_additional_data(&self) -> BuiltinAdditionalData {
+        let mut verified_addresses = self.verified_addresses.borrow().clone();
+        verified_addresses.sort_by_key(|addr| (addr.segment_index, addr.offset));
+        BuiltinAdditionalData::Hash(verified_addresses)
+    }
+
     pub fn get_used_cells(&self, vm: &VirtualMachine) -> Result<usize, MemoryError> {
         let base = self.base();
         vm.segments
@@ -153,7 +251,9 @@ impl HashBuiltinRunner {
                 * safe_div_usize(vm.current_step, ratio as usize)
                     .map_err(|_| MemoryError::InsufficientAllocatedCells)?;
             if used > size {
-                return Err(MemoryError::InsufficientAllocatedCells);
+                return Err(MemoryError::InsufficientAllocatedCellsError(
+                    "pedersen", used, size,
+                ));
             }
             Ok((used, size))
         }
@@ -199,13 +299,31 @@ impl HashBuiltinRunner {
     }
 }
 
+fn compute_pedersen_hash(num_a: &Felt, num_b: &Felt) -> Result<Felt, RunnerError> {
+    //Convert MaybeRelocatable to FieldElement
+    let a_string = num_a.to_str_radix(10);
+    let b_string = num_b.to_str_radix(10);
+    let (y, x) = match (
+        FieldElement::from_dec_str(&a_string),
+        FieldElement::from_dec_str(&b_string),
+    ) {
+        (Ok(field_element_a), Ok(field_element_b)) => (field_element_a, field_element_b),
+        _ => return Err(RunnerError::FailedStringConversion),
+    };
+    //Compute pedersen Hash
+    let fe_result = pedersen_hash(&x, &y);
+    //Convert result from FieldElement to Felt
+    let r_byte_slice = fe_result.to_bytes_be();
+    Ok(Felt::from_bytes_be(&r_byte_slice))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
     use crate::types::program::Program;
     use crate::utils::test_utils::*;
-    use crate::vm::runners::cairo_runner::CairoRunner;
+    use crate::vm::runners::cairo_runner::{CairoRunner, RunResources};
     use crate::vm::{
         errors::memory_errors::MemoryError, runners::builtin_runner::BuiltinRunner,
         vm_core::VirtualMachine,
@@ -322,6 +440,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_used_cells_and_allocated_size_exceeds_allocation() {
+        let builtin: BuiltinRunner = HashBuiltinRunner::new(10, true).into();
+        let mut vm = vm!();
+        vm.segments.segment_used_sizes = Some(vec![6]);
+        vm.current_step = 10;
+        assert_eq!(
+            builtin.get_used_cells_and_allocated_size(&vm),
+            Err(MemoryError::InsufficientAllocatedCellsError(
+                "pedersen", 6, 3
+            ))
+        );
+    }
+
     #[test]
     fn get_used_cells_and_allocated_size_test() {
         let builtin: BuiltinRunner = HashBuiltinRunner::new(10, true).into();
@@ -361,7 +493,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_used_cells_and_allocated_size(&vm), Ok((0, 3)));
@@ -404,7 +541,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(3));
@@ -428,6 +570,83 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn deduce_memory_cells_matches_deduce_memory_cell() {
+        let memory = memory![
+            ((0, 0), 1),
+            ((0, 1), 2),
+            ((0, 3), 32),
+            ((0, 4), 72),
+            ((0, 5), 0),
+            ((0, 6), 5),
+            ((0, 7), 7),
+            ((0, 8), 9)
+        ];
+        let sequential = HashBuiltinRunner::new(8, true);
+        let expected: Vec<(usize, MaybeRelocatable)> = (0..memory.data[0].len())
+            .filter_map(|offset| {
+                sequential
+                    .deduce_memory_cell(&Relocatable::from((0, offset)), &memory)
+                    .unwrap()
+                    .map(|value| (offset, value))
+            })
+            .collect();
+
+        let parallel = HashBuiltinRunner::new(8, true);
+        assert_eq!(parallel.deduce_memory_cells(&memory.data[0]), Ok(expected));
+        assert_eq!(
+            parallel.verified_addresses.into_inner(),
+            sequential.verified_addresses.into_inner()
+        );
+    }
+
+    #[test]
+    fn deduce_memory_cell_pedersen_cache_hit() {
+        let memory = memory![
+            ((0, 0), 32),
+            ((0, 1), 72),
+            ((0, 3), 32),
+            ((0, 4), 72),
+            ((0, 6), 1),
+            ((0, 7), 2)
+        ];
+        let builtin = HashBuiltinRunner::new(8, true);
+        let first = builtin.deduce_memory_cell(&Relocatable::from((0, 2)), &memory);
+        let second = builtin.deduce_memory_cell(&Relocatable::from((0, 5)), &memory);
+        assert_eq!(first, second);
+        assert_eq!(builtin.stats(), HashCacheStats { hits: 1, misses: 1 });
+
+        let uncached = HashBuiltinRunner::new_with_cache_capacity(8, true, 0);
+        assert_eq!(
+            uncached.deduce_memory_cell(&Relocatable::from((0, 5)), &memory),
+            second
+        );
+    }
+
+    #[test]
+    fn deduce_memory_cell_pedersen_cache_capacity() {
+        let memory = memory![
+            ((0, 0), 1),
+            ((0, 1), 2),
+            ((0, 3), 3),
+            ((0, 4), 4),
+            ((0, 6), 5),
+            ((0, 7), 6),
+            ((0, 9), 5),
+            ((0, 10), 6)
+        ];
+        let builtin = HashBuiltinRunner::new_with_cache_capacity(8, true, 2);
+        for offset in [2, 5, 8, 11] {
+            builtin
+                .deduce_memory_cell(&Relocatable::from((0, offset)), &memory)
+                .unwrap();
+        }
+        // The third pair doesn't fit in the cache, so its repetition is a miss too
+        assert_eq!(builtin.cache.borrow().len(), 2);
+        assert_eq!(builtin.stats(), HashCacheStats { hits: 0, misses: 4 });
+    }
+
     #[test]
     fn deduce_memory_cell_pedersen_for_preset_memory_incorrect_offset() {
         let memory = memory![((0, 4), 32), ((0, 5), 72), ((0, 6), 0)];
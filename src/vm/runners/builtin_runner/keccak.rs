@@ -98,54 +98,54 @@ impl KeccakBuiltinRunner {
             };
         }
 
-        if let Some((i, bits)) = self.state_rep.iter().enumerate().next() {
-            let value1 = memory
+        let mut input_felts = vec![];
+
+        for (i, bits) in self.state_rep.iter().enumerate() {
+            let value = memory
                 .get(&(first_input_addr + i))
                 .map_err(RunnerError::FailedMemoryGet)?
                 .ok_or(RunnerError::NonRelocatableAddress)?;
 
-            let val = match value1.as_ref() {
+            let val = match value.as_ref() {
                 MaybeRelocatable::Int(val) => val,
                 _ => return Err(RunnerError::FoundNonInt),
             };
 
             if val >= &(Felt::one() << *bits) {
                 return Err(RunnerError::IntegerBiggerThanPowerOfTwo(
-                    value1.clone().into_owned(),
-                    *bits as u32,
+                    (first_input_addr + i).into(),
+                    *bits,
                     val.clone(),
                 ));
             }
 
-            let mut input_felts = vec![];
-
-            for i in 0..self.n_input_cells {
-                let value2 = memory
-                    .get(&(first_input_addr + i as usize))
-                    .map_err(RunnerError::FailedMemoryGet)?;
-
-                input_felts.push(value2)
-            }
+            input_felts.push(Some(value))
+        }
 
-            let mut input_felts_u64 = maybe_reloc_vec_to_u64_array(&input_felts)
-                .map_err(|_| RunnerError::MaybeRelocVecToU64ArrayError)?;
+        let mut input_felts_u64 = maybe_reloc_vec_to_u64_array(&input_felts)
+            .map_err(|_| RunnerError::MaybeRelocVecToU64ArrayError)?;
 
-            let len = input_felts_u64.len();
-            let mut input_felts_u64 = left_pad_u64(&mut input_felts_u64, KECCAK_ARRAY_LEN - len)
-                .try_into()
-                .map_err(|_| RunnerError::SliceToArrayError)?;
+        let len = input_felts_u64.len();
+        let mut input_felts_u64 = left_pad_u64(&mut input_felts_u64, KECCAK_ARRAY_LEN - len)
+            .try_into()
+            .map_err(|_| RunnerError::SliceToArrayError)?;
 
-            keccak::f1600(&mut input_felts_u64);
+        keccak::f1600(&mut input_felts_u64);
 
-            let bigint_values = u64_array_to_mayberelocatable_vec(&input_felts_u64);
+        let bigint_values = u64_array_to_mayberelocatable_vec(&input_felts_u64);
 
-            return Ok(Some(bigint_values[address.offset - 1].clone()));
-        }
-        Ok(None)
+        Ok(Some(
+            bigint_values[index - self.n_input_cells as usize].clone(),
+        ))
     }
 
     pub fn get_allocated_memory_units(&self, vm: &VirtualMachine) -> Result<usize, MemoryError> {
-        let value = safe_div_usize(vm.current_step, self.ratio as usize)
+        let ratio = self.ratio as usize;
+        let min_step = ratio * self.instances_per_component as usize;
+        if vm.current_step < min_step {
+            return Err(MemoryError::InsufficientAllocatedCells);
+        }
+        let value = safe_div_usize(vm.current_step, ratio)
             .map_err(|_| MemoryError::ErrorCalculatingMemoryUnits)?;
         Ok(self.cells_per_instance as usize * value)
     }
@@ -168,19 +168,14 @@ impl KeccakBuiltinRunner {
         &self,
         vm: &VirtualMachine,
     ) -> Result<(usize, usize), MemoryError> {
-        let ratio = self.ratio as usize;
-
-        let cells_per_instance = self.cells_per_instance;
-        let min_step = ratio * self.instances_per_component as usize;
-        if vm.current_step < min_step {
-            Err(MemoryError::InsufficientAllocatedCells)
-        } else {
-            let used = self.get_used_cells(vm)?;
-            let size = cells_per_instance as usize
-                * safe_div_usize(vm.current_step, ratio)
-                    .map_err(|_| MemoryError::InsufficientAllocatedCells)?;
-            Ok((used, size))
+        let size = self.get_allocated_memory_units(vm)?;
+        let used = self.get_used_cells(vm)?;
+        if used > size {
+            return Err(MemoryError::InsufficientAllocatedCellsError(
+                "keccak", used, size,
+            ));
         }
+        Ok((used, size))
     }
 
     pub fn get_used_instances(&self, vm: &VirtualMachine) -> Result<usize, MemoryError> {
@@ -259,7 +254,7 @@ mod tests {
     use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
     use crate::types::program::Program;
     use crate::utils::test_utils::*;
-    use crate::vm::runners::cairo_runner::CairoRunner;
+    use crate::vm::runners::cairo_runner::{CairoRunner, RunResources};
     use crate::vm::{
         errors::{memory_errors::MemoryError, runner_errors::RunnerError},
         runners::builtin_runner::BuiltinRunner,
@@ -396,7 +391,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(
@@ -407,7 +407,14 @@ mod tests {
 
     #[test]
     fn get_allocated_memory_units() {
-        let builtin = KeccakBuiltinRunner::new(&KeccakInstanceDef::new(10), true);
+        let builtin = KeccakBuiltinRunner::new(
+            &KeccakInstanceDef {
+                _ratio: 10,
+                _state_rep: vec![200; 8],
+                _instance_per_component: 1,
+            },
+            true,
+        );
 
         let mut vm = vm!();
 
@@ -442,7 +449,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(16));
@@ -569,7 +581,7 @@ mod tests {
         assert_eq!(
             result,
             Ok(Some(MaybeRelocatable::from(Felt::new(
-                3086936446498698982_u64
+                12127349108128288754_u64
             ))))
         );
     }
@@ -601,4 +613,85 @@ mod tests {
         let result = builtin.deduce_memory_cell(&Relocatable::from((0, 2)), &memory);
         assert_eq!(result, Ok(None));
     }
+
+    #[test]
+    fn get_allocated_memory_units_insufficient_steps() {
+        let builtin = KeccakBuiltinRunner::new(&KeccakInstanceDef::new(10), true);
+
+        let mut vm = vm!();
+        vm.current_step = 159;
+
+        assert_eq!(
+            builtin.get_allocated_memory_units(&vm),
+            Err(MemoryError::InsufficientAllocatedCells)
+        );
+    }
+
+    #[test]
+    fn custom_instance_def_allocation_and_deduction() {
+        let instance_def = KeccakInstanceDef {
+            _ratio: 32,
+            _state_rep: vec![64; 4],
+            _instance_per_component: 2,
+        };
+        let builtin = KeccakBuiltinRunner::new(&instance_def, true);
+        assert_eq!(builtin.n_input_cells, 4);
+        assert_eq!(builtin.cells_per_instance, 8);
+
+        let mut vm = vm!();
+        vm.current_step = 63;
+        assert_eq!(
+            builtin.get_allocated_memory_units(&vm),
+            Err(MemoryError::InsufficientAllocatedCells)
+        );
+        vm.current_step = 96;
+        assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(24));
+        vm.segments.segment_used_sizes = Some(vec![8]);
+        assert_eq!(builtin.get_used_cells_and_allocated_size(&vm), Ok((8, 24)));
+
+        let memory = memory![
+            ((0, 0), 1),
+            ((0, 1), 2),
+            ((0, 2), 3),
+            ((0, 3), 4),
+            ((0, 8), 5),
+            ((0, 9), 6),
+            ((0, 10), 7),
+            ((0, 11), 8)
+        ];
+        // The second output cell of the first instance
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((0, 5)), &memory),
+            Ok(Some(MaybeRelocatable::from(Felt::new(
+                12702663891955393722_u64
+            ))))
+        );
+        // The third output cell of the second instance
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((0, 14)), &memory),
+            Ok(Some(MaybeRelocatable::from(Felt::new(
+                8384763601317375207_u64
+            ))))
+        );
+    }
+
+    #[test]
+    fn deduce_memory_cell_checks_every_input_width() {
+        let instance_def = KeccakInstanceDef {
+            _ratio: 32,
+            _state_rep: vec![8; 4],
+            _instance_per_component: 1,
+        };
+        let builtin = KeccakBuiltinRunner::new(&instance_def, true);
+
+        let memory = memory![((0, 0), 1), ((0, 1), 2), ((0, 2), 256), ((0, 3), 4)];
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((0, 5)), &memory),
+            Err(RunnerError::IntegerBiggerThanPowerOfTwo(
+                MaybeRelocatable::from((0, 2)),
+                8,
+                Felt::new(256)
+            ))
+        );
+    }
 }
@@ -2,24 +2,29 @@ use crate::types::relocatable::{MaybeRelocatable, Relocatable};
 use crate::vm::errors::memory_errors::{self, MemoryError};
 use crate::vm::errors::runner_errors::RunnerError;
 use crate::vm::errors::vm_errors::VirtualMachineError;
+use crate::vm::runners::air_private_input::PrivateInput;
+use crate::vm::runners::cairo_pie::BuiltinAdditionalData;
 use crate::vm::vm_core::VirtualMachine;
 use crate::vm::vm_memory::memory::Memory;
 use crate::vm::vm_memory::memory_segments::MemorySegmentManager;
+use felt::Felt;
 
 mod bitwise;
 mod ec_op;
 mod hash;
 mod keccak;
 mod output;
+mod poseidon;
 mod range_check;
 mod signature;
 
 pub use self::keccak::KeccakBuiltinRunner;
 pub use bitwise::BitwiseBuiltinRunner;
 pub use ec_op::EcOpBuiltinRunner;
-pub use hash::HashBuiltinRunner;
+pub use hash::{HashBuiltinRunner, HashCacheStats, DEFAULT_HASH_CACHE_CAPACITY};
 use num_integer::div_floor;
-pub use output::OutputBuiltinRunner;
+pub use output::{OutputBuiltinRunner, OutputPage};
+pub use poseidon::PoseidonBuiltinRunner;
 pub use range_check::RangeCheckBuiltinRunner;
 pub use signature::SignatureBuiltinRunner;
 
@@ -39,6 +44,7 @@ pub enum BuiltinRunner {
     Output(OutputBuiltinRunner),
     RangeCheck(RangeCheckBuiltinRunner),
     Keccak(KeccakBuiltinRunner),
+    Poseidon(PoseidonBuiltinRunner),
     Signature(SignatureBuiltinRunner),
 }
 
@@ -60,6 +66,9 @@ impl BuiltinRunner {
                 range_check.initialize_segments(segments, memory)
             }
             BuiltinRunner::Keccak(ref mut keccak) => keccak.initialize_segments(segments, memory),
+            BuiltinRunner::Poseidon(ref mut poseidon) => {
+                poseidon.initialize_segments(segments, memory)
+            }
             BuiltinRunner::Signature(ref mut signature) => {
                 signature.initialize_segments(segments, memory)
             }
@@ -74,6 +83,7 @@ impl BuiltinRunner {
             BuiltinRunner::Output(ref output) => output.initial_stack(),
             BuiltinRunner::RangeCheck(ref range_check) => range_check.initial_stack(),
             BuiltinRunner::Keccak(ref keccak) => keccak.initial_stack(),
+            BuiltinRunner::Poseidon(ref poseidon) => poseidon.initial_stack(),
             BuiltinRunner::Signature(ref signature) => signature.initial_stack(),
         }
     }
@@ -92,6 +102,7 @@ impl BuiltinRunner {
                 range_check.final_stack(vm, stack_pointer)
             }
             BuiltinRunner::Keccak(ref keccak) => keccak.final_stack(vm, stack_pointer),
+            BuiltinRunner::Poseidon(ref poseidon) => poseidon.final_stack(vm, stack_pointer),
             BuiltinRunner::Signature(ref signature) => signature.final_stack(vm, stack_pointer),
         }
     }
@@ -110,6 +121,7 @@ impl BuiltinRunner {
                 range_check.get_allocated_memory_units(vm)
             }
             BuiltinRunner::Keccak(ref keccak) => keccak.get_allocated_memory_units(vm),
+            BuiltinRunner::Poseidon(ref poseidon) => poseidon.get_allocated_memory_units(vm),
             BuiltinRunner::Signature(ref signature) => signature.get_allocated_memory_units(vm),
         }
     }
@@ -123,6 +135,7 @@ impl BuiltinRunner {
             BuiltinRunner::Output(ref output) => output.base(),
             BuiltinRunner::RangeCheck(ref range_check) => range_check.base(),
             BuiltinRunner::Keccak(ref keccak) => keccak.base(),
+            BuiltinRunner::Poseidon(ref poseidon) => poseidon.base(),
             BuiltinRunner::Signature(ref signature) => signature.base(),
         }
     }
@@ -135,6 +148,7 @@ impl BuiltinRunner {
             BuiltinRunner::Output(_) => None,
             BuiltinRunner::RangeCheck(range_check) => Some(range_check.ratio()),
             BuiltinRunner::Keccak(keccak) => Some(keccak.ratio()),
+            BuiltinRunner::Poseidon(poseidon) => Some(poseidon.ratio()),
             BuiltinRunner::Signature(ref signature) => Some(signature.ratio()),
         }
     }
@@ -147,6 +161,7 @@ impl BuiltinRunner {
             BuiltinRunner::Output(ref output) => output.add_validation_rule(memory),
             BuiltinRunner::RangeCheck(ref range_check) => range_check.add_validation_rule(memory),
             BuiltinRunner::Keccak(ref keccak) => keccak.add_validation_rule(memory),
+            BuiltinRunner::Poseidon(ref poseidon) => poseidon.add_validation_rule(memory),
             BuiltinRunner::Signature(ref signature) => signature.add_validation_rule(memory),
         }
     }
@@ -165,6 +180,7 @@ impl BuiltinRunner {
                 range_check.deduce_memory_cell(address, memory)
             }
             BuiltinRunner::Keccak(ref keccak) => keccak.deduce_memory_cell(address, memory),
+            BuiltinRunner::Poseidon(ref poseidon) => poseidon.deduce_memory_cell(address, memory),
             BuiltinRunner::Signature(ref signature) => {
                 signature.deduce_memory_cell(address, memory)
             }
@@ -197,10 +213,83 @@ impl BuiltinRunner {
                 range_check.get_memory_segment_addresses()
             }
             BuiltinRunner::Keccak(ref keccak) => keccak.get_memory_segment_addresses(),
+            BuiltinRunner::Poseidon(ref poseidon) => poseidon.get_memory_segment_addresses(),
             BuiltinRunner::Signature(ref signature) => signature.get_memory_segment_addresses(),
         }
     }
 
+    /// Returns the data needed to reproduce the builtin's memory validation from a Cairo PIE.
+    pub fn get_additional_data(&self) -> BuiltinAdditionalData {
+        match self {
+            BuiltinRunner::Hash(ref hash) => hash.get_additional_data(),
+            BuiltinRunner::Output(ref output) => output.get_additional_data(),
+            BuiltinRunner::Signature(ref signature) => signature.get_additional_data(),
+            _ => BuiltinAdditionalData::Empty,
+        }
+    }
+
+    /// Returns the inputs of every builtin instance whose input cells are all set,
+    /// as needed by the AIR private input.
+    pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
+        let pair = |(index, inputs): (usize, Vec<Felt>)| PrivateInput::Pair {
+            index,
+            x: inputs[0].clone(),
+            y: inputs[1].clone(),
+        };
+        let state = |(index, inputs): (usize, Vec<Felt>)| PrivateInput::State { index, inputs };
+        match self {
+            BuiltinRunner::RangeCheck(ref x) => {
+                instance_inputs(memory, x.base(), x.cells_per_instance, x.n_input_cells)
+                    .into_iter()
+                    .map(|(index, inputs)| PrivateInput::Value {
+                        index,
+                        value: inputs[0].clone(),
+                    })
+                    .collect()
+            }
+            BuiltinRunner::Hash(ref x) => {
+                instance_inputs(memory, x.base(), x.cells_per_instance, x.n_input_cells)
+                    .into_iter()
+                    .map(pair)
+                    .collect()
+            }
+            BuiltinRunner::Bitwise(ref x) => {
+                instance_inputs(memory, x.base(), x.cells_per_instance, x.n_input_cells)
+                    .into_iter()
+                    .map(pair)
+                    .collect()
+            }
+            // Input cells are laid out as p_x, p_y, q_x, q_y, m
+            BuiltinRunner::EcOp(ref x) => {
+                instance_inputs(memory, x.base(), x.cells_per_instance, x.n_input_cells)
+                    .into_iter()
+                    .map(|(index, inputs)| PrivateInput::EcOp {
+                        index,
+                        p_x: inputs[0].clone(),
+                        p_y: inputs[1].clone(),
+                        q_x: inputs[2].clone(),
+                        q_y: inputs[3].clone(),
+                        m: inputs[4].clone(),
+                    })
+                    .collect()
+            }
+            BuiltinRunner::Keccak(ref x) => {
+                instance_inputs(memory, x.base(), x.cells_per_instance, x.n_input_cells)
+                    .into_iter()
+                    .map(state)
+                    .collect()
+            }
+            BuiltinRunner::Poseidon(ref x) => {
+                instance_inputs(memory, x.base(), x.cells_per_instance, x.n_input_cells)
+                    .into_iter()
+                    .map(state)
+                    .collect()
+            }
+            BuiltinRunner::Signature(ref signature) => signature.air_private_input(memory),
+            BuiltinRunner::Output(_) => Vec::new(),
+        }
+    }
+
     pub fn get_used_cells(&self, vm: &VirtualMachine) -> Result<usize, MemoryError> {
         match self {
             BuiltinRunner::Bitwise(ref bitwise) => bitwise.get_used_cells(vm),
@@ -209,6 +298,7 @@ impl BuiltinRunner {
             BuiltinRunner::Output(ref output) => output.get_used_cells(vm),
             BuiltinRunner::RangeCheck(ref range_check) => range_check.get_used_cells(vm),
             BuiltinRunner::Keccak(ref keccak) => keccak.get_used_cells(vm),
+            BuiltinRunner::Poseidon(ref poseidon) => poseidon.get_used_cells(vm),
             BuiltinRunner::Signature(ref signature) => signature.get_used_cells(vm),
         }
     }
@@ -221,6 +311,7 @@ impl BuiltinRunner {
             BuiltinRunner::Output(ref output) => output.get_used_instances(vm),
             BuiltinRunner::RangeCheck(ref range_check) => range_check.get_used_instances(vm),
             BuiltinRunner::Keccak(ref keccak) => keccak.get_used_instances(vm),
+            BuiltinRunner::Poseidon(ref poseidon) => poseidon.get_used_instances(vm),
             BuiltinRunner::Signature(ref signature) => signature.get_used_instances(vm),
         }
     }
@@ -269,6 +360,7 @@ impl BuiltinRunner {
             BuiltinRunner::RangeCheck(x) => (x.cells_per_instance, x.n_input_cells),
             BuiltinRunner::Output(_) => unreachable!(),
             BuiltinRunner::Keccak(x) => (x.cells_per_instance, x.n_input_cells),
+            BuiltinRunner::Poseidon(x) => (x.cells_per_instance, x.n_input_cells),
             BuiltinRunner::Signature(ref x) => (x.cells_per_instance, x.n_input_cells),
         };
 
@@ -298,6 +390,7 @@ impl BuiltinRunner {
                 BuiltinRunner::Output(_) => "output",
                 BuiltinRunner::RangeCheck(_) => "range_check",
                 BuiltinRunner::Keccak(_) => "keccak",
+                BuiltinRunner::Poseidon(_) => "poseidon",
                 BuiltinRunner::Signature(_) => "ecdsa",
             })
             .into());
@@ -328,6 +421,7 @@ impl BuiltinRunner {
                     BuiltinRunner::Output(_) => "output",
                     BuiltinRunner::RangeCheck(_) => "range_check",
                     BuiltinRunner::Keccak(_) => "keccak",
+                    BuiltinRunner::Poseidon(_) => "poseidon",
                     BuiltinRunner::Signature(_) => "ecdsa",
                 },
                 missing_offsets,
@@ -364,6 +458,7 @@ impl BuiltinRunner {
                 range_check.get_used_cells_and_allocated_size(vm)
             }
             BuiltinRunner::Keccak(ref keccak) => keccak.get_used_cells_and_allocated_size(vm),
+            BuiltinRunner::Poseidon(ref poseidon) => poseidon.get_used_cells_and_allocated_size(vm),
             BuiltinRunner::Signature(ref signature) => {
                 signature.get_used_cells_and_allocated_size(vm)
             }
@@ -378,17 +473,90 @@ impl BuiltinRunner {
             BuiltinRunner::Output(ref mut output) => output.stop_ptr = Some(stop_ptr),
             BuiltinRunner::RangeCheck(ref mut range_check) => range_check.stop_ptr = Some(stop_ptr),
             BuiltinRunner::Keccak(ref mut keccak) => keccak.stop_ptr = Some(stop_ptr),
+            BuiltinRunner::Poseidon(ref mut poseidon) => poseidon.stop_ptr = Some(stop_ptr),
             BuiltinRunner::Signature(ref mut signature) => signature.stop_ptr = Some(stop_ptr),
         }
     }
 }
 
+// Generates an accessor pair per variant, returning the concrete runner if the BuiltinRunner
+// wraps one of that type, and None otherwise
+macro_rules! runner_accessors {
+    ($($variant:ident($runner:ty): $as_runner:ident, $as_runner_mut:ident;)*) => {
+        impl BuiltinRunner {
+            $(
+                #[doc = concat!("Returns the inner [`", stringify!($runner), "`], if this is a `", stringify!($variant), "` runner.")]
+                pub fn $as_runner(&self) -> Option<&$runner> {
+                    match self {
+                        BuiltinRunner::$variant(runner) => Some(runner),
+                        _ => None,
+                    }
+                }
+
+                #[doc = concat!("Returns the inner [`", stringify!($runner), "`] mutably, if this is a `", stringify!($variant), "` runner.")]
+                pub fn $as_runner_mut(&mut self) -> Option<&mut $runner> {
+                    match self {
+                        BuiltinRunner::$variant(runner) => Some(runner),
+                        _ => None,
+                    }
+                }
+            )*
+        }
+    };
+}
+
+runner_accessors! {
+    Bitwise(BitwiseBuiltinRunner): as_bitwise_runner, as_bitwise_runner_mut;
+    EcOp(EcOpBuiltinRunner): as_ec_op_runner, as_ec_op_runner_mut;
+    Hash(HashBuiltinRunner): as_hash_runner, as_hash_runner_mut;
+    Output(OutputBuiltinRunner): as_output_runner, as_output_runner_mut;
+    RangeCheck(RangeCheckBuiltinRunner): as_range_check_runner, as_range_check_runner_mut;
+    Keccak(KeccakBuiltinRunner): as_keccak_runner, as_keccak_runner_mut;
+    Poseidon(PoseidonBuiltinRunner): as_poseidon_runner, as_poseidon_runner_mut;
+    Signature(SignatureBuiltinRunner): as_signature_runner, as_signature_runner_mut;
+}
+
+/// Returns the index and input cells of every instance of the builtin segment at `base` whose
+/// input cells are all integers.
+pub(crate) fn instance_inputs(
+    memory: &Memory,
+    base: isize,
+    cells_per_instance: u32,
+    n_input_cells: u32,
+) -> Vec<(usize, Vec<Felt>)> {
+    let segment = match memory.data.get(base as usize) {
+        Some(segment) => segment,
+        None => return Vec::new(),
+    };
+    segment
+        .chunks(cells_per_instance as usize)
+        .enumerate()
+        .filter_map(|(index, cells)| {
+            let inputs = cells
+                .get(..n_input_cells as usize)?
+                .iter()
+                .map(|cell| match cell {
+                    Some(MaybeRelocatable::Int(value)) => Some(value.clone()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some((index, inputs))
+        })
+        .collect()
+}
+
 impl From<KeccakBuiltinRunner> for BuiltinRunner {
     fn from(runner: KeccakBuiltinRunner) -> Self {
         BuiltinRunner::Keccak(runner)
     }
 }
 
+impl From<PoseidonBuiltinRunner> for BuiltinRunner {
+    fn from(runner: PoseidonBuiltinRunner) -> Self {
+        BuiltinRunner::Poseidon(runner)
+    }
+}
+
 impl From<BitwiseBuiltinRunner> for BuiltinRunner {
     fn from(runner: BitwiseBuiltinRunner) -> Self {
         BuiltinRunner::Bitwise(runner)
@@ -431,15 +599,18 @@ mod tests {
     use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
     use crate::types::instance_definitions::ecdsa_instance_def::EcdsaInstanceDef;
     use crate::types::instance_definitions::keccak_instance_def::KeccakInstanceDef;
+    use crate::types::instance_definitions::poseidon_instance_def::PoseidonInstanceDef;
     use crate::types::program::Program;
-    use crate::vm::runners::cairo_runner::CairoRunner;
+    use crate::vm::runners::cairo_runner::{CairoRunner, RunResources};
     use crate::{
+        relocatable,
         types::instance_definitions::{
             bitwise_instance_def::BitwiseInstanceDef, ec_op_instance_def::EcOpInstanceDef,
         },
         utils::test_utils::*,
         vm::vm_core::VirtualMachine,
     };
+    use felt::NewFelt;
 
     #[test]
     fn get_memory_accesses_missing_segment_used_sizes() {
@@ -521,7 +692,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(5));
@@ -564,7 +740,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(7));
@@ -607,7 +788,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(3));
@@ -650,7 +836,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(1));
@@ -694,7 +885,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(16));
@@ -751,6 +947,16 @@ mod tests {
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(0));
     }
 
+    #[test]
+    fn get_allocated_memory_units_poseidon() {
+        let builtin = BuiltinRunner::Poseidon(PoseidonBuiltinRunner::new(
+            &PoseidonInstanceDef::default(),
+            true,
+        ));
+        let vm = vm!();
+        assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(0));
+    }
+
     #[test]
     fn get_range_check_usage_range_check() {
         let builtin = BuiltinRunner::RangeCheck(RangeCheckBuiltinRunner::new(8, 8, true));
@@ -799,6 +1005,15 @@ mod tests {
         assert_eq!(builtin.get_used_diluted_check_units(270, 7), 1255);
     }
 
+    #[test]
+    fn get_used_diluted_check_units_poseidon() {
+        let builtin = BuiltinRunner::Poseidon(PoseidonBuiltinRunner::new(
+            &PoseidonInstanceDef::default(),
+            true,
+        ));
+        assert_eq!(builtin.get_used_diluted_check_units(270, 7), 0);
+    }
+
     #[test]
     fn get_used_diluted_check_units_keccak_zero_case() {
         let builtin = BuiltinRunner::Keccak(KeccakBuiltinRunner::new(
@@ -841,6 +1056,89 @@ mod tests {
         assert_eq!(builtin.get_used_diluted_check_units(270, 7), 0);
     }
 
+    // One runner of each type, in the order of the BuiltinRunner variants
+    fn one_runner_of_each_type() -> Vec<BuiltinRunner> {
+        vec![
+            BitwiseBuiltinRunner::new(&BitwiseInstanceDef::default(), true).into(),
+            EcOpBuiltinRunner::new(&EcOpInstanceDef::default(), true).into(),
+            HashBuiltinRunner::new(8, true).into(),
+            OutputBuiltinRunner::new(true).into(),
+            RangeCheckBuiltinRunner::new(8, 8, true).into(),
+            KeccakBuiltinRunner::new(&KeccakInstanceDef::default(), true).into(),
+            PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), true).into(),
+            SignatureBuiltinRunner::new(&EcdsaInstanceDef::default(), true).into(),
+        ]
+    }
+
+    #[test]
+    fn runner_accessors_match_variant() {
+        for (i, mut builtin) in one_runner_of_each_type().into_iter().enumerate() {
+            let matches = [
+                builtin.as_bitwise_runner().is_some(),
+                builtin.as_ec_op_runner().is_some(),
+                builtin.as_hash_runner().is_some(),
+                builtin.as_output_runner().is_some(),
+                builtin.as_range_check_runner().is_some(),
+                builtin.as_keccak_runner().is_some(),
+                builtin.as_poseidon_runner().is_some(),
+                builtin.as_signature_runner().is_some(),
+            ];
+            let mut_matches = [
+                builtin.as_bitwise_runner_mut().is_some(),
+                builtin.as_ec_op_runner_mut().is_some(),
+                builtin.as_hash_runner_mut().is_some(),
+                builtin.as_output_runner_mut().is_some(),
+                builtin.as_range_check_runner_mut().is_some(),
+                builtin.as_keccak_runner_mut().is_some(),
+                builtin.as_poseidon_runner_mut().is_some(),
+                builtin.as_signature_runner_mut().is_some(),
+            ];
+            let expected: Vec<bool> = (0..8).map(|j| j == i).collect();
+            assert_eq!(matches.to_vec(), expected);
+            assert_eq!(mut_matches.to_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn runner_accessor_mut_updates_runner() {
+        let mut builtin: BuiltinRunner = OutputBuiltinRunner::new(true).into();
+        let mut vm = vm!();
+        builtin.initialize_segments(&mut vm.segments, &mut vm.memory);
+        builtin
+            .as_output_runner_mut()
+            .unwrap()
+            .add_page(1, relocatable!(0, 2), 3)
+            .unwrap();
+        assert_eq!(builtin.as_output_runner().unwrap().pages.len(), 1);
+        assert!(builtin.as_signature_runner_mut().is_none());
+    }
+
+    #[test]
+    fn uniform_interface_on_every_runner() {
+        for mut builtin in one_runner_of_each_type() {
+            let mut vm = vm!();
+            builtin.initialize_segments(&mut vm.segments, &mut vm.memory);
+            assert_eq!(builtin.base(), 0);
+            assert_eq!(builtin.add_validation_rule(&mut vm.memory), Ok(()));
+            assert_eq!(
+                builtin.deduce_memory_cell(&relocatable!(0, 0), &vm.memory),
+                Ok(None)
+            );
+            assert_eq!(builtin.get_memory_segment_addresses().1, (0, None));
+
+            // An empty execution segment whose last cell is the builtin's stop pointer
+            vm.segments.add(&mut vm.memory);
+            vm.insert_value(&relocatable!(1, 0), relocatable!(0, 0))
+                .unwrap();
+            vm.segments.compute_effective_sizes(&vm.memory);
+            assert_eq!(builtin.get_used_cells(&vm), Ok(0));
+            assert_eq!(
+                builtin.final_stack(&vm, relocatable!(1, 1)),
+                Ok((relocatable!(1, 0), 0))
+            );
+        }
+    }
+
     #[test]
     fn get_memory_segment_addresses_test() {
         let bitwise_builtin: BuiltinRunner =
@@ -1325,4 +1623,68 @@ mod tests {
             assert_eq!(stop_ptr, Some(ptr));
         }
     }
+
+    #[test]
+    fn air_private_input_hash_skips_incomplete_instance() {
+        let builtin = BuiltinRunner::Hash(HashBuiltinRunner::new(8, true));
+        let memory = memory![((0, 0), 1), ((0, 1), 2), ((0, 2), 3), ((0, 3), 4)];
+        assert_eq!(
+            builtin.air_private_input(&memory),
+            vec![PrivateInput::Pair {
+                index: 0,
+                x: Felt::new(1),
+                y: Felt::new(2),
+            }]
+        );
+    }
+
+    #[test]
+    fn air_private_input_range_check() {
+        let builtin = BuiltinRunner::RangeCheck(RangeCheckBuiltinRunner::new(8, 8, true));
+        let memory = memory![((0, 0), 7), ((0, 2), 9)];
+        assert_eq!(
+            builtin.air_private_input(&memory),
+            vec![
+                PrivateInput::Value {
+                    index: 0,
+                    value: Felt::new(7),
+                },
+                PrivateInput::Value {
+                    index: 2,
+                    value: Felt::new(9),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn air_private_input_ec_op_orders_inputs() {
+        let builtin =
+            BuiltinRunner::EcOp(EcOpBuiltinRunner::new(&EcOpInstanceDef::default(), true));
+        let memory = memory![
+            ((0, 0), 1),
+            ((0, 1), 2),
+            ((0, 2), 3),
+            ((0, 3), 4),
+            ((0, 4), 5)
+        ];
+        assert_eq!(
+            builtin.air_private_input(&memory),
+            vec![PrivateInput::EcOp {
+                index: 0,
+                p_x: Felt::new(1),
+                p_y: Felt::new(2),
+                q_x: Felt::new(3),
+                q_y: Felt::new(4),
+                m: Felt::new(5),
+            }]
+        );
+    }
+
+    #[test]
+    fn air_private_input_output_is_empty() {
+        let builtin = BuiltinRunner::Output(OutputBuiltinRunner::new(true));
+        let memory = memory![((0, 0), 1)];
+        assert!(builtin.air_private_input(&memory).is_empty());
+    }
 }
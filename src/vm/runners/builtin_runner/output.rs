@@ -1,13 +1,23 @@
 use crate::types::relocatable::{MaybeRelocatable, Relocatable};
 use crate::vm::errors::memory_errors::MemoryError;
 use crate::vm::errors::runner_errors::RunnerError;
+use crate::vm::runners::cairo_pie::BuiltinAdditionalData;
 use crate::vm::vm_core::VirtualMachine;
 use crate::vm::vm_memory::memory::Memory;
 use crate::vm::vm_memory::memory_segments::MemorySegmentManager;
+use std::collections::HashMap;
+
+/// A range of output cells, given as offsets into the output segment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputPage {
+    pub start: usize,
+    pub size: usize,
+}
 
 #[derive(Debug, Clone)]
 pub struct OutputBuiltinRunner {
     base: isize,
+    pub(crate) pages: HashMap<usize, OutputPage>,
     pub(crate) stop_ptr: Option<usize>,
     pub(crate) _included: bool,
 }
@@ -16,6 +26,7 @@ impl OutputBuiltinRunner {
     pub fn new(included: bool) -> OutputBuiltinRunner {
         OutputBuiltinRunner {
             base: 0,
+            pages: HashMap::new(),
             stop_ptr: None,
             _included: included,
         }
@@ -61,6 +72,15 @@ impl OutputBuiltinRunner {
         ("output", (self.base, self.stop_ptr))
     }
 
+    pub fn get_additional_data(&self) -> BuiltinAdditionalData {
+        BuiltinAdditionalData::Output(
+            self.pages
+                .iter()
+                .map(|(id, page)| (*id, (page.start, page.size)))
+                .collect(),
+        )
+    }
+
     pub fn get_used_cells(&self, vm: &VirtualMachine) -> Result<usize, MemoryError> {
         let base = self.base();
         vm.segments
@@ -83,6 +103,58 @@ impl OutputBuiltinRunner {
         self.get_used_cells(vm)
     }
 
+    /// Assigns the `page_size` output cells starting at `page_start` to the page `page_id`.
+    /// Page 0 is reserved for the cells that aren't part of any other page.
+    pub fn add_page(
+        &mut self,
+        page_id: usize,
+        page_start: Relocatable,
+        page_size: usize,
+    ) -> Result<(), RunnerError> {
+        if page_start.segment_index != self.base {
+            return Err(RunnerError::OutputPageNotInSegment(page_start, self.base));
+        }
+        if page_id == 0 || self.pages.contains_key(&page_id) {
+            return Err(RunnerError::OutputPageIdInUse(page_id));
+        }
+        let start = page_start.offset;
+        let end = start
+            .checked_add(page_size)
+            .ok_or(RunnerError::OutputPageSizeOverflow(page_id, page_size))?;
+        for (other_id, other) in self.pages.iter() {
+            if start < other.start.saturating_add(other.size) && other.start < end {
+                return Err(RunnerError::OutputPagesOverlap(page_id, *other_id));
+            }
+        }
+        self.pages.insert(
+            page_id,
+            OutputPage {
+                start,
+                size: page_size,
+            },
+        );
+        Ok(())
+    }
+
+    /// Returns the (offset, page_id) pair of every used output cell.
+    pub fn get_public_memory(
+        &self,
+        vm: &VirtualMachine,
+    ) -> Result<Vec<(usize, usize)>, RunnerError> {
+        let size = self.get_used_cells(vm)?;
+        let mut public_memory: Vec<(usize, usize)> = (0..size).map(|offset| (offset, 0)).collect();
+        for (page_id, page) in self.pages.iter() {
+            let end = match page.start.checked_add(page.size) {
+                Some(end) if end <= size => end,
+                _ => return Err(RunnerError::OutputPageOutOfBounds(*page_id, size)),
+            };
+            for (_, cell_page_id) in public_memory[page.start..end].iter_mut() {
+                *cell_page_id = *page_id;
+            }
+        }
+        Ok(public_memory)
+    }
+
     pub fn final_stack(
         &self,
         vm: &VirtualMachine,
@@ -127,6 +199,7 @@ impl Default for OutputBuiltinRunner {
 mod tests {
     use super::*;
     use crate::{
+        relocatable,
         utils::test_utils::*,
         vm::{
             errors::memory_errors::MemoryError, runners::builtin_runner::BuiltinRunner,
@@ -363,4 +436,104 @@ mod tests {
         vm.segments.segment_used_sizes = Some(vec![4]);
         assert_eq!(builtin.get_used_cells(&vm), Ok(4));
     }
+
+    #[test]
+    fn add_page_not_in_segment() {
+        let mut builtin = OutputBuiltinRunner::new(true);
+        builtin.base = 2;
+
+        assert_eq!(
+            builtin.add_page(1, relocatable!(1, 0), 3),
+            Err(RunnerError::OutputPageNotInSegment(relocatable!(1, 0), 2))
+        );
+    }
+
+    #[test]
+    fn add_page_id_in_use() {
+        let mut builtin = OutputBuiltinRunner::new(true);
+
+        assert_eq!(
+            builtin.add_page(0, relocatable!(0, 0), 3),
+            Err(RunnerError::OutputPageIdInUse(0))
+        );
+        assert_eq!(builtin.add_page(1, relocatable!(0, 0), 3), Ok(()));
+        assert_eq!(
+            builtin.add_page(1, relocatable!(0, 3), 3),
+            Err(RunnerError::OutputPageIdInUse(1))
+        );
+    }
+
+    #[test]
+    fn add_page_overlap() {
+        let mut builtin = OutputBuiltinRunner::new(true);
+
+        assert_eq!(builtin.add_page(1, relocatable!(0, 2), 3), Ok(()));
+        assert_eq!(
+            builtin.add_page(2, relocatable!(0, 4), 3),
+            Err(RunnerError::OutputPagesOverlap(2, 1))
+        );
+        assert_eq!(
+            builtin.add_page(2, relocatable!(0, 0), 3),
+            Err(RunnerError::OutputPagesOverlap(2, 1))
+        );
+        assert_eq!(builtin.add_page(2, relocatable!(0, 5), 1), Ok(()));
+        assert_eq!(builtin.add_page(3, relocatable!(0, 0), 2), Ok(()));
+    }
+
+    #[test]
+    fn add_page_size_overflow() {
+        let mut builtin = OutputBuiltinRunner::new(true);
+
+        assert_eq!(
+            builtin.add_page(1, relocatable!(0, 2), usize::MAX),
+            Err(RunnerError::OutputPageSizeOverflow(1, usize::MAX))
+        );
+        assert_eq!(builtin.add_page(1, relocatable!(0, 0), 2), Ok(()));
+    }
+
+    #[test]
+    fn get_public_memory() {
+        let mut builtin = OutputBuiltinRunner::new(true);
+        let mut vm = vm!();
+
+        vm.segments.segment_used_sizes = Some(vec![6]);
+        builtin.add_page(1, relocatable!(0, 1), 2).unwrap();
+        builtin.add_page(2, relocatable!(0, 4), 1).unwrap();
+        assert_eq!(
+            builtin.get_public_memory(&vm),
+            Ok(vec![(0, 0), (1, 1), (2, 1), (3, 0), (4, 2), (5, 0)])
+        );
+    }
+
+    #[test]
+    fn get_public_memory_page_out_of_bounds() {
+        let mut builtin = OutputBuiltinRunner::new(true);
+        let mut vm = vm!();
+
+        vm.segments.segment_used_sizes = Some(vec![4]);
+        builtin.add_page(1, relocatable!(0, 2), 3).unwrap();
+        assert_eq!(
+            builtin.get_public_memory(&vm),
+            Err(RunnerError::OutputPageOutOfBounds(1, 4))
+        );
+    }
+
+    #[test]
+    fn get_public_memory_page_size_overflow() {
+        let mut builtin = OutputBuiltinRunner::new(true);
+        let mut vm = vm!();
+
+        vm.segments.segment_used_sizes = Some(vec![4]);
+        builtin.pages.insert(
+            1,
+            OutputPage {
+                start: 2,
+                size: usize::MAX,
+            },
+        );
+        assert_eq!(
+            builtin.get_public_memory(&vm),
+            Err(RunnerError::OutputPageOutOfBounds(1, 4))
+        );
+    }
 }
@@ -0,0 +1,492 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use crate::math_utils::safe_div_usize;
+use crate::types::instance_definitions::poseidon_instance_def::{
+    PoseidonInstanceDef, CELLS_PER_POSEIDON, INPUT_CELLS_PER_POSEIDON,
+};
+use crate::types::relocatable::{MaybeRelocatable, Relocatable};
+use crate::vm::errors::memory_errors::MemoryError;
+use crate::vm::errors::runner_errors::RunnerError;
+use crate::vm::vm_core::VirtualMachine;
+use crate::vm::vm_memory::memory::Memory;
+use crate::vm::vm_memory::memory_segments::MemorySegmentManager;
+use felt::{Felt, FeltOps};
+use num_integer::div_ceil;
+use starknet_crypto::{poseidon_permute_comp, FieldElement};
+
+#[derive(Debug, Clone)]
+pub struct PoseidonBuiltinRunner {
+    pub base: isize,
+    ratio: u32,
+    pub(crate) cells_per_instance: u32,
+    pub(crate) n_input_cells: u32,
+    pub(crate) stop_ptr: Option<usize>,
+    pub(crate) _included: bool,
+    instances_per_component: u32,
+    // A single permutation yields all three output cells of an instance, so the
+    // results are cached here until each one is requested by deduce_memory_cell
+    cache: RefCell<HashMap<Relocatable, Felt>>,
+}
+
+impl PoseidonBuiltinRunner {
+    pub(crate) fn new(instance_def: &PoseidonInstanceDef, included: bool) -> Self {
+        PoseidonBuiltinRunner {
+            base: 0,
+            ratio: instance_def.ratio,
+            cells_per_instance: CELLS_PER_POSEIDON,
+            n_input_cells: INPUT_CELLS_PER_POSEIDON,
+            stop_ptr: None,
+            _included: included,
+            instances_per_component: 1,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn initialize_segments(
+        &mut self,
+        segments: &mut MemorySegmentManager,
+        memory: &mut Memory,
+    ) {
+        self.base = segments.add(memory).segment_index
+    }
+
+    pub fn initial_stack(&self) -> Vec<MaybeRelocatable> {
+        if self._included {
+            vec![MaybeRelocatable::from((self.base, 0))]
+        } else {
+            vec![]
+        }
+    }
+
+    pub fn base(&self) -> isize {
+        self.base
+    }
+
+    pub fn ratio(&self) -> u32 {
+        self.ratio
+    }
+
+    pub fn add_validation_rule(&self, _memory: &mut Memory) -> Result<(), RunnerError> {
+        Ok(())
+    }
+
+    pub fn deduce_memory_cell(
+        &self,
+        address: &Relocatable,
+        memory: &Memory,
+    ) -> Result<Option<MaybeRelocatable>, RunnerError> {
+        let index = address.offset % self.cells_per_instance as usize;
+        if index < self.n_input_cells as usize {
+            return Ok(None);
+        }
+        if let Some(felt) = self.cache.borrow().get(address) {
+            return Ok(Some(MaybeRelocatable::from(felt.clone())));
+        }
+
+        let first_input_addr = address
+            .sub_usize(index)
+            .map_err(|_| RunnerError::BaseNotFinished)?;
+        let first_output_addr = first_input_addr + self.n_input_cells as usize;
+
+        let mut poseidon_state = [FieldElement::ZERO; 3];
+        for (i, state_elem) in poseidon_state.iter_mut().enumerate() {
+            let num = match memory.get(&(first_input_addr + i)) {
+                Ok(Some(value)) => match value.as_ref() {
+                    MaybeRelocatable::Int(num) => num.clone(),
+                    _ => return Err(RunnerError::FoundNonInt),
+                },
+                _ => return Ok(None),
+            };
+            //Convert Felt to FieldElement
+            *state_elem = FieldElement::from_dec_str(&num.to_str_radix(10))
+                .map_err(|_| RunnerError::FailedStringConversion)?;
+        }
+
+        poseidon_permute_comp(&mut poseidon_state);
+
+        let mut cache = self.cache.borrow_mut();
+        for (i, elem) in poseidon_state.iter().enumerate() {
+            cache.insert(
+                first_output_addr + i,
+                Felt::from_bytes_be(&elem.to_bytes_be()),
+            );
+        }
+        Ok(cache
+            .get(address)
+            .map(|felt| MaybeRelocatable::from(felt.clone())))
+    }
+
+    pub fn get_allocated_memory_units(&self, vm: &VirtualMachine) -> Result<usize, MemoryError> {
+        let value = safe_div_usize(vm.current_step, self.ratio as usize)
+            .map_err(|_| MemoryError::ErrorCalculatingMemoryUnits)?;
+        Ok(self.cells_per_instance as usize * value)
+    }
+
+    pub fn get_memory_segment_addresses(&self) -> (&'static str, (isize, Option<usize>)) {
+        ("poseidon", (self.base, self.stop_ptr))
+    }
+
+    pub fn get_used_cells(&self, vm: &VirtualMachine) -> Result<usize, MemoryError> {
+        let base = self.base();
+        vm.segments
+            .get_segment_used_size(
+                base.try_into()
+                    .map_err(|_| MemoryError::AddressInTemporarySegment(base))?,
+            )
+            .ok_or(MemoryError::MissingSegmentUsedSizes)
+    }
+
+    pub fn get_used_cells_and_allocated_size(
+        &self,
+        vm: &VirtualMachine,
+    ) -> Result<(usize, usize), MemoryError> {
+        let ratio = self.ratio as usize;
+        let cells_per_instance = self.cells_per_instance;
+        let min_step = ratio * self.instances_per_component as usize;
+        if vm.current_step < min_step {
+            Err(MemoryError::InsufficientAllocatedCells)
+        } else {
+            let used = self.get_used_cells(vm)?;
+            let size = cells_per_instance as usize
+                * safe_div_usize(vm.current_step, ratio)
+                    .map_err(|_| MemoryError::InsufficientAllocatedCells)?;
+            if used > size {
+                return Err(MemoryError::InsufficientAllocatedCellsError(
+                    "poseidon", used, size,
+                ));
+            }
+            Ok((used, size))
+        }
+    }
+
+    pub fn get_used_instances(&self, vm: &VirtualMachine) -> Result<usize, MemoryError> {
+        let used_cells = self.get_used_cells(vm)?;
+        Ok(div_ceil(used_cells, self.cells_per_instance as usize))
+    }
+
+    pub fn final_stack(
+        &self,
+        vm: &VirtualMachine,
+        pointer: Relocatable,
+    ) -> Result<(Relocatable, usize), RunnerError> {
+        if self._included {
+            if let Ok(stop_pointer) =
+                vm.get_relocatable(&(pointer.sub_usize(1)).map_err(|_| RunnerError::FinalStack)?)
+            {
+                if self.base() != stop_pointer.segment_index {
+                    return Err(RunnerError::InvalidStopPointer("poseidon".to_string()));
+                }
+                let stop_ptr = stop_pointer.offset;
+                let num_instances = self
+                    .get_used_instances(vm)
+                    .map_err(|_| RunnerError::FinalStack)?;
+                let used_cells = num_instances * self.cells_per_instance as usize;
+                if stop_ptr != used_cells {
+                    return Err(RunnerError::InvalidStopPointer("poseidon".to_string()));
+                }
+                Ok((
+                    pointer.sub_usize(1).map_err(|_| RunnerError::FinalStack)?,
+                    stop_ptr,
+                ))
+            } else {
+                Err(RunnerError::FinalStack)
+            }
+        } else {
+            let stop_ptr = self.base() as usize;
+            Ok((pointer, stop_ptr))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor;
+    use crate::types::program::Program;
+    use crate::utils::test_utils::*;
+    use crate::vm::runners::builtin_runner::BuiltinRunner;
+    use crate::vm::runners::cairo_runner::{CairoRunner, RunResources};
+    use starknet_crypto::{poseidon_hash, poseidon_hash_single};
+    use std::path::Path;
+
+    fn field_element_to_felt(elem: FieldElement) -> Felt {
+        Felt::from_bytes_be(&elem.to_bytes_be())
+    }
+
+    #[test]
+    fn get_used_instances() {
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::new(10), true);
+
+        let mut vm = vm!();
+        vm.segments.segment_used_sizes = Some(vec![7]);
+
+        assert_eq!(builtin.get_used_instances(&vm), Ok(2));
+    }
+
+    #[test]
+    fn final_stack() {
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::new(10), true);
+
+        let mut vm = vm!();
+
+        vm.memory = memory![
+            ((0, 0), (0, 0)),
+            ((0, 1), (0, 1)),
+            ((2, 0), (0, 0)),
+            ((2, 1), (0, 0))
+        ];
+
+        vm.segments.segment_used_sizes = Some(vec![0]);
+
+        let pointer = Relocatable::from((2, 2));
+
+        assert_eq!(
+            builtin.final_stack(&vm, pointer).unwrap(),
+            (Relocatable::from((2, 1)), 0)
+        );
+    }
+
+    #[test]
+    fn final_stack_error_stop_pointer() {
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::new(10), true);
+
+        let mut vm = vm!();
+
+        vm.memory = memory![
+            ((0, 0), (0, 0)),
+            ((0, 1), (0, 1)),
+            ((2, 0), (0, 0)),
+            ((2, 1), (0, 0))
+        ];
+
+        vm.segments.segment_used_sizes = Some(vec![999]);
+
+        let pointer = Relocatable::from((2, 2));
+
+        assert_eq!(
+            builtin.final_stack(&vm, pointer),
+            Err(RunnerError::InvalidStopPointer("poseidon".to_string()))
+        );
+    }
+
+    #[test]
+    fn final_stack_error_when_not_included() {
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::new(10), false);
+
+        let mut vm = vm!();
+
+        vm.memory = memory![
+            ((0, 0), (0, 0)),
+            ((0, 1), (0, 1)),
+            ((2, 0), (0, 0)),
+            ((2, 1), (0, 0))
+        ];
+
+        vm.segments.segment_used_sizes = Some(vec![0]);
+
+        let pointer = Relocatable::from((2, 2));
+
+        assert_eq!(
+            builtin.final_stack(&vm, pointer).unwrap(),
+            (Relocatable::from((2, 2)), 0)
+        );
+    }
+
+    #[test]
+    fn get_allocated_memory_units() {
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::new(10), true);
+
+        let mut vm = vm!();
+        vm.current_step = 20;
+
+        assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(12));
+    }
+
+    #[test]
+    fn get_used_cells_and_allocated_size_insufficient_steps() {
+        let builtin: BuiltinRunner =
+            PoseidonBuiltinRunner::new(&PoseidonInstanceDef::new(10), true).into();
+
+        let mut vm = vm!();
+        vm.segments.segment_used_sizes = Some(vec![0]);
+        vm.current_step = 9;
+
+        assert_eq!(
+            builtin.get_used_cells_and_allocated_size(&vm),
+            Err(MemoryError::InsufficientAllocatedCells)
+        );
+    }
+
+    #[test]
+    fn get_used_cells_and_allocated_size() {
+        let builtin: BuiltinRunner =
+            PoseidonBuiltinRunner::new(&PoseidonInstanceDef::new(10), true).into();
+
+        let mut vm = vm!();
+        vm.segments.segment_used_sizes = Some(vec![6]);
+        vm.current_step = 20;
+
+        assert_eq!(builtin.get_used_cells_and_allocated_size(&vm), Ok((6, 12)));
+    }
+
+    #[test]
+    fn get_memory_segment_addresses() {
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), true);
+
+        assert_eq!(
+            builtin.get_memory_segment_addresses(),
+            ("poseidon", (0, None))
+        );
+    }
+
+    #[test]
+    fn get_used_cells_missing_segment_used_sizes() {
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), true);
+        let vm = vm!();
+
+        assert_eq!(
+            builtin.get_used_cells(&vm),
+            Err(MemoryError::MissingSegmentUsedSizes)
+        );
+    }
+
+    #[test]
+    fn get_used_cells() {
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), true);
+        let mut vm = vm!();
+
+        vm.segments.segment_used_sizes = Some(vec![4]);
+        assert_eq!(builtin.get_used_cells(&vm), Ok(4));
+    }
+
+    #[test]
+    fn initial_stack_included_test() {
+        let poseidon_builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), true);
+        assert_eq!(
+            poseidon_builtin.initial_stack(),
+            vec![mayberelocatable!(0, 0)]
+        )
+    }
+
+    #[test]
+    fn initial_stack_not_included_test() {
+        let poseidon_builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), false);
+        assert_eq!(poseidon_builtin.initial_stack(), Vec::new())
+    }
+
+    #[test]
+    fn deduce_memory_cell_input_cell() {
+        let memory = memory![((0, 0), 1), ((0, 1), 2), ((0, 2), 2)];
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), true);
+
+        let result = builtin.deduce_memory_cell(&Relocatable::from((0, 2)), &memory);
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn deduce_memory_cell_missing_input() {
+        let memory = memory![((0, 0), 1), ((0, 1), 2)];
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), true);
+
+        let result = builtin.deduce_memory_cell(&Relocatable::from((0, 3)), &memory);
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn deduce_memory_cell_relocatable_input() {
+        let memory = memory![((0, 0), 1), ((0, 1), (1, 0)), ((0, 2), 2)];
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), true);
+
+        let result = builtin.deduce_memory_cell(&Relocatable::from((0, 3)), &memory);
+        assert_eq!(result, Err(RunnerError::FoundNonInt));
+    }
+
+    // poseidon_hash(x, y) is defined as the first element of the permutation of [x, y, 2]
+    #[test]
+    fn deduce_memory_cell_matches_poseidon_hash() {
+        let memory = memory![((0, 6), 1), ((0, 7), 2), ((0, 8), 2)];
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), true);
+
+        let expected = poseidon_hash(FieldElement::from(1_u64), FieldElement::from(2_u64));
+        let result = builtin.deduce_memory_cell(&Relocatable::from((0, 9)), &memory);
+        assert_eq!(
+            result,
+            Ok(Some(MaybeRelocatable::from(field_element_to_felt(
+                expected
+            ))))
+        );
+    }
+
+    // poseidon_hash_single(x) is defined as the first element of the permutation of [x, 0, 1]
+    #[test]
+    fn deduce_memory_cell_matches_poseidon_hash_single() {
+        let memory = memory![((0, 0), 42), ((0, 1), 0), ((0, 2), 1)];
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), true);
+
+        let expected = poseidon_hash_single(FieldElement::from(42_u64));
+        let result = builtin.deduce_memory_cell(&Relocatable::from((0, 3)), &memory);
+        assert_eq!(
+            result,
+            Ok(Some(MaybeRelocatable::from(field_element_to_felt(
+                expected
+            ))))
+        );
+    }
+
+    #[test]
+    fn deduce_memory_cell_all_outputs_from_one_permutation() {
+        let memory = memory![((0, 0), 1), ((0, 1), 2), ((0, 2), 3)];
+        let builtin = PoseidonBuiltinRunner::new(&PoseidonInstanceDef::default(), true);
+
+        let mut state = [
+            FieldElement::from(1_u64),
+            FieldElement::from(2_u64),
+            FieldElement::from(3_u64),
+        ];
+        poseidon_permute_comp(&mut state);
+
+        for (i, elem) in state.iter().enumerate() {
+            let result = builtin.deduce_memory_cell(&Relocatable::from((0, 3 + i)), &memory);
+            assert_eq!(
+                result,
+                Ok(Some(MaybeRelocatable::from(field_element_to_felt(*elem))))
+            );
+        }
+        // The next instance shouldn't be deduced from the previous instance's cache
+        let result = builtin.deduce_memory_cell(&Relocatable::from((0, 9)), &memory);
+        assert_eq!(result, Ok(None));
+    }
+
+    #[test]
+    fn run_program_with_poseidon_builtin() {
+        let program = Program::from_file(
+            Path::new("cairo_programs/poseidon_builtin.json"),
+            Some("main"),
+        )
+        .expect("Call to `Program::from_file()` failed.");
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, "all");
+        let mut vm = vm!();
+
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .expect("Call to `CairoRunner::run_until_pc()` failed.");
+
+        let (name, builtin) = &vm.get_builtin_runners()[0];
+        assert_eq!(name, "poseidon");
+        assert_eq!(
+            builtin.deduce_memory_cell(&Relocatable::from((builtin.base(), 3)), &vm.memory),
+            Ok(Some(MaybeRelocatable::from(field_element_to_felt(
+                poseidon_hash(FieldElement::from(1_u64), FieldElement::from(2_u64))
+            ))))
+        );
+    }
+}
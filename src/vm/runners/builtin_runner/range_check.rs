@@ -104,7 +104,7 @@ impl RangeCheckBuiltinRunner {
                 }
             },
         ));
-        memory.add_validation_rule(
+        memory.set_validation_rule(
             self.base
                 .to_usize()
                 .ok_or(RunnerError::RunnerInTemporarySegment(self.base))?,
@@ -156,25 +156,29 @@ impl RangeCheckBuiltinRunner {
                 * safe_div_usize(vm.current_step, ratio as usize)
                     .map_err(|_| MemoryError::InsufficientAllocatedCells)?;
             if used > size {
-                return Err(MemoryError::InsufficientAllocatedCells);
+                return Err(MemoryError::InsufficientAllocatedCellsError(
+                    "range_check",
+                    used,
+                    size,
+                ));
             }
             Ok((used, size))
         }
     }
 
+    /// Returns the minimum and maximum of the 16-bit parts the checked values are split into,
+    /// or None if the range check segment is empty.
     pub fn get_range_check_usage(&self, memory: &Memory) -> Option<(usize, usize)> {
         let mut rc_bounds: Option<(usize, usize)> = None;
         let range_check_segment = memory.data.get(self.base as usize)?;
         let inner_rc_bound = Felt::new(self.inner_rc_bound);
-        for value in range_check_segment {
+        for value in range_check_segment.iter().flatten() {
             //Split val into n_parts parts.
+            let mut value = value.get_int_ref().ok()?.clone();
             for _ in 0..self.n_parts {
-                let part_val = value
-                    .as_ref()?
-                    .get_int_ref()
-                    .ok()?
-                    .mod_floor(&inner_rc_bound)
-                    .to_usize()?;
+                let (quotient, part_val) = value.div_mod_floor(&inner_rc_bound);
+                let part_val = part_val.to_usize()?;
+                value = quotient;
                 rc_bounds = Some(match rc_bounds {
                     None => (part_val, part_val),
                     Some((rc_min, rc_max)) => {
@@ -245,7 +249,10 @@ mod tests {
         types::program::Program,
         utils::test_utils::*,
         vm::{
-            runners::{builtin_runner::BuiltinRunner, cairo_runner::CairoRunner},
+            runners::{
+                builtin_runner::BuiltinRunner,
+                cairo_runner::{CairoRunner, RunResources},
+            },
             vm_core::VirtualMachine,
         },
     };
@@ -399,7 +406,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_used_cells_and_allocated_size(&vm), Ok((0, 1)));
@@ -442,7 +454,12 @@ mod tests {
         let address = cairo_runner.initialize(&mut vm).unwrap();
 
         cairo_runner
-            .run_until_pc(address, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                address,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .unwrap();
 
         assert_eq!(builtin.get_allocated_memory_units(&vm), Ok(1));
@@ -549,7 +566,7 @@ mod tests {
     fn get_range_check_usage_succesful_a() {
         let builtin = RangeCheckBuiltinRunner::new(8, 8, true);
         let memory = memory![((0, 0), 1), ((0, 1), 2), ((0, 2), 3), ((0, 3), 4)];
-        assert_eq!(builtin.get_range_check_usage(&memory), Some((1, 4)));
+        assert_eq!(builtin.get_range_check_usage(&memory), Some((0, 4)));
     }
 
     #[test]
@@ -561,7 +578,7 @@ mod tests {
             ((0, 2), 31349610736_i64),
             ((0, 3), 413468326585859_i64)
         ];
-        assert_eq!(builtin.get_range_check_usage(&memory), Some((6384, 62821)));
+        assert_eq!(builtin.get_range_check_usage(&memory), Some((0, 62821)));
     }
 
     #[test]
@@ -575,7 +592,26 @@ mod tests {
             ((0, 4), 75346043276073460326_i128),
             ((0, 5), 87234598724867609478353436890268_i128)
         ];
-        assert_eq!(builtin.get_range_check_usage(&memory), Some((10480, 42341)));
+        assert_eq!(builtin.get_range_check_usage(&memory), Some((0, 61576)));
+    }
+
+    #[test]
+    fn get_range_check_usage_two_parts() {
+        let builtin = RangeCheckBuiltinRunner::new(8, 2, true);
+        let memory = memory![
+            ((0, 0), 1465218365),
+            ((0, 1), 2134570341),
+            ((0, 2), 31349610736_i64),
+            ((0, 3), 413468326585859_i64)
+        ];
+        assert_eq!(builtin.get_range_check_usage(&memory), Some((6331, 62821)));
+    }
+
+    #[test]
+    fn get_range_check_usage_with_gaps() {
+        let builtin = RangeCheckBuiltinRunner::new(8, 2, true);
+        let memory = memory![((0, 1), 0xffff0001_i64), ((0, 3), 0x10002)];
+        assert_eq!(builtin.get_range_check_usage(&memory), Some((1, 65535)));
     }
 
     #[test]
@@ -6,6 +6,10 @@ use crate::{
     },
     vm::{
         errors::{memory_errors::MemoryError, runner_errors::RunnerError},
+        runners::{
+            air_private_input::PrivateInput, builtin_runner::instance_inputs,
+            cairo_pie::BuiltinAdditionalData,
+        },
         vm_core::VirtualMachine,
         vm_memory::{
             memory::{Memory, ValidationRule},
@@ -13,7 +17,7 @@ use crate::{
         },
     },
 };
-use felt::{Felt, FeltOps};
+use felt::{felt_const, Felt, FeltOps};
 use num_integer::{div_ceil, Integer};
 use num_traits::ToPrimitive;
 use starknet_crypto::{verify, FieldElement, Signature};
@@ -158,7 +162,7 @@ impl SignatureBuiltinRunner {
                 }
             },
         ));
-        memory.add_validation_rule(
+        memory.set_validation_rule(
             self.base
                 .to_usize()
                 .ok_or(RunnerError::RunnerInTemporarySegment(self.base))?,
@@ -193,6 +197,53 @@ impl SignatureBuiltinRunner {
         ("ecdsa", (self.base, self.stop_ptr))
     }
 
+    pub fn get_additional_data(&self) -> BuiltinAdditionalData {
+        let signatures = self
+            .signatures
+            .borrow()
+            .iter()
+            .map(|(addr, signature)| {
+                (
+                    *addr,
+                    (
+                        Felt::from_bytes_be(&signature.r.to_bytes_be()),
+                        Felt::from_bytes_be(&signature.s.to_bytes_be()),
+                    ),
+                )
+            })
+            .collect();
+        BuiltinAdditionalData::Signature(signatures)
+    }
+
+    /// Returns the public key, message and signature of every signed instance, with the
+    /// signature given as (r, w) where w is the inverse of s modulo the curve order.
+    pub fn air_private_input(&self, memory: &Memory) -> Vec<PrivateInput> {
+        let ec_order =
+            felt_const!("0x800000000000010ffffffffffffffffb781126dcae7b2321e66a241adc64d2f");
+        let signatures = self.signatures.borrow();
+        instance_inputs(
+            memory,
+            self.base,
+            self.cells_per_instance,
+            self.n_input_cells,
+        )
+        .into_iter()
+        .filter_map(|(index, inputs)| {
+            let pubkey_addr =
+                Relocatable::from((self.base, index * self.cells_per_instance as usize));
+            let signature = signatures.get(&pubkey_addr)?;
+            let s = Felt::from_bytes_be(&signature.s.to_bytes_be());
+            Some(PrivateInput::Signature {
+                index,
+                pubkey: inputs[0].clone(),
+                msg: inputs[1].clone(),
+                r: Felt::from_bytes_be(&signature.r.to_bytes_be()),
+                w: s.modpow(&(ec_order.clone() - 2_u32), &ec_order),
+            })
+        })
+        .collect()
+    }
+
     pub fn get_used_cells(&self, vm: &VirtualMachine) -> Result<usize, MemoryError> {
         let base = self.base();
         vm.segments
@@ -218,7 +269,9 @@ impl SignatureBuiltinRunner {
                 * safe_div_usize(vm.current_step, ratio)
                     .map_err(|_| MemoryError::InsufficientAllocatedCells)?;
             if used > size {
-                return Err(MemoryError::InsufficientAllocatedCells);
+                return Err(MemoryError::InsufficientAllocatedCellsError(
+                    "ecdsa", used, size,
+                ));
             }
             Ok((used, size))
         }
@@ -277,6 +330,8 @@ mod tests {
             vm_memory::{memory::Memory, memory_segments::MemorySegmentManager},
         },
     };
+    use felt::{felt_str, NewFelt};
+    use num_traits::One;
 
     #[test]
     fn initialize_segments_for_ecdsa() {
@@ -490,4 +545,111 @@ mod tests {
         let result = builtin.deduce_memory_cell(&Relocatable::from((0, 5)), &memory);
         assert_eq!(result, Ok(None));
     }
+
+    // Signature of the message 2 by the public key stored at (1, 0)
+    fn valid_signature() -> (Felt, Felt) {
+        (
+            felt_str!(
+                "1839793652349538280924927302501143912227271479439798783640887258675143576352"
+            ),
+            felt_str!(
+                "1819432147005223164874083361865404672584671743718628757598322238853218813979"
+            ),
+        )
+    }
+
+    fn signed_memory() -> Memory {
+        memory![
+            (
+                (1, 0),
+                (
+                    "874739451078007766457464989774322083649278607533249481151382481072868806602",
+                    10
+                )
+            ),
+            ((1, 1), 2)
+        ]
+    }
+
+    #[test]
+    fn validate_corrupted_signature() {
+        let mut builtin = SignatureBuiltinRunner::new(&EcdsaInstanceDef::default(), true);
+        let (r, s) = valid_signature();
+        builtin
+            .add_signature(Relocatable::from((1, 0)), &(r, s + Felt::one()))
+            .unwrap();
+
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = signed_memory();
+        builtin.initialize_segments(&mut segments, &mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
+        assert_eq!(
+            memory.validate_existing_memory(),
+            Err(MemoryError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn add_signature_keeps_first_signature() {
+        let mut builtin = SignatureBuiltinRunner::new(&EcdsaInstanceDef::default(), true);
+        let (r, s) = valid_signature();
+        builtin
+            .add_signature(Relocatable::from((1, 0)), &(r.clone(), s.clone()))
+            .unwrap();
+        builtin
+            .add_signature(Relocatable::from((1, 0)), &(Felt::one(), Felt::one()))
+            .unwrap();
+
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = signed_memory();
+        builtin.initialize_segments(&mut segments, &mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
+        assert_eq!(memory.validate_existing_memory(), Ok(()));
+        assert_eq!(
+            builtin.get_additional_data(),
+            BuiltinAdditionalData::Signature(HashMap::from([(Relocatable::from((1, 0)), (r, s))]))
+        );
+    }
+
+    #[test]
+    fn air_private_input_signed_instance() {
+        let mut builtin = SignatureBuiltinRunner::new(&EcdsaInstanceDef::default(), true);
+        builtin.base = 1;
+        let (r, s) = valid_signature();
+        builtin
+            .add_signature(Relocatable::from((1, 0)), &(r.clone(), s.clone()))
+            .unwrap();
+
+        let inputs = builtin.air_private_input(&signed_memory());
+        let ec_order = felt_str!(
+            "3618502788666131213697322783095070105526743751716087489154079457884512865583"
+        );
+        match inputs.as_slice() {
+            [PrivateInput::Signature {
+                index: 0,
+                pubkey,
+                msg,
+                r: signature_r,
+                w,
+            }] => {
+                assert_eq!(
+                    pubkey,
+                    &felt_str!(
+                        "874739451078007766457464989774322083649278607533249481151382481072868806602"
+                    )
+                );
+                assert_eq!(msg, &Felt::new(2));
+                assert_eq!(signature_r, &r);
+                assert!(((w.to_biguint() * s.to_biguint()) % ec_order.to_biguint()).is_one());
+            }
+            _ => panic!("Expected a single signature input, got {inputs:?}"),
+        }
+    }
+
+    #[test]
+    fn air_private_input_skips_unsigned_instance() {
+        let mut builtin = SignatureBuiltinRunner::new(&EcdsaInstanceDef::default(), true);
+        builtin.base = 1;
+        assert!(builtin.air_private_input(&signed_memory()).is_empty());
+    }
 }
@@ -0,0 +1,294 @@
+use crate::{
+    types::relocatable::{MaybeRelocatable, Relocatable},
+    vm::runners::cairo_runner::{ExecutionResources, SegmentInfo},
+};
+use felt::{Felt, FeltOps};
+use serde::{ser::Error, ser::SerializeMap, ser::SerializeSeq, Serialize, Serializer};
+use std::{collections::HashMap, fs::File, io, io::Write, path::Path, str::FromStr};
+use zip::{write::FileOptions, CompressionMethod, ZipWriter};
+
+// Memory addresses are encoded as ADDR_BASE + segment_index * OFFSET_BASE + offset,
+// in ADDR_SIZE_IN_BYTES little-endian bytes.
+const ADDR_SIZE_IN_BYTES: usize = 8;
+const ADDR_BASE: u64 = 1 << 63;
+const OFFSET_BASE: u64 = 1 << 47;
+// Relocatable values are encoded as 2**255 + segment_index * OFFSET_BASE + offset.
+const RELOCATE_BASE_FLAG: u8 = 0x80;
+const FIELD_BYTES: usize = 32;
+
+pub const CAIRO_PIE_VERSION: &str = "1.0";
+
+/// The program as stored in a Cairo PIE: only the data needed to run it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StrippedProgram {
+    pub data: Vec<Felt>,
+    pub builtins: Vec<String>,
+    pub main: usize,
+    pub prime: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CairoPieMetadata {
+    pub program: StrippedProgram,
+    pub program_segment: SegmentInfo,
+    pub execution_segment: SegmentInfo,
+    pub ret_fp_segment: SegmentInfo,
+    pub ret_pc_segment: SegmentInfo,
+    pub builtin_segments: HashMap<String, SegmentInfo>,
+    pub extra_segments: Vec<SegmentInfo>,
+}
+
+/// Extra information a builtin runner needs to reproduce its memory validation,
+/// e.g. the addresses checked by the pedersen builtin.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum BuiltinAdditionalData {
+    // Page id -> (start offset, size)
+    Output(HashMap<usize, (usize, usize)>),
+    // Sorted verified addresses
+    Hash(Vec<Relocatable>),
+    // Address -> (r, s)
+    Signature(HashMap<Relocatable, (Felt, Felt)>),
+    Empty,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CairoPie {
+    pub metadata: CairoPieMetadata,
+    pub memory: Vec<((usize, usize), MaybeRelocatable)>,
+    // Keyed by builtin runner name, e.g. "pedersen_builtin"
+    pub additional_data: HashMap<String, BuiltinAdditionalData>,
+    pub execution_resources: ExecutionResources,
+}
+
+impl CairoPie {
+    /// Writes the PIE as a zip file in the layout cairo-lang expects.
+    pub fn write_zip(&self, path: &Path) -> io::Result<()> {
+        let mut zip = ZipWriter::new(File::create(path)?);
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip.start_file("metadata.json", options)?;
+        serde_json::to_writer(&mut zip, &self.metadata)?;
+        zip.start_file("memory.bin", options)?;
+        zip.write_all(&serialize_memory(&self.memory))?;
+        zip.start_file("additional_data.json", options)?;
+        serde_json::to_writer(&mut zip, &self.additional_data)?;
+        zip.start_file("execution_resources.json", options)?;
+        serde_json::to_writer(&mut zip, &self.execution_resources)?;
+        zip.start_file("version.json", options)?;
+        serde_json::to_writer(&mut zip, &HashMap::from([("cairo_pie", CAIRO_PIE_VERSION)]))?;
+
+        zip.finish()?;
+        Ok(())
+    }
+}
+
+/// Encodes memory as a sequence of (address, value) pairs, using
+/// ADDR_SIZE_IN_BYTES bytes for the address and FIELD_BYTES bytes for the value.
+pub fn serialize_memory(memory: &[((usize, usize), MaybeRelocatable)]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(memory.len() * (ADDR_SIZE_IN_BYTES + FIELD_BYTES));
+    for ((segment_index, offset), value) in memory {
+        let address = ADDR_BASE + *segment_index as u64 * OFFSET_BASE + *offset as u64;
+        bytes.extend_from_slice(&address.to_le_bytes());
+        match value {
+            MaybeRelocatable::Int(num) => bytes.extend_from_slice(&num.to_bytes_le()),
+            MaybeRelocatable::RelocatableValue(rel) => {
+                let mut value = [0; FIELD_BYTES];
+                let encoded = rel.segment_index as u64 * OFFSET_BASE + rel.offset as u64;
+                value[..8].copy_from_slice(&encoded.to_le_bytes());
+                value[FIELD_BYTES - 1] |= RELOCATE_BASE_FLAG;
+                bytes.extend_from_slice(&value);
+            }
+        }
+    }
+    bytes
+}
+
+fn felt_to_number(num: &Felt) -> Result<serde_json::Number, serde_json::Error> {
+    serde_json::Number::from_str(&num.to_str_radix(10))
+}
+
+impl Serialize for StrippedProgram {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(4))?;
+        let data: Vec<String> = self
+            .data
+            .iter()
+            .map(|num| format!("0x{}", num.to_str_radix(16)))
+            .collect();
+        map.serialize_entry("data", &data)?;
+        map.serialize_entry("builtins", &self.builtins)?;
+        map.serialize_entry("main", &self.main)?;
+        map.serialize_entry("prime", &self.prime)?;
+        map.end()
+    }
+}
+
+impl Serialize for BuiltinAdditionalData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BuiltinAdditionalData::Output(pages) => {
+                let mut map = serializer.serialize_map(Some(2))?;
+                let pages: HashMap<String, [usize; 2]> = pages
+                    .iter()
+                    .map(|(id, (start, size))| (id.to_string(), [*start, *size]))
+                    .collect();
+                map.serialize_entry("pages", &pages)?;
+                map.serialize_entry("attributes", &HashMap::<String, ()>::new())?;
+                map.end()
+            }
+            BuiltinAdditionalData::Hash(addresses) => {
+                let mut seq = serializer.serialize_seq(Some(addresses.len()))?;
+                for address in addresses {
+                    seq.serialize_element(&(address.segment_index, address.offset))?;
+                }
+                seq.end()
+            }
+            BuiltinAdditionalData::Signature(signatures) => {
+                let mut signatures: Vec<_> = signatures.iter().collect();
+                signatures.sort_by_key(|(address, _)| (address.segment_index, address.offset));
+                let mut seq = serializer.serialize_seq(Some(signatures.len()))?;
+                for (address, (r, s)) in signatures {
+                    let r = felt_to_number(r).map_err(S::Error::custom)?;
+                    let s = felt_to_number(s).map_err(S::Error::custom)?;
+                    seq.serialize_element(&((address.segment_index, address.offset), (r, s)))?;
+                }
+                seq.end()
+            }
+            BuiltinAdditionalData::Empty => serializer.serialize_map(Some(0))?.end(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::relocatable;
+    use crate::utils::test_utils::*;
+    use num_traits::{One, Zero};
+
+    #[test]
+    fn serialize_memory_empty() {
+        assert!(serialize_memory(&[]).is_empty());
+    }
+
+    #[test]
+    fn serialize_memory_fixture() {
+        let memory = vec![
+            ((0, 0), mayberelocatable!(1)),
+            ((1, 3), mayberelocatable!(2, 5)),
+        ];
+        let mut expected = vec![0; 80];
+        // Address (0, 0): 2**63
+        expected[7] = 0x80;
+        // Value 1
+        expected[8] = 1;
+        // Address (1, 3): 2**63 + 2**47 + 3
+        expected[40] = 3;
+        expected[45] = 0x80;
+        expected[47] = 0x80;
+        // Value (2, 5): 2**255 + 2 * 2**47 + 5
+        expected[48] = 5;
+        expected[54] = 1;
+        expected[79] = 0x80;
+        assert_eq!(serialize_memory(&memory), expected);
+    }
+
+    #[test]
+    fn serialize_memory_big_felt() {
+        let memory = vec![((0, 1), mayberelocatable!(-1))];
+        let bytes = serialize_memory(&memory);
+        assert_eq!(bytes.len(), ADDR_SIZE_IN_BYTES + FIELD_BYTES);
+        assert_eq!(bytes[..8], [1, 0, 0, 0, 0, 0, 0, 0x80]);
+        assert_eq!(bytes[8..], (Felt::zero() - Felt::one()).to_bytes_le());
+    }
+
+    #[test]
+    fn serialize_additional_data_hash() {
+        let data = BuiltinAdditionalData::Hash(vec![relocatable!(3, 2), relocatable!(3, 5)]);
+        assert_eq!(serde_json::to_string(&data).unwrap(), "[[3,2],[3,5]]");
+    }
+
+    #[test]
+    fn serialize_additional_data_signature() {
+        let data = BuiltinAdditionalData::Signature(HashMap::from([
+            (relocatable!(4, 2), (Felt::new(7), Felt::new(8))),
+            (relocatable!(4, 0), (Felt::new(5), Felt::new(6))),
+        ]));
+        assert_eq!(
+            serde_json::to_string(&data).unwrap(),
+            "[[[4,0],[5,6]],[[4,2],[7,8]]]"
+        );
+    }
+
+    #[test]
+    fn serialize_additional_data_output() {
+        let data = BuiltinAdditionalData::Output(HashMap::from([(1, (0, 3))]));
+        assert_eq!(
+            serde_json::to_string(&data).unwrap(),
+            r#"{"pages":{"1":[0,3]},"attributes":{}}"#
+        );
+    }
+
+    #[test]
+    fn serialize_stripped_program() {
+        let program = StrippedProgram {
+            data: vec![Felt::new(0x480680017fff8000_u64), Felt::new(1)],
+            builtins: vec!["output".to_string()],
+            main: 0,
+            prime: "0x800000000000011000000000000000000000000000000000000000000000001".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_string(&program).unwrap(),
+            r#"{"data":["0x480680017fff8000","0x1"],"builtins":["output"],"main":0,"prime":"0x800000000000011000000000000000000000000000000000000000000000001"}"#
+        );
+    }
+
+    #[test]
+    fn write_zip_contains_pie_files() {
+        let segment = |index| SegmentInfo { index, size: 0 };
+        let cairo_pie = CairoPie {
+            metadata: CairoPieMetadata {
+                program: StrippedProgram {
+                    data: vec![Felt::new(1)],
+                    builtins: Vec::new(),
+                    main: 0,
+                    prime: "0x800000000000011000000000000000000000000000000000000000000000001"
+                        .to_string(),
+                },
+                program_segment: SegmentInfo { index: 0, size: 1 },
+                execution_segment: segment(1),
+                ret_fp_segment: segment(2),
+                ret_pc_segment: segment(3),
+                builtin_segments: HashMap::new(),
+                extra_segments: Vec::new(),
+            },
+            memory: vec![((0, 0), mayberelocatable!(1))],
+            additional_data: HashMap::new(),
+            execution_resources: ExecutionResources {
+                n_steps: 1,
+                n_memory_holes: 0,
+                builtin_instance_counter: HashMap::new(),
+            },
+        };
+        let path = std::env::temp_dir().join("cairo_pie_write_zip_test.zip");
+        cairo_pie.write_zip(&path).unwrap();
+
+        let mut archive = zip::ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "additional_data.json",
+                "execution_resources.json",
+                "memory.bin",
+                "metadata.json",
+                "version.json"
+            ]
+        );
+        let mut memory = Vec::new();
+        io::Read::read_to_end(&mut archive.by_name("memory.bin").unwrap(), &mut memory).unwrap();
+        assert_eq!(memory, serialize_memory(&cairo_pie.memory));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 use crate::{
+    cairo_run::write_binary_memory,
     hint_processor::hint_processor_definition::{HintProcessor, HintReference},
     math_utils::safe_div_usize,
     serde::deserialize_program::OffsetValue,
@@ -17,32 +18,43 @@ use crate::{
     utils::is_subsequence,
     vm::{
         errors::{
-            memory_errors::MemoryError, runner_errors::RunnerError, trace_errors::TraceError,
-            vm_errors::VirtualMachineError,
+            encode_errors::EncodeError, memory_errors::MemoryError, runner_errors::RunnerError,
+            trace_errors::TraceError, vm_errors::VirtualMachineError,
         },
         security::verify_secure_runner,
         trace::get_perm_range_check_limits,
         vm_memory::memory::RelocateValue,
         {
+            runners::air_private_input::AirPrivateInput,
+            runners::air_public_input::{MemorySegmentAddresses, PublicInput, PublicMemoryEntry},
             runners::builtin_runner::{
                 BitwiseBuiltinRunner, BuiltinRunner, EcOpBuiltinRunner, HashBuiltinRunner,
                 OutputBuiltinRunner, RangeCheckBuiltinRunner, SignatureBuiltinRunner,
             },
-            trace::trace_entry::{relocate_trace_register, RelocatedTraceEntry},
+            runners::cairo_pie::{CairoPie, CairoPieMetadata, StrippedProgram},
+            trace::trace_entry::{relocate_trace_register, RelocatedTraceEntry, TraceEntry},
             vm_core::VirtualMachine,
         },
     },
 };
-use felt::{Felt, FeltOps};
+use felt::{Felt, FeltOps, NewFelt};
 use num_integer::div_rem;
 use num_traits::Zero;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::Serialize;
 use std::{
     any::Any,
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     io,
+    ops::{Add, Sub},
+    path::Path,
 };
 
-use super::builtin_runner::KeccakBuiltinRunner;
+use super::builtin_runner::{KeccakBuiltinRunner, PoseidonBuiltinRunner};
+
+// Encoding of the `jmp rel 0` instruction, which is followed by its immediate, 0
+const JMP_REL_0: u64 = 0x10780017fff7fff;
 
 pub struct CairoRunner {
     pub(crate) program: Program,
@@ -61,6 +73,8 @@ pub struct CairoRunner {
     pub relocated_memory: Vec<Option<Felt>>,
     pub relocated_trace: Option<Vec<RelocatedTraceEntry>>,
     pub exec_scopes: ExecutionScopes,
+    // Compiled on the first run and reused by the later runs until clear_hint_data_cache
+    hint_data_dictionary: Option<HashMap<usize, Vec<Box<dyn Any>>>>,
 }
 
 impl CairoRunner {
@@ -79,9 +93,23 @@ impl CairoRunner {
             "all" => CairoLayout::all_instance(),
             name => return Err(RunnerError::InvalidLayoutName(name.to_string())),
         };
-        Ok(CairoRunner {
+        Ok(CairoRunner::new_with_layout(
+            program,
+            cairo_layout,
+            proof_mode,
+        ))
+    }
+
+    /// Creates a runner for a layout that isn't selectable by name, such as
+    /// `CairoLayout::dynamic`
+    pub fn new_with_layout(
+        program: &Program,
+        layout: CairoLayout,
+        proof_mode: bool,
+    ) -> CairoRunner {
+        CairoRunner {
             program: program.clone(),
-            layout: cairo_layout,
+            layout,
             final_pc: None,
             program_base: None,
             execution_base: None,
@@ -96,7 +124,32 @@ impl CairoRunner {
             relocated_trace: None,
             exec_scopes: ExecutionScopes::new(),
             execution_public_memory: if proof_mode { Some(Vec::new()) } else { None },
-        })
+            hint_data_dictionary: None,
+        }
+    }
+
+    /// Clears the state left by a run, so that the program can be run again with `initialize`.
+    /// The program, layout, proof mode and compiled hint data are preserved, as is the capacity
+    /// of the relocated memory. The VM should be reset along with the runner, see
+    /// `VirtualMachine::reset`.
+    pub fn reset(&mut self) {
+        self.final_pc = None;
+        self.program_base = None;
+        self.execution_base = None;
+        self.initial_ap = None;
+        self.initial_fp = None;
+        self.initial_pc = None;
+        self.run_ended = false;
+        self.segments_finalized = false;
+        self.execution_public_memory = if self.proof_mode {
+            Some(Vec::new())
+        } else {
+            None
+        };
+        self.original_steps = None;
+        self.relocated_memory.clear();
+        self.relocated_trace = None;
+        self.exec_scopes = ExecutionScopes::new();
     }
 
     pub fn initialize(&mut self, vm: &mut VirtualMachine) -> Result<Relocatable, RunnerError> {
@@ -107,6 +160,30 @@ impl CairoRunner {
         Ok(end)
     }
 
+    ///Initializes the runner to execute the function at `entrypoint` with the given initial stack,
+    /// which should contain the function's arguments, including the builtin pointers it takes.
+    ///In proof mode, the function returns into a dummy frame and then loops on the program's
+    /// `__end__` label (or on a `jmp rel 0` appended to the program if it has none), so that the
+    /// execution can be padded and proven like a run from main.
+    ///Returns the value of the program counter after returning from the function.
+    pub fn initialize_from_entrypoint(
+        &mut self,
+        vm: &mut VirtualMachine,
+        entrypoint: usize,
+        stack: Vec<MaybeRelocatable>,
+    ) -> Result<Relocatable, RunnerError> {
+        self.initialize_builtins(vm)?;
+        self.initialize_segments(vm, None);
+        let end = if self.proof_mode {
+            self.initialize_proof_mode_entrypoint(vm, entrypoint, stack)?
+        } else {
+            let return_fp = vm.segments.add(&mut vm.memory);
+            self.initialize_function_entrypoint(vm, entrypoint, stack, return_fp.into())?
+        };
+        self.initialize_vm(vm)?;
+        Ok(end)
+    }
+
     pub fn initialize_builtins(&self, vm: &mut VirtualMachine) -> Result<(), RunnerError> {
         let builtin_ordered_list = vec![
             String::from("output"),
@@ -116,6 +193,7 @@ impl CairoRunner {
             String::from("bitwise"),
             String::from("ec_op"),
             String::from("keccak"),
+            String::from("poseidon"),
         ];
         if !is_subsequence(&self.program.builtins, &builtin_ordered_list) {
             return Err(RunnerError::DisorderedBuiltins);
@@ -197,6 +275,16 @@ impl CairoRunner {
             }
         }
 
+        if let Some(instance_def) = self.layout.builtins.poseidon.as_ref() {
+            let included = self.program.builtins.contains(&"poseidon".to_string());
+            if included || self.proof_mode {
+                builtin_runners.push((
+                    "poseidon".to_string(),
+                    PoseidonBuiltinRunner::new(instance_def, included).into(),
+                ));
+            }
+        }
+
         let inserted_builtins = builtin_runners
             .iter()
             .map(|x| &x.0)
@@ -404,6 +492,44 @@ impl CairoRunner {
         }
     }
 
+    fn initialize_proof_mode_entrypoint(
+        &mut self,
+        vm: &mut VirtualMachine,
+        entrypoint: usize,
+        stack: Vec<MaybeRelocatable>,
+    ) -> Result<Relocatable, RunnerError> {
+        let exec_base = self.execution_base.ok_or(RunnerError::NoExecBase)?;
+        let prog_base = self.program_base.ok_or(RunnerError::NoProgBase)?;
+        let end = match self.program.end {
+            Some(end) => end,
+            None => {
+                // Place the end loop right after the program, so it is part of the program's
+                // public memory
+                let end = self.program.data.len();
+                self.program.data.extend([
+                    MaybeRelocatable::from(Felt::new(JMP_REL_0)),
+                    MaybeRelocatable::from(Felt::zero()),
+                ]);
+                self.program.end = Some(end);
+                end
+            }
+        };
+        let end = prog_base + end;
+        // As in initialize_main_entrypoint, the dummy frame satisfies [fp - 2] = fp. The function
+        // returns into it, at the end loop.
+        let dummy_fp = MaybeRelocatable::from(exec_base + 2);
+        let mut full_stack = vec![dummy_fp.clone(), MaybeRelocatable::from(Felt::zero())];
+        full_stack.extend(stack);
+        full_stack.extend([dummy_fp, MaybeRelocatable::from(end)]);
+        // The whole initial stack is public, as it holds the function's arguments
+        self.execution_public_memory = Some(Vec::from_iter(0..full_stack.len()));
+        self.initial_fp = Some(exec_base + full_stack.len());
+        self.initial_ap = self.initial_fp;
+        self.initialize_state(vm, entrypoint, full_stack)?;
+        self.final_pc = Some(end);
+        Ok(end)
+    }
+
     pub fn initialize_vm(&mut self, vm: &mut VirtualMachine) -> Result<(), RunnerError> {
         vm.run_context.pc = *self.initial_pc.as_ref().ok_or(RunnerError::NoPC)?;
         vm.run_context.ap = self.initial_ap.as_ref().ok_or(RunnerError::NoAP)?.offset;
@@ -447,6 +573,7 @@ impl CairoRunner {
                     offset1: reference.value_address.offset1.clone(),
                     offset2: reference.value_address.offset2.clone(),
                     dereference: reference.value_address.dereference,
+                    inner_dereference: reference.value_address.inner_dereference,
                     // only store `ap` tracking data if the reference is referred to it
                     ap_tracking_data: match (
                         &reference.value_address.offset1,
@@ -492,29 +619,65 @@ impl CairoRunner {
         Ok(hint_data_dictionary)
     }
 
+    // Compiles the hint data of the program with hint_processor, unless a previous run did
+    fn compile_hint_data(
+        &mut self,
+        hint_processor: &mut dyn HintProcessor,
+    ) -> Result<(), VirtualMachineError> {
+        if self.hint_data_dictionary.is_none() {
+            let references = self.get_reference_list();
+            self.hint_data_dictionary =
+                Some(self.get_hint_data_dictionary(&references, hint_processor)?);
+        }
+        Ok(())
+    }
+
+    /// Drops the hint data compiled by the previous runs, which is otherwise reused by
+    /// `run_until_pc` and `run_for_steps`, also after `reset`. It must be called before running
+    /// the program with a different hint processor, as the hint data of a processor is opaque
+    /// to the others.
+    pub fn clear_hint_data_cache(&mut self) {
+        self.hint_data_dictionary = None;
+    }
+
     pub fn get_constants(&self) -> &HashMap<String, Felt> {
-        &self.program.constants
+        self.program.get_constants()
     }
 
     pub fn get_program_builtins(&self) -> &Vec<String> {
         &self.program.builtins
     }
 
+    /// Runs until pc reaches address, consuming one step of run_resources per instruction and per
+    /// executed hint. Fails with UnfinishedExecution if the resources run out before that.
+    /// The hints are compiled on the first run and reused by the following ones, see
+    /// `clear_hint_data_cache`.
+    /// Fails with NoEntrypoint if the runner wasn't initialized with an entrypoint, e.g. when a
+    /// library program without main() is run before calling initialize_function_entrypoint.
     pub fn run_until_pc(
         &mut self,
         address: Relocatable,
+        run_resources: &mut RunResources,
         vm: &mut VirtualMachine,
         hint_processor: &mut dyn HintProcessor,
     ) -> Result<(), VirtualMachineError> {
-        let references = self.get_reference_list();
-        let hint_data_dictionary = self.get_hint_data_dictionary(&references, hint_processor)?;
-        while vm.run_context.pc != address {
+        if self.initial_pc.is_none() {
+            return Err(RunnerError::NoEntrypoint.into());
+        }
+        self.compile_hint_data(hint_processor)?;
+        let hint_data_dictionary = self.hint_data_dictionary.get_or_insert_with(HashMap::new);
+        while vm.run_context.pc != address && !run_resources.consumed() {
             vm.step(
                 hint_processor,
                 &mut self.exec_scopes,
-                &hint_data_dictionary,
-                &self.program.constants,
+                hint_data_dictionary,
+                self.program.get_constants(),
+                run_resources,
             )?;
+            run_resources.consume_step();
+        }
+        if vm.run_context.pc != address {
+            return Err(VirtualMachineError::UnfinishedExecution);
         }
         Ok(())
     }
@@ -526,8 +689,8 @@ impl CairoRunner {
         vm: &mut VirtualMachine,
         hint_processor: &mut dyn HintProcessor,
     ) -> Result<(), VirtualMachineError> {
-        let references = self.get_reference_list();
-        let hint_data_dictionary = self.get_hint_data_dictionary(&references, hint_processor)?;
+        self.compile_hint_data(hint_processor)?;
+        let hint_data_dictionary = self.hint_data_dictionary.get_or_insert_with(HashMap::new);
 
         for remaining_steps in (1..=steps).rev() {
             if self.final_pc.as_ref() == Some(&vm.run_context.pc) {
@@ -537,8 +700,9 @@ impl CairoRunner {
             vm.step(
                 hint_processor,
                 &mut self.exec_scopes,
-                &hint_data_dictionary,
-                &self.program.constants,
+                hint_data_dictionary,
+                self.program.get_constants(),
+                &mut RunResources::default(),
             )?;
         }
 
@@ -705,7 +869,8 @@ impl CairoRunner {
                     Ok(_) => break,
                     Err(e) => match e {
                         VirtualMachineError::MemoryError(
-                            MemoryError::InsufficientAllocatedCells,
+                            MemoryError::InsufficientAllocatedCells
+                            | MemoryError::InsufficientAllocatedCellsError(..),
                         ) => {}
                         e => return Err(e),
                     },
@@ -731,17 +896,31 @@ impl CairoRunner {
         if !(self.relocated_memory.is_empty()) {
             return Err(MemoryError::Relocation);
         }
+        // Segments are relocated independently and then written in order, so that the
+        // result doesn't depend on whether they were relocated in parallel.
+        #[cfg(feature = "parallel")]
+        let relocated_segments: Vec<_> = vm
+            .memory
+            .data
+            .par_iter()
+            .enumerate()
+            .map(|(index, segment)| relocate_segment(index, segment, relocation_table))
+            .collect();
+        #[cfg(not(feature = "parallel"))]
+        let relocated_segments: Vec<_> = vm
+            .memory
+            .data
+            .iter()
+            .enumerate()
+            .map(|(index, segment)| relocate_segment(index, segment, relocation_table))
+            .collect();
+
         //Relocated addresses start at 1
         self.relocated_memory.push(None);
-        for (index, segment) in vm.memory.data.iter().enumerate() {
-            for (seg_offset, element) in segment.iter().enumerate() {
+        for segment in relocated_segments {
+            for element in segment? {
                 match element {
-                    Some(elem) => {
-                        let relocated_addr = relocate_address(
-                            Relocatable::from((index as isize, seg_offset)),
-                            relocation_table,
-                        )?;
-                        let value = relocate_value(elem.clone(), relocation_table)?;
+                    Some((relocated_addr, value)) => {
                         if self.relocated_memory.len() <= relocated_addr {
                             self.relocated_memory.resize(relocated_addr + 1, None);
                         }
@@ -764,27 +943,39 @@ impl CairoRunner {
             return Err(TraceError::AlreadyRelocated);
         }
 
-        let trace = vm.trace.as_ref().ok_or(TraceError::TraceNotEnabled)?.iter();
-        let mut relocated_trace = Vec::<RelocatedTraceEntry>::with_capacity(trace.len());
-        for entry in trace {
-            relocated_trace.push(RelocatedTraceEntry {
+        let trace = vm.trace.as_ref().ok_or(TraceError::TraceNotEnabled)?;
+        let relocate_entry = |entry: &TraceEntry| -> Result<RelocatedTraceEntry, TraceError> {
+            Ok(RelocatedTraceEntry {
                 pc: relocate_trace_register(&entry.pc, relocation_table)?,
                 ap: relocate_trace_register(&entry.ap, relocation_table)?,
                 fp: relocate_trace_register(&entry.fp, relocation_table)?,
             })
-        }
+        };
+        // The entries are collected in order before checking for errors, so that the
+        // first failing entry is reported, as in the sequential version
+        #[cfg(feature = "parallel")]
+        let relocated_trace = trace
+            .par_iter()
+            .map(relocate_entry)
+            .collect::<Vec<_>>()
+            .into_iter()
+            .collect::<Result<_, _>>()?;
+        #[cfg(not(feature = "parallel"))]
+        let relocated_trace = trace.iter().map(relocate_entry).collect::<Result<_, _>>()?;
         self.relocated_trace = Some(relocated_trace);
         Ok(())
     }
 
     pub fn relocate(&mut self, vm: &mut VirtualMachine) -> Result<(), TraceError> {
+        // Pointers into temporary segments can only be flattened once they've been
+        // relocated onto real segments.
+        vm.memory.relocate_memory()?;
         vm.segments.compute_effective_sizes(&vm.memory);
-        // relocate_segments can fail if compute_effective_sizes is not called before.
-        // The expect should be unreachable.
+        // Fails if a segment was written past its finalized size
         let relocation_table = vm
             .segments
             .relocate_segments()
-            .expect("compute_effective_sizes called but relocate_memory still returned error");
+            .map_err(TraceError::MemoryError)?;
         if let Err(memory_error) = self.relocate_memory(vm, &relocation_table) {
             return Err(TraceError::MemoryError(memory_error));
         }
@@ -794,6 +985,37 @@ impl CairoRunner {
         Ok(())
     }
 
+    /// Relocates the VM's trace entry by entry and streams it into writer, using the same binary
+    /// encoding as `write_binary_trace` (ap, fp and pc as little endian u64 values).
+    /// Unlike `relocate`, the relocated trace is never collected into memory.
+    pub fn write_relocated_trace<W: io::Write>(
+        &self,
+        vm: &mut VirtualMachine,
+        writer: &mut W,
+    ) -> Result<(), TraceError> {
+        vm.segments.compute_effective_sizes(&vm.memory);
+        let relocation_table = vm.segments.relocate_segments()?;
+        let trace = vm.trace.as_ref().ok_or(TraceError::TraceNotEnabled)?;
+        for (i, entry) in trace.iter().enumerate() {
+            for register in [&entry.ap, &entry.fp, &entry.pc] {
+                let value = relocate_trace_register(register, &relocation_table)? as u64;
+                writer
+                    .write_all(&value.to_le_bytes())
+                    .map_err(|e| TraceError::WriteFail(i, e.to_string()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the relocated memory to the file at path, in the binary format produced by
+    /// cairo-lang. Fails with `MemoryNotRelocated` if `relocate` wasn't called.
+    pub fn write_binary_memory(&self, path: &Path) -> Result<(), EncodeError> {
+        if self.relocated_memory.is_empty() {
+            return Err(EncodeError::MemoryNotRelocated);
+        }
+        write_binary_memory(&self.relocated_memory, path)
+    }
+
     pub fn get_builtin_segments_info(
         &self,
         vm: &VirtualMachine,
@@ -815,14 +1037,13 @@ impl CairoRunner {
         Ok(builtin_segments)
     }
 
+    /// Returns the resources used by the run. The steps are counted by the VM whether the
+    /// trace is enabled or not, so traced and untraced runs report the same resources.
     pub fn get_execution_resources(
         &self,
         vm: &VirtualMachine,
     ) -> Result<ExecutionResources, TraceError> {
-        let n_steps = match self.original_steps {
-            Some(x) => x,
-            None => vm.trace.as_ref().map(|x| x.len()).unwrap_or(0),
-        };
+        let n_steps = self.original_steps.unwrap_or(vm.current_step);
         let n_memory_holes = self.get_memory_holes(vm)?;
 
         let mut builtin_instance_counter = HashMap::new();
@@ -840,6 +1061,218 @@ impl CairoRunner {
         })
     }
 
+    /// Builds a Cairo PIE from a finished run.
+    /// Builtin stop pointers and segment sizes must already be computed.
+    pub fn get_cairo_pie(&self, vm: &VirtualMachine) -> Result<CairoPie, VirtualMachineError> {
+        let program_base = self.program_base.ok_or(RunnerError::NoProgBase)?;
+        let execution_base = self.execution_base.ok_or(RunnerError::NoExecBase)?;
+        let get_segment_info = |index: isize| -> Result<SegmentInfo, RunnerError> {
+            let size = vm
+                .segments
+                .get_segment_size(index as usize)
+                .ok_or(MemoryError::MissingSegmentUsedSizes)?;
+            Ok(SegmentInfo { index, size })
+        };
+
+        let builtin_segments: HashMap<String, SegmentInfo> = self
+            .get_builtin_segments_info(vm)?
+            .into_iter()
+            .map(|(name, info)| (name.to_string(), info))
+            .collect();
+        // The return fp and pc are located right after the builtin pointers.
+        let n_used_builtins = self.program.builtins.len();
+        let (ret_fp, ret_pc) = match (
+            vm.get_relocatable(&(execution_base + n_used_builtins))?,
+            vm.get_relocatable(&(execution_base + (n_used_builtins + 1)))?,
+        ) {
+            (ret_fp, ret_pc) if ret_fp.offset == 0 && ret_pc.offset == 0 => (ret_fp, ret_pc),
+            _ => return Err(RunnerError::InvalidReturnPointers.into()),
+        };
+        let known_segments: HashSet<isize> = builtin_segments
+            .values()
+            .map(|info| info.index)
+            .chain([
+                program_base.segment_index,
+                execution_base.segment_index,
+                ret_fp.segment_index,
+                ret_pc.segment_index,
+            ])
+            .collect();
+        let extra_segments = (0..vm.segments.num_segments as isize)
+            .filter(|index| !known_segments.contains(index))
+            .map(get_segment_info)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let program = StrippedProgram {
+            data: self
+                .program
+                .data
+                .iter()
+                .map(|value| match value {
+                    MaybeRelocatable::Int(num) => Ok(num.clone()),
+                    _ => Err(RunnerError::FoundNonInt),
+                })
+                .collect::<Result<_, _>>()?,
+            builtins: self.program.builtins.clone(),
+            main: self.program.main.ok_or(RunnerError::MissingMain)?,
+            prime: self.program.prime.clone(),
+        };
+        let metadata = CairoPieMetadata {
+            program,
+            program_segment: get_segment_info(program_base.segment_index)?,
+            execution_segment: get_segment_info(execution_base.segment_index)?,
+            ret_fp_segment: get_segment_info(ret_fp.segment_index)?,
+            ret_pc_segment: get_segment_info(ret_pc.segment_index)?,
+            builtin_segments,
+            extra_segments,
+        };
+
+        let mut memory = Vec::new();
+        for (segment_index, segment) in vm.memory.data.iter().enumerate() {
+            for (offset, value) in segment.iter().enumerate() {
+                if let Some(value) = value {
+                    memory.push(((segment_index, offset), value.clone()));
+                }
+            }
+        }
+
+        // cairo-lang keys additional data and builtin counters by runner name.
+        let runner_name = |name: &str| format!("{name}_builtin");
+        let additional_data = vm
+            .builtin_runners
+            .iter()
+            .map(|(name, builtin)| (runner_name(name), builtin.get_additional_data()))
+            .collect();
+        let mut execution_resources = self.get_execution_resources(vm)?;
+        execution_resources.builtin_instance_counter = execution_resources
+            .builtin_instance_counter
+            .into_iter()
+            .map(|(name, count)| (runner_name(&name), count))
+            .collect();
+
+        Ok(CairoPie {
+            metadata,
+            memory,
+            additional_data,
+            execution_resources,
+        })
+    }
+
+    /// Builds the public input of the prover from a finished proof mode run.
+    /// The run must have been traced, and its segments finalized and relocated.
+    pub fn get_air_public_input(
+        &self,
+        vm: &VirtualMachine,
+    ) -> Result<PublicInput, VirtualMachineError> {
+        let trace = match self.relocated_trace {
+            Some(ref trace) if !trace.is_empty() => trace,
+            _ => return Err(RunnerError::NoRelocatedTrace.into()),
+        };
+        let (rc_min, rc_max) = self
+            .get_perm_range_check_limits(vm)?
+            .ok_or(RunnerError::NoRelocatedTrace)?;
+        let relocation_table = vm.segments.relocate_segments()?;
+
+        let public_memory = vm
+            .segments
+            .get_public_memory_addresses(&relocation_table)?
+            .into_iter()
+            .map(|(address, page)| {
+                let value = self
+                    .relocated_memory
+                    .get(address)
+                    .cloned()
+                    .flatten()
+                    .ok_or(RunnerError::PublicMemoryCellNotFound(address))?;
+                Ok(PublicMemoryEntry {
+                    address,
+                    value,
+                    page,
+                })
+            })
+            .collect::<Result<Vec<_>, RunnerError>>()?;
+
+        // The program and execution segments are delimited by the first and last pc and ap.
+        let (first, last) = (&trace[0], &trace[trace.len() - 1]);
+        let mut memory_segments = BTreeMap::from([
+            (
+                "program".to_string(),
+                MemorySegmentAddresses {
+                    begin_addr: first.pc,
+                    stop_ptr: last.pc,
+                },
+            ),
+            (
+                "execution".to_string(),
+                MemorySegmentAddresses {
+                    begin_addr: first.ap,
+                    stop_ptr: last.ap,
+                },
+            ),
+        ]);
+        for (_, builtin) in vm.builtin_runners.iter() {
+            let (name, (index, stop_ptr)) = builtin.get_memory_segment_addresses();
+            let index = index as usize;
+            let begin_addr =
+                *relocation_table
+                    .get(index)
+                    .ok_or(MemoryError::UnallocatedSegment(
+                        index,
+                        relocation_table.len(),
+                    ))?;
+            // Runs that didn't read the return values leave the stop pointers unset
+            let size = match stop_ptr {
+                Some(stop_ptr) => stop_ptr,
+                None => vm
+                    .segments
+                    .get_segment_used_size(index)
+                    .ok_or(MemoryError::MissingSegmentUsedSizes)?,
+            };
+            memory_segments.insert(
+                name.to_string(),
+                MemorySegmentAddresses {
+                    begin_addr,
+                    stop_ptr: begin_addr + size,
+                },
+            );
+        }
+
+        Ok(PublicInput {
+            layout: self.layout._name.clone(),
+            rc_min,
+            rc_max,
+            n_steps: trace.len(),
+            memory_segments,
+            public_memory,
+            dynamic_params: self
+                .layout
+                .dynamic_params
+                .as_ref()
+                .map(|params| params.to_public_input_params()),
+        })
+    }
+
+    /// Builds the private input of the prover from a finished run, pointing it to the
+    /// trace and memory files written at `trace_path` and `memory_path`.
+    pub fn get_air_private_input(
+        &self,
+        vm: &VirtualMachine,
+        trace_path: &str,
+        memory_path: &str,
+    ) -> AirPrivateInput {
+        let builtins = vm
+            .builtin_runners
+            .iter()
+            .filter(|(_, builtin)| builtin.as_output_runner().is_none())
+            .map(|(name, builtin)| (name.clone(), builtin.air_private_input(&vm.memory)))
+            .collect();
+        AirPrivateInput {
+            trace_path: trace_path.to_string(),
+            memory_path: memory_path.to_string(),
+            builtins,
+        }
+    }
+
     pub fn get_output(&mut self, vm: &mut VirtualMachine) -> Result<String, RunnerError> {
         let mut output = Vec::<u8>::new();
         self.write_output(vm, &mut output)?;
@@ -847,39 +1280,48 @@ impl CairoRunner {
         Ok(output)
     }
 
-    /// Writes the values hosted in the output builtin's segment.
-    /// Does nothing if the output builtin is not present in the program.
-    pub fn write_output(
-        &mut self,
-        vm: &mut VirtualMachine,
-        stdout: &mut dyn io::Write,
-    ) -> Result<(), RunnerError> {
-        let builtin = vm
-            .builtin_runners
-            .iter_mut()
-            .find_map(|(k, v)| match k.as_str() {
-                "output" => Some(v),
-                _ => None,
-            });
-        let builtin = match builtin {
-            Some(x) => x,
-            _ => return Ok(()),
+    /// Returns the values written to the output builtin's segment, up to its used size.
+    /// Returns an empty vector if the output builtin is not present in the program, and fails
+    /// with `ExpectedInteger` if one of the cells holds a relocatable value.
+    pub fn read_output(&self, vm: &VirtualMachine) -> Result<Vec<Felt>, RunnerError> {
+        let builtin = match vm.builtin_runners.iter().find(|(name, _)| name == "output") {
+            Some((_, builtin)) => builtin,
+            None => return Ok(Vec::new()),
         };
 
-        let segment_used_sizes = vm.segments.compute_effective_sizes(&vm.memory);
         let base = builtin.base();
-
         let segment_index: usize = base
             .try_into()
             .map_err(|_| RunnerError::RunnerInTemporarySegment(base))?;
+        // Read from the memory, as the used sizes computed before may already be stale
+        let used_size = vm.get_segment_used_size(segment_index).unwrap_or(0);
+
+        (0..used_size)
+            .map(|offset| {
+                let address = Relocatable::from((base, offset));
+                match vm.memory.get(&address) {
+                    Ok(Some(value)) => match value.as_ref() {
+                        MaybeRelocatable::Int(value) => Ok(value.clone()),
+                        MaybeRelocatable::RelocatableValue(_) => {
+                            Err(RunnerError::ExpectedInteger(address.into()))
+                        }
+                    },
+                    _ => Err(RunnerError::MemoryGet(address.into())),
+                }
+            })
+            .collect()
+    }
 
-        for i in 0..segment_used_sizes[segment_index] {
-            let value = vm
-                .memory
-                .get_integer(&(base, i).into())
-                .map_err(|_| RunnerError::MemoryGet((base, i).into()))?
-                .to_bigint();
-            writeln!(stdout, "{}", value).map_err(|_| RunnerError::WriteFail)?;
+    /// Writes the values hosted in the output builtin's segment.
+    /// Does nothing if the output builtin is not present in the program.
+    pub fn write_output(
+        &mut self,
+        vm: &mut VirtualMachine,
+        stdout: &mut dyn io::Write,
+    ) -> Result<(), RunnerError> {
+        vm.segments.compute_effective_sizes(&vm.memory);
+        for value in self.read_output(vm)? {
+            writeln!(stdout, "{}", value.to_bigint()).map_err(|_| RunnerError::WriteFail)?;
         }
 
         Ok(())
@@ -903,12 +1345,12 @@ impl CairoRunner {
             public_memory.push((i, 0_usize))
         }
         vm.segments.finalize(
-            Some(size),
             self.program_base
                 .as_ref()
                 .ok_or(RunnerError::NoProgBase)?
                 .segment_index as usize,
-            Some(&public_memory),
+            Some(size),
+            Some(public_memory),
         );
         let mut public_memory = Vec::with_capacity(size);
         let exec_base = self
@@ -924,13 +1366,18 @@ impl CairoRunner {
             public_memory.push((elem + exec_base.offset, 0))
         }
         vm.segments
-            .finalize(None, exec_base.segment_index as usize, Some(&public_memory));
+            .finalize(exec_base.segment_index as usize, None, Some(public_memory));
         for (_, builtin_runner) in vm.builtin_runners.iter() {
             let (_, size) = builtin_runner
                 .get_used_cells_and_allocated_size(vm)
                 .map_err(RunnerError::FinalizeSegements)?;
+            // The output cells are public, split into the pages added to the output builtin
+            let public_memory = builtin_runner
+                .as_output_runner()
+                .map(|output| output.get_public_memory(vm))
+                .transpose()?;
             vm.segments
-                .finalize(Some(size), builtin_runner.base() as usize, None)
+                .finalize(builtin_runner.base() as usize, Some(size), public_memory)
         }
         self.segments_finalized = true;
         Ok(())
@@ -967,7 +1414,7 @@ impl CairoRunner {
 
         self.initialize_vm(vm)?;
 
-        self.run_until_pc(end, vm, hint_processor)?;
+        self.run_until_pc(end, &mut RunResources::default(), vm, hint_processor)?;
         self.end_run(true, false, vm, hint_processor)?;
 
         if verify_secure {
@@ -977,6 +1424,40 @@ impl CairoRunner {
         Ok(())
     }
 
+    /// Runs the function `entrypoint` of the `__main__` module with the given arguments.
+    /// The builtins and segments must be initialized beforehand, e.g. with
+    /// `initialize_function_runner`. Once the run ends, the function's return values can be read
+    /// with `VirtualMachine::get_return_values`.
+    pub fn run_from_named_entrypoint(
+        &mut self,
+        entrypoint: &str,
+        args: &[CairoArg],
+        vm: &mut VirtualMachine,
+        hint_processor: &mut dyn HintProcessor,
+    ) -> Result<(), VirtualMachineError> {
+        let entrypoint_pc = self
+            .program
+            .identifiers
+            .get(&format!("__main__.{entrypoint}"))
+            .and_then(|identifier| identifier.pc)
+            .ok_or_else(|| RunnerError::EntrypointNotFound(entrypoint.to_string()))?;
+
+        let stack = args
+            .iter()
+            .map(|arg| vm.segments.gen_cairo_arg(arg, &mut vm.memory))
+            .collect::<Result<Vec<MaybeRelocatable>, MemoryError>>()?;
+
+        let return_fp = vm.segments.add(&mut vm.memory);
+        let end =
+            self.initialize_function_entrypoint(vm, entrypoint_pc, stack, return_fp.into())?;
+
+        self.initialize_vm(vm)?;
+
+        self.run_until_pc(end, &mut RunResources::default(), vm, hint_processor)?;
+        self.end_run(true, false, vm, hint_processor)?;
+        Ok(())
+    }
+
     // Returns Ok(()) if there are enough allocated cells for the builtins.
     // If not, the number of steps should be increased or a different layout should be used.
     pub fn check_used_cells(&self, vm: &VirtualMachine) -> Result<(), VirtualMachineError> {
@@ -1114,19 +1595,134 @@ impl CairoRunner {
     }
 }
 
+/// Step budget for a run. Each VM step and each hint execution consumes one step.
+/// A budget of None never runs out.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RunResources {
+    n_steps: Option<usize>,
+}
+
+impl RunResources {
+    pub fn new(n_steps: usize) -> Self {
+        RunResources {
+            n_steps: Some(n_steps),
+        }
+    }
+
+    pub fn consumed(&self) -> bool {
+        self.n_steps == Some(0)
+    }
+
+    pub fn consume_step(&mut self) {
+        if let Some(n_steps) = self.n_steps.as_mut() {
+            *n_steps = n_steps.saturating_sub(1);
+        }
+    }
+
+    /// Returns the remaining steps, or None if the budget is unlimited.
+    pub fn get_n_steps(&self) -> Option<usize> {
+        self.n_steps
+    }
+}
+
+/// Argument passed to a function run with `CairoRunner::run_from_named_entrypoint`.
+/// Arrays and composed args are written into a new segment and passed as a pointer to it.
 #[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CairoArg {
+    Single(MaybeRelocatable),
+    Array(Vec<MaybeRelocatable>),
+    Composed(Vec<CairoArg>),
+}
+
+impl From<MaybeRelocatable> for CairoArg {
+    fn from(value: MaybeRelocatable) -> Self {
+        CairoArg::Single(value)
+    }
+}
+
+impl From<Vec<MaybeRelocatable>> for CairoArg {
+    fn from(values: Vec<MaybeRelocatable>) -> Self {
+        CairoArg::Array(values)
+    }
+}
+
+// Relocates the cells of a memory segment, keeping holes as None
+fn relocate_segment(
+    index: usize,
+    segment: &[Option<MaybeRelocatable>],
+    relocation_table: &Vec<usize>,
+) -> Result<Vec<Option<(usize, Felt)>>, MemoryError> {
+    segment
+        .iter()
+        .enumerate()
+        .map(|(seg_offset, element)| {
+            element
+                .as_ref()
+                .map(|elem| {
+                    let address = Relocatable::from((index as isize, seg_offset));
+                    let value = match elem {
+                        MaybeRelocatable::RelocatableValue(value) => {
+                            relocate_value(elem.clone(), relocation_table).map_err(|error| {
+                                MemoryError::ValueRelocation(address, *value, Box::new(error))
+                            })?
+                        }
+                        MaybeRelocatable::Int(num) => num.clone(),
+                    };
+                    Ok((relocate_address(address, relocation_table)?, value))
+                })
+                .transpose()
+        })
+        .collect()
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct SegmentInfo {
     pub index: isize,
     pub size: usize,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
 pub struct ExecutionResources {
     pub n_steps: usize,
     pub n_memory_holes: usize,
     pub builtin_instance_counter: HashMap<String, usize>,
 }
 
+impl Add for ExecutionResources {
+    type Output = ExecutionResources;
+
+    fn add(self, rhs: ExecutionResources) -> ExecutionResources {
+        let mut builtin_instance_counter = self.builtin_instance_counter;
+        for (builtin_name, counter) in rhs.builtin_instance_counter {
+            *builtin_instance_counter.entry(builtin_name).or_insert(0) += counter;
+        }
+        ExecutionResources {
+            n_steps: self.n_steps + rhs.n_steps,
+            n_memory_holes: self.n_memory_holes + rhs.n_memory_holes,
+            builtin_instance_counter,
+        }
+    }
+}
+
+impl Sub for ExecutionResources {
+    type Output = ExecutionResources;
+
+    // Counters are saturated at zero, as the result is meant to be the usage difference between
+    // a run and an earlier snapshot of it.
+    fn sub(self, rhs: ExecutionResources) -> ExecutionResources {
+        let mut builtin_instance_counter = self.builtin_instance_counter;
+        for (builtin_name, counter) in rhs.builtin_instance_counter {
+            let entry = builtin_instance_counter.entry(builtin_name).or_insert(0);
+            *entry = entry.saturating_sub(counter);
+        }
+        ExecutionResources {
+            n_steps: self.n_steps.saturating_sub(rhs.n_steps),
+            n_memory_holes: self.n_memory_holes.saturating_sub(rhs.n_memory_holes),
+            builtin_instance_counter,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1134,15 +1730,25 @@ mod tests {
         hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
         relocatable,
         serde::deserialize_program::{Identifier, ReferenceManager},
-        types::instance_definitions::bitwise_instance_def::BitwiseInstanceDef,
+        types::{
+            instance_definitions::bitwise_instance_def::BitwiseInstanceDef,
+            layout::DynamicLayoutParams,
+        },
         utils::test_utils::*,
-        vm::{trace::trace_entry::TraceEntry, vm_memory::memory::Memory},
+        vm::{
+            trace::trace_entry::TraceEntry, vm_core::DEFAULT_CANCELLATION_CHECK_INTERVAL,
+            vm_memory::memory::Memory,
+        },
     };
     use felt::{felt_str, NewFelt};
     use num_traits::One;
     use std::{
         collections::{HashMap, HashSet},
         path::Path,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
     };
 
     #[test]
@@ -1189,34 +1795,145 @@ mod tests {
     }
 
     #[test]
-    fn create_cairo_runner_with_ordered_but_missing_builtins() {
-        //This test works with basic Program definition, will later be updated to use Program::new() when fully defined
-        let program = program!["output", "ecdsa"];
-        //We only check that the creation doesnt panic
-        let _cairo_runner = cairo_runner!(program);
+    fn initialize_builtins_with_poseidon() {
+        let program = program!["range_check", "poseidon"];
+        let cairo_runner = cairo_runner!(program, "all");
+        let mut vm = vm!();
+        cairo_runner.initialize_builtins(&mut vm).unwrap();
+        assert_eq!(vm.builtin_runners[0].0, String::from("range_check"));
+        assert_eq!(vm.builtin_runners[1].0, String::from("poseidon"));
+        assert!(matches!(
+            vm.builtin_runners[1].1,
+            BuiltinRunner::Poseidon(_)
+        ));
     }
 
     #[test]
-    fn initialize_segments_with_base() {
-        //This test works with basic Program definition, will later be updated to use Program::new() when fully defined
-        let program = program!["output"];
-        let mut cairo_runner = cairo_runner!(program);
+    fn initialize_builtins_with_poseidon_missing_from_layout() {
+        let program = program!["poseidon"];
+        let cairo_runner = cairo_runner!(program, "small");
         let mut vm = vm!();
-        let program_base = Some(Relocatable {
-            segment_index: 5,
-            offset: 9,
-        });
-        vm.segments.num_segments = 6;
-        cairo_runner.initialize_builtins(&mut vm).unwrap();
-        cairo_runner.initialize_segments(&mut vm, program_base);
         assert_eq!(
-            cairo_runner.program_base,
-            Some(Relocatable {
-                segment_index: 5,
-                offset: 9,
-            })
+            cairo_runner.initialize_builtins(&mut vm),
+            Err(RunnerError::NoBuiltinForInstance(
+                HashSet::from([String::from("poseidon")]),
+                String::from("small")
+            ))
         );
-        assert_eq!(
+    }
+
+    fn dynamic_layout_params() -> DynamicLayoutParams {
+        DynamicLayoutParams {
+            output: true,
+            pedersen_ratio: Some(3),
+            range_check_ratio: None,
+            ecdsa_ratio: None,
+            bitwise_ratio: Some(5),
+            ec_op_ratio: None,
+            keccak_ratio: None,
+            poseidon_ratio: None,
+            memory_units_per_step: 16,
+        }
+    }
+
+    #[test]
+    fn initialize_builtins_with_dynamic_layout() {
+        let program = program!["output", "pedersen", "bitwise"];
+        let cairo_runner = CairoRunner::new_with_layout(
+            &program,
+            CairoLayout::dynamic(dynamic_layout_params()),
+            false,
+        );
+        let mut vm = vm!();
+        cairo_runner.initialize_builtins(&mut vm).unwrap();
+        let builtins: Vec<(&str, Option<u32>)> = vm
+            .builtin_runners
+            .iter()
+            .map(|(name, builtin)| (name.as_str(), builtin.ratio()))
+            .collect();
+        assert_eq!(
+            builtins,
+            [
+                ("output", None),
+                ("pedersen", Some(3)),
+                ("bitwise", Some(5))
+            ]
+        );
+    }
+
+    #[test]
+    fn initialize_builtins_with_builtin_missing_from_dynamic_layout() {
+        let program = program!["pedersen", "range_check"];
+        let cairo_runner = CairoRunner::new_with_layout(
+            &program,
+            CairoLayout::dynamic(dynamic_layout_params()),
+            false,
+        );
+        let mut vm = vm!();
+        assert_eq!(
+            cairo_runner.initialize_builtins(&mut vm),
+            Err(RunnerError::NoBuiltinForInstance(
+                HashSet::from([String::from("range_check")]),
+                String::from("dynamic")
+            ))
+        );
+    }
+
+    #[test]
+    fn get_allocated_memory_units_with_dynamic_layout() {
+        let program = program!["pedersen"];
+        let cairo_runner = CairoRunner::new_with_layout(
+            &program,
+            CairoLayout::dynamic(dynamic_layout_params()),
+            false,
+        );
+        let mut vm = vm!();
+        cairo_runner.initialize_builtins(&mut vm).unwrap();
+        // One pedersen instance of 3 cells every 3 steps
+        vm.current_step = 9;
+        assert_eq!(
+            vm.builtin_runners[0].1.get_allocated_memory_units(&vm),
+            Ok(9)
+        );
+        // 8 steps fit the ratio 8 of the fixed layouts, but not the dynamic ratio
+        vm.current_step = 8;
+        assert_eq!(
+            cairo_runner.check_memory_usage(&vm),
+            Err(VirtualMachineError::MemoryError(
+                MemoryError::ErrorCalculatingMemoryUnits
+            ))
+        );
+    }
+
+    #[test]
+    fn create_cairo_runner_with_ordered_but_missing_builtins() {
+        //This test works with basic Program definition, will later be updated to use Program::new() when fully defined
+        let program = program!["output", "ecdsa"];
+        //We only check that the creation doesnt panic
+        let _cairo_runner = cairo_runner!(program);
+    }
+
+    #[test]
+    fn initialize_segments_with_base() {
+        //This test works with basic Program definition, will later be updated to use Program::new() when fully defined
+        let program = program!["output"];
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let program_base = Some(Relocatable {
+            segment_index: 5,
+            offset: 9,
+        });
+        vm.segments.num_segments = 6;
+        cairo_runner.initialize_builtins(&mut vm).unwrap();
+        cairo_runner.initialize_segments(&mut vm, program_base);
+        assert_eq!(
+            cairo_runner.program_base,
+            Some(Relocatable {
+                segment_index: 5,
+                offset: 9,
+            })
+        );
+        assert_eq!(
             cairo_runner.execution_base,
             Some(Relocatable {
                 segment_index: 6,
@@ -1814,7 +2531,12 @@ mod tests {
         cairo_runner.initialize_vm(&mut vm).unwrap();
         //Execution Phase
         assert_eq!(
-            cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            cairo_runner.run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            ),
             Ok(())
         );
         //Check final values against Python VM
@@ -1895,7 +2617,12 @@ mod tests {
         cairo_runner.initialize_vm(&mut vm).unwrap();
         //Execution Phase
         assert_eq!(
-            cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            cairo_runner.run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            ),
             Ok(())
         );
         //Check final values against Python VM
@@ -2001,7 +2728,12 @@ mod tests {
         cairo_runner.initialize_vm(&mut vm).unwrap();
         //Execution Phase
         assert_eq!(
-            cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            cairo_runner.run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            ),
             Ok(())
         );
         //Check final values against Python VM
@@ -2135,7 +2867,12 @@ mod tests {
         cairo_runner.initialize_vm(&mut vm).unwrap();
         //Execution Phase
         assert_eq!(
-            cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            cairo_runner.run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            ),
             Ok(())
         );
         //Check final values against Python VM
@@ -2286,6 +3023,117 @@ mod tests {
         assert_eq!(cairo_runner.relocated_memory[9], Some(Felt::new(5)));
     }
 
+    #[test]
+    fn relocate_segment_written_past_finalized_size() {
+        let program = program!();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        vm.memory = memory![((0, 0), 1), ((1, 0), 2), ((1, 1), 3), ((1, 2), 4)];
+        vm.segments.num_segments = 2;
+        vm.segments.finalize(1, Some(2), None);
+        assert_eq!(
+            cairo_runner.relocate(&mut vm),
+            Err(TraceError::MemoryError(MemoryError::SegmentSizeMismatch(
+                1, 3, 2
+            )))
+        );
+    }
+
+    #[test]
+    fn relocate_cross_segment_pointers() {
+        let program = program!();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        vm.memory = memory![
+            ((0, 0), (2, 1)),
+            ((1, 0), (0, 0)),
+            ((1, 1), 7),
+            ((2, 0), 1),
+            ((2, 1), (1, 1))
+        ];
+        vm.segments.num_segments = 3;
+        assert_eq!(cairo_runner.relocate(&mut vm), Ok(()));
+        // Segments 0, 1 and 2 start at addresses 1, 2 and 4
+        assert_eq!(
+            cairo_runner.relocated_memory,
+            vec![
+                None,
+                Some(Felt::new(5)),
+                Some(Felt::new(1)),
+                Some(Felt::new(7)),
+                Some(Felt::new(1)),
+                Some(Felt::new(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn relocate_pointers_to_temporary_segment() {
+        let program = program!();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        for _ in 0..2 {
+            vm.segments.add(&mut vm.memory);
+        }
+        let temp_segment = vm.segments.add_temporary_segment(&mut vm.memory);
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from((-1, 1)),
+            )
+            .unwrap();
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((-1, 0)),
+                &MaybeRelocatable::from(Felt::new(3)),
+            )
+            .unwrap();
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((-1, 1)),
+                &MaybeRelocatable::from((0, 0)),
+            )
+            .unwrap();
+        vm.memory
+            .add_relocation_rule(temp_segment, relocatable!(1, 0))
+            .unwrap();
+        assert_eq!(cairo_runner.relocate(&mut vm), Ok(()));
+        // The temporary segment is moved onto segment 1, which starts at address 2
+        assert_eq!(
+            cairo_runner.relocated_memory,
+            vec![
+                None,
+                Some(Felt::new(3)),
+                Some(Felt::new(3)),
+                Some(Felt::new(1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn relocate_pointer_to_temporary_segment_without_rule() {
+        let program = program!();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        vm.segments.add(&mut vm.memory);
+        vm.segments.add(&mut vm.memory);
+        vm.segments.add_temporary_segment(&mut vm.memory);
+        vm.memory
+            .insert(
+                &MaybeRelocatable::from((1, 2)),
+                &MaybeRelocatable::from((-1, 0)),
+            )
+            .unwrap();
+        assert_eq!(
+            cairo_runner.relocate(&mut vm),
+            Err(TraceError::MemoryError(MemoryError::ValueRelocation(
+                relocatable!(1, 2),
+                relocatable!(-1, 0),
+                Box::new(MemoryError::TemporarySegmentInRelocation(-1)),
+            )))
+        );
+    }
+
     #[test]
     /* Program used:
     %builtins output
@@ -2364,7 +3212,12 @@ mod tests {
         let end = cairo_runner.initialize_main_entrypoint(&mut vm).unwrap();
         cairo_runner.initialize_vm(&mut vm).unwrap();
         assert_eq!(
-            cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            cairo_runner.run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            ),
             Ok(())
         );
         vm.segments.compute_effective_sizes(&vm.memory);
@@ -2499,7 +3352,12 @@ mod tests {
         let end = cairo_runner.initialize_main_entrypoint(&mut vm).unwrap();
         cairo_runner.initialize_vm(&mut vm).unwrap();
         assert_eq!(
-            cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            cairo_runner.run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            ),
             Ok(())
         );
         vm.segments.compute_effective_sizes(&vm.memory);
@@ -2608,6 +3466,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn relocate_matches_cell_by_cell_relocation() {
+        let program =
+            Program::from_file(Path::new("cairo_programs/pedersen_test.json"), Some("main"))
+                .expect("Call to `Program::from_file()` failed.");
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, "all", false);
+        let mut vm = vm!(true);
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap();
+        cairo_runner
+            .end_run(false, false, &mut vm, &mut hint_processor)
+            .unwrap();
+        cairo_runner.relocate(&mut vm).unwrap();
+
+        // Relocate memory and trace one cell and one entry at a time, whether or not the
+        // parallel feature is enabled
+        let relocation_table = vm.segments.relocate_segments().unwrap();
+        let mut relocated_memory = vec![None];
+        for (index, segment) in vm.memory.data.iter().enumerate() {
+            for (offset, element) in segment.iter().enumerate() {
+                match element {
+                    Some(elem) => {
+                        let addr = relocate_address(
+                            Relocatable::from((index as isize, offset)),
+                            &relocation_table,
+                        )
+                        .unwrap();
+                        if relocated_memory.len() <= addr {
+                            relocated_memory.resize(addr + 1, None);
+                        }
+                        relocated_memory[addr] =
+                            Some(relocate_value(elem.clone(), &relocation_table).unwrap());
+                    }
+                    None => relocated_memory.push(None),
+                }
+            }
+        }
+        let relocated_trace: Vec<RelocatedTraceEntry> = vm
+            .trace
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|entry| RelocatedTraceEntry {
+                pc: relocate_trace_register(&entry.pc, &relocation_table).unwrap(),
+                ap: relocate_trace_register(&entry.ap, &relocation_table).unwrap(),
+                fp: relocate_trace_register(&entry.fp, &relocation_table).unwrap(),
+            })
+            .collect();
+
+        assert_eq!(cairo_runner.relocated_memory, relocated_memory);
+        assert_eq!(cairo_runner.relocated_trace, Some(relocated_trace));
+    }
+
     #[test]
     fn write_output_from_preset_memory() {
         let program = program!["output"];
@@ -2673,7 +3593,12 @@ mod tests {
         //Execution Phase
         let mut hint_processor = BuiltinHintProcessor::new_empty();
         assert_eq!(
-            cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            cairo_runner.run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            ),
             Ok(())
         );
 
@@ -2682,6 +3607,65 @@ mod tests {
         assert_eq!(String::from_utf8(stdout), Ok(String::from("1\n17\n")));
     }
 
+    #[test]
+    fn read_output_from_preset_memory() {
+        let program = program!["output"];
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        cairo_runner.initialize_builtins(&mut vm).unwrap();
+        cairo_runner.initialize_segments(&mut vm, None);
+
+        vm.memory = memory![((2, 0), 1), ((2, 1), (-1))];
+        assert_eq!(
+            cairo_runner.read_output(&vm),
+            Ok(vec![Felt::one(), Felt::zero() - Felt::one()])
+        );
+    }
+
+    #[test]
+    fn read_output_after_computing_effective_sizes() {
+        let program = program!["output"];
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        cairo_runner.initialize_builtins(&mut vm).unwrap();
+        cairo_runner.initialize_segments(&mut vm, None);
+
+        vm.memory = memory![((2, 0), 1)];
+        vm.segments.compute_effective_sizes(&vm.memory);
+        vm.memory
+            .insert(&mayberelocatable!(2, 1), &mayberelocatable!(2))
+            .unwrap();
+        assert_eq!(
+            cairo_runner.read_output(&vm),
+            Ok(vec![Felt::one(), Felt::new(2)])
+        );
+    }
+
+    #[test]
+    fn read_output_relocatable_value() {
+        let program = program!["output"];
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        cairo_runner.initialize_builtins(&mut vm).unwrap();
+        cairo_runner.initialize_segments(&mut vm, None);
+
+        vm.memory = memory![((2, 0), 1), ((2, 1), (1, 0))];
+        vm.segments.segment_used_sizes = Some(vec![0, 0, 2]);
+        assert_eq!(
+            cairo_runner.read_output(&vm),
+            Err(RunnerError::ExpectedInteger(MaybeRelocatable::from((2, 1))))
+        );
+    }
+
+    #[test]
+    fn read_output_without_output_builtin() {
+        let program = program![];
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        cairo_runner.initialize_segments(&mut vm, None);
+        assert_eq!(cairo_runner.read_output(&vm), Ok(Vec::new()));
+    }
+
     #[test]
     fn write_output_from_preset_memory_neg_output() {
         let program = program!["output"];
@@ -2756,7 +3740,12 @@ mod tests {
 
         let mut hint_processor = BuiltinHintProcessor::new_empty();
         assert_eq!(
-            cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+            cairo_runner.run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            ),
             Ok(())
         );
 
@@ -3236,7 +4225,12 @@ mod tests {
 
         let end = cairo_runner.initialize(&mut vm).unwrap();
         cairo_runner
-            .run_until_pc(end, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
             .expect("Call to `CairoRunner::run_until_pc()` failed.");
         assert_eq!(
             cairo_runner.end_run(false, false, &mut vm, &mut hint_processor),
@@ -3245,21 +4239,469 @@ mod tests {
     }
 
     #[test]
-    fn get_builtin_segments_info_empty() {
-        let program = program!();
+    fn initialize_from_entrypoint_proof_mode_non_main_function() {
+        let program = Program::from_file(
+            Path::new("cairo_programs/proof_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .expect("Call to `Program::from_file()` failed.");
+        let fib = program.identifiers["__main__.fib"].pc.unwrap();
+        let program_end = program.end.unwrap();
 
-        let cairo_runner = cairo_runner!(program);
-        let vm = vm!();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, "all", true);
+        let mut vm = vm!(true);
 
-        assert_eq!(
-            cairo_runner.get_builtin_segments_info(&vm),
-            Ok(HashMap::new()),
-        );
+        let stack = vec![
+            mayberelocatable!(1),
+            mayberelocatable!(1),
+            mayberelocatable!(10),
+        ];
+        let end = cairo_runner
+            .initialize_from_entrypoint(&mut vm, fib, stack)
+            .unwrap();
+        let exec_base = cairo_runner.execution_base.unwrap();
+        assert_eq!(end, cairo_runner.program_base.unwrap() + program_end);
+        assert_eq!(cairo_runner.initial_pc, Some(relocatable!(0, fib)));
+        assert_eq!(cairo_runner.initial_fp, Some(exec_base + 7));
+        assert_eq!(cairo_runner.initial_ap, Some(exec_base + 7));
+
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap();
+        // The function returned into the dummy frame
+        assert_eq!(vm.run_context.fp, exec_base.offset + 2);
+        assert_eq!(vm.get_return_values(1), Ok(vec![mayberelocatable!(144)]));
+
+        cairo_runner
+            .end_run(false, false, &mut vm, &mut hint_processor)
+            .unwrap();
+        assert_eq!(cairo_runner.read_return_values(&vm), Ok(()));
+        assert_eq!(cairo_runner.finalize_segments(&mut vm), Ok(()));
+        // The initial stack is public, and fib returns no builtin pointers
+        assert_eq!(cairo_runner.execution_public_memory, Some((0..7).collect()));
     }
 
     #[test]
-    fn get_builtin_segments_info_base_not_finished() {
-        let program = program!();
+    fn initialize_from_entrypoint_proof_mode_appends_end_loop() {
+        // ret
+        let program = program!(data = vec![mayberelocatable!(0x208b7fff7fff7ffe_u64)]);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, "plain", true);
+        let mut vm = vm!();
+
+        let end = cairo_runner
+            .initialize_from_entrypoint(&mut vm, 0, Vec::new())
+            .unwrap();
+        assert_eq!(end, relocatable!(0, 1));
+        assert_eq!(
+            cairo_runner.program.data,
+            vec![
+                mayberelocatable!(0x208b7fff7fff7ffe_u64),
+                mayberelocatable!(JMP_REL_0),
+                mayberelocatable!(0)
+            ]
+        );
+        assert_eq!(cairo_runner.execution_public_memory, Some(vec![0, 1, 2, 3]));
+        check_memory!(
+            vm.memory,
+            ((1, 0), (1, 2)),
+            ((1, 1), 0),
+            ((1, 2), (1, 2)),
+            ((1, 3), (0, 1))
+        );
+
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap();
+        assert_eq!(vm.run_context.fp, 2);
+        assert_eq!(vm.run_context.pc, end);
+    }
+
+    #[test]
+    fn write_relocated_trace_matches_relocated_trace_encoding() {
+        let program = Program::from_file(Path::new("cairo_programs/fibonacci.json"), Some("main"))
+            .expect("Call to `Program::from_file()` failed.");
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!(true);
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .expect("Call to `CairoRunner::run_until_pc()` failed.");
+
+        let mut streamed_trace = Vec::new();
+        cairo_runner
+            .write_relocated_trace(&mut vm, &mut streamed_trace)
+            .unwrap();
+
+        cairo_runner.relocate(&mut vm).unwrap();
+        let mut collected_trace = Vec::new();
+        for entry in cairo_runner.relocated_trace.unwrap() {
+            bincode::serialize_into(&mut collected_trace, &entry).unwrap();
+        }
+        assert!(!streamed_trace.is_empty());
+        assert_eq!(streamed_trace, collected_trace);
+    }
+
+    #[test]
+    fn write_relocated_trace_write_fail() {
+        let program = Program::from_file(Path::new("cairo_programs/fibonacci.json"), Some("main"))
+            .expect("Call to `Program::from_file()` failed.");
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!(true);
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .expect("Call to `CairoRunner::run_until_pc()` failed.");
+
+        // Fits the ap and fp of the first entry, but not its pc
+        let mut buffer = [0_u8; 20];
+        assert!(matches!(
+            cairo_runner.write_relocated_trace(&mut vm, &mut &mut buffer[..]),
+            Err(TraceError::WriteFail(0, _))
+        ));
+    }
+
+    #[test]
+    fn run_until_pc_run_resources_exhausted() {
+        // jmp rel 0
+        let program = program!(
+            data = vec_data!((73324237875544063_i64), (0)),
+            main = Some(0),
+        );
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        let mut run_resources = RunResources::new(10);
+        assert_eq!(
+            cairo_runner.run_until_pc(end, &mut run_resources, &mut vm, &mut hint_processor),
+            Err(VirtualMachineError::UnfinishedExecution)
+        );
+        assert_eq!(vm.current_step, 10);
+        assert!(run_resources.consumed());
+        assert_eq!(run_resources.get_n_steps(), Some(0));
+    }
+
+    #[test]
+    fn run_until_pc_cancelled_from_another_thread() {
+        // jmp rel 0
+        let program = program!(
+            data = vec_data!((73324237875544063_i64), (0)),
+            main = Some(0),
+        );
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        let cancellation_token = Arc::new(AtomicBool::new(false));
+        vm.set_cancellation_token(Arc::clone(&cancellation_token));
+
+        let canceller = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(10));
+            cancellation_token.store(true, Ordering::Relaxed);
+        });
+        // The budget only bounds the test if the cancellation is never observed
+        let mut run_resources = RunResources::new(100_000_000);
+        let result =
+            cairo_runner.run_until_pc(end, &mut run_resources, &mut vm, &mut hint_processor);
+        canceller.join().unwrap();
+
+        assert_eq!(
+            result,
+            Err(VirtualMachineError::Cancelled(
+                relocatable!(0, 0),
+                vm.current_step
+            ))
+        );
+        assert_eq!(vm.current_step % DEFAULT_CANCELLATION_CHECK_INTERVAL, 0);
+        assert!(!run_resources.consumed());
+    }
+
+    #[test]
+    fn run_until_pc_cancelled_at_next_check() {
+        // jmp rel 0
+        let program = program!(
+            data = vec_data!((73324237875544063_i64), (0)),
+            main = Some(0),
+        );
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_for_steps(15, &mut vm, &mut hint_processor)
+            .unwrap();
+
+        vm.set_cancellation_token(Arc::new(AtomicBool::new(true)));
+        vm.set_cancellation_check_interval(10);
+        assert_eq!(
+            cairo_runner.run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            ),
+            Err(VirtualMachineError::Cancelled(relocatable!(0, 0), 20))
+        );
+        assert_eq!(vm.current_step, 20);
+    }
+
+    #[test]
+    fn run_until_pc_run_resources_remaining() {
+        let program = Program::from_file(Path::new("cairo_programs/fibonacci.json"), Some("main"))
+            .expect("Call to `Program::from_file()` failed.");
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+
+        let mut cairo_runner = cairo_runner!(program.clone());
+        let mut vm = vm!();
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        let mut run_resources = RunResources::default();
+        cairo_runner
+            .run_until_pc(end, &mut run_resources, &mut vm, &mut hint_processor)
+            .unwrap();
+        assert_eq!(run_resources.get_n_steps(), None);
+        let n_steps = vm.current_step;
+
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        let mut run_resources = RunResources::new(n_steps + 5);
+        assert_eq!(
+            cairo_runner.run_until_pc(end, &mut run_resources, &mut vm, &mut hint_processor),
+            Ok(())
+        );
+        assert_eq!(run_resources.get_n_steps(), Some(5));
+    }
+
+    #[test]
+    fn write_relocated_trace_not_enabled() {
+        let program = program!();
+        let cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        assert_eq!(
+            cairo_runner.write_relocated_trace(&mut vm, &mut Vec::new()),
+            Err(TraceError::TraceNotEnabled)
+        );
+    }
+
+    // Counts the hints it compiles, delegating the rest to the builtin hint processor
+    struct CountingHintProcessor {
+        builtin: BuiltinHintProcessor,
+        compiled_hints: std::cell::Cell<usize>,
+    }
+
+    impl HintProcessor for CountingHintProcessor {
+        fn execute_hint(
+            &mut self,
+            vm: &mut VirtualMachine,
+            exec_scopes: &mut ExecutionScopes,
+            hint_data: &Box<dyn Any>,
+            constants: &HashMap<String, Felt>,
+            run_resources: &mut RunResources,
+        ) -> Result<(), crate::vm::errors::hint_errors::HintError> {
+            self.builtin
+                .execute_hint(vm, exec_scopes, hint_data, constants, run_resources)
+        }
+
+        fn compile_hint(
+            &self,
+            hint_code: &str,
+            ap_tracking_data: &crate::serde::deserialize_program::ApTracking,
+            reference_ids: &HashMap<String, usize>,
+            references: &HashMap<usize, HintReference>,
+        ) -> Result<Box<dyn Any>, VirtualMachineError> {
+            self.compiled_hints.set(self.compiled_hints.get() + 1);
+            self.builtin
+                .compile_hint(hint_code, ap_tracking_data, reference_ids, references)
+        }
+    }
+
+    #[test]
+    fn hints_are_compiled_once_per_program() {
+        use crate::serde::deserialize_program::{ApTracking, FlowTrackingData, HintParams};
+        // func main() { %{ memory[ap] = segments.add() %} ret }
+        let program = program!(
+            data = vec_data!((2345108766317314046_i64)),
+            main = Some(0),
+            hints = std::sync::Arc::new(HashMap::from([(
+                0,
+                vec![HintParams {
+                    code: String::from("memory[ap] = segments.add()"),
+                    accessible_scopes: Vec::new(),
+                    flow_tracking_data: FlowTrackingData {
+                        ap_tracking: ApTracking::new(),
+                        reference_ids: HashMap::new(),
+                    },
+                }],
+            )])),
+        );
+        let mut hint_processor = CountingHintProcessor {
+            builtin: BuiltinHintProcessor::new_empty(),
+            compiled_hints: std::cell::Cell::new(0),
+        };
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+
+        cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_for_steps(1, &mut vm, &mut hint_processor)
+            .unwrap();
+        assert_eq!(
+            cairo_runner.run_for_steps(1, &mut vm, &mut hint_processor),
+            Err(VirtualMachineError::EndOfProgram(1))
+        );
+
+        // Running the program again after a reset reuses the compiled hints
+        cairo_runner.reset();
+        vm.reset();
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap();
+        assert_eq!(hint_processor.compiled_hints.get(), 1);
+    }
+
+    #[test]
+    fn clear_hint_data_cache_compiles_hints_again() {
+        use crate::serde::deserialize_program::{ApTracking, FlowTrackingData, HintParams};
+        // func main() { %{ memory[ap] = segments.add() %} ret }
+        let program = program!(
+            data = vec_data!((2345108766317314046_i64)),
+            main = Some(0),
+            hints = std::sync::Arc::new(HashMap::from([(
+                0,
+                vec![HintParams {
+                    code: String::from("memory[ap] = segments.add()"),
+                    accessible_scopes: Vec::new(),
+                    flow_tracking_data: FlowTrackingData {
+                        ap_tracking: ApTracking::new(),
+                        reference_ids: HashMap::new(),
+                    },
+                }],
+            )])),
+        );
+        let mut first_hint_processor = CountingHintProcessor {
+            builtin: BuiltinHintProcessor::new_empty(),
+            compiled_hints: std::cell::Cell::new(0),
+        };
+        let mut second_hint_processor = CountingHintProcessor {
+            builtin: BuiltinHintProcessor::new_empty(),
+            compiled_hints: std::cell::Cell::new(0),
+        };
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut first_hint_processor,
+            )
+            .unwrap();
+
+        // The hint data compiled by the first processor isn't handed to the second one
+        cairo_runner.reset();
+        cairo_runner.clear_hint_data_cache();
+        vm.reset();
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut second_hint_processor,
+            )
+            .unwrap();
+        assert_eq!(first_hint_processor.compiled_hints.get(), 1);
+        assert_eq!(second_hint_processor.compiled_hints.get(), 1);
+
+        // Going back to the first processor compiles its hint data again
+        cairo_runner.reset();
+        cairo_runner.clear_hint_data_cache();
+        vm.reset();
+        cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_for_steps(1, &mut vm, &mut first_hint_processor)
+            .unwrap();
+        assert_eq!(first_hint_processor.compiled_hints.get(), 2);
+        assert_eq!(second_hint_processor.compiled_hints.get(), 1);
+    }
+
+    #[test]
+    fn write_binary_memory_not_relocated() {
+        let program = program!();
+        let cairo_runner = cairo_runner!(program);
+        let path = std::env::temp_dir().join("write_binary_memory_not_relocated.memory");
+        assert!(matches!(
+            cairo_runner.write_binary_memory(&path),
+            Err(EncodeError::MemoryNotRelocated)
+        ));
+    }
+
+    #[test]
+    fn write_binary_memory_round_trip() {
+        let program = program!();
+        let mut cairo_runner = cairo_runner!(program);
+        cairo_runner.relocated_memory = vec![None, Some(Felt::new(1)), None, Some(Felt::new(-1))];
+        let path = std::env::temp_dir().join("write_binary_memory_round_trip.memory");
+        cairo_runner.write_binary_memory(&path).unwrap();
+
+        let mut file = std::fs::File::open(&path).unwrap();
+        assert_eq!(
+            crate::cairo_run::read_encoded_memory(&mut file).unwrap(),
+            cairo_runner.relocated_memory
+        );
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn get_builtin_segments_info_empty() {
+        let program = program!();
+
+        let cairo_runner = cairo_runner!(program);
+        let vm = vm!();
+
+        assert_eq!(
+            cairo_runner.get_builtin_segments_info(&vm),
+            Ok(HashMap::new()),
+        );
+    }
+
+    #[test]
+    fn get_builtin_segments_info_base_not_finished() {
+        let program = program!();
 
         let cairo_runner = cairo_runner!(program);
         let mut vm = vm!();
@@ -3336,6 +4778,145 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_execution_resources_steps_without_trace() {
+        let program = program!();
+
+        let cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+
+        vm.current_step = 7;
+        vm.segments.segment_used_sizes = Some(vec![4]);
+        assert_eq!(
+            cairo_runner.get_execution_resources(&vm),
+            Ok(ExecutionResources {
+                n_steps: 7,
+                n_memory_holes: 0,
+                builtin_instance_counter: HashMap::new(),
+            }),
+        );
+    }
+
+    #[test]
+    fn get_cairo_pie_fibonacci() {
+        let program = Program::from_file(Path::new("cairo_programs/fibonacci.json"), Some("main"))
+            .expect("Call to `Program::from_file()` failed.");
+
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap();
+        cairo_runner
+            .end_run(false, false, &mut vm, &mut hint_processor)
+            .unwrap();
+
+        let cairo_pie = cairo_runner.get_cairo_pie(&vm).unwrap();
+        let metadata = &cairo_pie.metadata;
+        assert_eq!(metadata.program.data.len(), program.data.len());
+        assert_eq!(metadata.program.main, program.main.unwrap());
+        assert_eq!(
+            metadata.program_segment,
+            SegmentInfo {
+                index: 0,
+                size: program.data.len(),
+            }
+        );
+        assert_eq!(metadata.execution_segment.index, 1);
+        assert_eq!(metadata.ret_fp_segment, SegmentInfo { index: 2, size: 0 });
+        assert_eq!(metadata.ret_pc_segment, SegmentInfo { index: 3, size: 0 });
+        assert!(metadata.builtin_segments.is_empty());
+        assert!(metadata.extra_segments.is_empty());
+        assert_eq!(cairo_pie.memory[0], ((0, 0), program.data[0].clone()));
+        assert_eq!(
+            cairo_pie.execution_resources,
+            cairo_runner.get_execution_resources(&vm).unwrap()
+        );
+    }
+
+    #[test]
+    fn get_cairo_pie_no_program_base() {
+        let program = program!();
+
+        let cairo_runner = cairo_runner!(program);
+        let vm = vm!();
+        assert_eq!(
+            cairo_runner.get_cairo_pie(&vm),
+            Err(VirtualMachineError::RunnerError(RunnerError::NoProgBase))
+        );
+    }
+
+    #[test]
+    fn execution_resources_add() {
+        let a = ExecutionResources {
+            n_steps: 10,
+            n_memory_holes: 2,
+            builtin_instance_counter: HashMap::from([
+                ("output".to_string(), 1),
+                ("pedersen".to_string(), 3),
+            ]),
+        };
+        let b = ExecutionResources {
+            n_steps: 5,
+            n_memory_holes: 1,
+            builtin_instance_counter: HashMap::from([
+                ("pedersen".to_string(), 2),
+                ("range_check".to_string(), 4),
+            ]),
+        };
+        assert_eq!(
+            a + b,
+            ExecutionResources {
+                n_steps: 15,
+                n_memory_holes: 3,
+                builtin_instance_counter: HashMap::from([
+                    ("output".to_string(), 1),
+                    ("pedersen".to_string(), 5),
+                    ("range_check".to_string(), 4),
+                ]),
+            }
+        );
+    }
+
+    #[test]
+    fn execution_resources_sub() {
+        let a = ExecutionResources {
+            n_steps: 10,
+            n_memory_holes: 2,
+            builtin_instance_counter: HashMap::from([
+                ("output".to_string(), 1),
+                ("pedersen".to_string(), 3),
+            ]),
+        };
+        let b = ExecutionResources {
+            n_steps: 4,
+            n_memory_holes: 3,
+            builtin_instance_counter: HashMap::from([
+                ("pedersen".to_string(), 2),
+                ("range_check".to_string(), 4),
+            ]),
+        };
+        assert_eq!(
+            a - b,
+            ExecutionResources {
+                n_steps: 6,
+                n_memory_holes: 0,
+                builtin_instance_counter: HashMap::from([
+                    ("output".to_string(), 1),
+                    ("pedersen".to_string(), 1),
+                    ("range_check".to_string(), 0),
+                ]),
+            }
+        );
+    }
+
     /// Test that the call to .run_from_entrypoint() with args.count() != 1 when
     /// typed_args is true fails.
     #[test]
@@ -3580,6 +5161,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn finalize_segments_output_builtin_pages() {
+        let mut program = program!["output"];
+        program.data = vec_data![(1), (2), (3), (4)];
+        let mut cairo_runner = cairo_runner!(program, "all", true);
+        cairo_runner.program_base = Some(Relocatable::from((0, 0)));
+        cairo_runner.execution_base = Some(Relocatable::from((1, 0)));
+        cairo_runner.execution_public_memory = Some(vec![]);
+        cairo_runner.run_ended = true;
+        let mut vm = vm!();
+        vm.segments.add(&mut vm.memory);
+        vm.segments.add(&mut vm.memory);
+        let mut output_builtin = OutputBuiltinRunner::new(true);
+        output_builtin.initialize_segments(&mut vm.segments, &mut vm.memory);
+        // Six outputs, the last three of them in page 1
+        vm.memory = memory![
+            ((2, 0), 10),
+            ((2, 1), 20),
+            ((2, 2), 30),
+            ((2, 3), 40),
+            ((2, 4), 50),
+            ((2, 5), 60)
+        ];
+        output_builtin.add_page(1, relocatable!(2, 3), 3).unwrap();
+        vm.builtin_runners = vec![("output".to_string(), output_builtin.into())];
+        vm.segments.compute_effective_sizes(&vm.memory);
+
+        assert_eq!(cairo_runner.finalize_segments(&mut vm), Ok(()));
+        assert_eq!(vm.segments.segment_sizes.get(&2), Some(&6));
+        assert_eq!(
+            vm.segments.public_memory_offsets.get(&2),
+            Some(&vec![(0, 0), (1, 0), (2, 0), (3, 1), (4, 1), (5, 1)])
+        );
+    }
+
     /// Test that ensures get_perm_range_check_limits() returns an error when
     /// trace is not enabled.
     #[test]
@@ -3668,7 +5284,7 @@ mod tests {
 
         assert_eq!(
             cairo_runner.get_perm_range_check_limits(&vm),
-            Ok(Some((-31440, 1328))),
+            Ok(Some((-31440, 33023))),
         );
     }
 
@@ -3794,6 +5410,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn check_used_cells_pedersen_exceeds_allocated_cells() {
+        let program = Program::from_file(
+            Path::new("cairo_programs/pedersen_heavy.json"),
+            Some("main"),
+        )
+        .expect("Call to `Program::from_file()` failed.");
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, "small");
+        let mut vm = vm!();
+
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap();
+        cairo_runner
+            .end_run(false, false, &mut vm, &mut hint_processor)
+            .unwrap();
+
+        // The inputs of 100 instances are written, the output of the last one is not
+        let used = 3 * 100 - 1;
+        // The small layout allocates a pedersen instance every 8 steps
+        let allocated = 3 * (vm.current_step / 8);
+        assert_eq!(
+            cairo_runner.check_used_cells(&vm),
+            Err(VirtualMachineError::MemoryError(
+                MemoryError::InsufficientAllocatedCellsError("pedersen", used, allocated)
+            ))
+        );
+    }
+
+    #[test]
+    fn end_run_proof_mode_pads_steps_until_pedersen_cells_are_allocated() {
+        let program = Program::from_file(
+            Path::new("cairo_programs/proof_programs/pedersen_heavy.json"),
+            Some("main"),
+        )
+        .expect("Call to `Program::from_file()` failed.");
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, "small", true);
+        let mut vm = vm!(true);
+
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap();
+        assert_eq!(
+            cairo_runner.end_run(false, false, &mut vm, &mut hint_processor),
+            Ok(())
+        );
+        assert_eq!(cairo_runner.check_used_cells(&vm), Ok(()));
+    }
+
     #[test]
     fn check_used_cells_check_memory_usage_error() {
         let program = program!();
@@ -4270,4 +5949,238 @@ mod tests {
             Ok(()),
         );
     }
+
+    #[test]
+    fn run_from_named_entrypoint_with_felt_and_array() {
+        let program =
+            Program::from_file(Path::new("cairo_programs/entrypoint_args.json"), None).unwrap();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        cairo_runner.initialize_function_runner(&mut vm).unwrap();
+
+        let args = [
+            CairoArg::Single(mayberelocatable!(4)),
+            CairoArg::Array(vec![mayberelocatable!(5), mayberelocatable!(6)]),
+        ];
+        assert_eq!(
+            cairo_runner.run_from_named_entrypoint(
+                "sum_with_array",
+                &args,
+                &mut vm,
+                &mut hint_processor
+            ),
+            Ok(())
+        );
+        assert_eq!(vm.get_return_values(1), Ok(vec![mayberelocatable!(15)]));
+    }
+
+    #[test]
+    fn run_from_named_entrypoint_with_composed_arg() {
+        let program =
+            Program::from_file(Path::new("cairo_programs/entrypoint_args.json"), None).unwrap();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        cairo_runner.initialize_function_runner(&mut vm).unwrap();
+
+        let args = [CairoArg::Composed(vec![
+            CairoArg::Single(mayberelocatable!(7)),
+            CairoArg::Array(vec![mayberelocatable!(8)]),
+        ])];
+        assert_eq!(
+            cairo_runner.run_from_named_entrypoint("sum_pair", &args, &mut vm, &mut hint_processor),
+            Ok(())
+        );
+        assert_eq!(vm.get_return_values(1), Ok(vec![mayberelocatable!(15)]));
+    }
+
+    #[test]
+    fn run_from_named_entrypoint_missing_entrypoint() {
+        let program =
+            Program::from_file(Path::new("cairo_programs/entrypoint_args.json"), None).unwrap();
+        let mut cairo_runner = cairo_runner!(program);
+        let mut vm = vm!();
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        cairo_runner.initialize_function_runner(&mut vm).unwrap();
+
+        assert_eq!(
+            cairo_runner.run_from_named_entrypoint(
+                "not_a_function",
+                &[],
+                &mut vm,
+                &mut hint_processor
+            ),
+            Err(VirtualMachineError::RunnerError(
+                RunnerError::EntrypointNotFound(String::from("not_a_function"))
+            ))
+        );
+    }
+
+    fn run_proof_mode_fibonacci() -> (CairoRunner, VirtualMachine) {
+        let program = Program::from_file(
+            Path::new("cairo_programs/proof_programs/fibonacci.json"),
+            Some("main"),
+        )
+        .expect("Call to `Program::from_file()` failed.");
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut cairo_runner = cairo_runner!(program, "small", true);
+        let mut vm = vm!(true);
+
+        let end = cairo_runner.initialize(&mut vm).unwrap();
+        cairo_runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap();
+        cairo_runner
+            .end_run(false, false, &mut vm, &mut hint_processor)
+            .unwrap();
+        cairo_runner.read_return_values(&vm).unwrap();
+        cairo_runner.finalize_segments(&mut vm).unwrap();
+        cairo_runner.relocate(&mut vm).unwrap();
+        (cairo_runner, vm)
+    }
+
+    #[test]
+    fn get_air_public_input_proof_mode_fibonacci() {
+        let (cairo_runner, vm) = run_proof_mode_fibonacci();
+        let public_input = cairo_runner.get_air_public_input(&vm).unwrap();
+        let trace = cairo_runner.relocated_trace.as_ref().unwrap();
+
+        assert_eq!(public_input.layout, "small");
+        assert_eq!(public_input.n_steps, trace.len());
+        assert!(public_input.n_steps.is_power_of_two());
+        assert!(public_input.rc_min <= public_input.rc_max);
+        assert_eq!(public_input.dynamic_params, None);
+
+        // The program segment is relocated to address 1 and is public in page 0
+        let program_len = cairo_runner.program.data.len();
+        for (i, entry) in public_input.public_memory[..program_len].iter().enumerate() {
+            assert_eq!(entry.address, i + 1);
+            assert_eq!(entry.page, 0);
+            assert_eq!(
+                Some(&entry.value),
+                cairo_runner.relocated_memory[i + 1].as_ref()
+            );
+        }
+
+        let segment_names: Vec<&str> = public_input
+            .memory_segments
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(
+            segment_names,
+            [
+                "ecdsa",
+                "execution",
+                "output",
+                "pedersen",
+                "program",
+                "range_check"
+            ]
+        );
+        let program_segment = public_input.memory_segments["program"];
+        assert_eq!(program_segment.begin_addr, trace[0].pc);
+        assert_eq!(program_segment.stop_ptr, trace[trace.len() - 1].pc);
+        let execution_segment = public_input.memory_segments["execution"];
+        assert_eq!(execution_segment.begin_addr, trace[0].ap);
+        assert_eq!(execution_segment.stop_ptr, trace[trace.len() - 1].ap);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&public_input.serialize_json().unwrap()).unwrap();
+        let mut keys: Vec<&str> = json
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .collect();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                "dynamic_params",
+                "layout",
+                "memory_segments",
+                "n_steps",
+                "public_memory",
+                "rc_max",
+                "rc_min"
+            ]
+        );
+        assert!(json["public_memory"][0]["value"]
+            .as_str()
+            .unwrap()
+            .starts_with("0x"));
+    }
+
+    #[test]
+    fn get_air_public_input_matches_cairo_lang_fixture() {
+        // Generated by cairo-lang's cairo-run with --air_public_input, see the Makefile
+        let fixture: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(
+                "cairo_programs/proof_programs/fibonacci.air_public_input.json",
+            )
+            .expect("Missing fixture, run `make cairo_proof_programs`"),
+        )
+        .unwrap();
+        let (cairo_runner, vm) = run_proof_mode_fibonacci();
+        let public_input: serde_json::Value = serde_json::from_str(
+            &cairo_runner
+                .get_air_public_input(&vm)
+                .unwrap()
+                .serialize_json()
+                .unwrap(),
+        )
+        .unwrap();
+
+        for field in [
+            "layout",
+            "rc_min",
+            "rc_max",
+            "n_steps",
+            "memory_segments",
+            "public_memory",
+        ] {
+            assert_eq!(public_input[field], fixture[field], "field {field} differs");
+        }
+        // cairo-lang omits the dynamic params of the static layouts
+        assert_eq!(public_input["dynamic_params"], serde_json::Value::Null);
+        assert_eq!(fixture["dynamic_params"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn get_air_public_input_without_relocated_trace() {
+        let program = program!();
+        let cairo_runner = cairo_runner!(program);
+        let vm = vm!(true);
+        assert_eq!(
+            cairo_runner.get_air_public_input(&vm),
+            Err(VirtualMachineError::RunnerError(
+                RunnerError::NoRelocatedTrace
+            ))
+        );
+    }
+
+    #[test]
+    fn get_air_private_input_proof_mode_fibonacci() {
+        let (cairo_runner, vm) = run_proof_mode_fibonacci();
+        let private_input = cairo_runner.get_air_private_input(&vm, "trace.bin", "memory.bin");
+
+        assert_eq!(private_input.trace_path, "trace.bin");
+        assert_eq!(private_input.memory_path, "memory.bin");
+        // fibonacci uses no builtins, and the output builtin has no private input
+        assert_eq!(
+            private_input.builtins,
+            HashMap::from([
+                ("pedersen".to_string(), Vec::new()),
+                ("range_check".to_string(), Vec::new()),
+                ("ecdsa".to_string(), Vec::new()),
+            ])
+        );
+    }
 }
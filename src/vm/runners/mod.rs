@@ -1,2 +1,5 @@
+pub mod air_private_input;
+pub mod air_public_input;
 pub mod builtin_runner;
+pub mod cairo_pie;
 pub mod cairo_runner;
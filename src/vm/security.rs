@@ -1,7 +1,5 @@
 use super::{
-    errors::{
-        memory_errors::MemoryError, runner_errors::RunnerError, vm_errors::VirtualMachineError,
-    },
+    errors::{runner_errors::RunnerError, security_errors::SecurityError},
     runners::cairo_runner::CairoRunner,
     vm_core::VirtualMachine,
 };
@@ -16,14 +14,15 @@ use std::{collections::HashMap, mem::swap};
 ///   - All accesses to the builtin segments must be within the range defined by
 ///     the builtins themselves.
 ///   - There mustn't be accesses to the program segment outside the program
-///     data range.
+///     data range, and the program data must not have been modified.
+///   - All accesses to segments finalized with a size must be within that size.
 ///
 /// Note: Each builtin is responsible for checking its own segments' data.
 pub fn verify_secure_runner(
     runner: &CairoRunner,
     verify_builtins: bool,
     vm: &mut VirtualMachine,
-) -> Result<(), VirtualMachineError> {
+) -> Result<(), SecurityError> {
     let program_base = runner
         .program_base
         .as_ref()
@@ -36,7 +35,7 @@ pub fn verify_secure_runner(
 
     let builtin_segment_by_index = builtin_segments
         .iter()
-        .map(|(seg_name, seg_info)| (seg_info.index, (seg_name, seg_info)))
+        .map(|(seg_name, seg_info)| (seg_info.index, (*seg_name, seg_info)))
         .collect::<HashMap<_, _>>();
 
     let memory_iter = vm
@@ -53,9 +52,9 @@ pub fn verify_secure_runner(
         });
     for (addr, value) in memory_iter {
         // Check builtin segment bounds.
-        if let Some((_, seg_info)) = builtin_segment_by_index.get(&addr.segment_index) {
+        if let Some((name, seg_info)) = builtin_segment_by_index.get(&addr.segment_index) {
             if addr.offset >= seg_info.size {
-                return Err(RunnerError::FailedMemoryGet(MemoryError::NumOutOfBounds).into());
+                return Err(SecurityError::OutOfBoundsBuiltinSegmentAccess(*name, addr));
             }
         }
 
@@ -63,40 +62,76 @@ pub fn verify_secure_runner(
         if addr.segment_index == program_base.segment_index
             && addr.offset >= runner.program.data.len()
         {
-            return Err(RunnerError::FailedMemoryGet(MemoryError::NumOutOfBounds).into());
+            return Err(SecurityError::OutOfBoundsProgramSegmentAccess(addr));
         }
 
-        // Check value validity (when relocatable, that the segment exists and
-        // is not temporary).
-        if !vm
+        // Check the bounds of segments finalized with a size.
+        if let Some(size) = vm
             .segments
-            .is_valid_memory_value(value)
-            .map_err(RunnerError::FailedMemoryGet)?
+            .segment_sizes
+            .get(&(addr.segment_index as usize))
         {
-            return Err(
-                RunnerError::FailedMemoryGet(MemoryError::InvalidMemoryValue(addr, value.clone()))
-                    .into(),
-            );
+            if addr.offset >= *size {
+                return Err(SecurityError::OutOfBoundsSegmentAccess(addr, *size));
+            }
+        }
+
+        // Check value validity (when relocatable, that the segment exists and
+        // is not temporary).
+        if !vm.segments.is_valid_memory_value(value)? {
+            return Err(SecurityError::InvalidMemoryValue(addr, value.clone()));
+        }
+    }
+
+    // Check that the program data wasn't modified during the run.
+    for (i, expected) in runner.program.data.iter().enumerate() {
+        let addr = program_base + i;
+        let value = vm.memory.get(&addr)?;
+        if value.as_deref() != Some(expected) {
+            return Err(SecurityError::ModifiedProgramSegment(
+                addr,
+                expected.clone(),
+                value.map(|value| value.into_owned()),
+            ));
         }
     }
 
     // This swap is needed to avoid double mutable borrows.
     let mut tmp = Vec::new();
     swap(&mut tmp, &mut vm.builtin_runners);
-    for (_, builtin_runner) in &tmp {
-        builtin_runner.run_security_checks(vm)?;
-    }
+    let result = tmp
+        .iter()
+        .try_for_each(|(_, builtin_runner)| builtin_runner.run_security_checks(vm));
     swap(&mut tmp, &mut vm.builtin_runners);
 
-    Ok(())
+    result.map_err(|error| SecurityError::VirtualMachine(Box::new(error)))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{relocatable, types::program::Program, utils::test_utils::*};
-    use felt::Felt;
+    use crate::{
+        hint_processor::builtin_hint_processor::builtin_hint_processor_definition::{
+            BuiltinHintProcessor, HintFunc,
+        },
+        relocatable,
+        serde::deserialize_program::{ApTracking, FlowTrackingData, HintParams},
+        types::{
+            instance_definitions::bitwise_instance_def::BitwiseInstanceDef,
+            program::{Program, ProgramBuilder},
+        },
+        utils::test_utils::*,
+        vm::{
+            errors::memory_errors::MemoryError,
+            runners::{
+                builtin_runner::BitwiseBuiltinRunner,
+                cairo_runner::{CairoRunner, RunResources},
+            },
+        },
+    };
+    use felt::{Felt, NewFelt};
     use num_traits::Zero;
+    use std::sync::Arc;
 
     #[test]
     fn verify_secure_runner_without_program_base() {
@@ -137,7 +172,9 @@ mod test {
 
         assert_eq!(
             verify_secure_runner(&runner, true, &mut vm),
-            Err(RunnerError::FailedMemoryGet(MemoryError::NumOutOfBounds).into())
+            Err(SecurityError::OutOfBoundsProgramSegmentAccess(
+                relocatable!(0, 0)
+            ))
         );
     }
 
@@ -145,10 +182,10 @@ mod test {
     fn verify_secure_runner_success() {
         let program = program!(
             data = vec![
-                Felt::zero().into(),
-                Felt::zero().into(),
-                Felt::zero().into(),
-                Felt::zero().into(),
+                relocatable!(1, 0).into(),
+                relocatable!(2, 1).into(),
+                relocatable!(3, 2).into(),
+                relocatable!(4, 3).into(),
             ],
             main = Some(0),
         );
@@ -168,4 +205,130 @@ mod test {
 
         assert_eq!(verify_secure_runner(&runner, true, &mut vm), Ok(()));
     }
+
+    #[test]
+    fn verify_secure_runner_builtin_out_of_bounds() {
+        let program = program!(main = Some(0),);
+
+        let mut runner = cairo_runner!(program);
+        let mut vm = vm!();
+
+        runner.initialize(&mut vm).unwrap();
+
+        let mut builtin = BitwiseBuiltinRunner::new(&BitwiseInstanceDef::default(), true);
+        builtin.base = 2;
+        builtin.stop_ptr = Some(1);
+        vm.builtin_runners = vec![("bitwise".to_string(), builtin.into())];
+        vm.memory.data = vec![vec![], vec![], vec![None, Some(Felt::zero().into())]];
+        vm.segments.segment_used_sizes = Some(vec![0, 0, 2]);
+
+        assert_eq!(
+            verify_secure_runner(&runner, true, &mut vm),
+            Err(SecurityError::OutOfBoundsBuiltinSegmentAccess(
+                "bitwise",
+                relocatable!(2, 1)
+            ))
+        );
+    }
+
+    #[test]
+    fn verify_secure_runner_finalized_segment_out_of_bounds() {
+        let program = program!(main = Some(0),);
+
+        let mut runner = cairo_runner!(program);
+        let mut vm = vm!();
+
+        runner.initialize(&mut vm).unwrap();
+
+        vm.memory.data = vec![
+            vec![],
+            vec![Some(Felt::zero().into()), Some(Felt::zero().into())],
+        ];
+        vm.segments.segment_used_sizes = Some(vec![0, 2]);
+        vm.segments.finalize(1, Some(1), None);
+
+        assert_eq!(
+            verify_secure_runner(&runner, true, &mut vm),
+            Err(SecurityError::OutOfBoundsSegmentAccess(
+                relocatable!(1, 1),
+                1
+            ))
+        );
+    }
+
+    #[test]
+    fn verify_secure_runner_temporary_segment_value() {
+        let program = program!(main = Some(0),);
+
+        let mut runner = cairo_runner!(program);
+        let mut vm = vm!();
+
+        runner.initialize(&mut vm).unwrap();
+
+        vm.memory.data = vec![vec![], vec![Some(relocatable!(-1, 0).into())]];
+        vm.segments.segment_used_sizes = Some(vec![0, 1]);
+
+        assert_eq!(
+            verify_secure_runner(&runner, true, &mut vm),
+            Err(SecurityError::Memory(
+                MemoryError::AddressInTemporarySegment(-1)
+            ))
+        );
+    }
+
+    #[test]
+    fn verify_secure_runner_program_modified_by_hint() {
+        // [ap] = 1000, ap++
+        // ret
+        let program = ProgramBuilder::new()
+            .data(vec![
+                Felt::new(0x480680017fff8000_u64).into(),
+                Felt::new(1000).into(),
+                Felt::new(0x208b7fff7fff7ffe_u64).into(),
+            ])
+            .main(0)
+            .hint(
+                0,
+                HintParams {
+                    code: "overwrite_program".to_string(),
+                    accessible_scopes: Vec::new(),
+                    flow_tracking_data: FlowTrackingData {
+                        ap_tracking: ApTracking::new(),
+                        reference_ids: HashMap::new(),
+                    },
+                },
+            )
+            .build()
+            .unwrap();
+
+        // A malicious hint that rewrites the immediate of the first instruction
+        let overwrite_program = HintFunc(Box::new(|vm, _, _, _, _| {
+            vm.memory.data[0][1] = Some(Felt::new(1).into());
+            Ok(())
+        }));
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        hint_processor.add_hint("overwrite_program".to_string(), Arc::new(overwrite_program));
+
+        let mut runner = CairoRunner::new(&program, "plain", false).unwrap();
+        let mut vm = VirtualMachine::new(false, Vec::new());
+        let end = runner.initialize(&mut vm).unwrap();
+        runner
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor,
+            )
+            .unwrap();
+        vm.segments.compute_effective_sizes(&vm.memory);
+
+        assert_eq!(
+            verify_secure_runner(&runner, true, &mut vm),
+            Err(SecurityError::ModifiedProgramSegment(
+                relocatable!(0, 1),
+                Felt::new(1000).into(),
+                Some(Felt::new(1).into())
+            ))
+        );
+    }
 }
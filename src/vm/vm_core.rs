@@ -1,3 +1,5 @@
+#[cfg(feature = "hooks")]
+use crate::vm::hooks::Hooks;
 use crate::{
     hint_processor::hint_processor_definition::HintProcessor,
     serde::deserialize_program::{ApTracking, Attribute},
@@ -15,16 +17,33 @@ use crate::{
             exec_scope_errors::ExecScopeError, memory_errors::MemoryError,
             vm_errors::VirtualMachineError,
         },
-        runners::builtin_runner::{BuiltinRunner, RangeCheckBuiltinRunner, SignatureBuiltinRunner},
+        runners::{
+            builtin_runner::{BuiltinRunner, RangeCheckBuiltinRunner, SignatureBuiltinRunner},
+            cairo_runner::RunResources,
+        },
         trace::trace_entry::TraceEntry,
         vm_memory::{memory::Memory, memory_segments::MemorySegmentManager},
     },
 };
 use felt::Felt;
 use num_traits::{ToPrimitive, Zero};
-use std::{any::Any, borrow::Cow, collections::HashMap};
+use std::{
+    any::Any,
+    borrow::Cow,
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// How many steps are executed between checks of the cancellation token, unless configured
+/// with `VirtualMachine::set_cancellation_check_interval`.
+pub const DEFAULT_CANCELLATION_CHECK_INTERVAL: usize = 1024;
 
 const MAX_TRACEBACK_ENTRIES: u32 = 20;
+// The program is always loaded into the first segment, its decoded instructions are cached
+const PROGRAM_SEGMENT_INDEX: isize = 0;
 
 #[derive(PartialEq, Debug)]
 pub struct Operands {
@@ -86,6 +105,15 @@ pub struct VirtualMachine {
     pub(crate) error_message_attributes: Vec<Attribute>,
     skip_instruction_execution: bool,
     run_finished: bool,
+    // Decoded instructions of the program segment, indexed by offset. Memory is write-once,
+    // so a decoded cell can't change under the cache while the run lasts.
+    instruction_cache: Vec<Option<Instruction>>,
+    // Once set to true by another thread, the run stops at the next step that is a multiple
+    // of cancellation_check_interval
+    cancellation_token: Option<Arc<AtomicBool>>,
+    cancellation_check_interval: usize,
+    #[cfg(feature = "hooks")]
+    pub(crate) hooks: Hooks,
 }
 
 impl HintData {
@@ -130,9 +158,43 @@ impl VirtualMachine {
             segments: MemorySegmentManager::new(),
             error_message_attributes,
             run_finished: false,
+            instruction_cache: Vec::new(),
+            cancellation_token: None,
+            cancellation_check_interval: DEFAULT_CANCELLATION_CHECK_INTERVAL,
+            #[cfg(feature = "hooks")]
+            hooks: Hooks::default(),
         }
     }
 
+    /// Clears the state left by a run, so that the VM can be initialized by a new `CairoRunner`
+    /// as if it were fresh. The segments, memory, builtin runners, trace entries, accessed
+    /// addresses, registers, step count and decoded instructions are cleared, keeping the capacity of their
+    /// underlying buffers. Whether the trace is enabled, the error message attributes, the
+    /// cancellation token and the hooks are preserved.
+    pub fn reset(&mut self) {
+        self.run_context = RunContext {
+            pc: Relocatable::from((0, 0)),
+            ap: 0,
+            fp: 0,
+        };
+        self.builtin_runners.clear();
+        self.segments.reset();
+        self._program_base = None;
+        self.memory.reset();
+        if let Some(accessed_addresses) = self.accessed_addresses.as_mut() {
+            accessed_addresses.clear();
+        } else {
+            self.accessed_addresses = Some(Vec::new());
+        }
+        if let Some(trace) = self.trace.as_mut() {
+            trace.clear();
+        }
+        self.current_step = 0;
+        self.skip_instruction_execution = false;
+        self.run_finished = false;
+        self.instruction_cache.clear();
+    }
+
     ///Returns the encoded instruction (the value at pc) and the immediate value (the value at pc + 1, if it exists in the memory).
     fn get_instruction_encoding(
         &self,
@@ -219,12 +281,12 @@ impl VirtualMachine {
 
     fn update_registers(
         &mut self,
-        instruction: Instruction,
+        instruction: &Instruction,
         operands: Operands,
     ) -> Result<(), VirtualMachineError> {
-        self.update_fp(&instruction, &operands)?;
-        self.update_ap(&instruction, &operands)?;
-        self.update_pc(&instruction, &operands)?;
+        self.update_fp(instruction, &operands)?;
+        self.update_ap(instruction, &operands)?;
+        self.update_pc(instruction, &operands)?;
         Ok(())
     }
 
@@ -430,16 +492,22 @@ impl VirtualMachine {
         operands_addresses: &OperandsAddresses,
     ) -> Result<(), VirtualMachineError> {
         if deduced_operands.was_op0_deducted() {
+            #[cfg(feature = "hooks")]
+            self.execute_memory_write_hook(&operands_addresses.op0_addr, &operands.op0)?;
             self.memory
                 .insert(&operands_addresses.op0_addr, &operands.op0)
                 .map_err(VirtualMachineError::MemoryError)?;
         }
         if deduced_operands.was_op1_deducted() {
+            #[cfg(feature = "hooks")]
+            self.execute_memory_write_hook(&operands_addresses.op1_addr, &operands.op1)?;
             self.memory
                 .insert(&operands_addresses.op1_addr, &operands.op1)
                 .map_err(VirtualMachineError::MemoryError)?;
         }
         if deduced_operands.was_dest_deducted() {
+            #[cfg(feature = "hooks")]
+            self.execute_memory_write_hook(&operands_addresses.dst_addr, &operands.dst)?;
             self.memory
                 .insert(&operands_addresses.dst_addr, &operands.dst)
                 .map_err(VirtualMachineError::MemoryError)?;
@@ -448,11 +516,11 @@ impl VirtualMachine {
         Ok(())
     }
 
-    fn run_instruction(&mut self, instruction: Instruction) -> Result<(), VirtualMachineError> {
+    fn run_instruction(&mut self, instruction: &Instruction) -> Result<(), VirtualMachineError> {
         let (operands, operands_addresses, deduced_operands) =
-            self.compute_operands(&instruction)?;
+            self.compute_operands(instruction)?;
         self.insert_deduced_operands(deduced_operands, &operands, &operands_addresses)?;
-        self.opcode_assertions(&instruction, &operands)?;
+        self.opcode_assertions(instruction, &operands)?;
 
         if let Some(ref mut trace) = &mut self.trace {
             trace.push(TraceEntry {
@@ -494,25 +562,49 @@ impl VirtualMachine {
         exec_scopes: &mut ExecutionScopes,
         hint_data_dictionary: &HashMap<usize, Vec<Box<dyn Any>>>,
         constants: &HashMap<String, Felt>,
+        run_resources: &mut RunResources,
     ) -> Result<(), VirtualMachineError> {
         if let Some(hint_list) = hint_data_dictionary.get(&self.run_context.pc.offset) {
             for (hint_index, hint_data) in hint_list.iter().enumerate() {
                 hint_executor
-                    .execute_hint(self, exec_scopes, hint_data, constants)
-                    .map_err(|err| VirtualMachineError::Hint(hint_index, Box::new(err)))?
+                    .execute_hint(self, exec_scopes, hint_data, constants, run_resources)
+                    .map_err(|err| VirtualMachineError::Hint(hint_index, Box::new(err)))?;
+                run_resources.consume_step();
             }
         }
         Ok(())
     }
 
     pub fn step_instruction(&mut self) -> Result<(), VirtualMachineError> {
-        let instruction = self.decode_current_instruction()?;
-        self.run_instruction(instruction).map_err(|err| {
+        #[cfg(feature = "hooks")]
+        self.execute_pre_step_hook()?;
+        let pc = self.run_context.pc;
+        let result = if pc.segment_index == PROGRAM_SEGMENT_INDEX {
+            let cached = self
+                .instruction_cache
+                .get_mut(pc.offset)
+                .and_then(Option::take);
+            let instruction = match cached {
+                Some(instruction) => instruction,
+                None => self.decode_current_instruction()?,
+            };
+            let result = self.run_instruction(&instruction);
+            if self.instruction_cache.len() <= pc.offset {
+                self.instruction_cache.resize_with(pc.offset + 1, || None);
+            }
+            self.instruction_cache[pc.offset] = Some(instruction);
+            result
+        } else {
+            // Code outside of the program segment isn't cached
+            let instruction = self.decode_current_instruction()?;
+            self.run_instruction(&instruction)
+        };
+        result.map_err(|err| {
             let pc = &self.get_pc().offset;
             let attr_error_msg = &self
                 .error_message_attributes
                 .iter()
-                .find(|attr| attr.start_pc <= *pc && attr.end_pc >= *pc);
+                .find(|attr| attr.start_pc <= *pc && attr.end_pc > *pc);
             match attr_error_msg {
                 Some(attr) => VirtualMachineError::ErrorMessageAttribute(
                     attr.value.to_string(),
@@ -522,6 +614,8 @@ impl VirtualMachine {
             }
         })?;
         self.skip_instruction_execution = false;
+        #[cfg(feature = "hooks")]
+        self.execute_post_step_hook()?;
         Ok(())
     }
 
@@ -531,8 +625,25 @@ impl VirtualMachine {
         exec_scopes: &mut ExecutionScopes,
         hint_data_dictionary: &HashMap<usize, Vec<Box<dyn Any>>>,
         constants: &HashMap<String, Felt>,
+        run_resources: &mut RunResources,
     ) -> Result<(), VirtualMachineError> {
-        self.step_hint(hint_executor, exec_scopes, hint_data_dictionary, constants)?;
+        if let Some(cancellation_token) = &self.cancellation_token {
+            if self.current_step % self.cancellation_check_interval == 0
+                && cancellation_token.load(Ordering::Relaxed)
+            {
+                return Err(VirtualMachineError::Cancelled(
+                    self.run_context.pc,
+                    self.current_step,
+                ));
+            }
+        }
+        self.step_hint(
+            hint_executor,
+            exec_scopes,
+            hint_data_dictionary,
+            constants,
+            run_resources,
+        )?;
         self.step_instruction()
     }
 
@@ -681,6 +792,21 @@ impl VirtualMachine {
                 .base()
                 .try_into()
                 .map_err(|_| MemoryError::AddressInTemporarySegment(builtin.base()))?;
+            #[cfg(feature = "parallel")]
+            if let Some(hash) = builtin.as_hash_runner() {
+                let segment = &self.memory.data[index];
+                for (offset, deduced_memory_cell) in hash.deduce_memory_cells(segment)? {
+                    let value = &segment[offset];
+                    if Some(&deduced_memory_cell) != value.as_ref() && value != &None {
+                        return Err(VirtualMachineError::InconsistentAutoDeduction(
+                            name.to_owned(),
+                            deduced_memory_cell,
+                            value.to_owned(),
+                        ));
+                    }
+                }
+                continue;
+            }
             for (offset, value) in self.memory.data[index].iter().enumerate() {
                 if let Some(deduced_memory_cell) = builtin
                     .deduce_memory_cell(&Relocatable::from((index as isize, offset)), &self.memory)
@@ -811,19 +937,16 @@ impl VirtualMachine {
         self.memory.get_relocatable(key)
     }
 
-    ///Gets a MaybeRelocatable value from memory indicated by a generic address
+    ///Gets a MaybeRelocatable value from memory indicated by a generic address.
+    ///The value is only cloned if its address had to be relocated
     pub fn get_maybe<'a, 'b: 'a, K: 'a>(
         &'b self,
         key: &'a K,
-    ) -> Result<Option<MaybeRelocatable>, MemoryError>
+    ) -> Result<Option<Cow<'b, MaybeRelocatable>>, MemoryError>
     where
         Relocatable: TryFrom<&'a K>,
     {
-        match self.memory.get(key) {
-            Ok(Some(cow)) => Ok(Some(cow.into_owned())),
-            Ok(None) => Ok(None),
-            Err(error) => Err(error),
-        }
+        self.memory.get(key)
     }
 
     /// Returns a reference to the vector with all builtins present in the virtual machine
@@ -835,12 +958,28 @@ impl VirtualMachine {
         &mut self.builtin_runners
     }
 
+    /// Sets a flag that cancels the run when set to true, e.g. from another thread. It is
+    /// checked every `DEFAULT_CANCELLATION_CHECK_INTERVAL` steps by default, and once it is
+    /// set, `step` fails with `VirtualMachineError::Cancelled`.
+    pub fn set_cancellation_token(&mut self, cancellation_token: Arc<AtomicBool>) {
+        self.cancellation_token = Some(cancellation_token);
+    }
+
+    /// Sets how many steps are executed between checks of the cancellation token.
+    /// An interval of 0 is treated as 1, checking the token before every step.
+    pub fn set_cancellation_check_interval(&mut self, interval: usize) {
+        self.cancellation_check_interval = interval.max(1);
+    }
+
     ///Inserts a value into a memory address given by a Relocatable value
     pub fn insert_value<T: Into<MaybeRelocatable>>(
         &mut self,
         key: &Relocatable,
         val: T,
     ) -> Result<(), VirtualMachineError> {
+        let val = val.into();
+        #[cfg(feature = "hooks")]
+        self.execute_memory_write_hook(key, &val)?;
         self.memory.insert_value(key, val)
     }
 
@@ -848,7 +987,7 @@ impl VirtualMachine {
     pub fn load_data(
         &mut self,
         ptr: &MaybeRelocatable,
-        data: &Vec<MaybeRelocatable>,
+        data: &[MaybeRelocatable],
     ) -> Result<MaybeRelocatable, MemoryError> {
         self.segments.load_data(&mut self.memory, ptr, data)
     }
@@ -901,28 +1040,21 @@ impl VirtualMachine {
     }
 
     pub fn get_range_check_builtin(&self) -> Result<&RangeCheckBuiltinRunner, VirtualMachineError> {
-        for (name, builtin) in &self.builtin_runners {
-            if name == &String::from("range_check") {
-                if let BuiltinRunner::RangeCheck(range_check_builtin) = builtin {
-                    return Ok(range_check_builtin);
-                };
-            }
-        }
-        Err(VirtualMachineError::NoRangeCheckBuiltin)
+        self.builtin_runners
+            .iter()
+            .filter(|(name, _)| name == "range_check")
+            .find_map(|(_, builtin)| builtin.as_range_check_runner())
+            .ok_or(VirtualMachineError::NoRangeCheckBuiltin)
     }
 
     pub fn get_signature_builtin(
         &mut self,
     ) -> Result<&mut SignatureBuiltinRunner, VirtualMachineError> {
-        for (name, builtin) in self.get_builtin_runners_as_mut() {
-            if name == &String::from("ecdsa") {
-                if let BuiltinRunner::Signature(signature_builtin) = builtin {
-                    return Ok(signature_builtin);
-                };
-            }
-        }
-
-        Err(VirtualMachineError::NoSignatureBuiltin)
+        self.builtin_runners
+            .iter_mut()
+            .filter(|(name, _)| name == "ecdsa")
+            .find_map(|(_, builtin)| builtin.as_signature_runner_mut())
+            .ok_or(VirtualMachineError::NoSignatureBuiltin)
     }
     pub fn disable_trace(&mut self) {
         self.trace = None
@@ -943,8 +1075,19 @@ impl VirtualMachine {
         self.run_context.set_pc(pc)
     }
 
+    /// Returns the used size of a segment (the offset past its last written cell), read from
+    /// the memory, so it doesn't need compute_effective_sizes to be called first.
     pub fn get_segment_used_size(&self, index: usize) -> Option<usize> {
-        self.segments.get_segment_used_size(index)
+        self.memory.data.get(index).map(Vec::len)
+    }
+
+    /// Returns the finalized size of a segment, or its used size if it wasn't finalized.
+    pub fn get_segment_size(&self, index: usize) -> Option<usize> {
+        self.segments
+            .segment_sizes
+            .get(&index)
+            .copied()
+            .or_else(|| self.get_segment_used_size(index))
     }
 
     pub fn add_temporary_segment(&mut self) -> Relocatable {
@@ -957,6 +1100,7 @@ impl VirtualMachine {
     ///   - Source address's segment must be negative (temporary).
     ///   - Source address's offset must be zero.
     ///   - There shouldn't already be relocation at the source segment.
+    ///   - Destination address's segment must not be temporary.
     pub fn add_relocation_rule(
         &mut self,
         src_ptr: Relocatable,
@@ -978,7 +1122,7 @@ impl VirtualMachine {
 
     /// Proxy to MemorySegmentManager::compute_effective_sizes() to make it accessible from outside
     /// cairo-rs.
-    pub fn compute_effective_sizes(&mut self) -> &Vec<usize> {
+    pub fn compute_effective_sizes(&mut self) -> Vec<usize> {
         self.segments.compute_effective_sizes(&self.memory)
     }
 }
@@ -1013,6 +1157,59 @@ mod tests {
     use felt::{felt_str, NewFelt};
     use std::{collections::HashSet, path::Path};
 
+    #[test]
+    fn reset_clears_run_state_and_keeps_capacity() {
+        let mut vm = vm!(true);
+        add_segments!(vm, 3);
+        vm.memory = memory![((0, 0), 5), ((1, 0), 1), ((2, 3), (1, 0))];
+        vm.memory
+            .validated_addresses
+            .insert(mayberelocatable!(1, 0));
+        vm.builtin_runners.push((
+            String::from("bitwise"),
+            BitwiseBuiltinRunner::new(&BitwiseInstanceDef::default(), true).into(),
+        ));
+        vm.trace.as_mut().unwrap().push(TraceEntry {
+            pc: relocatable!(0, 0),
+            ap: relocatable!(1, 2),
+            fp: relocatable!(1, 2),
+        });
+        vm.run_context.pc = relocatable!(0, 4);
+        vm.run_context.ap = 2;
+        vm.run_context.fp = 2;
+        vm.current_step = 1;
+        vm.run_finished = true;
+        let memory_capacity = vm.memory.data.capacity();
+        let mut segment_capacities: Vec<usize> = vm.memory.data.iter().map(Vec::capacity).collect();
+        let builtins_capacity = vm.builtin_runners.capacity();
+
+        vm.reset();
+        assert!(vm.memory.data.is_empty());
+        assert!(vm.memory.validated_addresses.is_empty());
+        assert_eq!(vm.segments.num_segments, 0);
+        assert!(vm.builtin_runners.is_empty());
+        assert_eq!(vm.trace, Some(Vec::new()));
+        assert_eq!(vm.accessed_addresses, Some(Vec::new()));
+        assert_eq!(vm.run_context.pc, relocatable!(0, 0));
+        assert_eq!(vm.run_context.ap, 0);
+        assert_eq!(vm.run_context.fp, 0);
+        assert_eq!(vm.current_step, 0);
+        assert!(!vm.run_finished);
+        assert_eq!(vm.memory.data.capacity(), memory_capacity);
+        assert_eq!(vm.builtin_runners.capacity(), builtins_capacity);
+
+        // The next run numbers its segments from scratch, reusing the cleared ones
+        assert_eq!(vm.add_memory_segment(), relocatable!(0, 0));
+        vm.add_memory_segment();
+        vm.add_memory_segment();
+        assert!(vm.memory.data.iter().all(Vec::is_empty));
+        let mut reused_capacities: Vec<usize> = vm.memory.data.iter().map(Vec::capacity).collect();
+        segment_capacities.sort_unstable();
+        reused_capacities.sort_unstable();
+        assert_eq!(reused_capacities, segment_capacities);
+        assert!(segment_capacities.iter().all(|capacity| *capacity > 0));
+    }
+
     #[test]
     fn get_instruction_encoding_successful_without_imm() {
         let mut vm = vm!();
@@ -1646,7 +1843,7 @@ mod tests {
         vm.run_context.ap = 5;
         vm.run_context.fp = 6;
 
-        assert_eq!(Ok(()), vm.update_registers(instruction, operands));
+        assert_eq!(Ok(()), vm.update_registers(&instruction, operands));
         assert_eq!(vm.run_context.pc, Relocatable::from((0, 5)));
         assert_eq!(vm.run_context.ap, 5);
         assert_eq!(vm.run_context.fp, 6);
@@ -1679,7 +1876,7 @@ mod tests {
         let mut vm = vm!();
         run_context!(vm, 4, 5, 6);
 
-        assert_eq!(Ok(()), vm.update_registers(instruction, operands));
+        assert_eq!(Ok(()), vm.update_registers(&instruction, operands));
         assert_eq!(vm.run_context.pc, Relocatable::from((0, 12)));
         assert_eq!(vm.run_context.ap, 7);
         assert_eq!(vm.run_context.fp, 11);
@@ -2457,7 +2654,8 @@ mod tests {
                 &mut hint_processor,
                 exec_scopes_ref!(),
                 &HashMap::new(),
-                &HashMap::new()
+                &HashMap::new(),
+                &mut RunResources::default()
             ),
             Ok(())
         );
@@ -2696,7 +2894,8 @@ mod tests {
                 &mut hint_processor,
                 exec_scopes_ref!(),
                 &HashMap::new(),
-                &HashMap::new()
+                &HashMap::new(),
+                &mut RunResources::default()
             ),
             Ok(())
         );
@@ -2776,7 +2975,8 @@ mod tests {
                     &mut hint_processor,
                     exec_scopes_ref!(),
                     &HashMap::new(),
-                    &HashMap::new()
+                    &HashMap::new(),
+                    &mut RunResources::default()
                 ),
                 Ok(())
             );
@@ -2872,7 +3072,8 @@ mod tests {
                 &mut hint_processor,
                 exec_scopes_ref!(),
                 &HashMap::new(),
-                &HashMap::new()
+                &HashMap::new(),
+                &mut RunResources::default()
             ),
             Ok(())
         );
@@ -2893,7 +3094,8 @@ mod tests {
                 &mut hint_processor,
                 exec_scopes_ref!(),
                 &HashMap::new(),
-                &HashMap::new()
+                &HashMap::new(),
+                &mut RunResources::default()
             ),
             Ok(())
         );
@@ -2915,7 +3117,8 @@ mod tests {
                 &mut hint_processor,
                 exec_scopes_ref!(),
                 &HashMap::new(),
-                &HashMap::new()
+                &HashMap::new(),
+                &mut RunResources::default()
             ),
             Ok(())
         );
@@ -3299,7 +3502,7 @@ mod tests {
                 )))
             ))
         );
-        assert_eq!(error.unwrap_err().to_string(), "Inconsistent auto-deduction for builtin ec_op, expected 2739017437753868763038285897969098325279422804143820990343394856167768859289, got Some(Int(2778063437308421278851140253538604815869848682781135193774472480292420096757))");
+        assert_eq!(error.unwrap_err().to_string(), "Inconsistent auto-deduction for builtin ec_op, expected 2739017437753868763038285897969098325279422804143820990343394856167768859289, got Some(Int(0x6245403e2fafe5df3b79ea28d050d477771bc560fc59e915b302cc9b70a92f5))");
     }
 
     #[test]
@@ -3404,6 +3607,31 @@ mod tests {
         RelocatableValue(segment_index=1, offset=1): RelocatableValue(segment_index=3, offset=0)}
      */
 
+    #[test]
+    fn step_hint_consumes_run_resources() {
+        let mut vm = vm!();
+        let hint_data = || {
+            any_box!(HintProcessorData::new_default(
+                "vm_enter_scope()".to_string(),
+                HashMap::new(),
+            ))
+        };
+        let hint_data_dictionary = HashMap::from([(0_usize, vec![hint_data(), hint_data()])]);
+        let mut hint_processor = BuiltinHintProcessor::new_empty();
+        let mut run_resources = RunResources::new(5);
+        assert_eq!(
+            vm.step_hint(
+                &mut hint_processor,
+                exec_scopes_ref!(),
+                &hint_data_dictionary,
+                &HashMap::new(),
+                &mut run_resources
+            ),
+            Ok(())
+        );
+        assert_eq!(run_resources.get_n_steps(), Some(3));
+    }
+
     #[test]
     fn test_step_for_preset_memory_with_alloc_hint() {
         let mut vm = vm!(true);
@@ -3453,7 +3681,8 @@ mod tests {
                     &mut hint_processor,
                     exec_scopes_ref!(),
                     &hint_data_dictionary,
-                    &HashMap::new()
+                    &HashMap::new(),
+                    &mut RunResources::default()
                 ),
                 Ok(())
             );
@@ -3594,11 +3823,39 @@ mod tests {
     }
 
     #[test]
-    fn get_segment_used_size_before_computing_used() {
+    fn get_segment_used_size_missing_segment() {
         let vm = vm!();
         assert_eq!(None, vm.get_segment_used_size(2));
     }
 
+    #[test]
+    fn get_segment_used_size_without_computing_used() {
+        let mut vm = vm!();
+        vm.add_memory_segment();
+        vm.add_memory_segment();
+        vm.insert_value(&relocatable!(1, 2), Felt::new(1)).unwrap();
+        vm.insert_value(&relocatable!(0, 0), Felt::new(2)).unwrap();
+        vm.insert_value(&relocatable!(1, 4), Felt::new(3)).unwrap();
+        assert_eq!(vm.get_segment_used_size(0), Some(1));
+        assert_eq!(vm.get_segment_used_size(1), Some(5));
+        assert_eq!(vm.segments.segment_used_sizes, None);
+
+        vm.add_memory_segment();
+        assert_eq!(vm.get_segment_used_size(2), Some(0));
+    }
+
+    #[test]
+    fn get_segment_size_prefers_finalized_size() {
+        let mut vm = vm!();
+        vm.add_memory_segment();
+        vm.add_memory_segment();
+        vm.insert_value(&relocatable!(0, 1), Felt::new(1)).unwrap();
+        vm.segments.finalize(0, Some(10), None);
+        assert_eq!(vm.get_segment_size(0), Some(10));
+        assert_eq!(vm.get_segment_size(1), Some(0));
+        assert_eq!(vm.get_segment_size(2), None);
+    }
+
     #[test]
     fn get_and_set_pc() {
         let mut vm = vm!();
@@ -3649,6 +3906,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn get_maybe_borrows_value() {
+        let mut vm = vm!();
+        vm.memory = memory![((1, 0), 5), ((1, 1), (2, 3))];
+        assert!(matches!(
+            vm.get_maybe(&relocatable!(1, 0)),
+            Ok(Some(Cow::Borrowed(MaybeRelocatable::Int(num)))) if num == &Felt::new(5)
+        ));
+        assert_eq!(
+            vm.get_maybe(&relocatable!(1, 1)),
+            Ok(Some(Cow::Borrowed(&MaybeRelocatable::from((2, 3)))))
+        );
+    }
+
     #[test]
     fn end_run_error() {
         let mut vm = vm!();
@@ -3694,6 +3965,65 @@ mod tests {
         );
     }
 
+    #[test]
+    fn step_instruction_caches_program_instructions() {
+        let mut vm = vm!();
+        // jmp rel 0
+        vm.memory = memory![((0, 0), 0x10780017fff7fff_i64), ((0, 1), 0), ((1, 0), 0)];
+        vm.run_context.ap = 1;
+        vm.run_context.fp = 1;
+
+        for _ in 0..3 {
+            assert_eq!(vm.step_instruction(), Ok(()));
+        }
+        assert_eq!(vm.run_context.pc, relocatable!(0, 0));
+        assert_eq!(vm.current_step, 3);
+        assert_eq!(vm.instruction_cache.len(), 1);
+        assert_eq!(
+            vm.instruction_cache[0].as_ref(),
+            Some(&vm.decode_current_instruction().unwrap())
+        );
+
+        vm.reset();
+        assert!(vm.instruction_cache.is_empty());
+    }
+
+    #[test]
+    fn step_instruction_outside_program_segment_bypasses_cache() {
+        let mut vm = vm!();
+        // jmp rel 0
+        vm.memory = memory![((2, 0), 0x10780017fff7fff_i64), ((2, 1), 0), ((1, 0), 0)];
+        vm.run_context.pc = relocatable!(2, 0);
+        vm.run_context.ap = 1;
+        vm.run_context.fp = 1;
+
+        assert_eq!(vm.step_instruction(), Ok(()));
+        assert_eq!(vm.run_context.pc, relocatable!(2, 0));
+        assert_eq!(vm.current_step, 1);
+        assert!(vm.instruction_cache.is_empty());
+    }
+
+    #[test]
+    fn overwriting_cached_instruction_fails() {
+        let mut vm = vm!();
+        // jmp rel 0
+        vm.memory = memory![((0, 0), 0x10780017fff7fff_i64), ((0, 1), 0), ((1, 0), 0)];
+        vm.run_context.ap = 1;
+        vm.run_context.fp = 1;
+        assert_eq!(vm.step_instruction(), Ok(()));
+
+        // Memory is write-once, so a program can't rewrite an instruction that was cached
+        assert!(matches!(
+            vm.insert_value(&relocatable!(0, 0), Felt::new(0x48307ffe7fff8000_i64)),
+            Err(VirtualMachineError::MemoryError(
+                MemoryError::InconsistentMemory(..)
+            ))
+        ));
+        assert_eq!(vm.step_instruction(), Ok(()));
+        assert_eq!(vm.run_context.pc, relocatable!(0, 0));
+        assert_eq!(vm.current_step, 2);
+    }
+
     #[test]
     fn add_relocation_rule_test() {
         let mut vm = vm!();
@@ -3704,7 +4034,7 @@ mod tests {
         );
         assert_eq!(
             vm.add_relocation_rule((-2, 0).into(), (-1, 1).into()),
-            Ok(()),
+            Err(MemoryError::AddressInTemporarySegment(-1)),
         );
         assert_eq!(
             vm.add_relocation_rule((5, 0).into(), (0, 0).into()),
@@ -3832,7 +4162,7 @@ mod tests {
         let segment = vm.segments.add(&mut vm.memory);
         vm.load_data(
             &segment.into(),
-            &vec![
+            &[
                 mayberelocatable!(1),
                 mayberelocatable!(2),
                 mayberelocatable!(3),
@@ -3841,7 +4171,7 @@ mod tests {
         )
         .expect("Could not load data into memory.");
 
-        assert_eq!(vm.compute_effective_sizes(), &vec![4]);
+        assert_eq!(vm.compute_effective_sizes(), vec![4]);
     }
 
     #[test]
@@ -3900,7 +4230,12 @@ mod tests {
 
         let end = cairo_runner.initialize(&mut vm).unwrap();
         assert!(cairo_runner
-            .run_until_pc(end, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            )
             .is_err());
         let expected_traceback = vec![
             (Relocatable::from((1, 3)), Relocatable::from((0, 97))),
@@ -3924,7 +4259,12 @@ mod tests {
 
         let end = cairo_runner.initialize(&mut vm).unwrap();
         assert!(cairo_runner
-            .run_until_pc(end, &mut vm, &mut hint_processor)
+            .run_until_pc(
+                end,
+                &mut RunResources::default(),
+                &mut vm,
+                &mut hint_processor
+            )
             .is_err());
         let expected_traceback = vec![(Relocatable::from((1, 2)), Relocatable::from((0, 34)))];
         assert_eq!(vm.get_traceback_entries(), expected_traceback);
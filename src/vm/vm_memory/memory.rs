@@ -7,6 +7,7 @@ use felt::Felt;
 use std::{
     borrow::Cow,
     collections::{HashMap, HashSet},
+    fmt,
     mem::swap,
 };
 
@@ -15,6 +16,31 @@ pub struct ValidationRule(
     pub  Box<dyn Fn(&Memory, &MaybeRelocatable) -> Result<Vec<MaybeRelocatable>, MemoryError>>,
 );
 
+/// A memory cell whose value differs between two memories, as returned by [`Memory::diff`].
+/// A value of `None` means the cell is missing in that memory.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryDiff {
+    pub address: Relocatable,
+    pub lhs: Option<MaybeRelocatable>,
+    pub rhs: Option<MaybeRelocatable>,
+}
+
+impl fmt::Display for MemoryDiff {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let display_value = |value: &Option<MaybeRelocatable>| match value {
+            Some(value) => value.to_string(),
+            None => String::from("None"),
+        };
+        write!(
+            f,
+            "{}: {} != {}",
+            self.address,
+            display_value(&self.lhs),
+            display_value(&self.rhs)
+        )
+    }
+}
+
 pub struct Memory {
     pub data: Vec<Vec<Option<MaybeRelocatable>>>,
     pub temp_data: Vec<Vec<Option<MaybeRelocatable>>>,
@@ -22,7 +48,11 @@ pub struct Memory {
     // zero; that is, segment_index = -1 maps to key 0, -2 to key 1...
     pub(crate) relocation_rules: HashMap<usize, Relocatable>,
     pub validated_addresses: HashSet<MaybeRelocatable>,
-    validation_rules: HashMap<usize, ValidationRule>,
+    // Rules are applied in the order they were added to their segment
+    validation_rules: HashMap<usize, Vec<ValidationRule>>,
+    // Empty segments left by reset, handed out again when segments are added so that the
+    // next run reuses their allocations
+    segment_pool: Vec<Vec<Option<MaybeRelocatable>>>,
 }
 
 impl Memory {
@@ -33,8 +63,27 @@ impl Memory {
             relocation_rules: HashMap::new(),
             validated_addresses: HashSet::<MaybeRelocatable>::new(),
             validation_rules: HashMap::new(),
+            segment_pool: Vec::new(),
         }
     }
+
+    /// Removes every segment, relocation rule, validated address and validation rule, keeping
+    /// the allocated capacity. The segments are cleared in place and reused by the segments
+    /// added afterwards.
+    pub(crate) fn reset(&mut self) {
+        for mut segment in self.data.drain(..).chain(self.temp_data.drain(..)) {
+            segment.clear();
+            self.segment_pool.push(segment);
+        }
+        self.relocation_rules.clear();
+        self.validated_addresses.clear();
+        self.validation_rules.clear();
+    }
+    /// Returns an empty segment, reusing one left by reset if there is any.
+    pub(crate) fn new_segment(&mut self) -> Vec<Option<MaybeRelocatable>> {
+        self.segment_pool.pop().unwrap_or_default()
+    }
+
     ///Inserts an MaybeRelocatable value into an address given by a MaybeRelocatable::Relocatable
     /// Will panic if the segment index given by the address corresponds to a non-allocated segment
     /// If the address isnt contiguous with previously inserted data, memory gaps will be represented by inserting None values
@@ -84,6 +133,72 @@ impl Memory {
         self.validate_memory_cell(&MaybeRelocatable::from(key))
     }
 
+    ///Writes `data` into consecutive addresses starting at `ptr` and returns the first address after it.
+    /// The data is checked against the existing cells before anything is written, so an inconsistent
+    /// write leaves the memory untouched. The segment is then resized once to fit the whole data, and
+    /// the segment's validation rules (if any) are applied after all the values have been written
+    pub fn load_data(
+        &mut self,
+        ptr: &Relocatable,
+        data: &[MaybeRelocatable],
+    ) -> Result<Relocatable, MemoryError> {
+        if data.is_empty() {
+            return Ok(*ptr);
+        }
+        let (value_index, value_offset) = from_relocatable_to_indexes(ptr);
+
+        let memory = if ptr.segment_index.is_negative() {
+            &mut self.temp_data
+        } else {
+            &mut self.data
+        };
+
+        let memory_len = memory.len();
+        let segment = memory
+            .get_mut(value_index)
+            .ok_or(MemoryError::UnallocatedSegment(value_index, memory_len))?;
+
+        //Existing memory cannot be changed, so check every cell before writing any of them
+        for (i, val) in data.iter().enumerate() {
+            if let Some(Some(current_value)) = segment.get(value_offset + i) {
+                if current_value != val {
+                    return Err(MemoryError::InconsistentMemory(
+                        (ptr + i).into(),
+                        current_value.to_owned(),
+                        val.clone(),
+                    ));
+                }
+            }
+        }
+
+        let end = value_offset + data.len();
+        if segment.len() < end {
+            segment.resize(end, None);
+        }
+        for (cell, val) in segment[value_offset..end].iter_mut().zip(data) {
+            if cell.is_none() {
+                *cell = Some(val.clone());
+            }
+        }
+
+        if !ptr.segment_index.is_negative() {
+            if let Some(validation_rules) = self.validation_rules.get(&value_index) {
+                let mut validated_addresses = Vec::new();
+                for i in 0..data.len() {
+                    let address = MaybeRelocatable::from(ptr + i);
+                    if !self.validated_addresses.contains(&address) {
+                        for validation_rule in validation_rules {
+                            validated_addresses.extend(validation_rule.0(self, &address)?);
+                        }
+                    }
+                }
+                self.validated_addresses.extend(validated_addresses);
+            }
+        }
+
+        Ok(ptr + data.len())
+    }
+
     /// Retrieve a value from memory (either normal or temporary) and apply relocation rules
     pub(crate) fn get<'a, 'b: 'a, K: 'a>(
         &'b self,
@@ -112,73 +227,44 @@ impl Memory {
     }
 
     /// Relocates the memory according to the relocation rules and clears `self.relocaction_rules`.
+    /// Every address stored in memory is relocated, and the contents of each temporary segment with
+    /// a relocation rule are moved onto its destination.
     pub fn relocate_memory(&mut self) -> Result<(), MemoryError> {
         if self.relocation_rules.is_empty() {
             return Ok(());
         }
 
-        let mut prev_data = Vec::new();
-        let mut prev_temp_data = Vec::new();
-        swap(&mut self.data, &mut prev_data);
-        swap(&mut self.temp_data, &mut prev_temp_data);
-
-        let data_iter = prev_data
-            .into_iter()
-            .enumerate()
-            .flat_map(|(segment_index, segment_data)| {
-                segment_data
-                    .into_iter()
-                    .enumerate()
-                    .map(move |(cell_offset, cell_data)| {
-                        (
-                            Relocatable::from((segment_index as isize, cell_offset)),
-                            cell_data,
-                        )
-                    })
-            })
-            .chain(prev_temp_data.into_iter().enumerate().flat_map(
-                |(segment_index, segment_data)| {
-                    let segment_index = -(segment_index as isize) - 1;
-                    segment_data
-                        .into_iter()
-                        .enumerate()
-                        .map(move |(cell_offset, cell_data)| {
-                            (
-                                Relocatable::from((segment_index as isize, cell_offset)),
-                                cell_data,
-                            )
-                        })
-                },
-            ));
-        for (addr, value) in data_iter {
-            // After the following check, addr.segment_index cannot be negative, therefore it is
-            // safe to cast to `usize`.
-            if addr.segment_index.is_negative() {
-                continue;
+        let mut data = Vec::new();
+        swap(&mut self.data, &mut data);
+        for cell in data.iter_mut().flatten().flatten() {
+            if let MaybeRelocatable::RelocatableValue(addr) = cell {
+                *addr = self.relocate_value(*addr);
             }
+        }
+        self.data = data;
 
-            let value = match value {
-                Some(x) => x,
+        for index in 0..self.temp_data.len() {
+            let base_addr = match self.relocation_rules.get(&index) {
+                Some(base_addr) => *base_addr,
                 None => continue,
             };
-
-            let new_addr: Relocatable = self
-                .relocate_value(&MaybeRelocatable::RelocatableValue(addr))
-                .into_owned()
-                .try_into()?;
-            let new_value = self.relocate_value(&value).into_owned();
-
-            if new_addr.segment_index as usize >= self.data.len() {
-                self.data
-                    .resize(new_addr.segment_index as usize + 1, Vec::new());
-            }
-
-            let segment_data = &mut self.data[new_addr.segment_index as usize];
-            if new_addr.offset as usize >= segment_data.len() {
-                segment_data.resize(new_addr.offset + 1, None);
+            let mut segment = Vec::new();
+            swap(&mut self.temp_data[index], &mut segment);
+            for (offset, value) in segment.into_iter().enumerate() {
+                if let Some(value) = value {
+                    let value = self.relocate_value(&value).into_owned();
+                    self.insert(&(base_addr + offset), &value)?;
+                }
             }
-
-            segment_data[new_addr.offset] = Some(new_value);
+        }
+        // Temporary segments without a rule keep their index, so only trailing relocated
+        // segments can be dropped
+        while !self.temp_data.is_empty()
+            && self
+                .relocation_rules
+                .contains_key(&(self.temp_data.len() - 1))
+        {
+            self.temp_data.pop();
         }
 
         self.relocation_rules.clear();
@@ -191,7 +277,8 @@ impl Memory {
     ///   - Source address's segment must be negative (temporary).
     ///   - Source address's offset must be zero.
     ///   - There shouldn't already be relocation at the source segment.
-    pub(crate) fn add_relocation_rule(
+    ///   - Destination address's segment must not be temporary.
+    pub fn add_relocation_rule(
         &mut self,
         src_ptr: Relocatable,
         dst_ptr: Relocatable,
@@ -204,6 +291,11 @@ impl Memory {
         if src_ptr.offset != 0 {
             return Err(MemoryError::NonZeroOffset(src_ptr.offset));
         }
+        if dst_ptr.segment_index < 0 {
+            return Err(MemoryError::AddressInTemporarySegment(
+                dst_ptr.segment_index,
+            ));
+        }
 
         // Adjust the segment index to begin at zero, as per the struct field's
         // comment.
@@ -248,18 +340,37 @@ impl Memory {
             .map_err(VirtualMachineError::MemoryError)
     }
 
+    ///Adds a rule to be applied to the values inserted in the segment, after the rules already in it
     pub fn add_validation_rule(&mut self, segment_index: usize, rule: ValidationRule) {
-        self.validation_rules.insert(segment_index, rule);
+        self.validation_rules
+            .entry(segment_index)
+            .or_default()
+            .push(rule);
+    }
+
+    ///Replaces the rules of the segment with rule, so that adding it again doesn't stack it.
+    ///Used by the builtins, which own their segments
+    pub fn set_validation_rule(&mut self, segment_index: usize, rule: ValidationRule) {
+        self.validation_rules.insert(segment_index, vec![rule]);
+    }
+
+    ///Removes every validation rule of the segment. Addresses already validated stay validated
+    pub fn clear_validation_rules(&mut self, segment_index: usize) {
+        self.validation_rules.remove(&segment_index);
     }
 
     fn validate_memory_cell(&mut self, address: &MaybeRelocatable) -> Result<(), MemoryError> {
         if let &MaybeRelocatable::RelocatableValue(ref rel_addr) = address {
             if !self.validated_addresses.contains(address) {
-                for (index, validation_rule) in self.validation_rules.iter() {
-                    if rel_addr.segment_index == *index as isize {
-                        self.validated_addresses
-                            .extend(validation_rule.0(self, address)?);
+                let validation_rules = usize::try_from(rel_addr.segment_index)
+                    .ok()
+                    .and_then(|index| self.validation_rules.get(&index));
+                if let Some(validation_rules) = validation_rules {
+                    let mut validated_addresses = Vec::new();
+                    for validation_rule in validation_rules {
+                        validated_addresses.extend(validation_rule.0(self, address)?);
                     }
+                    self.validated_addresses.extend(validated_addresses);
                 }
             }
             Ok(())
@@ -267,7 +378,41 @@ impl Memory {
             Err(MemoryError::AddressNotRelocatable)
         }
     }
-    ///Applies validation_rules to the current memory
+    ///Returns the cells whose values differ between self and other, including the cells present
+    /// in only one of them. Segments are compared in order, starting with the temporary ones.
+    /// Relocation rules are not applied
+    pub fn diff(&self, other: &Memory) -> Vec<MemoryDiff> {
+        let mut diffs = Vec::new();
+        let segment_pairs = [
+            (&self.temp_data, &other.temp_data, true),
+            (&self.data, &other.data, false),
+        ];
+        for (lhs_data, rhs_data, temporary) in segment_pairs {
+            for index in 0..lhs_data.len().max(rhs_data.len()) {
+                let segment_index = if temporary {
+                    -(index as isize) - 1
+                } else {
+                    index as isize
+                };
+                let lhs_segment = lhs_data.get(index).map(Vec::as_slice).unwrap_or_default();
+                let rhs_segment = rhs_data.get(index).map(Vec::as_slice).unwrap_or_default();
+                for offset in 0..lhs_segment.len().max(rhs_segment.len()) {
+                    let lhs = lhs_segment.get(offset).cloned().flatten();
+                    let rhs = rhs_segment.get(offset).cloned().flatten();
+                    if lhs != rhs {
+                        diffs.push(MemoryDiff {
+                            address: Relocatable::from((segment_index, offset)),
+                            lhs,
+                            rhs,
+                        });
+                    }
+                }
+            }
+        }
+        diffs
+    }
+
+    ///Applies validation_rules to the current memory, in segment and offset order
     //Should be called during initialization, as None values will raise a FoundNonInt error
     pub fn validate_existing_memory(&mut self) -> Result<(), MemoryError> {
         for i in 0..self.data.len() {
@@ -379,6 +524,7 @@ mod memory_tests {
         },
     };
     use felt::{felt_str, NewFelt};
+    use std::{cell::RefCell, rc::Rc};
 
     use crate::vm::errors::memory_errors::MemoryError;
 
@@ -751,6 +897,61 @@ mod memory_tests {
         );
     }
 
+    #[test]
+    fn get_relocatable_valid() {
+        let memory = memory![((0, 0), (1, 3))];
+        assert_eq!(
+            memory.get_relocatable(&Relocatable::from((0, 0))),
+            Ok(Relocatable::from((1, 3)))
+        );
+    }
+
+    #[test]
+    fn get_relocatable_invalid_expected_relocatable() {
+        let memory = memory![((0, 0), 10)];
+        assert_eq!(
+            memory.get_relocatable(&Relocatable::from((0, 0))),
+            Err(VirtualMachineError::ExpectedRelocatable(
+                MaybeRelocatable::from((0, 0))
+            ))
+        );
+    }
+
+    #[test]
+    fn get_integer_range_valid() {
+        let memory = memory![((0, 0), 1), ((0, 1), 2), ((0, 2), 3)];
+        assert_eq!(
+            memory.get_integer_range(&Relocatable::from((0, 0)), 3),
+            Ok(vec![
+                Cow::Borrowed(&Felt::new(1)),
+                Cow::Borrowed(&Felt::new(2)),
+                Cow::Borrowed(&Felt::new(3))
+            ])
+        );
+    }
+
+    #[test]
+    fn get_integer_range_gap() {
+        let memory = memory![((0, 0), 1), ((0, 2), 3)];
+        assert_eq!(
+            memory.get_integer_range(&Relocatable::from((0, 0)), 3),
+            Err(VirtualMachineError::ExpectedInteger(
+                MaybeRelocatable::from((0, 1))
+            ))
+        );
+    }
+
+    #[test]
+    fn get_integer_range_relocatable_mid_range() {
+        let memory = memory![((0, 0), 1), ((0, 1), 2), ((0, 2), (0, 0)), ((0, 3), 4)];
+        assert_eq!(
+            memory.get_integer_range(&Relocatable::from((0, 0)), 4),
+            Err(VirtualMachineError::ExpectedInteger(
+                MaybeRelocatable::from((0, 2))
+            ))
+        );
+    }
+
     #[test]
     fn default_memory() {
         let mem: Memory = Default::default();
@@ -779,7 +980,7 @@ mod memory_tests {
         );
         assert_eq!(
             memory.add_relocation_rule((-2, 0).into(), (-1, 1).into()),
-            Ok(()),
+            Err(MemoryError::AddressInTemporarySegment(-1)),
         );
         assert_eq!(
             memory.add_relocation_rule((5, 0).into(), (0, 0).into()),
@@ -971,6 +1172,7 @@ mod memory_tests {
             ((1, 1), 5),
             ((1, 2), (-1, 2))
         ];
+        memory.data.push(Vec::new());
         memory.temp_data = vec![vec![
             mayberelocatable!(7).into(),
             mayberelocatable!(8).into(),
@@ -994,8 +1196,387 @@ mod memory_tests {
                     mayberelocatable!(5).into(),
                     mayberelocatable!(2, 3).into(),
                 ],
+                vec![
+                    None,
+                    mayberelocatable!(7).into(),
+                    mayberelocatable!(8).into(),
+                    mayberelocatable!(9).into(),
+                ],
             ],
         );
         assert!(memory.temp_data.is_empty());
     }
+
+    #[test]
+    fn relocate_memory_temporary_segment_pointing_to_temporary_segment() {
+        let mut memory = Memory::new();
+        memory.data.push(vec![None, None]);
+        memory.data.push(Vec::new());
+        memory.temp_data = vec![
+            vec![mayberelocatable!(-2, 1).into(), mayberelocatable!(4).into()],
+            vec![mayberelocatable!(5).into(), mayberelocatable!(6).into()],
+        ];
+        memory
+            .add_relocation_rule((-1, 0).into(), (1, 0).into())
+            .unwrap();
+        memory
+            .add_relocation_rule((-2, 0).into(), (0, 0).into())
+            .unwrap();
+
+        assert_eq!(memory.relocate_memory(), Ok(()));
+        assert_eq!(
+            memory
+                .get(&mayberelocatable!(1, 0))
+                .unwrap()
+                .unwrap()
+                .as_ref(),
+            &mayberelocatable!(0, 1)
+        );
+        assert_eq!(
+            memory.get_integer(&(1, 1).into()).unwrap().as_ref(),
+            &Felt::new(4)
+        );
+        assert_eq!(
+            memory.get_integer(&(0, 0).into()).unwrap().as_ref(),
+            &Felt::new(5)
+        );
+        assert_eq!(
+            memory.get_integer(&(0, 1).into()).unwrap().as_ref(),
+            &Felt::new(6)
+        );
+        assert!(memory.temp_data.is_empty());
+    }
+
+    #[test]
+    fn relocate_memory_keeps_temporary_segments_without_rules() {
+        let mut memory = Memory::new();
+        memory.data.push(Vec::new());
+        memory.temp_data = vec![
+            vec![mayberelocatable!(1).into()],
+            vec![mayberelocatable!(2).into()],
+        ];
+        memory
+            .add_relocation_rule((-2, 0).into(), (0, 0).into())
+            .unwrap();
+
+        assert_eq!(memory.relocate_memory(), Ok(()));
+        assert_eq!(memory.data, vec![vec![mayberelocatable!(2).into()]]);
+        assert_eq!(memory.temp_data, vec![vec![mayberelocatable!(1).into()]]);
+        assert_eq!(
+            memory
+                .get(&mayberelocatable!(-1, 0))
+                .unwrap()
+                .unwrap()
+                .as_ref(),
+            &mayberelocatable!(1)
+        );
+    }
+
+    #[test]
+    fn relocate_memory_inconsistent_destination() {
+        let mut memory = memory![((0, 0), 1)];
+        memory.temp_data = vec![vec![mayberelocatable!(2).into()]];
+        memory
+            .add_relocation_rule((-1, 0).into(), (0, 0).into())
+            .unwrap();
+
+        assert_eq!(
+            memory.relocate_memory(),
+            Err(MemoryError::InconsistentMemory(
+                mayberelocatable!(0, 0),
+                mayberelocatable!(1),
+                mayberelocatable!(2)
+            ))
+        );
+    }
+
+    #[test]
+    fn load_data_matches_sequential_inserts() {
+        let data = vec![
+            mayberelocatable!(1),
+            mayberelocatable!(2, 3),
+            mayberelocatable!(4),
+        ];
+        let mut memory = Memory::new();
+        memory.data.push(vec![None, Some(mayberelocatable!(2, 3))]);
+        let mut expected = Memory::new();
+        expected
+            .data
+            .push(vec![None, Some(mayberelocatable!(2, 3))]);
+        for (i, value) in data.iter().enumerate() {
+            expected
+                .insert(&MaybeRelocatable::from((0, i)), value)
+                .unwrap();
+        }
+
+        assert_eq!(
+            memory.load_data(&Relocatable::from((0, 0)), &data),
+            Ok(Relocatable::from((0, 3)))
+        );
+        assert_eq!(memory.data, expected.data);
+        assert_eq!(memory.validated_addresses, expected.validated_addresses);
+    }
+
+    #[test]
+    fn load_data_unallocated_segment() {
+        let mut memory = Memory::new();
+        assert_eq!(
+            memory.load_data(&Relocatable::from((0, 0)), &[mayberelocatable!(1)]),
+            Err(MemoryError::UnallocatedSegment(0, 0))
+        );
+    }
+
+    #[test]
+    fn load_data_inconsistent_memory() {
+        let mut memory = memory![((0, 1), 5)];
+        assert_eq!(
+            memory.load_data(
+                &Relocatable::from((0, 0)),
+                &[
+                    mayberelocatable!(1),
+                    mayberelocatable!(6),
+                    mayberelocatable!(7)
+                ]
+            ),
+            Err(MemoryError::InconsistentMemory(
+                mayberelocatable!(0, 1),
+                mayberelocatable!(5),
+                mayberelocatable!(6)
+            ))
+        );
+        // Nothing gets written when the data is inconsistent
+        assert_eq!(memory.data[0], vec![None, Some(mayberelocatable!(5))]);
+    }
+
+    // Rule that records the addresses it sees and rejects cells holding `rejected`
+    fn auditing_rule(
+        audited: Rc<RefCell<Vec<MaybeRelocatable>>>,
+        rejected: Felt,
+    ) -> ValidationRule {
+        ValidationRule(Box::new(
+            move |memory: &Memory,
+                  address: &MaybeRelocatable|
+                  -> Result<Vec<MaybeRelocatable>, MemoryError> {
+                audited.borrow_mut().push(address.clone());
+                let value = memory
+                    .get(address)?
+                    .ok_or(MemoryError::FoundNonInt)?
+                    .into_owned();
+                if value == MaybeRelocatable::from(rejected.clone()) {
+                    return Err(MemoryError::InvalidMemoryValue(
+                        Relocatable::try_from(address)?,
+                        value,
+                    ));
+                }
+                Ok(Vec::new())
+            },
+        ))
+    }
+
+    #[test]
+    fn multiple_validation_rules_in_segment_all_apply() {
+        let mut builtin = RangeCheckBuiltinRunner::new(8, 8, true);
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        builtin.initialize_segments(&mut segments, &mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
+        let audited = Rc::new(RefCell::new(Vec::new()));
+        memory.add_validation_rule(0, auditing_rule(audited.clone(), Felt::new(7)));
+
+        memory
+            .insert_value(&Relocatable::from((0, 0)), Felt::new(5))
+            .unwrap();
+        assert!(memory
+            .validated_addresses
+            .contains(&MaybeRelocatable::from((0, 0))));
+        assert_eq!(*audited.borrow(), vec![mayberelocatable!(0, 0)]);
+
+        memory
+            .load_data(
+                &Relocatable::from((0, 1)),
+                &[mayberelocatable!(1), mayberelocatable!(2)],
+            )
+            .unwrap();
+        assert_eq!(
+            *audited.borrow(),
+            vec![
+                mayberelocatable!(0, 0),
+                mayberelocatable!(0, 1),
+                mayberelocatable!(0, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn multiple_validation_rules_in_segment_each_can_reject() {
+        let mut builtin = RangeCheckBuiltinRunner::new(8, 8, true);
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        builtin.initialize_segments(&mut segments, &mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
+        let audited = Rc::new(RefCell::new(Vec::new()));
+        memory.add_validation_rule(0, auditing_rule(audited, Felt::new(7)));
+
+        assert_eq!(
+            memory.insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(Felt::new(-10))
+            ),
+            Err(MemoryError::NumOutOfBounds)
+        );
+        assert_eq!(
+            memory.insert(
+                &MaybeRelocatable::from((0, 1)),
+                &MaybeRelocatable::from(Felt::new(7))
+            ),
+            Err(MemoryError::InvalidMemoryValue(
+                Relocatable::from((0, 1)),
+                mayberelocatable!(7)
+            ))
+        );
+    }
+
+    #[test]
+    fn builtin_validation_rule_replaces_the_segment_rules() {
+        let mut builtin = RangeCheckBuiltinRunner::new(8, 8, true);
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        builtin.initialize_segments(&mut segments, &mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
+        builtin.add_validation_rule(&mut memory).unwrap();
+        assert_eq!(memory.validation_rules[&0].len(), 1);
+
+        let audited = Rc::new(RefCell::new(Vec::new()));
+        memory.add_validation_rule(0, auditing_rule(audited, Felt::new(7)));
+        assert_eq!(memory.validation_rules[&0].len(), 2);
+        builtin.add_validation_rule(&mut memory).unwrap();
+        assert_eq!(memory.validation_rules[&0].len(), 1);
+    }
+
+    #[test]
+    fn clear_validation_rules_detaches_every_rule() {
+        let mut builtin = RangeCheckBuiltinRunner::new(8, 8, true);
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        builtin.initialize_segments(&mut segments, &mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
+        let audited = Rc::new(RefCell::new(Vec::new()));
+        memory.add_validation_rule(0, auditing_rule(audited.clone(), Felt::new(7)));
+
+        memory.clear_validation_rules(0);
+        assert_eq!(
+            memory.insert(
+                &MaybeRelocatable::from((0, 0)),
+                &MaybeRelocatable::from(Felt::new(-10))
+            ),
+            Ok(())
+        );
+        assert_eq!(memory.validate_existing_memory(), Ok(()));
+        assert!(memory.validated_addresses.is_empty());
+        assert!(audited.borrow().is_empty());
+    }
+
+    #[test]
+    fn load_data_applies_validation_rule() {
+        let mut builtin = RangeCheckBuiltinRunner::new(8, 8, true);
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        builtin.initialize_segments(&mut segments, &mut memory);
+        builtin.add_validation_rule(&mut memory).unwrap();
+
+        assert_eq!(
+            memory.load_data(
+                &Relocatable::from((0, 0)),
+                &[mayberelocatable!(45), mayberelocatable!(46)]
+            ),
+            Ok(Relocatable::from((0, 2)))
+        );
+        assert!(memory
+            .validated_addresses
+            .contains(&MaybeRelocatable::from((0, 0))));
+        assert!(memory
+            .validated_addresses
+            .contains(&MaybeRelocatable::from((0, 1))));
+
+        assert_eq!(
+            memory.load_data(
+                &Relocatable::from((0, 2)),
+                &[MaybeRelocatable::from(Felt::new(-10))]
+            ),
+            Err(MemoryError::NumOutOfBounds)
+        );
+    }
+
+    #[test]
+    fn diff_equal_memories() {
+        let memory = memory![((0, 0), 1), ((1, 1), (0, 0))];
+        assert_eq!(memory.diff(&memory![((0, 0), 1), ((1, 1), (0, 0))]), vec![]);
+    }
+
+    #[test]
+    fn diff_different_values() {
+        let lhs = memory![((0, 0), 1), ((0, 1), 2), ((1, 0), (0, 0))];
+        let rhs = memory![((0, 0), 1), ((0, 1), 3), ((1, 0), (0, 1))];
+        assert_eq!(
+            lhs.diff(&rhs),
+            vec![
+                MemoryDiff {
+                    address: Relocatable::from((0, 1)),
+                    lhs: Some(mayberelocatable!(2)),
+                    rhs: Some(mayberelocatable!(3)),
+                },
+                MemoryDiff {
+                    address: Relocatable::from((1, 0)),
+                    lhs: Some(mayberelocatable!(0, 0)),
+                    rhs: Some(mayberelocatable!(0, 1)),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_missing_cells() {
+        let lhs = memory![((0, 0), 1), ((0, 2), 3)];
+        let rhs = memory![((0, 0), 1), ((0, 1), 2)];
+        assert_eq!(
+            lhs.diff(&rhs),
+            vec![
+                MemoryDiff {
+                    address: Relocatable::from((0, 1)),
+                    lhs: None,
+                    rhs: Some(mayberelocatable!(2)),
+                },
+                MemoryDiff {
+                    address: Relocatable::from((0, 2)),
+                    lhs: Some(mayberelocatable!(3)),
+                    rhs: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_different_segment_count() {
+        let mut lhs = memory![((0, 0), 1)];
+        lhs.temp_data = vec![vec![Some(mayberelocatable!(4))]];
+        let rhs = memory![((0, 0), 1), ((1, 0), 2)];
+        let diffs = lhs.diff(&rhs);
+        assert_eq!(
+            diffs,
+            vec![
+                MemoryDiff {
+                    address: Relocatable::from((-1, 0)),
+                    lhs: Some(mayberelocatable!(4)),
+                    rhs: None,
+                },
+                MemoryDiff {
+                    address: Relocatable::from((1, 0)),
+                    lhs: None,
+                    rhs: Some(mayberelocatable!(2)),
+                },
+            ]
+        );
+        assert_eq!(diffs[0].to_string(), "-1:0: 4 != None");
+        assert_eq!(diffs[1].to_string(), "1:0: None != 2");
+    }
 }
@@ -3,7 +3,7 @@ use crate::{
     utils::from_relocatable_to_indexes,
     vm::{
         errors::memory_errors::MemoryError, errors::vm_errors::VirtualMachineError,
-        vm_core::VirtualMachine, vm_memory::memory::Memory,
+        runners::cairo_runner::CairoArg, vm_core::VirtualMachine, vm_memory::memory::Memory,
     },
 };
 
@@ -29,7 +29,8 @@ impl MemorySegmentManager {
     pub fn add(&mut self, memory: &mut Memory) -> Relocatable {
         let segment_index = self.num_segments;
         self.num_segments += 1;
-        memory.data.push(Vec::new());
+        let segment = memory.new_segment();
+        memory.data.push(segment);
         Relocatable {
             segment_index: segment_index as isize,
             offset: 0,
@@ -40,7 +41,8 @@ impl MemorySegmentManager {
     ///Negative segment_index indicates its refer to a temporary segment
     pub fn add_temporary_segment(&mut self, memory: &mut Memory) -> Relocatable {
         self.num_temp_segments += 1;
-        memory.temp_data.push(Vec::new());
+        let segment = memory.new_segment();
+        memory.temp_data.push(segment);
         Relocatable {
             segment_index: -(self.num_temp_segments as isize),
             offset: 0,
@@ -52,12 +54,12 @@ impl MemorySegmentManager {
         &mut self,
         memory: &mut Memory,
         ptr: &MaybeRelocatable,
-        data: &Vec<MaybeRelocatable>,
+        data: &[MaybeRelocatable],
     ) -> Result<MaybeRelocatable, MemoryError> {
-        for (num, value) in data.iter().enumerate() {
-            memory.insert(&ptr.add_usize(num), value)?;
-        }
-        Ok(ptr.add_usize(data.len()))
+        let ptr: Relocatable = ptr
+            .try_into()
+            .map_err(|_| MemoryError::AddressNotRelocatable)?;
+        Ok(memory.load_data(&ptr, data)?.into())
     }
 
     pub fn new() -> MemorySegmentManager {
@@ -70,18 +72,42 @@ impl MemorySegmentManager {
         }
     }
 
-    /// Calculates the size (number of non-none elements) of each memory segment.
-    pub fn compute_effective_sizes(&mut self, memory: &Memory) -> &Vec<usize> {
-        self.segment_used_sizes
-            .get_or_insert_with(|| memory.data.iter().map(Vec::len).collect())
+    /// Forgets every segment, keeping the allocated capacity.
+    pub(crate) fn reset(&mut self) {
+        self.num_segments = 0;
+        self.num_temp_segments = 0;
+        self.segment_sizes.clear();
+        self.segment_used_sizes = None;
+        self.public_memory_offsets.clear();
+    }
+
+    /// Calculates the effective size of each memory segment: its finalized size if it was
+    /// finalized, or else its used size (the offset past the last written cell).
+    /// The used sizes are recomputed from the memory on every call, so they are never stale.
+    pub fn compute_effective_sizes(&mut self, memory: &Memory) -> Vec<usize> {
+        let used_sizes = self.segment_used_sizes.get_or_insert_with(Vec::new);
+        used_sizes.clear();
+        used_sizes.extend(memory.data.iter().map(Vec::len));
+        used_sizes
+            .iter()
+            .enumerate()
+            .map(|(index, used_size)| {
+                self.segment_sizes
+                    .get(&index)
+                    .copied()
+                    .unwrap_or(*used_size)
+            })
+            .collect()
     }
 
-    ///Returns the number of used segments when they are already computed.
-    ///Returns None otherwise.
+    ///Returns the used size of a segment as of the last call to compute_effective_sizes.
+    ///Returns None if the sizes weren't computed yet, see VirtualMachine::get_segment_used_size
+    ///for a query that reads the memory directly.
     pub fn get_segment_used_size(&self, index: usize) -> Option<usize> {
         self.segment_used_sizes.as_ref()?.get(index).copied()
     }
 
+    ///Returns the finalized size of a segment, or its used size if it wasn't finalized.
     pub fn get_segment_size(&self, index: usize) -> Option<usize> {
         self.segment_sizes
             .get(&index)
@@ -90,15 +116,23 @@ impl MemorySegmentManager {
     }
 
     ///Returns a vector that contains the first relocated address of each memory segment
+    ///Finalized segments are relocated using their finalized size, which must not be smaller than their used size
     pub fn relocate_segments(&self) -> Result<Vec<usize>, MemoryError> {
         let first_addr = 1;
         let mut relocation_table = vec![first_addr];
         match &self.segment_used_sizes {
             Some(segment_used_sizes) => {
-                for (i, _size) in segment_used_sizes.iter().enumerate() {
+                for (i, used_size) in segment_used_sizes.iter().enumerate() {
                     let segment_size = self
                         .get_segment_size(i)
                         .ok_or(MemoryError::SegmentNotFinalized(i))?;
+                    if *used_size > segment_size {
+                        return Err(MemoryError::SegmentSizeMismatch(
+                            i,
+                            *used_size,
+                            segment_size,
+                        ));
+                    }
 
                     relocation_table.push(relocation_table[i] + segment_size);
                 }
@@ -125,11 +159,39 @@ impl MemorySegmentManager {
             let base = self.add(memory);
             self.write_arg(memory, &base, value)?;
             Ok(base.into())
+        } else if let Some(value) = arg.downcast_ref::<CairoArg>() {
+            Ok(self.gen_cairo_arg(value, memory)?)
         } else {
             Err(VirtualMachineError::NotImplemented)
         }
     }
 
+    ///Converts a CairoArg into the value passed to the function.
+    ///Arrays and composed args are written into a new segment, and their base is returned.
+    pub fn gen_cairo_arg(
+        &mut self,
+        arg: &CairoArg,
+        memory: &mut Memory,
+    ) -> Result<MaybeRelocatable, MemoryError> {
+        match arg {
+            CairoArg::Single(value) => Ok(value.clone()),
+            CairoArg::Array(values) => {
+                let base = self.add(memory);
+                self.load_data(memory, &base.into(), values)?;
+                Ok(base.into())
+            }
+            CairoArg::Composed(args) => {
+                let values = args
+                    .iter()
+                    .map(|arg| self.gen_cairo_arg(arg, memory))
+                    .collect::<Result<Vec<MaybeRelocatable>, MemoryError>>()?;
+                let base = self.add(memory);
+                self.load_data(memory, &base.into(), &values)?;
+                Ok(base.into())
+            }
+        }
+    }
+
     pub fn gen_typed_args(
         &self,
         args: Vec<&dyn Any>,
@@ -163,11 +225,11 @@ impl MemorySegmentManager {
                 vector,
             )
         } else if let Some(vector) = arg.downcast_ref::<Vec<Relocatable>>() {
-            let data = &vector.iter().map(|value| value.into()).collect();
+            let data: Vec<MaybeRelocatable> = vector.iter().map(|value| value.into()).collect();
             self.load_data(
                 memory,
                 &MaybeRelocatable::from((ptr.segment_index, ptr.offset)),
-                data,
+                &data,
             )
         } else {
             Err(MemoryError::WriteArg)
@@ -234,20 +296,46 @@ impl MemorySegmentManager {
     // * size - The size of the segment (to be used in relocate_segments).
     // * public_memory - A list of offsets for memory cells that will be considered as public
     // memory.
-    pub(crate) fn finalize(
+    pub fn finalize(
         &mut self,
-        size: Option<usize>,
         segment_index: usize,
-        public_memory: Option<&Vec<(usize, usize)>>,
+        size: Option<usize>,
+        public_memory: Option<Vec<(usize, usize)>>,
     ) {
         if let Some(size) = size {
             self.segment_sizes.insert(segment_index, size);
         }
         if let Some(public_memory) = public_memory {
             self.public_memory_offsets
-                .insert(segment_index, public_memory.clone());
+                .insert(segment_index, public_memory);
         }
     }
+
+    ///Returns the relocated address and page id of every public memory cell, in segment order.
+    /// The relocation_table is the one returned by relocate_segments
+    pub fn get_public_memory_addresses(
+        &self,
+        relocation_table: &[usize],
+    ) -> Result<Vec<(usize, usize)>, MemoryError> {
+        let mut segment_indexes: Vec<usize> = self.public_memory_offsets.keys().copied().collect();
+        segment_indexes.sort_unstable();
+        let mut addresses = Vec::new();
+        for segment_index in segment_indexes {
+            let segment_start =
+                relocation_table
+                    .get(segment_index)
+                    .ok_or(MemoryError::UnallocatedSegment(
+                        segment_index,
+                        relocation_table.len(),
+                    ))?;
+            addresses.extend(
+                self.public_memory_offsets[&segment_index]
+                    .iter()
+                    .map(|(offset, page_id)| (segment_start + offset, *page_id)),
+            );
+        }
+        Ok(addresses)
+    }
 }
 
 impl Default for MemorySegmentManager {
@@ -460,6 +548,46 @@ mod tests {
         assert_eq!(None, segments.get_segment_used_size(2));
     }
 
+    #[test]
+    fn compute_effective_sizes_is_idempotent() {
+        let mut segments = MemorySegmentManager::new();
+        let memory = memory![((0, 2), 1), ((1, 0), 1)];
+        assert_eq!(segments.compute_effective_sizes(&memory), vec![3, 1]);
+        assert_eq!(segments.compute_effective_sizes(&memory), vec![3, 1]);
+    }
+
+    #[test]
+    fn compute_effective_sizes_after_new_writes() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory);
+        segments.add(&mut memory);
+        memory
+            .insert(&MaybeRelocatable::from((0, 0)), &mayberelocatable!(1))
+            .unwrap();
+        assert_eq!(segments.compute_effective_sizes(&memory), vec![1, 0]);
+
+        // Interleaved writes to both segments
+        memory
+            .insert(&MaybeRelocatable::from((1, 3)), &mayberelocatable!(2))
+            .unwrap();
+        memory
+            .insert(&MaybeRelocatable::from((0, 1)), &mayberelocatable!(3))
+            .unwrap();
+        memory
+            .insert(&MaybeRelocatable::from((1, 1)), &mayberelocatable!(4))
+            .unwrap();
+        assert_eq!(segments.compute_effective_sizes(&memory), vec![2, 4]);
+        assert_eq!(segments.get_segment_used_size(0), Some(2));
+        assert_eq!(segments.get_segment_used_size(1), Some(4));
+
+        // An empty segment is added with a size of zero
+        segments.add(&mut memory);
+        assert_eq!(segments.get_segment_used_size(2), None);
+        assert_eq!(segments.compute_effective_sizes(&memory), vec![2, 4, 0]);
+        assert_eq!(segments.get_segment_used_size(2), Some(0));
+    }
+
     #[test]
     fn relocate_segments_one_segment() {
         let mut segments = MemorySegmentManager::new();
@@ -798,6 +926,38 @@ mod tests {
         );
     }
 
+    /// Test that the call to .gen_arg() with a CairoArg nested two levels deep
+    /// writes each vector into its own segment, linked by pointers.
+    #[test]
+    fn gen_arg_nested_cairo_arg() {
+        let mut memory_segment_manager = MemorySegmentManager::new();
+        let mut vm = vm!();
+
+        let arg = CairoArg::Composed(vec![
+            CairoArg::Single(mayberelocatable!(1)),
+            CairoArg::Composed(vec![
+                CairoArg::Single(mayberelocatable!(2)),
+                CairoArg::Array(vec![mayberelocatable!(3), mayberelocatable!(4)]),
+            ]),
+            CairoArg::Single(mayberelocatable!(0, 1)),
+        ]);
+        assert_eq!(
+            memory_segment_manager.gen_arg(&arg, &mut vm.memory),
+            Ok(mayberelocatable!(2, 0)),
+        );
+        assert_eq!(memory_segment_manager.num_segments, 3);
+        check_memory![
+            vm.memory,
+            ((0, 0), 3),
+            ((0, 1), 4),
+            ((1, 0), 2),
+            ((1, 1), (0, 0)),
+            ((2, 0), 1),
+            ((2, 1), (1, 0)),
+            ((2, 2), (0, 1))
+        ];
+    }
+
     /// Test that the call to .gen_arg() with any other argument returns a not
     /// implemented error.
     #[test]
@@ -811,6 +971,64 @@ mod tests {
         );
     }
 
+    /// Test that the call to .gen_cairo_arg() with a single value just passes it
+    /// through.
+    #[test]
+    fn gen_cairo_arg_single() {
+        let mut memory_segment_manager = MemorySegmentManager::new();
+        let mut vm = vm!();
+
+        assert_eq!(
+            memory_segment_manager
+                .gen_cairo_arg(&CairoArg::Single(mayberelocatable!(1234)), &mut vm.memory),
+            Ok(mayberelocatable!(1234)),
+        );
+        assert_eq!(memory_segment_manager.num_segments, 0);
+    }
+
+    /// Test that the call to .gen_cairo_arg() with an array writes its contents
+    /// into a new segment and returns a pointer to it.
+    #[test]
+    fn gen_cairo_arg_array() {
+        let mut memory_segment_manager = MemorySegmentManager::new();
+        let mut vm = vm!();
+
+        assert_eq!(
+            memory_segment_manager.gen_cairo_arg(
+                &CairoArg::Array(vec![mayberelocatable!(1), mayberelocatable!(2)]),
+                &mut vm.memory
+            ),
+            Ok(mayberelocatable!(0, 0)),
+        );
+        check_memory![vm.memory, ((0, 0), 1), ((0, 1), 2)];
+    }
+
+    /// Test that the call to .gen_cairo_arg() with a composed arg writes each of
+    /// its members into a new segment, with arrays replaced by pointers.
+    #[test]
+    fn gen_cairo_arg_composed() {
+        let mut memory_segment_manager = MemorySegmentManager::new();
+        let mut vm = vm!();
+
+        assert_eq!(
+            memory_segment_manager.gen_cairo_arg(
+                &CairoArg::Composed(vec![
+                    CairoArg::Single(mayberelocatable!(7)),
+                    CairoArg::Array(vec![mayberelocatable!(8), mayberelocatable!(9)]),
+                ]),
+                &mut vm.memory
+            ),
+            Ok(mayberelocatable!(1, 0)),
+        );
+        check_memory![
+            vm.memory,
+            ((0, 0), 8),
+            ((0, 1), 9),
+            ((1, 0), 7),
+            ((1, 1), (0, 0))
+        ];
+    }
+
     /// Test that the call to .gen_typed_args() with an empty vector returns an
     /// empty vector.
     #[test]
@@ -866,14 +1084,14 @@ mod tests {
     #[test]
     fn finalize_no_size_nor_memory_no_change() {
         let mut segments = MemorySegmentManager::new();
-        segments.finalize(None, 0, None);
+        segments.finalize(0, None, None);
         assert_eq!(segments, MemorySegmentManager::new());
     }
 
     #[test]
     fn finalize_no_memory() {
         let mut segments = MemorySegmentManager::new();
-        segments.finalize(Some(42), 0, None);
+        segments.finalize(0, Some(42), None);
         assert!(segments.public_memory_offsets.is_empty());
         assert_eq!(segments.segment_sizes, HashMap::from([(0, 42)]));
     }
@@ -881,7 +1099,7 @@ mod tests {
     #[test]
     fn finalize_no_size() {
         let mut segments = MemorySegmentManager::new();
-        segments.finalize(None, 0, Some(&vec![(1_usize, 2_usize)]));
+        segments.finalize(0, None, Some(vec![(1_usize, 2_usize)]));
         assert_eq!(
             segments.public_memory_offsets,
             HashMap::from([(0_usize, vec![(1_usize, 2_usize)])])
@@ -892,11 +1110,110 @@ mod tests {
     #[test]
     fn finalize_all_args() {
         let mut segments = MemorySegmentManager::new();
-        segments.finalize(Some(42), 0, Some(&vec![(1_usize, 2_usize)]));
+        segments.finalize(0, Some(42), Some(vec![(1_usize, 2_usize)]));
         assert_eq!(
             segments.public_memory_offsets,
             HashMap::from([(0_usize, vec![(1_usize, 2_usize)])])
         );
         assert_eq!(segments.segment_sizes, HashMap::from([(0, 42)]));
     }
+
+    #[test]
+    fn relocate_segments_finalized_before_writes() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory);
+        segments.add(&mut memory);
+        segments.finalize(0, Some(10), None);
+        memory
+            .insert_value(&Relocatable::from((0, 2)), Felt::new(1))
+            .unwrap();
+        memory
+            .insert_value(&Relocatable::from((1, 0)), Felt::new(2))
+            .unwrap();
+        segments.compute_effective_sizes(&memory);
+        assert_eq!(segments.relocate_segments(), Ok(vec![1, 11]));
+    }
+
+    #[test]
+    fn relocate_segments_finalized_after_writes() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory);
+        segments.add(&mut memory);
+        memory
+            .insert_value(&Relocatable::from((0, 2)), Felt::new(1))
+            .unwrap();
+        memory
+            .insert_value(&Relocatable::from((1, 0)), Felt::new(2))
+            .unwrap();
+        segments.compute_effective_sizes(&memory);
+        segments.finalize(0, Some(3), Some(vec![(0, 0), (2, 0)]));
+        assert_eq!(segments.relocate_segments(), Ok(vec![1, 4]));
+        assert_eq!(
+            segments.public_memory_offsets,
+            HashMap::from([(0, vec![(0, 0), (2, 0)])])
+        );
+    }
+
+    #[test]
+    fn compute_effective_sizes_prefers_finalized_sizes() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory);
+        segments.add(&mut memory);
+        segments.finalize(0, Some(10), None);
+        assert_eq!(segments.compute_effective_sizes(&memory), vec![10, 0]);
+
+        memory
+            .insert_value(&Relocatable::from((0, 2)), Felt::new(1))
+            .unwrap();
+        memory
+            .insert_value(&Relocatable::from((1, 1)), Felt::new(2))
+            .unwrap();
+        assert_eq!(segments.compute_effective_sizes(&memory), vec![10, 2]);
+        // The used sizes are still the observed ones
+        assert_eq!(segments.get_segment_used_size(0), Some(3));
+
+        segments.finalize(1, Some(4), None);
+        assert_eq!(segments.compute_effective_sizes(&memory), vec![10, 4]);
+    }
+
+    #[test]
+    fn relocate_segments_finalized_size_mismatch() {
+        let mut segments = MemorySegmentManager::new();
+        let mut memory = Memory::new();
+        segments.add(&mut memory);
+        segments.add(&mut memory);
+        segments.finalize(1, Some(2), None);
+        memory
+            .insert_value(&Relocatable::from((1, 4)), Felt::new(1))
+            .unwrap();
+        segments.compute_effective_sizes(&memory);
+        assert_eq!(
+            segments.relocate_segments(),
+            Err(MemoryError::SegmentSizeMismatch(1, 5, 2))
+        );
+    }
+
+    #[test]
+    fn get_public_memory_addresses_in_segment_order() {
+        let mut segments = MemorySegmentManager::new();
+        segments.finalize(2, None, Some(vec![(1, 1), (0, 0)]));
+        segments.finalize(0, None, Some(vec![(0, 0), (1, 0)]));
+        assert_eq!(
+            segments.get_public_memory_addresses(&[1, 3, 10]),
+            Ok(vec![(1, 0), (2, 0), (11, 1), (10, 0)])
+        );
+    }
+
+    #[test]
+    fn get_public_memory_addresses_segment_not_relocated() {
+        let mut segments = MemorySegmentManager::new();
+        segments.finalize(3, None, Some(vec![(0, 0)]));
+        assert_eq!(
+            segments.get_public_memory_addresses(&[1, 3]),
+            Err(MemoryError::UnallocatedSegment(3, 2))
+        );
+    }
 }
@@ -2,7 +2,8 @@ use cairo_rs::{
     hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
     types::program::Program,
     vm::{
-        runners::cairo_runner::CairoRunner, trace::trace_entry::RelocatedTraceEntry,
+        runners::cairo_runner::{CairoRunner, RunResources},
+        trace::trace_entry::RelocatedTraceEntry,
         vm_core::VirtualMachine,
     },
 };
@@ -19,7 +20,12 @@ fn bitwise_integration_test() {
     let mut vm = VirtualMachine::new(true, Vec::new());
     let end = cairo_runner.initialize(&mut vm).unwrap();
     assert!(
-        cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor) == Ok(()),
+        cairo_runner.run_until_pc(
+            end,
+            &mut RunResources::default(),
+            &mut vm,
+            &mut hint_processor
+        ) == Ok(()),
         "Execution failed"
     );
     assert!(
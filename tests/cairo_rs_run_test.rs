@@ -0,0 +1,107 @@
+use assert_cmd::Command;
+use std::{fs, path::PathBuf};
+
+fn cairo_rs_run() -> Command {
+    Command::cargo_bin("cairo-rs-run").unwrap()
+}
+
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(name)
+}
+
+#[test]
+fn trace_and_memory_match_cairo_lang() {
+    let trace_path = temp_path("cairo_rs_run_struct.trace");
+    let memory_path = temp_path("cairo_rs_run_struct.memory");
+    cairo_rs_run()
+        .arg("cairo_programs/struct.json")
+        .args(["--layout", "all"])
+        .arg("--trace_file")
+        .arg(&trace_path)
+        .arg("--memory_file")
+        .arg(&memory_path)
+        .assert()
+        .success();
+
+    assert_eq!(
+        fs::read(&trace_path).unwrap(),
+        fs::read("cairo_programs/trace_memory/cairo_trace_struct").unwrap()
+    );
+    assert_eq!(
+        fs::read(&memory_path).unwrap(),
+        fs::read("cairo_programs/trace_memory/cairo_memory_struct").unwrap()
+    );
+    fs::remove_file(trace_path).unwrap();
+    fs::remove_file(memory_path).unwrap();
+}
+
+#[test]
+fn print_output() {
+    let output = cairo_rs_run()
+        .arg("cairo_programs/bitwise_output.json")
+        .args(["--layout", "all", "--print_output"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout)
+        .unwrap()
+        .starts_with("Program Output: \n"));
+}
+
+#[test]
+fn secure_run() {
+    cairo_rs_run()
+        .arg("cairo_programs/bitwise_output.json")
+        .args(["--layout", "all", "--secure_run"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn proof_mode() {
+    cairo_rs_run()
+        .arg("cairo_programs/proof_programs/fibonacci.json")
+        .args(["--layout", "all", "--proof_mode"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn missing_entrypoint() {
+    let output = cairo_rs_run()
+        .arg("cairo_programs/struct.json")
+        .args(["--entrypoint", "missing"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "Entrypoint missing not found\n"
+    );
+}
+
+#[test]
+fn invalid_layout() {
+    let output = cairo_rs_run()
+        .arg("cairo_programs/struct.json")
+        .args(["--layout", "invalid"])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("invalid is not a valid layout"));
+}
+
+#[test]
+fn failed_run_prints_traceback() {
+    let output = cairo_rs_run()
+        .arg("cairo_programs/bad_programs/bad_dict_update.json")
+        .args(["--layout", "all"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8(output.stderr)
+        .unwrap()
+        .contains("Cairo traceback (most recent call last):\ncairo_programs/bad_programs/bad_dict_update.cairo:10:5: (pc=0:34)"));
+}
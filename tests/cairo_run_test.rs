@@ -12,6 +12,7 @@ fn cairo_run_test() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -27,6 +28,7 @@ fn cairo_run_array_sum() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -42,6 +44,7 @@ fn cairo_run_big_struct() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -57,6 +60,7 @@ fn cairo_run_call_function_assign_param_by_name() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -72,6 +76,7 @@ fn cairo_run_function_return() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -87,6 +92,7 @@ fn cairo_run_function_return_if_print() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -102,6 +108,7 @@ fn cairo_run_function_return_to_variable() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -117,6 +124,7 @@ fn cairo_run_if_and_prime() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -132,6 +140,7 @@ fn cairo_run_if_in_function() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -147,6 +156,7 @@ fn cairo_run_if_list() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -162,6 +172,7 @@ fn cairo_run_jmp() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -177,6 +188,7 @@ fn cairo_run_jmp_if_condition() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -192,6 +204,7 @@ fn cairo_run_pointers() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -207,6 +220,7 @@ fn cairo_run_print() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -222,6 +236,7 @@ fn cairo_run_return() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -237,6 +252,7 @@ fn cairo_run_reversed_register_instructions() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -252,6 +268,7 @@ fn cairo_run_simple_print() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -267,6 +284,7 @@ fn cairo_run_test_addition_if() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -282,6 +300,7 @@ fn cairo_run_test_reverse_if() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -297,6 +316,7 @@ fn cairo_run_test_subtraction_if() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -312,6 +332,7 @@ fn cairo_run_use_imported_module() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -327,6 +348,7 @@ fn cairo_run_bitwise_output() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -342,6 +364,7 @@ fn cairo_run_bitwise_recursion() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -357,6 +380,7 @@ fn cairo_run_integration() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -372,6 +396,7 @@ fn cairo_run_integration_with_alloc_locals() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -387,6 +412,7 @@ fn cairo_run_compare_arrays() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -402,6 +428,7 @@ fn cairo_run_compare_greater_array() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -417,6 +444,7 @@ fn cairo_run_compare_lesser_array() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -432,6 +460,7 @@ fn cairo_run_assert_le_felt_hint() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -447,6 +476,7 @@ fn cairo_run_assert_250_bit_element_array() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -462,6 +492,7 @@ fn cairo_abs_value() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -477,6 +508,7 @@ fn cairo_run_compare_different_arrays() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -492,6 +524,7 @@ fn cairo_run_assert_nn() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -507,6 +540,7 @@ fn cairo_run_sqrt() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -522,6 +556,7 @@ fn cairo_run_assert_not_zero() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -537,6 +572,7 @@ fn cairo_run_split_int() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -552,6 +588,7 @@ fn cairo_run_split_int_big() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -567,6 +604,7 @@ fn cairo_run_split_felt() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -582,6 +620,7 @@ fn cairo_run_math_cmp() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -597,6 +636,7 @@ fn cairo_run_unsigned_div_rem() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -612,6 +652,7 @@ fn cairo_run_signed_div_rem() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -627,6 +668,7 @@ fn cairo_run_assert_lt_felt() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -642,6 +684,7 @@ fn cairo_run_memcpy() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -657,6 +700,7 @@ fn cairo_run_memset() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -672,6 +716,7 @@ fn cairo_run_pow() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -687,6 +732,7 @@ fn cairo_run_dict() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -702,6 +748,7 @@ fn cairo_run_dict_update() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -717,6 +764,7 @@ fn cairo_run_uint256() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -732,6 +780,7 @@ fn cairo_run_find_element() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -747,6 +796,7 @@ fn cairo_run_search_sorted_lower() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -762,6 +812,7 @@ fn cairo_run_usort() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -777,6 +828,7 @@ fn cairo_run_usort_bad() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     );
     assert!(err.is_err());
@@ -797,6 +849,7 @@ fn cairo_run_dict_write_bad() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .is_err());
@@ -808,6 +861,7 @@ fn cairo_run_dict_write_bad() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .err();
@@ -827,6 +881,7 @@ fn cairo_run_dict_update_bad() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .is_err());
@@ -837,6 +892,7 @@ fn cairo_run_dict_update_bad() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .err();
@@ -855,6 +911,7 @@ fn cairo_run_squash_dict() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -870,6 +927,7 @@ fn cairo_run_dict_squash() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -885,6 +943,7 @@ fn cairo_run_set_add() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -900,6 +959,7 @@ fn cairo_run_secp() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -915,6 +975,7 @@ fn cairo_run_signature() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -930,6 +991,7 @@ fn cairo_run_secp_ec() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -945,6 +1007,7 @@ fn cairo_run_blake2s_hello_world_hash() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -960,6 +1023,7 @@ fn cairo_run_finalize_blake2s() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -974,6 +1038,7 @@ fn cairo_run_unsafe_keccak() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -989,6 +1054,7 @@ fn cairo_run_blake2s_felts() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1004,6 +1070,7 @@ fn cairo_run_unsafe_keccak_finalize() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1019,6 +1086,7 @@ fn cairo_run_keccak_add_uint256() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1034,6 +1102,7 @@ fn cairo_run_private_keccak() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1049,6 +1118,7 @@ fn cairo_run_keccak_copy_inputs() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1064,6 +1134,7 @@ fn cairo_run_finalize_keccak() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1079,6 +1150,7 @@ fn cairo_run_operations_with_data() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1094,6 +1166,7 @@ fn cairo_run_sha256() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1109,6 +1182,7 @@ fn cairo_run_math_cmp_and_pow_integration() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1124,6 +1198,7 @@ fn cairo_run_uint256_integration() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1139,6 +1214,7 @@ fn cairo_run_set_integration() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1154,6 +1230,7 @@ fn cairo_run_memory_module_integration() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1169,6 +1246,7 @@ fn cairo_run_dict_integration() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1184,6 +1262,7 @@ fn cairo_run_secp_integration() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1199,6 +1278,7 @@ fn cairo_run_keccak_integration() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1214,6 +1294,7 @@ fn cairo_run_blake2s_integration() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1229,6 +1310,7 @@ fn cairo_run_relocate_segments() {
         false,
         "small",
         false,
+        false,
         &mut hint_executor,
     )
     .expect("Couldn't run program");
@@ -1243,6 +1325,7 @@ fn cairo_run_error_msg_attr() {
         false,
         "all",
         false,
+        false,
         &mut hint_executor,
     )
     .err()
@@ -1250,3 +1333,19 @@ fn cairo_run_error_msg_attr() {
 
     assert!(err.to_string().contains("SafeUint256: addition overflow"));
 }
+
+#[test]
+fn cairo_run_poseidon_builtin() {
+    let mut hint_executor = BuiltinHintProcessor::new_empty();
+    cairo_run::cairo_run(
+        Path::new("cairo_programs/poseidon_builtin.json"),
+        "main",
+        false,
+        false,
+        "all",
+        false,
+        false,
+        &mut hint_executor,
+    )
+    .expect("Couldn't run program");
+}
@@ -0,0 +1,98 @@
+use cairo_rs::{
+    hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
+    types::program::Program,
+    vm::{
+        errors::trace_errors::TraceError,
+        runners::cairo_runner::{CairoRunner, ExecutionResources, RunResources},
+        vm_core::VirtualMachine,
+    },
+};
+use std::{collections::HashMap, path::Path};
+
+#[test]
+fn pedersen_test_execution_resources() {
+    let program = Program::from_file(Path::new("cairo_programs/pedersen_test.json"), Some("main"))
+        .expect("Failed to deserialize program");
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    let mut cairo_runner = CairoRunner::new(&program, "all", false).unwrap();
+    let mut vm = VirtualMachine::new(false, Vec::new());
+    let end = cairo_runner.initialize(&mut vm).unwrap();
+    cairo_runner
+        .run_until_pc(
+            end,
+            &mut RunResources::default(),
+            &mut vm,
+            &mut hint_processor,
+        )
+        .expect("Execution failed");
+    cairo_runner
+        .end_run(false, false, &mut vm, &mut hint_processor)
+        .unwrap();
+
+    // Resources reported by cairo-lang for the same program and layout
+    assert_eq!(
+        cairo_runner.get_execution_resources(&vm),
+        Ok(ExecutionResources {
+            n_steps: 14,
+            n_memory_holes: 0,
+            builtin_instance_counter: HashMap::from([
+                ("output".to_string(), 1),
+                ("pedersen".to_string(), 1),
+                ("range_check".to_string(), 0),
+            ]),
+        })
+    );
+}
+
+fn run_program(path: &str, trace_enabled: bool) -> (CairoRunner, VirtualMachine) {
+    let program =
+        Program::from_file(Path::new(path), Some("main")).expect("Failed to deserialize program");
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    let mut cairo_runner = CairoRunner::new(&program, "all", false).unwrap();
+    let mut vm = VirtualMachine::new(trace_enabled, Vec::new());
+    let end = cairo_runner.initialize(&mut vm).unwrap();
+    cairo_runner
+        .run_until_pc(
+            end,
+            &mut RunResources::default(),
+            &mut vm,
+            &mut hint_processor,
+        )
+        .expect("Execution failed");
+    cairo_runner
+        .end_run(false, false, &mut vm, &mut hint_processor)
+        .unwrap();
+    (cairo_runner, vm)
+}
+
+#[test]
+fn execution_resources_match_with_and_without_trace() {
+    for path in [
+        "cairo_programs/pedersen_test.json",
+        "cairo_programs/bitwise_builtin_test.json",
+        "cairo_programs/fibonacci.json",
+    ] {
+        let (traced_runner, traced_vm) = run_program(path, true);
+        let (untraced_runner, untraced_vm) = run_program(path, false);
+        let resources = traced_runner.get_execution_resources(&traced_vm).unwrap();
+        assert_eq!(
+            untraced_runner.get_execution_resources(&untraced_vm),
+            Ok(resources),
+            "{path}"
+        );
+    }
+}
+
+#[test]
+fn untraced_run_has_no_trace_to_write() {
+    let (mut cairo_runner, mut vm) = run_program("cairo_programs/fibonacci.json", false);
+    cairo_runner.relocate(&mut vm).unwrap();
+    assert!(cairo_runner.relocated_trace.is_none());
+
+    let mut trace_file = Vec::new();
+    assert_eq!(
+        cairo_runner.write_relocated_trace(&mut vm, &mut trace_file),
+        Err(TraceError::TraceNotEnabled)
+    );
+    assert!(trace_file.is_empty());
+}
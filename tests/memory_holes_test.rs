@@ -0,0 +1,49 @@
+use cairo_rs::{
+    hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
+    types::program::Program,
+    vm::{
+        runners::cairo_runner::{CairoRunner, RunResources},
+        vm_core::VirtualMachine,
+    },
+};
+use std::path::Path;
+
+fn run_and_get_memory_holes(path: &str) -> usize {
+    let program =
+        Program::from_file(Path::new(path), Some("main")).expect("Failed to deserialize program");
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    let mut cairo_runner = CairoRunner::new(&program, "all", false).unwrap();
+    let mut vm = VirtualMachine::new(false, Vec::new());
+    let end = cairo_runner.initialize(&mut vm).unwrap();
+    cairo_runner
+        .run_until_pc(
+            end,
+            &mut RunResources::default(),
+            &mut vm,
+            &mut hint_processor,
+        )
+        .expect("Execution failed");
+    cairo_runner
+        .end_run(false, false, &mut vm, &mut hint_processor)
+        .unwrap();
+    cairo_runner
+        .get_execution_resources(&vm)
+        .unwrap()
+        .n_memory_holes
+}
+
+#[test]
+fn memory_holes_none() {
+    // main only returns, reading the return fp and pc written at the start of the execution segment
+    assert_eq!(run_and_get_memory_holes("cairo_programs/struct.json"), 0);
+}
+
+#[test]
+fn memory_holes_unused_local() {
+    // The execution segment holds [return_fp, return_pc, skipped, used], and only skipped is
+    // never accessed
+    assert_eq!(
+        run_and_get_memory_holes("cairo_programs/memory_holes.json"),
+        1
+    );
+}
@@ -0,0 +1,54 @@
+use cairo_rs::{
+    hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
+    types::{program::Program, relocatable::MaybeRelocatable},
+    vm::{
+        runners::cairo_runner::{CairoRunner, RunResources},
+        vm_core::VirtualMachine,
+    },
+};
+use felt::{Felt, NewFelt};
+use std::path::Path;
+
+fn run_program(path: &str) -> (CairoRunner, VirtualMachine) {
+    let program =
+        Program::from_file(Path::new(path), Some("main")).expect("Failed to deserialize program");
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    let mut cairo_runner = CairoRunner::new(&program, "all", false).unwrap();
+    let mut vm = VirtualMachine::new(false, Vec::new());
+    let end = cairo_runner.initialize(&mut vm).unwrap();
+    cairo_runner
+        .run_until_pc(
+            end,
+            &mut RunResources::default(),
+            &mut vm,
+            &mut hint_processor,
+        )
+        .expect("Execution failed");
+    (cairo_runner, vm)
+}
+
+#[test]
+fn read_array_sum_output() {
+    let (cairo_runner, vm) = run_program("cairo_programs/array_sum.json");
+
+    assert_eq!(cairo_runner.read_output(&vm), Ok(vec![Felt::new(50)]));
+    // main returns the updated output_ptr, one cell past the output segment base
+    assert_eq!(
+        vm.get_return_values(1),
+        Ok(vec![MaybeRelocatable::from((2, 1))])
+    );
+}
+
+#[test]
+fn read_print_output() {
+    let (cairo_runner, vm) = run_program("cairo_programs/print.json");
+
+    assert_eq!(cairo_runner.read_output(&vm), Ok(vec![Felt::new(1234)]));
+}
+
+#[test]
+fn read_output_of_program_without_output_builtin() {
+    let (cairo_runner, vm) = run_program("cairo_programs/fibonacci.json");
+
+    assert_eq!(cairo_runner.read_output(&vm), Ok(Vec::new()));
+}
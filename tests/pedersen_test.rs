@@ -2,10 +2,17 @@ use std::path::Path;
 
 use cairo_rs::{
     hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
-    types::program::Program,
+    types::{
+        layout::{CairoLayout, DynamicLayoutParams},
+        program::Program,
+    },
     vm::trace::trace_entry::RelocatedTraceEntry,
-    vm::{runners::cairo_runner::CairoRunner, vm_core::VirtualMachine},
+    vm::{
+        runners::cairo_runner::{CairoRunner, ExecutionResources, RunResources},
+        vm_core::VirtualMachine,
+    },
 };
+use std::collections::HashMap;
 
 #[test]
 fn pedersen_integration_test() {
@@ -16,7 +23,12 @@ fn pedersen_integration_test() {
     let mut vm = VirtualMachine::new(true, Vec::new());
     let end = cairo_runner.initialize(&mut vm).unwrap();
     assert_eq!(
-        cairo_runner.run_until_pc(end, &mut vm, &mut hint_processor),
+        cairo_runner.run_until_pc(
+            end,
+            &mut RunResources::default(),
+            &mut vm,
+            &mut hint_processor
+        ),
         Ok(())
     );
     assert!(cairo_runner.relocate(&mut vm) == Ok(()), "Execution failed");
@@ -98,3 +110,69 @@ fn pedersen_integration_test() {
         Some(python_vm_relocated_trace)
     );
 }
+
+#[test]
+fn pedersen_dynamic_layout_integration_test() {
+    let program = Program::from_file(Path::new("cairo_programs/pedersen_test.json"), Some("main"))
+        .expect("Failed to deserialize program");
+    let layout = CairoLayout::dynamic(DynamicLayoutParams {
+        output: true,
+        pedersen_ratio: Some(7),
+        range_check_ratio: Some(2),
+        ecdsa_ratio: None,
+        bitwise_ratio: None,
+        ec_op_ratio: None,
+        keccak_ratio: None,
+        poseidon_ratio: None,
+        memory_units_per_step: 16,
+    });
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    let mut cairo_runner = CairoRunner::new_with_layout(&program, layout, false);
+    let mut vm = VirtualMachine::new(false, Vec::new());
+    let end = cairo_runner.initialize(&mut vm).unwrap();
+    cairo_runner
+        .run_until_pc(
+            end,
+            &mut RunResources::default(),
+            &mut vm,
+            &mut hint_processor,
+        )
+        .expect("Execution failed");
+    cairo_runner
+        .end_run(false, false, &mut vm, &mut hint_processor)
+        .unwrap();
+
+    let ratios: Vec<(&str, Option<u32>)> = vm
+        .get_builtin_runners()
+        .iter()
+        .map(|(name, builtin)| (name.as_str(), builtin.ratio()))
+        .collect();
+    assert_eq!(
+        ratios,
+        [
+            ("output", None),
+            ("pedersen", Some(7)),
+            ("range_check", Some(2))
+        ]
+    );
+    // 14 steps allocate 2 pedersen instances of 3 cells and 7 range check cells
+    let allocated_units: Vec<usize> = vm
+        .get_builtin_runners()
+        .iter()
+        .map(|(_, builtin)| builtin.get_allocated_memory_units(&vm).unwrap())
+        .collect();
+    assert_eq!(allocated_units, [0, 6, 7]);
+    assert_eq!(cairo_runner.check_memory_usage(&vm), Ok(()));
+    assert_eq!(
+        cairo_runner.get_execution_resources(&vm),
+        Ok(ExecutionResources {
+            n_steps: 14,
+            n_memory_holes: 0,
+            builtin_instance_counter: HashMap::from([
+                ("output".to_string(), 1),
+                ("pedersen".to_string(), 1),
+                ("range_check".to_string(), 0),
+            ]),
+        })
+    );
+}
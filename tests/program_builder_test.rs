@@ -0,0 +1,42 @@
+use cairo_rs::{
+    hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
+    types::{program::ProgramBuilder, relocatable::MaybeRelocatable},
+    vm::{
+        runners::cairo_runner::{CairoRunner, RunResources},
+        vm_core::VirtualMachine,
+    },
+};
+use felt::{Felt, NewFelt};
+
+#[test]
+fn run_program_built_in_code() {
+    // [ap] = 1000, ap++
+    // ret
+    let program = ProgramBuilder::new()
+        .data(vec![
+            MaybeRelocatable::from(Felt::new(0x480680017fff8000_u64)),
+            MaybeRelocatable::from(Felt::new(1000)),
+            MaybeRelocatable::from(Felt::new(0x208b7fff7fff7ffe_u64)),
+        ])
+        .main(0)
+        .build()
+        .unwrap();
+
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    let mut cairo_runner = CairoRunner::new(&program, "plain", false).unwrap();
+    let mut vm = VirtualMachine::new(false, Vec::new());
+    let end = cairo_runner.initialize(&mut vm).unwrap();
+    cairo_runner
+        .run_until_pc(
+            end,
+            &mut RunResources::default(),
+            &mut vm,
+            &mut hint_processor,
+        )
+        .unwrap();
+
+    assert_eq!(
+        vm.get_return_values(1).unwrap(),
+        vec![Felt::new(1000).into()]
+    );
+}
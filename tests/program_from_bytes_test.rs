@@ -0,0 +1,98 @@
+use cairo_rs::{
+    hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
+    types::{
+        program::Program,
+        relocatable::{MaybeRelocatable, Relocatable},
+    },
+    vm::{
+        errors::{runner_errors::RunnerError, vm_errors::VirtualMachineError},
+        runners::cairo_runner::{CairoArg, CairoRunner, RunResources},
+        vm_core::VirtualMachine,
+    },
+};
+use felt::{Felt, NewFelt};
+
+const VALID_PROGRAM_A: &[u8] =
+    include_bytes!("../cairo_programs/manually_compiled/valid_program_a.json");
+const LIBRARY_PROGRAM: &[u8] =
+    include_bytes!("../cairo_programs/manually_compiled/library_program.json");
+
+#[test]
+fn run_embedded_program() {
+    let program = Program::from_bytes(VALID_PROGRAM_A, Some("main")).unwrap();
+    assert_eq!(program.main, Some(0));
+
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    let mut cairo_runner = CairoRunner::new(&program, "plain", false).unwrap();
+    let mut vm = VirtualMachine::new(false, Vec::new());
+    let end = cairo_runner.initialize(&mut vm).unwrap();
+    cairo_runner
+        .run_until_pc(
+            end,
+            &mut RunResources::default(),
+            &mut vm,
+            &mut hint_processor,
+        )
+        .unwrap();
+
+    assert_eq!(
+        vm.get_return_values(1).unwrap(),
+        vec![Felt::new(3000).into()]
+    );
+}
+
+#[test]
+fn run_library_program_function() {
+    let program = Program::from_bytes(LIBRARY_PROGRAM, None).unwrap();
+    assert_eq!(program.main, None);
+
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    let mut cairo_runner = CairoRunner::new(&program, "plain", false).unwrap();
+    let mut vm = VirtualMachine::new(false, Vec::new());
+    cairo_runner.initialize_function_runner(&mut vm).unwrap();
+    cairo_runner
+        .run_from_named_entrypoint(
+            "add",
+            &[
+                CairoArg::Single(MaybeRelocatable::from(Felt::new(2))),
+                CairoArg::Single(MaybeRelocatable::from(Felt::new(3))),
+            ],
+            &mut vm,
+            &mut hint_processor,
+        )
+        .unwrap();
+
+    assert_eq!(vm.get_return_values(1).unwrap(), vec![Felt::new(5).into()]);
+}
+
+#[test]
+fn library_program_has_no_main_entrypoint() {
+    let program = Program::from_bytes(LIBRARY_PROGRAM, None).unwrap();
+
+    let mut cairo_runner = CairoRunner::new(&program, "plain", false).unwrap();
+    let mut vm = VirtualMachine::new(false, Vec::new());
+    assert_eq!(
+        cairo_runner.initialize(&mut vm),
+        Err(RunnerError::MissingMain)
+    );
+}
+
+#[test]
+fn run_until_pc_without_entrypoint() {
+    let program = Program::from_bytes(LIBRARY_PROGRAM, None).unwrap();
+
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    let mut cairo_runner = CairoRunner::new(&program, "plain", false).unwrap();
+    let mut vm = VirtualMachine::new(false, Vec::new());
+    cairo_runner.initialize_function_runner(&mut vm).unwrap();
+    // The program is loaded at the start of the first segment
+    assert_eq!(
+        cairo_runner.run_until_pc(
+            Relocatable::from((0, 1)),
+            &mut RunResources::default(),
+            &mut vm,
+            &mut hint_processor,
+        ),
+        Err(VirtualMachineError::RunnerError(RunnerError::NoEntrypoint))
+    );
+}
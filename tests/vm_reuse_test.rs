@@ -0,0 +1,79 @@
+use cairo_rs::{
+    hint_processor::builtin_hint_processor::builtin_hint_processor_definition::BuiltinHintProcessor,
+    types::program::Program,
+    vm::{
+        runners::cairo_runner::{CairoRunner, ExecutionResources, RunResources},
+        trace::trace_entry::RelocatedTraceEntry,
+        vm_core::VirtualMachine,
+    },
+};
+use felt::Felt;
+use std::path::Path;
+
+// Everything observable about a finished run
+#[derive(Debug, PartialEq)]
+struct RunResult {
+    relocated_memory: Vec<Option<Felt>>,
+    relocated_trace: Option<Vec<RelocatedTraceEntry>>,
+    execution_resources: ExecutionResources,
+    output: String,
+}
+
+fn load_program(path: &str) -> Program {
+    Program::from_file(Path::new(path), Some("main")).expect("Failed to deserialize program")
+}
+
+fn run(cairo_runner: &mut CairoRunner, vm: &mut VirtualMachine) -> RunResult {
+    let mut hint_processor = BuiltinHintProcessor::new_empty();
+    let end = cairo_runner.initialize(vm).unwrap();
+    cairo_runner
+        .run_until_pc(end, &mut RunResources::default(), vm, &mut hint_processor)
+        .expect("Execution failed");
+    cairo_runner
+        .end_run(false, false, vm, &mut hint_processor)
+        .unwrap();
+    cairo_runner.relocate(vm).unwrap();
+    RunResult {
+        relocated_memory: cairo_runner.relocated_memory.clone(),
+        relocated_trace: cairo_runner.relocated_trace.take(),
+        execution_resources: cairo_runner.get_execution_resources(vm).unwrap(),
+        output: cairo_runner.get_output(vm).unwrap(),
+    }
+}
+
+fn run_on_fresh_vm(path: &str) -> RunResult {
+    let program = load_program(path);
+    let mut cairo_runner = CairoRunner::new(&program, "all", false).unwrap();
+    let mut vm = VirtualMachine::new(true, Vec::new());
+    run(&mut cairo_runner, &mut vm)
+}
+
+#[test]
+fn reset_vm_runs_different_programs_like_fresh_vms() {
+    let paths = [
+        "cairo_programs/bitwise_output.json",
+        "cairo_programs/fibonacci.json",
+        "cairo_programs/pedersen_test.json",
+    ];
+    let mut vm = VirtualMachine::new(true, Vec::new());
+    for path in paths {
+        let program = load_program(path);
+        let mut cairo_runner = CairoRunner::new(&program, "all", false).unwrap();
+        vm.reset();
+        assert_eq!(run(&mut cairo_runner, &mut vm), run_on_fresh_vm(path));
+    }
+}
+
+#[test]
+fn reset_runner_reruns_the_same_program() {
+    let path = "cairo_programs/bitwise_output.json";
+    let program = load_program(path);
+    let mut cairo_runner = CairoRunner::new(&program, "all", false).unwrap();
+    let mut vm = VirtualMachine::new(true, Vec::new());
+    let first_run = run(&mut cairo_runner, &mut vm);
+
+    cairo_runner.reset();
+    vm.reset();
+    assert_eq!(run(&mut cairo_runner, &mut vm), first_run);
+    assert_eq!(first_run, run_on_fresh_vm(path));
+}